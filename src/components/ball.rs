@@ -3,11 +3,13 @@
 
 // First, we need to bring in some useful tools from other parts of our code
 use crate::game::constants::*; // This gives us access to important game settings like screen size
+use crate::game::court::CourtPreset; // The court the ball starts in the middle of
 use ggez::graphics::{
     Color,    // Lets us set colors (like making the ball yellow)
     DrawMode, // Helps us choose if shapes should be filled in or just outlined
     Mesh,     // Lets us create shapes we can draw on the screen
 };
+use std::collections::VecDeque; // A list we can add to at the back and remove from the front
 
 // This is like a blueprint for our ball - it stores all the important information about it
 pub struct Ball {
@@ -17,24 +19,50 @@ pub struct Ball {
     // (positive = moving right, negative = moving left)
     pub dy: f32, // How fast the ball is moving up or down
     // (positive = moving down, negative = moving up)
-    pub color: Color, // What color the ball should be
+    pub spin: f32, // How fast the ball is spinning (radians per second)
+    // (positive = clockwise on screen, which bends its path clockwise too)
+    pub color: Color,                // What color the ball should be
+    pub trail: VecDeque<(f32, f32)>, // Where the ball was over the last few steps (oldest first)
 }
 
 // Here we define all the things our ball can do
 impl Ball {
-    // This function creates a new ball in the middle of the screen
+    // This function creates a new ball in the middle of the classic court
     pub fn new() -> Self {
+        let (x, y) = CourtPreset::Classic.court().center();
         Ball {
-            x: SCREEN_WIDTH / 2.0,               // Put the ball in the middle horizontally
-            y: SCREEN_HEIGHT / 2.0,              // Put the ball in the middle vertically
-            dx: 0.0,                             // Start with the ball not moving left or right
-            dy: 0.0,                             // Start with the ball not moving up or down
-            color: Color::from_rgb(255, 255, 0), // Make the ball yellow
+            x,                      // Put the ball in the middle horizontally
+            y,                      // Put the ball in the middle vertically
+            dx: 0.0,                // Start with the ball not moving left or right
+            dy: 0.0,                // Start with the ball not moving up or down
+            spin: 0.0,              // Start without any spin
+            color: Color::WHITE,    // White, so the theme's ball color shows through
+            trail: VecDeque::new(), // No trail until the ball moves
         }
     }
 
     // This function moves the ball based on how much time has passed
     pub fn update(&mut self, delta: f32) {
+        // Remember where the ball was, keeping only the most recent spots.
+        // A ball that isn't moving has no trail
+        if self.dx == 0.0 && self.dy == 0.0 {
+            self.trail.clear();
+        } else {
+            self.trail.push_back((self.x, self.y));
+            if self.trail.len() > BALL_TRAIL_LENGTH {
+                self.trail.pop_front();
+            }
+        }
+
+        // Spin bends the ball's path a little every step, and wears off over time
+        if self.spin != 0.0 {
+            let (sin, cos) = (self.spin * BALL_SPIN_CURVE * delta).sin_cos();
+            let (dx, dy) = (self.dx, self.dy);
+            self.dx = dx * cos - dy * sin;
+            self.dy = dx * sin + dy * cos;
+            self.spin *= (1.0 - BALL_SPIN_DECAY * delta).max(0.0);
+        }
+
         // Move the ball horizontally:
         // New position = current position + (speed × time passed)
         self.x += self.dx * delta;
@@ -44,19 +72,86 @@ impl Ball {
         self.y += self.dy * delta;
     }
 
-    // This function creates the actual circle shape that will be drawn on the screen
+    // Stop the ball where it is and forget its trail
+    pub fn stop(&mut self) {
+        self.dx = 0.0;
+        self.dy = 0.0;
+        self.spin = 0.0;
+        self.trail.clear();
+    }
+
+    // How fast the ball is moving overall, in any direction
+    pub fn speed(&self) -> f32 {
+        self.dx.hypot(self.dy)
+    }
+
+    // Speed the ball up (or slow it down) without changing its direction,
+    // never going faster than MAX_BALL_SPEED
+    pub fn scale_speed(&mut self, factor: f32) {
+        let speed = self.speed();
+        if speed == 0.0 {
+            return;
+        }
+        let new_speed = (speed * factor).min(MAX_BALL_SPEED);
+        let ratio = new_speed / speed;
+        self.dx *= ratio;
+        self.dy *= ratio;
+    }
+
+    // Send the ball off at a given speed and angle (radians from flat, positive = down).
+    // `direction` is 1.0 to move right or -1.0 to move left
+    pub fn launch(&mut self, speed: f32, angle: f32, direction: f32) {
+        self.dx = direction.signum() * speed * angle.cos();
+        self.dy = speed * angle.sin();
+    }
+
+    // Pick up spin from a paddle moving at `paddle_velocity` (positive = down),
+    // so the ball swerves the way the paddle was moving. With `drag` (momentum
+    // physics) the paddle also carries the ball along with it, though never
+    // past MAX_BALL_SPEED. Call after the ball has been sent back, as the spin
+    // depends on which way it now travels
+    pub fn take_spin(&mut self, paddle_velocity: f32, drag: bool) {
+        if drag {
+            self.dy += paddle_velocity * PADDLE_SPIN_TRANSFER;
+            self.scale_speed(1.0);
+        }
+        self.spin = paddle_velocity * BALL_SPIN_TRANSFER * self.dx.signum();
+    }
+
+    // Change the bounce angle off the top or bottom wall for the ball's spin,
+    // like the spinning surface gripping the wall. Call after the bounce.
+    // The speed stays the same, and spin never makes the path steeper than a
+    // paddle-edge return or flatter than BALL_SPIN_MIN_BOUNCE_ANGLE
+    pub fn spin_off_wall(&mut self, top_wall: bool) {
+        if self.spin == 0.0 {
+            return;
+        }
+        // Clockwise spin slows the ball sideways off the top wall and speeds it
+        // up off the bottom one
+        let speed = self.speed();
+        let angle = (self.dy.abs() / self.dx.abs()).atan(); // Steepness, 0 = flat
+        let sideways = if top_wall { -1.0 } else { 1.0 };
+        let steeper = -sideways * self.dx.signum() * self.spin * BALL_SPIN_WALL_GRIP;
+        let new_angle = (angle + steeper).clamp(
+            angle.min(BALL_SPIN_MIN_BOUNCE_ANGLE),
+            angle.max(MAX_DEFLECTION_ANGLE),
+        );
+        self.dx = self.dx.signum() * speed * new_angle.cos();
+        self.dy = self.dy.signum() * speed * new_angle.sin();
+    }
+
+    // This function creates the circle shape that will be drawn on the screen.
+    // It has a radius of 1 and sits at 0, 0: the renderer keeps it and
+    // scales it up to BALL_RADIUS where the ball is each frame
     pub fn get_mesh(&self, ctx: &mut ggez::Context) -> ggez::GameResult<Mesh> {
         // Create a new circle shape with these settings:
         Mesh::new_circle(
             ctx,
             DrawMode::fill(), // Make it a solid circle (not just an outline)
-            ggez::mint::Point2 {
-                x: self.x, // Place it at the ball's current horizontal position
-                y: self.y, // Place it at the ball's current vertical position
-            },
-            BALL_RADIUS, // Make it this big (size comes from our game settings)
-            0.1,         // How smooth to make the circle (lower = smoother)
-            self.color,  // Color it yellow (or whatever color we set)
+            ggez::mint::Point2 { x: 0.0, y: 0.0 }, // Centered on 0, 0
+            1.0,              // Radius of 1, scaled up when drawn
+            0.1 / BALL_RADIUS, // As smooth as a full-size circle drawn with 0.1
+            self.color,       // Color it yellow (or whatever color we set)
         )
     }
 }
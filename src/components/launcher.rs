@@ -0,0 +1,131 @@
+// This file contains the ball machine for training mode. It stands at the
+// right end of the court and fires balls at the player at different heights,
+// angles and speeds (or the shots of a practice drill, in order), and counts
+// how many the player sends back in a row
+
+// Import needed tools and settings for our game
+use crate::components::ball::Ball;
+use crate::game::constants::*;
+use crate::game::court::Court;
+use crate::game::drills::{Drill, Shot};
+use crate::game::events::{EventListener, GameEvent};
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use ggez::GameResult;
+use rand::Rng;
+
+// Define what makes up the ball machine
+#[derive(Clone, Default)]
+pub struct BallLauncher {
+    pub y: f32,               // Height of the machine's mouth, from the top of the court
+    pub streak: u32,          // Balls returned in a row
+    pub best_streak: u32,     // Most balls returned in a row this session
+    pub drill: Option<Drill>, // Drill being played (None = random balls)
+    pub next_shot: usize,     // Which of the drill's shots is fired next
+    pub recording: Option<Vec<Shot>>, // Returns kept while recording a drill
+}
+
+impl BallLauncher {
+    // Create a machine that hasn't fired yet
+    pub fn new() -> Self {
+        BallLauncher::default()
+    }
+
+    // Start counting again for a new session, keeping the chosen drill
+    pub fn reset(&mut self) {
+        *self = BallLauncher {
+            drill: self.drill.take(),
+            ..BallLauncher::new()
+        };
+    }
+
+    // Fire the next ball at the player: the drill's next shot, or without a
+    // drill from a random height, at a random angle and speed. The ball leaves
+    // from the machine's mouth at the right end
+    pub fn fire(&mut self, ball: &mut Ball, court: &Court, rng: &mut impl Rng) {
+        let margin = LAUNCHER_HEIGHT / 2.0;
+        let (speed, angle) = match &self.drill {
+            Some(drill) => {
+                let shot = drill.shots[self.next_shot % drill.shots.len()];
+                self.next_shot = (self.next_shot + 1) % drill.shots.len();
+                self.y = (shot.height * court.height).clamp(margin, court.height - margin);
+                (shot.speed, shot.angle)
+            }
+            None => {
+                self.y = rng.gen_range(margin..court.height - margin);
+                let speed = rng.gen_range(LAUNCHER_MIN_SPEED..=LAUNCHER_MAX_SPEED);
+                let angle = rng.gen_range(-LAUNCHER_MAX_ANGLE..=LAUNCHER_MAX_ANGLE);
+                (speed, angle)
+            }
+        };
+
+        ball.stop();
+        ball.x = court.width - LAUNCHER_WIDTH - BALL_RADIUS;
+        ball.y = self.y;
+        ball.launch(speed, angle, -1.0);
+    }
+
+    // While recording, keep the ball the player just sent back as the
+    // drill's next shot (up to DRILL_MAX_SHOTS)
+    pub fn record_return(&mut self, ball: &Ball, court: &Court) {
+        if let Some(shots) = &mut self.recording {
+            if shots.len() < DRILL_MAX_SHOTS {
+                shots.push(Shot::from_return(ball, court));
+            }
+        }
+    }
+
+    // Create the machine's shape, facing the court (white, so the renderer
+    // can tint it with the theme)
+    pub fn get_mesh(&self, ctx: &mut ggez::Context, court: &Court) -> GameResult<Mesh> {
+        Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(
+                court.width - LAUNCHER_WIDTH,
+                self.y - LAUNCHER_HEIGHT / 2.0,
+                LAUNCHER_WIDTH,
+                LAUNCHER_HEIGHT,
+            ),
+            4.0,
+            Color::WHITE,
+        )
+    }
+
+    // Draw the returns in a row and the best so far where the score usually goes
+    pub fn draw_streak(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        theme: &Theme,
+    ) -> GameResult {
+        let mut streak_text = format!(
+            "Returns in a Row: {}  |  Best: {}",
+            self.streak, self.best_streak
+        );
+        if let Some(drill) = &self.drill {
+            streak_text.push_str(&format!("  |  Drill: {}", drill.name));
+        }
+        let text = Text::new(TextFragment::new(streak_text).scale(16.0).color(theme.text));
+        let dims = text.measure(ctx)?;
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 20.0]),
+        );
+        Ok(())
+    }
+}
+
+impl EventListener for BallLauncher {
+    // Every return adds to the streak; a ball that gets past the player ends it
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::PaddleHit { contact, .. } if contact.player == 1 => {
+                self.streak += 1;
+                self.best_streak = self.best_streak.max(self.streak);
+            }
+            GameEvent::PointScored { winner: 2 } => self.streak = 0,
+            _ => {}
+        }
+    }
+}
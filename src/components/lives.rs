@@ -0,0 +1,73 @@
+// This file handles the lives used in arcade mode, where missing the ball costs a life
+
+use crate::game::constants::*;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawMode, DrawParam, Mesh};
+use ggez::GameResult;
+
+// Keeps track of how many lives each player has left
+pub struct Lives {
+    pub player1: u32, // Your (left player) remaining lives
+    pub player2: u32, // AI (right player) remaining lives
+}
+
+impl Lives {
+    // Give both players the same number of lives
+    pub fn new(count: u32) -> Self {
+        Lives {
+            player1: count,
+            player2: count,
+        }
+    }
+
+    // Take one life away from a player (never goes below 0)
+    pub fn lose_life(&mut self, player: u8) {
+        let lives = if player == 1 {
+            &mut self.player1
+        } else {
+            &mut self.player2
+        };
+        *lives = lives.saturating_sub(1);
+    }
+
+    // Check if a player has no lives left
+    pub fn is_out(&self, player: u8) -> bool {
+        if player == 1 {
+            self.player1 == 0
+        } else {
+            self.player2 == 0
+        }
+    }
+
+    // Draw remaining lives as small circles under each side of the score
+    pub fn draw(&self, canvas: &mut Canvas, ctx: &mut ggez::Context, theme: &Theme) -> GameResult {
+        let icon = Mesh::new_circle(
+            ctx,
+            DrawMode::fill(),
+            [0.0, 0.0],
+            LIFE_ICON_RADIUS,
+            0.1,
+            theme.life,
+        )?;
+        let spacing = LIFE_ICON_RADIUS * 3.0;
+
+        // Your lives grow to the right from the left quarter of the screen
+        for i in 0..self.player1 {
+            let x = SCREEN_WIDTH / 4.0 + i as f32 * spacing;
+            canvas.draw(&icon, DrawParam::default().dest([x, 55.0]));
+        }
+
+        // AI lives grow to the left from the right quarter of the screen
+        for i in 0..self.player2 {
+            let x = 3.0 * SCREEN_WIDTH / 4.0 - i as f32 * spacing;
+            canvas.draw(&icon, DrawParam::default().dest([x, 55.0]));
+        }
+        Ok(())
+    }
+
+    // Give everyone their lives back for a new game
+    pub fn reset(&mut self, count: u32) {
+        self.player1 = count;
+        self.player2 = count;
+    }
+}
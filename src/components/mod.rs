@@ -1,5 +1,8 @@
 // This file organizes the main parts of our game into separate modules
 
 pub mod ball; // Contains everything about the ball (movement, appearance)
+pub mod launcher; // The ball machine for training mode
+pub mod lives; // Lives for arcade mode (losing them, drawing icons)
+pub mod obstacle; // Blocks in the middle of the court for obstacle mode
 pub mod paddle; // Contains paddle-related code (player controls, collision)
 pub mod score; // Handles scoring system and display
@@ -0,0 +1,135 @@
+// This file contains the blocks that stand in the middle of the court in
+// obstacle mode. The ball bounces off them; some of them slide up and down
+
+// Import needed tools and settings for our game
+use crate::components::ball::Ball;
+use crate::game::constants::*;
+use crate::game::court::Court;
+use ggez::graphics::{Color, DrawMode, Mesh, Rect};
+use ggez::GameResult;
+
+// Define what makes up an obstacle
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Obstacle {
+    pub x: f32,      // Left edge, from the left side of the court
+    pub y: f32,      // Top edge, from the top of the court
+    pub width: f32,  // How wide it is
+    pub height: f32, // How tall it is
+    pub speed: f32,  // How fast it slides (pixels per second, positive = down, 0 = stands still)
+    pub min_y: f32,  // Highest its top edge goes while sliding
+    pub max_y: f32,  // Lowest its top edge goes while sliding
+}
+
+impl Obstacle {
+    // Create a block that stays where it is put
+    pub fn fixed(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Obstacle {
+            x,
+            y,
+            width,
+            height,
+            speed: 0.0,
+            min_y: y,
+            max_y: y,
+        }
+    }
+
+    // Create a block that slides up and down between `min_y` and `max_y`
+    pub fn sliding(x: f32, y: f32, speed: f32, min_y: f32, max_y: f32) -> Self {
+        Obstacle {
+            speed,
+            min_y,
+            max_y,
+            ..Obstacle::fixed(x, y, OBSTACLE_WIDTH, OBSTACLE_HEIGHT)
+        }
+    }
+
+    // The area the block covers
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+
+    // Slide the block along, turning around at either end of its track
+    pub fn update(&mut self, delta: f32) {
+        if self.speed == 0.0 {
+            return;
+        }
+        self.y += self.speed * delta;
+        if self.y <= self.min_y {
+            self.y = self.min_y;
+            self.speed = self.speed.abs();
+        } else if self.y >= self.max_y {
+            self.y = self.max_y;
+            self.speed = -self.speed.abs();
+        }
+    }
+
+    // Create the actual shape that will be drawn on screen (white, so the
+    // renderer can tint it with the theme)
+    pub fn get_mesh(&self, ctx: &mut ggez::Context) -> GameResult<Mesh> {
+        Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), self.rect(), 3.0, Color::WHITE)
+    }
+}
+
+// The blocks for obstacle mode on a court: two standing in the middle above
+// and below the serve, and two sliding in opposite directions a third of the
+// way in from each end
+pub fn layout(court: &Court) -> Vec<Obstacle> {
+    let middle_x = court.width / 2.0 - OBSTACLE_WIDTH / 2.0;
+    let top = court.height * 0.2 - OBSTACLE_HEIGHT / 2.0;
+    let bottom = court.height * 0.8 - OBSTACLE_HEIGHT / 2.0;
+    let third = court.width / 3.0 - OBSTACLE_WIDTH / 2.0;
+    let two_thirds = court.width * 2.0 / 3.0 - OBSTACLE_WIDTH / 2.0;
+    vec![
+        Obstacle::fixed(middle_x, top, OBSTACLE_WIDTH, OBSTACLE_HEIGHT),
+        Obstacle::fixed(middle_x, bottom, OBSTACLE_WIDTH, OBSTACLE_HEIGHT),
+        Obstacle::sliding(third, top, OBSTACLE_SPEED, top, bottom),
+        Obstacle::sliding(two_thirds, bottom, -OBSTACLE_SPEED, top, bottom),
+    ]
+}
+
+// Bounce the ball off a rectangle if it is touching it, sending it back out
+// of whichever side it went in through. Works for any rectangle, moving or
+// not. Returns true if the ball bounced
+pub fn bounce_off_rect(ball: &mut Ball, rect: Rect) -> bool {
+    // The point of the rectangle closest to the ball's center
+    let nearest_x = ball.x.clamp(rect.x, rect.x + rect.w);
+    let nearest_y = ball.y.clamp(rect.y, rect.y + rect.h);
+    let (gap_x, gap_y) = (ball.x - nearest_x, ball.y - nearest_y);
+    if gap_x * gap_x + gap_y * gap_y > BALL_RADIUS * BALL_RADIUS {
+        return false;
+    }
+
+    // How far the ball reaches into the rectangle from each direction; the
+    // shallower one is the side it came in through
+    let from_left = ball.x < rect.x + rect.w / 2.0;
+    let from_top = ball.y < rect.y + rect.h / 2.0;
+    let overlap_x = if from_left {
+        ball.x + BALL_RADIUS - rect.x
+    } else {
+        rect.x + rect.w - (ball.x - BALL_RADIUS)
+    };
+    let overlap_y = if from_top {
+        ball.y + BALL_RADIUS - rect.y
+    } else {
+        rect.y + rect.h - (ball.y - BALL_RADIUS)
+    };
+
+    // Push the ball back out and send it away from that side
+    if overlap_x < overlap_y {
+        if from_left {
+            ball.x = rect.x - BALL_RADIUS;
+            ball.dx = -ball.dx.abs() * WALL_RESTITUTION;
+        } else {
+            ball.x = rect.x + rect.w + BALL_RADIUS;
+            ball.dx = ball.dx.abs() * WALL_RESTITUTION;
+        }
+    } else if from_top {
+        ball.y = rect.y - BALL_RADIUS;
+        ball.dy = -ball.dy.abs() * WALL_RESTITUTION;
+    } else {
+        ball.y = rect.y + rect.h + BALL_RADIUS;
+        ball.dy = ball.dy.abs() * WALL_RESTITUTION;
+    }
+    true
+}
@@ -2,6 +2,7 @@
 
 // Import needed tools and settings for our game
 use crate::game::constants::*;
+use crate::game::court::CourtPreset;
 use ggez::graphics::{
     Color,    // For setting paddle colors
     DrawMode, // For choosing how shapes are drawn
@@ -11,20 +12,38 @@ use ggez::graphics::{
 use ggez::GameResult;
 
 // Define what makes up a paddle
+#[derive(Clone)]
 pub struct Paddle {
-    pub x: f32,       // Position from left side of screen
-    pub y: f32,       // Position from top of screen
-    pub color: Color, // Paddle's color
+    pub x: f32,            // Position from left side of screen
+    pub y: f32,            // Position from top of screen
+    pub velocity: f32,     // Current vertical speed (positive = moving down)
+    pub acceleration: f32, // How fast the speed is changing (momentum physics)
+    pub mass: f32,         // How heavy the paddle is (momentum physics)
+    pub friction: f32,     // How quickly it slows down on its own (momentum physics)
+    pub height: f32,       // How tall the paddle is
+    pub speed: f32,        // Top speed when a player steers it (pixels per second)
+    pub hit_cooldown: f32, // Seconds left before the paddle can hit the ball again
+    pub court_height: f32, // How tall the court is that the paddle moves in
+    pub color: Color,      // Paddle's color
 }
 
 // Define what a paddle can do
 impl Paddle {
     // Create a new paddle at a specific position
     pub fn new(x: f32, y: f32) -> Self {
+        let court = CourtPreset::Classic.court();
         Paddle {
-            x,                   // Set left position
-            y,                   // Set top position
-            color: Color::WHITE, // Make it white
+            x,                          // Set left position
+            y,                          // Set top position
+            velocity: 0.0,              // Start standing still
+            acceleration: 0.0,          // Not speeding up or slowing down
+            mass: PADDLE_MASS,          // Default paddle weight
+            friction: PADDLE_FRICTION,  // Normal grip on the court
+            height: PADDLE_HEIGHT,      // Normal size
+            speed: PLAYER_PADDLE_SPEED, // Normal quickness
+            hit_cooldown: 0.0,          // Ready to hit straight away
+            court_height: court.height, // The classic court until a match sets it
+            color: Color::WHITE,        // Make it white
         }
     }
 
@@ -33,21 +52,69 @@ impl Paddle {
         // Update paddle position
         self.y += amount;
 
-        // Keep paddle within screen boundaries
-        self.y = self.y.clamp(0.0, SCREEN_HEIGHT - PADDLE_HEIGHT);
+        // Keep paddle within the court
+        let max_y = self.court_height - self.height;
+        self.y = self.y.clamp(0.0, max_y);
+
+        // Stop dead when pushing into the top or bottom edge
+        if (self.y <= 0.0 && self.velocity < 0.0) || (self.y >= max_y && self.velocity > 0.0) {
+            self.velocity = 0.0;
+        }
+    }
+
+    // How much quicker this paddle is than a normal one (1 = normal)
+    pub fn speed_scale(&self) -> f32 {
+        self.speed / PLAYER_PADDLE_SPEED
+    }
+
+    // Change speed towards the target speed, by at most `rate` per second
+    pub fn accelerate_towards(&mut self, target: f32, rate: f32, delta: f32) {
+        let max_change = rate * delta;
+        let change = (target - self.velocity).clamp(-max_change, max_change);
+        self.velocity += change;
+    }
+
+    // Push the paddle with a force, slowed down by friction (momentum physics)
+    pub fn apply_force(&mut self, force: f32, delta: f32) {
+        self.acceleration = force / self.mass - self.velocity * self.friction;
+        self.velocity += self.acceleration * delta;
+    }
+
+    // Work out the angle to send the ball back at, based on where it hit the paddle:
+    // the center returns it flat, the edges return it steeply (radians, positive = down)
+    pub fn deflection_angle(&self, ball_y: f32) -> f32 {
+        let center = self.y + self.height / 2.0;
+        let offset = ((ball_y - center) / (self.height / 2.0)).clamp(-1.0, 1.0);
+        offset * MAX_DEFLECTION_ANGLE
+    }
+
+    // Check if the paddle is allowed to return the ball right now
+    pub fn can_hit(&self) -> bool {
+        self.hit_cooldown <= 0.0
+    }
+
+    // Ignore the ball for a moment after returning it, so it can't bounce twice
+    pub fn start_hit_cooldown(&mut self) {
+        self.hit_cooldown = PADDLE_HIT_COOLDOWN;
+    }
+
+    // Count down the hit cooldown
+    pub fn update_cooldown(&mut self, delta: f32) {
+        self.hit_cooldown = (self.hit_cooldown - delta).max(0.0);
     }
 
-    // Create the actual shape that will be drawn on screen
+    // Create the shape that will be drawn on screen, with its top left corner
+    // at 0, 0 (the renderer keeps it and moves it to the paddle each frame)
     pub fn get_mesh(&self, ctx: &mut ggez::Context) -> GameResult<Mesh> {
         // Make a rounded rectangle for the paddle
         Mesh::new_rounded_rectangle(
             ctx,
             DrawMode::fill(), // Make it solid, not hollow
             Rect::new(
-                self.x,        // Left edge
-                self.y,        // Top edge
-                PADDLE_WIDTH,  // How wide
-                PADDLE_HEIGHT, // How tall
+                0.0,          // Left edge
+                0.0,          // Top edge
+                PADDLE_WIDTH, // How wide
+                self.height,  // How tall
             ),
             5.0,        // How rounded the corners are
             self.color, // What color to use
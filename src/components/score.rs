@@ -1,87 +1,196 @@
-// Import required modules for graphics, game constants, and timing
+// Import required modules for graphics, game constants, and shared listeners
 use crate::game::constants::*;
-use ggez::graphics::{Canvas, Color, DrawParam, Text, TextFragment};
+use crate::graphics::hud::{Anchor, HudLine, HudText};
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawParam, Text, TextFragment};
 use ggez::GameResult;
-use std::time::Instant;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// What happened when someone scored, passed to every listener
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScoreEvent {
+    pub scorer: u8,   // Who scored (1=you, 2=AI)
+    pub player1: u32, // Your score after the point
+    pub player2: u32, // AI score after the point
+}
+
+// Anything that wants to hear about score changes (HUD effects, sounds, stats...)
+pub trait ScoreListener {
+    // Called every time a point is scored
+    fn on_score(&mut self, event: &ScoreEvent);
+
+    // Called when the scores go back to 0
+    fn on_reset(&mut self) {}
+}
+
+// Shared handle to a listener, so its owner can still read it after registering
+pub type SharedListener = Rc<RefCell<dyn ScoreListener>>;
+
+// Remembers who just scored so the score board can highlight them. The
+// highlight runs on game time, so it stands still while the match is paused
+#[derive(Default)]
+pub struct ScoreFlash {
+    pub winner: Option<u8>, // Store who just scored (1=you, 2=AI)
+    pub age: Option<f32>,   // Seconds of play the highlight has shown (None = not showing)
+}
+
+impl ScoreFlash {
+    // Let `delta` seconds of play go by, ending the highlight once it has run its time
+    pub fn update(&mut self, delta: f32) {
+        if let Some(age) = self.age {
+            let age = age + delta;
+            self.age = (age < SCORE_FLASH_DURATION).then_some(age);
+        }
+    }
+}
+
+impl ScoreListener for ScoreFlash {
+    // Start the highlight animation for whoever scored
+    fn on_score(&mut self, event: &ScoreEvent) {
+        self.winner = Some(event.scorer);
+        self.age = Some(0.0);
+    }
+
+    // Clear the highlight
+    fn on_reset(&mut self) {
+        self.winner = None;
+        self.age = None;
+    }
+}
 
 // Main structure to handle game scoring and score display
 pub struct Score {
-    pub player1: u32,                 // Store your (left player) score
-    pub player2: u32,                 // Store AI (right player) score
-    pub flash_winner: Option<u8>,     // Store who just scored (1=you, 2=AI)
-    pub flash_start: Option<Instant>, // Timer for score highlight animation
+    pub player1: u32,                   // Store your (left player) score
+    pub player2: u32,                   // Store AI (right player) score
+    pub games_player1: u32,             // Games you have won this match
+    pub games_player2: u32,             // Games the AI has won this match
+    pub flash: Rc<RefCell<ScoreFlash>>, // Highlight animation, also a listener
+    listeners: Vec<SharedListener>,     // Everyone told about score changes
 }
 
 impl Score {
     // Create new score board with all scores at 0
     pub fn new() -> Self {
-        Score {
+        let flash = Rc::new(RefCell::new(ScoreFlash::default()));
+        let mut score = Score {
             player1: 0,
             player2: 0,
-            flash_winner: None,
-            flash_start: None,
-        }
+            games_player1: 0,
+            games_player2: 0,
+            flash: flash.clone(),
+            listeners: Vec::new(),
+        };
+        score.add_listener(flash); // The highlight reacts to points like any other listener
+        score
+    }
+
+    // Register something to be told about every score change
+    pub fn add_listener(&mut self, listener: SharedListener) {
+        self.listeners.push(listener);
     }
 
-    // Increase your score by 1 and trigger highlight animation
+    // Increase your score by 1 and tell the listeners
     pub fn increment_player1(&mut self) {
         self.player1 += 1;
-        self.flash_winner = Some(1);
-        self.flash_start = Some(Instant::now());
+        self.notify(1);
     }
 
-    // Increase AI's score by 1 and trigger highlight animation
+    // Increase AI's score by 1 and tell the listeners
     pub fn increment_player2(&mut self) {
         self.player2 += 1;
-        self.flash_winner = Some(2);
-        self.flash_start = Some(Instant::now());
-    }
-
-    // Main draw function that decides whether to show normal or highlighted score
-    pub fn draw(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> GameResult {
-        // Check if highlight animation is active (lasts 3 seconds)
-        if let Some(flash_start) = self.flash_start {
-            if flash_start.elapsed().as_secs_f32() < 3.0 {
-                self.draw_highlighted_score(canvas, ctx)?; // Show fancy animation
-            } else {
-                self.draw_normal_score(canvas, ctx)?; // Show regular score
-            }
+        self.notify(2);
+    }
+
+    // Move the score board's animations on by `delta` seconds of play
+    pub fn update(&mut self, delta: f32) {
+        self.flash.borrow_mut().update(delta);
+    }
+
+    // Check if the last scorer's score is still highlighted
+    pub fn flashing(&self) -> bool {
+        self.flash.borrow().age.is_some()
+    }
+
+    // Give a game to a player and start the next game from 0-0
+    pub fn win_game(&mut self, player: u8) {
+        if player == 1 {
+            self.games_player1 += 1;
+        } else {
+            self.games_player2 += 1;
+        }
+        self.player1 = 0;
+        self.player2 = 0;
+    }
+
+    // Start the current game again from 0-0, keeping the games already won
+    pub fn reset_points(&mut self) {
+        self.player1 = 0;
+        self.player2 = 0;
+        for listener in &self.listeners {
+            listener.borrow_mut().on_reset();
+        }
+    }
+
+    // Tell every listener who scored and what the score is now
+    fn notify(&self, scorer: u8) {
+        let event = ScoreEvent {
+            scorer,
+            player1: self.player1,
+            player2: self.player2,
+        };
+        for listener in &self.listeners {
+            listener.borrow_mut().on_score(&event);
+        }
+    }
+
+    // Main draw function that decides whether to show normal or highlighted
+    // score. The normal score line is kept in `hud` until the score changes
+    pub fn draw(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        theme: &Theme,
+        hud: &HudText,
+    ) -> GameResult {
+        // Check if highlight animation is active (lasts SCORE_FLASH_DURATION)
+        if self.flashing() {
+            self.draw_highlighted_score(canvas, ctx, theme)?; // Show fancy animation
         } else {
-            self.draw_normal_score(canvas, ctx)?; // Show regular score
+            self.draw_normal_score(canvas, ctx, theme, hud)?; // Show regular score
         }
         Ok(())
     }
 
     // Draw regular score display at top of screen
-    fn draw_normal_score(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> GameResult {
+    fn draw_normal_score(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        theme: &Theme,
+        hud: &HudText,
+    ) -> GameResult {
         // Create score text with both scores
         let score_text = format!(
             "Your Score: {}  |  Player 2 Score: {}",
             self.player1, self.player2
         );
 
-        // Setup text style (white, medium size)
-        let text = Text::new(
-            TextFragment::new(score_text)
-                .scale(16.0)
-                .color(Color::WHITE),
-        );
-
-        // Get text dimensions for centering
-        let dims = text.measure(ctx)?;
-
-        // Draw centered at top of screen
-        canvas.draw(
-            &text,
-            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 20.0]),
-        );
-        Ok(())
+        // Theme text color, medium size, centered at the top of the screen
+        let line = HudLine::new(score_text, 16.0, theme.text).at(Anchor::TopCenter, 20.0);
+        hud.draw(canvas, ctx, "score", &line)
     }
 
     // Draw animated score display when someone scores
-    fn draw_highlighted_score(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> GameResult {
+    fn draw_highlighted_score(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        theme: &Theme,
+    ) -> GameResult {
         // Setup positions based on who scored
-        let (winner_score, other_score, winner_x, other_x) = match self.flash_winner {
+        let flash_winner = self.flash.borrow().winner;
+        let (winner_score, other_score, winner_x, other_x) = match flash_winner {
             Some(1) => (
                 // You scored
                 self.player1,             // Winner score is yours
@@ -99,11 +208,11 @@ impl Score {
             _ => return Ok(()),
         };
 
-        // Create highlighted text for scorer (green, larger)
+        // Create highlighted text for scorer (highlight color, larger)
         let winner_text = Text::new(
             TextFragment::new(format!(
                 "{}: {}",
-                if self.flash_winner == Some(1) {
+                if flash_winner == Some(1) {
                     "Your Score"
                 } else {
                     "Player 2 Score"
@@ -111,15 +220,15 @@ impl Score {
                 winner_score
             ))
             .scale(18.0)
-            .color(Color::GREEN),
+            .color(theme.highlight),
         );
         let winner_dims = winner_text.measure(ctx)?;
 
-        // Create normal text for other player (white, regular size)
+        // Create normal text for other player (regular color and size)
         let other_text = Text::new(
             TextFragment::new(format!(
                 "{}: {}",
-                if self.flash_winner == Some(1) {
+                if flash_winner == Some(1) {
                     "Player 2 Score"
                 } else {
                     "Your Score"
@@ -127,7 +236,7 @@ impl Score {
                 other_score
             ))
             .scale(16.0)
-            .color(Color::WHITE),
+            .color(theme.text),
         );
         let other_dims = other_text.measure(ctx)?;
 
@@ -145,7 +254,7 @@ impl Score {
         let big_score = Text::new(
             TextFragment::new(winner_score.to_string())
                 .scale(22.0)
-                .color(Color::GREEN),
+                .color(theme.highlight),
         );
         let big_dims = big_score.measure(ctx)?;
         canvas.draw(
@@ -160,7 +269,10 @@ impl Score {
     pub fn reset(&mut self) {
         self.player1 = 0; // Your score to 0
         self.player2 = 0; // AI score to 0
-        self.flash_winner = None; // Clear winner highlight
-        self.flash_start = None; // Clear animation timer
+        self.games_player1 = 0; // Match score back to 0-0
+        self.games_player2 = 0;
+        for listener in &self.listeners {
+            listener.borrow_mut().on_reset(); // Clear winner highlight and anything else
+        }
     }
 }
@@ -0,0 +1,469 @@
+// This file contains the computer opponents that can control either paddle
+
+use crate::components::{ball::Ball, paddle::Paddle};
+use crate::game::constants::*;
+use crate::game::controller::{GameView, MatchRng, PaddleController};
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// How hard the computer opponent is to beat
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum AiDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    Impossible,
+}
+
+// The numbers that make each difficulty feel different
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AiParams {
+    pub reaction_speed: f32, // How fast the AI paddle moves (pixels per second)
+    pub error_margin: f32,   // How far off the AI's aim can be (pixels)
+    pub hesitation_chance: f64, // Chance each frame that the AI doesn't move
+    pub predictive: bool,    // Aim where the ball will arrive instead of where it is
+}
+
+// The speed and accuracy sliders on the settings screen: how many steps each
+// is moved away from what the difficulty plays like on its own (0 = unchanged)
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct AiTuning {
+    pub speed: i32,    // Steps faster (+) or slower (-)
+    pub accuracy: i32, // Steps more accurate (+) or sloppier (-)
+}
+
+impl AiDifficulty {
+    // Every difficulty, easiest first
+    pub const ALL: [AiDifficulty; 4] = [
+        AiDifficulty::Easy,
+        AiDifficulty::Medium,
+        AiDifficulty::Hard,
+        AiDifficulty::Impossible,
+    ];
+
+    // Get the tuning values for this difficulty
+    pub fn params(self) -> AiParams {
+        match self {
+            AiDifficulty::Easy => AiParams {
+                reaction_speed: AI_PADDLE_SPEED - 100.0,
+                error_margin: 25.0,
+                hesitation_chance: 0.3,
+                predictive: false,
+            },
+            AiDifficulty::Medium => AiParams {
+                reaction_speed: AI_PADDLE_SPEED - 10.0,
+                error_margin: 3.0,
+                hesitation_chance: 0.12,
+                predictive: false,
+            },
+            AiDifficulty::Hard => AiParams {
+                reaction_speed: AI_PADDLE_SPEED + 100.0,
+                error_margin: 2.0,
+                hesitation_chance: 0.05,
+                predictive: true,
+            },
+            AiDifficulty::Impossible => AiParams {
+                reaction_speed: AI_PADDLE_SPEED * 2.0,
+                error_margin: 0.0,
+                hesitation_chance: 0.0,
+                predictive: true,
+            },
+        }
+    }
+
+    // The tuning values for this difficulty with the sliders applied
+    pub fn tuned_params(self, tuning: AiTuning) -> AiParams {
+        tuning.apply(self.params())
+    }
+
+    // Move on to the next difficulty (wraps back to Easy)
+    pub fn next(self) -> Self {
+        match self {
+            AiDifficulty::Easy => AiDifficulty::Medium,
+            AiDifficulty::Medium => AiDifficulty::Hard,
+            AiDifficulty::Hard => AiDifficulty::Impossible,
+            AiDifficulty::Impossible => AiDifficulty::Easy,
+        }
+    }
+
+    // Look up a difficulty by its name, ignoring case (e.g. "hard")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "easy" => Some(AiDifficulty::Easy),
+            "medium" => Some(AiDifficulty::Medium),
+            "hard" => Some(AiDifficulty::Hard),
+            "impossible" => Some(AiDifficulty::Impossible),
+            _ => None,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "Easy",
+            AiDifficulty::Medium => "Medium",
+            AiDifficulty::Hard => "Hard",
+            AiDifficulty::Impossible => "Impossible",
+        }
+    }
+}
+
+impl AiTuning {
+    // Whether either slider has been moved off the difficulty's own values
+    pub fn is_custom(self) -> bool {
+        self != AiTuning::default()
+    }
+
+    // Whether both sliders are within the range the settings screen offers
+    pub fn in_range(self) -> bool {
+        self.speed.abs() <= AI_TUNING_STEPS && self.accuracy.abs() <= AI_TUNING_STEPS
+    }
+
+    // Change a difficulty's numbers by the slider steps. Speed scales how fast
+    // the paddle moves; accuracy narrows or widens its aim and makes it
+    // hesitate less or more. Whether it reads the ball's path stays as it is
+    pub fn apply(self, params: AiParams) -> AiParams {
+        let speed = 1.0 + AI_SPEED_PER_STEP * self.speed as f32;
+        let sloppier = -self.accuracy;
+        AiParams {
+            reaction_speed: params.reaction_speed * speed,
+            error_margin: (params.error_margin + AI_ERROR_PER_STEP * sloppier as f32).max(0.0),
+            hesitation_chance: (params.hesitation_chance
+                + AI_HESITATION_PER_STEP * sloppier as f64)
+                .clamp(0.0, AI_MAX_HESITATION),
+            predictive: params.predictive,
+        }
+    }
+
+    // Move a slider one step, wrapping from one end to the other
+    pub fn nudge(steps: i32, by: i32) -> i32 {
+        let range = AI_TUNING_STEPS * 2 + 1;
+        (steps + AI_TUNING_STEPS + by).rem_euclid(range) - AI_TUNING_STEPS
+    }
+}
+
+// The limits the adaptive AI stays inside, however far ahead or behind it is
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AdaptiveBounds {
+    pub min_speed: f32, // Slowest the paddle moves (pixels per second)
+    pub max_speed: f32, // Fastest the paddle moves (pixels per second)
+    pub min_error: f32, // Best aim (pixels off)
+    pub max_error: f32, // Worst aim (pixels off)
+}
+
+impl Default for AdaptiveBounds {
+    // The bounds from the settings file
+    fn default() -> Self {
+        AdaptiveBounds {
+            min_speed: ADAPTIVE_MIN_SPEED,
+            max_speed: ADAPTIVE_MAX_SPEED,
+            min_error: ADAPTIVE_MIN_ERROR,
+            max_error: ADAPTIVE_MAX_ERROR,
+        }
+    }
+}
+
+impl AdaptiveBounds {
+    // Move a difficulty's speed and aim toward the bounds by `push`: at 1 the
+    // fastest and most accurate allowed, at -1 the slowest and sloppiest, and
+    // at 0 unchanged (apart from being kept inside the bounds)
+    pub fn adjust(&self, params: AiParams, push: f32) -> AiParams {
+        let push = push.clamp(-1.0, 1.0);
+        let speed = params.reaction_speed.clamp(self.min_speed, self.max_speed);
+        let error = params.error_margin.clamp(self.min_error, self.max_error);
+        let (reaction_speed, error_margin) = if push >= 0.0 {
+            (
+                speed + (self.max_speed - speed) * push,
+                error - (error - self.min_error) * push,
+            )
+        } else {
+            (
+                speed + (speed - self.min_speed) * push,
+                error - (self.max_error - error) * push,
+            )
+        };
+        AiParams {
+            reaction_speed,
+            error_margin,
+            ..params
+        }
+    }
+}
+
+// How hard the adaptive AI should push, from -1 (ease right off) to 1 (play
+// its best), given its lead in points (negative = behind) and the average
+// paddle hits per point lately. Being behind pushes it harder and being ahead
+// eases it off, reaching the bounds ADAPTIVE_SCORE_SPAN points either way.
+// Short rallies mean the points are one-sided, so the push is at full
+// strength; by ADAPTIVE_CLOSE_RALLY hits play is already close and it's halved
+pub fn adaptive_push(score_gap: i32, recent_rally: f32) -> f32 {
+    let behind = (-score_gap as f32 / ADAPTIVE_SCORE_SPAN).clamp(-1.0, 1.0);
+    let one_sided = (1.0 - recent_rally / ADAPTIVE_CLOSE_RALLY).clamp(0.0, 1.0);
+    behind * (0.5 + 0.5 * one_sided)
+}
+
+// Check if the ball is travelling towards this paddle, whichever side it is on
+pub fn ball_approaching(ball: &Ball, paddle: &Paddle) -> bool {
+    if paddle.x > ball.x {
+        ball.dx > 0.0
+    } else {
+        ball.dx < 0.0
+    }
+}
+
+// Work out the height the ball will be at when it reaches the paddle,
+// bouncing it off the top and bottom walls along the way
+pub fn predict_ball_y(ball: &Ball, paddle: &Paddle) -> f32 {
+    if !ball_approaching(ball, paddle) {
+        return ball.y;
+    }
+
+    // The ball meets the left face of the right paddle, or the right face of the left one
+    let target_x = if ball.dx > 0.0 {
+        paddle.x - BALL_RADIUS
+    } else {
+        paddle.x + PADDLE_WIDTH + BALL_RADIUS
+    };
+    let time_to_reach = ((target_x - ball.x) / ball.dx).max(0.0);
+    let unfolded_y = ball.y + ball.dy * time_to_reach;
+
+    // Fold the straight-line path back into the court for each wall bounce
+    let travel = paddle.court_height - 2.0 * BALL_RADIUS;
+    let mut offset = (unfolded_y - BALL_RADIUS).rem_euclid(2.0 * travel);
+    if offset > travel {
+        offset = 2.0 * travel - offset;
+    }
+    BALL_RADIUS + offset
+}
+
+// How far the AI paddle moves this frame to line its center up with `target_y`,
+// with `rng` deciding when it hesitates and how far off its aim is
+pub fn chase_distance(
+    paddle: &Paddle,
+    target_y: f32,
+    params: &AiParams,
+    delta: f32,
+    rng: &mut impl Rng,
+) -> f32 {
+    let paddle_center = paddle.y + paddle.height / 2.0; // Get the center of the AI paddle
+    let hesitation = if rng.gen_bool(params.hesitation_chance) {
+        0.0
+    } else {
+        1.0
+    }; // Simulate hesitation
+    let error_margin: f32 = if params.error_margin > 0.0 {
+        rng.gen_range(-params.error_margin..params.error_margin) // Add random error to movement
+    } else {
+        0.0
+    };
+
+    // Move the AI paddle up or down, without overshooting the target. A
+    // quicker or slower paddle than normal speeds the AI up or down with it
+    let distance = target_y + error_margin - paddle_center;
+    let speed = params.reaction_speed * paddle.speed_scale();
+    let step = (speed * hesitation * delta).min(distance.abs());
+    step * distance.signum()
+}
+
+// Drift the AI paddle back to the middle of the court while waiting for a serve
+fn return_to_middle(paddle: &Paddle, delta: f32) -> f32 {
+    let middle_position = (paddle.court_height - paddle.height) / 2.0;
+    let distance_to_middle = middle_position - paddle.y;
+    if distance_to_middle.abs() > 1.0 {
+        distance_to_middle.signum() * AI_PADDLE_SPEED * paddle.speed_scale() * delta
+    } else {
+        0.0
+    }
+}
+
+// A computer player that chases where the ball is right now
+pub struct SimpleAi {
+    pub difficulty: AiDifficulty, // How quick and accurate it is
+    pub tuning: AiTuning,         // The speed and accuracy sliders on top of that
+    target: Option<f32>,          // Height it is heading for, if it is chasing the ball
+    rng: MatchRng,                // Its own random numbers, so a seed replays its mistakes
+}
+
+// A computer player that aims for where the ball is going to arrive
+pub struct PredictiveAi {
+    pub difficulty: AiDifficulty, // How quick and accurate it is
+    pub tuning: AiTuning,         // The speed and accuracy sliders on top of that
+    target: Option<f32>,          // Height it is heading for, if it is chasing the ball
+    rng: MatchRng,                // Its own random numbers, so a seed replays its mistakes
+}
+
+// A computer player that keeps the match close: it plays like its
+// difficulty while the score is level, and gets quicker and more accurate
+// when behind, or slower and sloppier when ahead, within its bounds
+pub struct AdaptiveAi {
+    pub difficulty: AiDifficulty, // How quick and accurate it is with the score level
+    pub tuning: AiTuning,         // The speed and accuracy sliders on top of that
+    pub bounds: AdaptiveBounds,   // How far it can stray from that either way
+    target: Option<f32>,          // Height it is heading for, if it is chasing the ball
+    rng: MatchRng,                // Its own random numbers, so a seed replays its mistakes
+}
+
+impl SimpleAi {
+    // Create a computer player for a difficulty; the same seed makes the same mistakes
+    pub fn new(difficulty: AiDifficulty, seed: u64) -> Self {
+        SimpleAi {
+            difficulty,
+            tuning: AiTuning::default(),
+            target: None,
+            rng: MatchRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl PredictiveAi {
+    // Create a computer player for a difficulty; the same seed makes the same mistakes
+    pub fn new(difficulty: AiDifficulty, seed: u64) -> Self {
+        PredictiveAi {
+            difficulty,
+            tuning: AiTuning::default(),
+            target: None,
+            rng: MatchRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl AdaptiveAi {
+    // Create an adaptive computer player for a difficulty; the same seed makes the same mistakes
+    pub fn new(difficulty: AiDifficulty, seed: u64) -> Self {
+        AdaptiveAi {
+            difficulty,
+            tuning: AiTuning::default(),
+            bounds: AdaptiveBounds::default(),
+            target: None,
+            rng: MatchRng::seed_from_u64(seed),
+        }
+    }
+
+    // The speed and aim it plays with for a score gap and recent rally length
+    pub fn params(&self, score_gap: i32, recent_rally: f32) -> AiParams {
+        let params = self.difficulty.tuned_params(self.tuning);
+        self.bounds
+            .adjust(params, adaptive_push(score_gap, recent_rally))
+    }
+}
+
+impl PaddleController for SimpleAi {
+    fn desired_move(&mut self, view: &GameView, delta: f32) -> f32 {
+        self.target = None;
+        if view.serving {
+            return return_to_middle(view.paddle, delta);
+        }
+        // Only chase the ball while it is coming towards the AI
+        if !ball_approaching(view.ball, view.paddle) {
+            return 0.0;
+        }
+        self.target = Some(view.ball.y);
+        let params = self.difficulty.tuned_params(self.tuning);
+        chase_distance(view.paddle, view.ball.y, &params, delta, &mut self.rng)
+    }
+
+    fn target_y(&self) -> Option<f32> {
+        self.target
+    }
+
+    fn rng_position(&self) -> Option<u64> {
+        Some(self.rng.get_word_pos() as u64)
+    }
+
+    fn set_rng_position(&mut self, position: u64) {
+        self.rng.set_word_pos(position as u128);
+    }
+}
+
+impl PaddleController for PredictiveAi {
+    fn desired_move(&mut self, view: &GameView, delta: f32) -> f32 {
+        self.target = None;
+        if view.serving {
+            return return_to_middle(view.paddle, delta);
+        }
+        if !ball_approaching(view.ball, view.paddle) {
+            return 0.0;
+        }
+        let target_y = predict_ball_y(view.ball, view.paddle);
+        self.target = Some(target_y);
+        let params = self.difficulty.tuned_params(self.tuning);
+        chase_distance(view.paddle, target_y, &params, delta, &mut self.rng)
+    }
+
+    fn target_y(&self) -> Option<f32> {
+        self.target
+    }
+
+    fn rng_position(&self) -> Option<u64> {
+        Some(self.rng.get_word_pos() as u64)
+    }
+
+    fn set_rng_position(&mut self, position: u64) {
+        self.rng.set_word_pos(position as u128);
+    }
+}
+
+impl PaddleController for AdaptiveAi {
+    fn desired_move(&mut self, view: &GameView, delta: f32) -> f32 {
+        self.target = None;
+        if view.serving {
+            return return_to_middle(view.paddle, delta);
+        }
+        if !ball_approaching(view.ball, view.paddle) {
+            return 0.0;
+        }
+        // The harder levels keep reading the ball's path ahead
+        let params = self.params(view.score_gap, view.recent_rally);
+        let target_y = if params.predictive {
+            predict_ball_y(view.ball, view.paddle)
+        } else {
+            view.ball.y
+        };
+        self.target = Some(target_y);
+        chase_distance(view.paddle, target_y, &params, delta, &mut self.rng)
+    }
+
+    fn target_y(&self) -> Option<f32> {
+        self.target
+    }
+
+    fn rng_position(&self) -> Option<u64> {
+        Some(self.rng.get_word_pos() as u64)
+    }
+
+    fn set_rng_position(&mut self, position: u64) {
+        self.rng.set_word_pos(position as u128);
+    }
+}
+
+// The computer player for a difficulty and slider tuning: the harder levels
+// read the ball's path ahead
+pub fn controller_for(
+    difficulty: AiDifficulty,
+    tuning: AiTuning,
+    seed: u64,
+) -> Box<dyn PaddleController> {
+    if difficulty.params().predictive {
+        Box::new(PredictiveAi {
+            tuning,
+            ..PredictiveAi::new(difficulty, seed)
+        })
+    } else {
+        Box::new(SimpleAi {
+            tuning,
+            ..SimpleAi::new(difficulty, seed)
+        })
+    }
+}
+
+// The adaptive computer player for a difficulty and slider tuning
+pub fn adaptive_controller_for(
+    difficulty: AiDifficulty,
+    tuning: AiTuning,
+    seed: u64,
+) -> Box<dyn PaddleController> {
+    Box::new(AdaptiveAi {
+        tuning,
+        ..AdaptiveAi::new(difficulty, seed)
+    })
+}
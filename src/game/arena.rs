@@ -0,0 +1,83 @@
+// This file holds the arenas that can be picked on the settings screen. Each
+// one pairs a look for the court with a change to how the match plays. They
+// are plain data: the simulation reads the numbers and the renderer the colors
+
+use crate::game::constants::*;
+use serde::{Deserialize, Serialize};
+
+// Ready-made arenas to pick from before a game
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Arena {
+    #[default]
+    Standard, // The normal court, nothing changed
+    Ice,    // Paddles slide about with momentum
+    Rubber, // Walls bounce the ball back faster
+    Space,  // Spin doesn't grip the walls
+}
+
+// What an arena changes, and how it looks
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ArenaRules {
+    pub paddle_friction: Option<f32>, // Paddles always slide, with this friction (None = as set)
+    pub wall_restitution: f32, // Ball speed kept off the top and bottom walls (above 1 = faster)
+    pub wall_spin_grip: bool,  // Spin changes the angle the ball comes off the wall
+    pub floor: Option<[u8; 4]>, // Tint laid over the court (red, green, blue, opacity)
+    pub lines: Option<[u8; 3]>, // Color of the court lines instead of the theme's
+}
+
+impl Arena {
+    // Every arena, in the order the settings screen cycles through them
+    pub const ALL: [Arena; 4] = [Arena::Standard, Arena::Ice, Arena::Rubber, Arena::Space];
+
+    // What playing in this arena is like
+    pub fn rules(self) -> ArenaRules {
+        let standard = ArenaRules {
+            paddle_friction: None,
+            wall_restitution: WALL_RESTITUTION,
+            wall_spin_grip: true,
+            floor: None,
+            lines: None,
+        };
+        match self {
+            Arena::Standard => standard,
+            Arena::Ice => ArenaRules {
+                paddle_friction: Some(ICE_PADDLE_FRICTION),
+                floor: Some([150, 210, 255, 40]),
+                lines: Some([200, 235, 255]),
+                ..standard
+            },
+            Arena::Rubber => ArenaRules {
+                wall_restitution: RUBBER_WALL_RESTITUTION,
+                floor: Some([255, 110, 40, 30]),
+                lines: Some([255, 140, 60]),
+                ..standard
+            },
+            Arena::Space => ArenaRules {
+                wall_spin_grip: false,
+                floor: Some([0, 0, 10, 140]),
+                lines: Some([150, 120, 255]),
+                ..standard
+            },
+        }
+    }
+
+    // Move on to the next arena (wraps back to Standard)
+    pub fn next(self) -> Self {
+        match self {
+            Arena::Standard => Arena::Ice,
+            Arena::Ice => Arena::Rubber,
+            Arena::Rubber => Arena::Space,
+            Arena::Space => Arena::Standard,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            Arena::Standard => "Standard",
+            Arena::Ice => "Ice",
+            Arena::Rubber => "Rubber",
+            Arena::Space => "Space",
+        }
+    }
+}
@@ -0,0 +1,220 @@
+// This file schedules sound cues against game time rather than wall-clock time,
+// so a countdown beep lands exactly when the number changes even if frames
+// stutter, and nothing plays early or late across a pause
+
+use crate::game::constants::*;
+use crate::game::events::{EventListener, GameEvent};
+use serde::{Deserialize, Serialize};
+
+// Sounds the game can ask for
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cue {
+    CountdownBeep, // A number of the 3-2-1 countdown appears
+    CountdownGo,   // The countdown ends and the ball is served
+    Goal,          // Someone scored a point
+    PaddleHit,     // A paddle returned the ball
+    Bounce,        // The ball bounced off a wall or a block
+    MenuClick,     // A menu entry was moved to or picked
+}
+
+// Kinds of sound, each with its own volume on the settings screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SoundCategory {
+    Hits,      // The ball meeting paddles, walls and blocks
+    Ui,        // Clicks while moving through the menus
+    Announcer, // The countdown calls and the point jingle
+    Crowd,     // The crowd murmuring in the background during a match
+}
+
+// How loud each kind of sound plays, from 0 (off) to MAX_SOUND_VOLUME. All
+// of it is also turned down by the main volume
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundMix {
+    pub hits: u32,
+    pub ui: u32,
+    pub announcer: u32,
+    pub crowd: u32,
+}
+
+impl Cue {
+    // Which volume the cue plays at
+    pub fn category(self) -> SoundCategory {
+        match self {
+            Cue::CountdownBeep | Cue::CountdownGo | Cue::Goal => SoundCategory::Announcer,
+            Cue::PaddleHit | Cue::Bounce => SoundCategory::Hits,
+            Cue::MenuClick => SoundCategory::Ui,
+        }
+    }
+}
+
+impl SoundCategory {
+    // Every kind, in the order they're listed on the settings screen
+    pub const ALL: [SoundCategory; 4] = [
+        SoundCategory::Hits,
+        SoundCategory::Ui,
+        SoundCategory::Announcer,
+        SoundCategory::Crowd,
+    ];
+
+    // Name shown on the settings screen
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundCategory::Hits => "Hit Sounds",
+            SoundCategory::Ui => "Menu Clicks",
+            SoundCategory::Announcer => "Announcer",
+            SoundCategory::Crowd => "Crowd",
+        }
+    }
+}
+
+impl Default for SoundMix {
+    // Everything all the way up
+    fn default() -> Self {
+        SoundMix {
+            hits: MAX_SOUND_VOLUME,
+            ui: MAX_SOUND_VOLUME,
+            announcer: MAX_SOUND_VOLUME,
+            crowd: MAX_SOUND_VOLUME,
+        }
+    }
+}
+
+impl SoundMix {
+    // The volume setting for one kind of sound
+    pub fn volume(&self, category: SoundCategory) -> u32 {
+        match category {
+            SoundCategory::Hits => self.hits,
+            SoundCategory::Ui => self.ui,
+            SoundCategory::Announcer => self.announcer,
+            SoundCategory::Crowd => self.crowd,
+        }
+    }
+
+    // Step one kind's volume by `by`, wrapping from full back round to off
+    pub fn slide(&mut self, category: SoundCategory, by: i32) {
+        let volume = match category {
+            SoundCategory::Hits => &mut self.hits,
+            SoundCategory::Ui => &mut self.ui,
+            SoundCategory::Announcer => &mut self.announcer,
+            SoundCategory::Crowd => &mut self.crowd,
+        };
+        let steps = MAX_SOUND_VOLUME as i32 + 1;
+        *volume = (*volume as i32 + by).rem_euclid(steps) as u32;
+    }
+
+    // How loud one kind of sound plays, 0 to 1
+    pub fn level(&self, category: SoundCategory) -> f32 {
+        self.volume(category) as f32 / MAX_SOUND_VOLUME as f32
+    }
+}
+
+// How hard the background music drives. It follows the ball's speed over the
+// last few seconds, and only ever changes a little at a time, so the music
+// swells as a rally heats up and settles when it calms down without lurching
+#[derive(Default)]
+pub struct MusicIntensity {
+    average_speed: f32, // Ball speed averaged over the last few seconds
+    level: f32,         // How far the driving layer is turned up (0 to MUSIC_MAX_INTENSITY)
+}
+
+// A cue waiting for its moment
+#[derive(Clone, Copy, Debug)]
+struct Scheduled {
+    at: f32, // Game time the cue should play at (seconds)
+    cue: Cue,
+}
+
+// Keeps game time and the cues waiting to be played
+#[derive(Default)]
+pub struct AudioScheduler {
+    pub clock: f32,        // Game time so far (seconds); only moves while the match runs
+    queue: Vec<Scheduled>, // Cues still in the future, soonest first
+    ready: Vec<Cue>,       // Cues whose time has come, waiting to be played
+}
+
+impl AudioScheduler {
+    // Start with nothing scheduled at game time zero
+    pub fn new() -> Self {
+        AudioScheduler::default()
+    }
+
+    // Play `cue` once `delay` seconds of game time have passed (0 = right away)
+    pub fn schedule_in(&mut self, delay: f32, cue: Cue) {
+        if delay <= 0.0 {
+            self.ready.push(cue);
+            return;
+        }
+        let at = self.clock + delay;
+        let index = self.queue.partition_point(|scheduled| scheduled.at <= at);
+        self.queue.insert(index, Scheduled { at, cue });
+    }
+
+    // Move game time forward and collect every cue that is now due
+    pub fn advance(&mut self, delta: f32) {
+        self.clock += delta;
+        // A tiny allowance so rounding in the step sizes can't push a cue one step late
+        let due = self
+            .queue
+            .partition_point(|scheduled| scheduled.at <= self.clock + CUE_TIME_TOLERANCE);
+        self.ready
+            .extend(self.queue.drain(..due).map(|scheduled| scheduled.cue));
+    }
+
+    // Hand over the cues that are due, in the order they should play
+    pub fn take_ready(&mut self) -> Vec<Cue> {
+        std::mem::take(&mut self.ready)
+    }
+
+    // Drop everything still waiting (e.g. when a match is abandoned)
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.ready.clear();
+    }
+}
+
+impl MusicIntensity {
+    // Start calm, as if the ball hadn't moved
+    pub fn new() -> Self {
+        MusicIntensity::default()
+    }
+
+    // Follow the ball for another `delta` seconds of play
+    pub fn update(&mut self, delta: f32, ball_speed: f32) {
+        // Blend the new speed in, with older speeds fading out over the window
+        let blend = (delta / MUSIC_SPEED_WINDOW).min(1.0);
+        self.average_speed += (ball_speed - self.average_speed) * blend;
+
+        // Serve speed and below is calm; the top speed is full on
+        let target = ((self.average_speed - BALL_SPEED) / (MAX_BALL_SPEED - BALL_SPEED))
+            .clamp(0.0, 1.0)
+            * MUSIC_MAX_INTENSITY;
+        let most = MUSIC_INTENSITY_RATE * delta;
+        self.level += (target - self.level).clamp(-most, most);
+    }
+
+    // How far the driving layer should be turned up
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    // Go back to calm for a new match
+    pub fn reset(&mut self) {
+        *self = MusicIntensity::new();
+    }
+}
+
+impl EventListener for AudioScheduler {
+    // Queue the cue that goes with an event, to play straight away
+    fn on_event(&mut self, event: &GameEvent) {
+        let cue = match event {
+            GameEvent::CountdownTick => Cue::CountdownBeep,
+            GameEvent::Serve { .. } => Cue::CountdownGo,
+            GameEvent::PointScored { .. } => Cue::Goal,
+            GameEvent::PaddleHit { .. } => Cue::PaddleHit,
+            GameEvent::WallBounce | GameEvent::ObstacleBounce => Cue::Bounce,
+            _ => return,
+        };
+        self.schedule_in(0.0, cue);
+    }
+}
@@ -0,0 +1,118 @@
+// This file times the frames of a render benchmark (--bench-render): the game
+// window plays computer against computer for a set number of frames, then
+// prints how long the frames took, so drawing speed can be compared between
+// computers and settings
+
+use crate::game::scene::Scene;
+use crate::game::simulation::GameMode;
+use crate::game::state::GameState;
+use std::fmt;
+
+// How long the frames took, in seconds
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct BenchReport {
+    pub frames: usize, // Frames timed
+    pub mean: f32,     // Average frame
+    pub p50: f32,      // Half the frames were at least this quick
+    pub p90: f32,      // Nine frames in ten were at least this quick
+    pub p99: f32,      // All but one frame in a hundred were at least this quick
+    pub worst: f32,    // Slowest frame
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ms = |seconds: f32| seconds * 1000.0;
+        writeln!(
+            f,
+            "Rendered {} frames, {:.2} ms on average ({:.0} frames per second)",
+            self.frames,
+            ms(self.mean),
+            1.0 / self.mean.max(f32::EPSILON)
+        )?;
+        write!(
+            f,
+            "Frame times: median {:.2} ms, 90% {:.2} ms, 99% {:.2} ms, worst {:.2} ms",
+            ms(self.p50),
+            ms(self.p90),
+            ms(self.p99),
+            ms(self.worst)
+        )
+    }
+}
+
+// Frame times collected during a benchmark
+#[derive(Clone, PartialEq, Debug)]
+pub struct FrameTimes {
+    pub frames: usize,   // Frames to time before the benchmark is over
+    pub times: Vec<f32>, // Each frame timed so far, in seconds
+}
+
+impl FrameTimes {
+    // Get ready to time `frames` frames
+    pub fn new(frames: usize) -> Self {
+        FrameTimes {
+            frames,
+            times: Vec::with_capacity(frames),
+        }
+    }
+
+    // Note how long a frame took (frames after the last one are left out)
+    pub fn record(&mut self, seconds: f32) {
+        if !self.done() {
+            self.times.push(seconds);
+        }
+    }
+
+    // Check if every frame has been timed
+    pub fn done(&self) -> bool {
+        self.times.len() >= self.frames
+    }
+
+    // Sum up the frames timed so far
+    pub fn report(&self) -> BenchReport {
+        if self.times.is_empty() {
+            return BenchReport::default();
+        }
+        let mut sorted = self.times.clone();
+        sorted.sort_by(f32::total_cmp);
+        BenchReport {
+            frames: sorted.len(),
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            p50: percentile(&sorted, 50.0),
+            p90: percentile(&sorted, 90.0),
+            p99: percentile(&sorted, 99.0),
+            worst: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+// The smallest time that at least `percent` of the (sorted) times are at or
+// under
+fn percentile(sorted: &[f32], percent: f32) -> f32 {
+    let rank = (percent / 100.0 * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// Running a render benchmark in the game window
+impl GameState {
+    // Start a render benchmark: the computer plays both sides for `frames`
+    // frames, with the saved settings, and the game closes once it's done
+    pub fn start_bench(&mut self, frames: usize) {
+        self.bench = Some(FrameTimes::new(frames));
+        self.start_match(GameMode::Demo);
+    }
+
+    // Time one benchmark frame, starting another match whenever one ends
+    pub fn run_bench(&mut self, frame_delta: f32) {
+        let bench = match &mut self.bench {
+            Some(bench) => bench,
+            None => return,
+        };
+        bench.record(frame_delta);
+        if bench.done() {
+            self.should_exit = true;
+        } else if self.scene == Scene::GameOver {
+            self.rematch();
+        }
+    }
+}
@@ -0,0 +1,289 @@
+// This file lets a program outside the game drive the right paddle.
+// The game listens on a local TCP port; once a bot connects it gets the match
+// state as one line of JSON every simulation step, and sends back lines like
+// {"move": -1.0} (-1 = full up, 1 = full down) to steer its paddle.
+// A bot can also ask for lockstep, where each step waits for its move so the
+// match plays out the same however slow the bot is
+
+use crate::components::paddle::Paddle;
+use crate::game::connection::LineConnection;
+use crate::game::constants::*;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::time::{Duration, Instant};
+
+// Where the ball is and how it is moving
+#[derive(Serialize)]
+pub struct BallState {
+    pub x: f32,    // Center, from the court's left edge
+    pub y: f32,    // Center, from the court's top edge
+    pub dx: f32,   // Sideways speed (positive = right, towards the bot)
+    pub dy: f32,   // Up/down speed (positive = down)
+    pub spin: f32, // Spin (positive = clockwise)
+}
+
+// Where a paddle is and how it is moving
+#[derive(Serialize)]
+pub struct PaddleState {
+    pub x: f32,        // Left edge
+    pub y: f32,        // Top edge
+    pub velocity: f32, // Up/down speed (positive = down)
+}
+
+// Everything a bot is told each step
+#[derive(Serialize)]
+pub struct BotState {
+    pub tick: u64,                    // Simulation steps sent so far
+    pub court_width: f32,             // Size of the court
+    pub court_height: f32,            // Size of the court
+    pub paddle_width: f32,            // Size of both paddles
+    pub paddle_height: f32,           // Size of both paddles
+    pub ball: BallState,              // The ball
+    pub you: PaddleState,             // The bot's paddle (right side)
+    pub opponent: PaddleState,        // The player's paddle (left side)
+    pub score: [u32; 2],              // Points in the current game: [you, opponent]
+    pub serving: bool,                // The countdown before a serve is running
+    pub winner: Option<&'static str>, // "you" or "opponent" once the match is over
+}
+
+// A command sent by the bot
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BotCommand {
+    #[serde(rename = "move")]
+    direction: Option<f32>, // Which way to push the paddle, -1 (up) to 1 (down)
+    tick: Option<u64>,      // Which state the move answers
+    lockstep: Option<bool>, // Turn lockstep on or off
+}
+
+// Listens for a bot and passes messages to and from it, without ever
+// making the game wait on the network
+pub struct BotServer {
+    listener: TcpListener,
+    client: Option<LineConnection>,
+    pub direction: f32, // The bot's latest move command
+    pub lockstep: bool, // Each step waits for the bot's move (the bot asks for this)
+    answered: bool,     // The bot has sent a move since the last state
+    waited: Duration,   // Time spent waiting for lockstep moves, not yet handed out
+    tick: u64,          // Simulation steps sent so far
+}
+
+impl BotState {
+    // Describe the match from the right paddle's side
+    pub fn new(sim: &Simulation, tick: u64) -> Self {
+        let paddle = |paddle: &Paddle| PaddleState {
+            x: paddle.x,
+            y: paddle.y,
+            velocity: paddle.velocity,
+        };
+        BotState {
+            tick,
+            court_width: sim.court.width,
+            court_height: sim.court.height,
+            paddle_width: PADDLE_WIDTH,
+            paddle_height: sim.player2.height,
+            ball: BallState {
+                x: sim.ball.x,
+                y: sim.ball.y,
+                dx: sim.ball.dx,
+                dy: sim.ball.dy,
+                spin: sim.ball.spin,
+            },
+            you: paddle(&sim.player2),
+            opponent: paddle(&sim.player1),
+            score: [sim.score.player2, sim.score.player1],
+            serving: sim.countdown.is_some(),
+            winner: sim
+                .winner
+                .map(|winner| if winner == 2 { "you" } else { "opponent" }),
+        }
+    }
+}
+
+impl BotServer {
+    // Start listening, e.g. on "127.0.0.1:7878"
+    pub fn bind(address: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(BotServer {
+            listener,
+            client: None,
+            direction: 0.0,
+            lockstep: false,
+            answered: true,
+            waited: Duration::ZERO,
+            tick: 0,
+        })
+    }
+
+    // The address bots should connect to
+    pub fn address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Check if a bot is connected
+    pub fn connected(&self) -> bool {
+        self.client.is_some()
+    }
+
+    // Pick up a newly connected bot and read whatever it has sent.
+    // Only one bot can play at a time; others are told so and closed
+    pub fn poll(&mut self) {
+        while let Ok((mut stream, _)) = self.listener.accept() {
+            if self.client.is_some() {
+                let _ = stream.write_all(b"{\"error\":\"another bot is already playing\"}\n");
+                continue;
+            }
+            if let Ok(client) = LineConnection::new(stream) {
+                self.client = Some(client);
+                self.tick = 0;
+                self.answered = true;
+            }
+        }
+
+        let mut lines = Vec::new();
+        if let Some(client) = &mut self.client {
+            match client.receive() {
+                Ok(received) => lines = received,
+                Err(_) => self.disconnect(),
+            }
+        }
+        for line in lines {
+            self.handle_line(&line);
+        }
+    }
+
+    // Read the bot's move for the next step. In lockstep this waits until the
+    // bot answers the last state, giving up after `timeout` so a stuck bot
+    // can't freeze the game. Returns false if the move didn't come in time
+    pub fn receive_move(&mut self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        self.poll();
+        while self.lockstep && !self.answered {
+            let remaining = timeout.saturating_sub(start.elapsed());
+            let client = match &mut self.client {
+                Some(client) if !remaining.is_zero() => client,
+                _ => break,
+            };
+            if client.wait(remaining).is_err() {
+                self.disconnect();
+                break;
+            }
+            self.poll();
+        }
+        self.waited += start.elapsed();
+
+        if self.lockstep && !self.answered {
+            // Go on with the last move; a late answer to this state is ignored
+            let reply = serde_json::json!({
+                "error": format!("no move for tick {} in time", self.tick.saturating_sub(1))
+            });
+            self.queue(&reply.to_string());
+            self.answered = true;
+            return false;
+        }
+        true
+    }
+
+    // How long the game has spent waiting for lockstep moves since last asked.
+    // That time is the bot thinking, so it shouldn't count as game time
+    pub fn take_waited(&mut self) -> f32 {
+        std::mem::take(&mut self.waited).as_secs_f32()
+    }
+
+    // Send the match state to the bot, if one is connected
+    pub fn send(&mut self, sim: &Simulation) {
+        if self.client.is_none() {
+            return;
+        }
+        let state = BotState::new(sim, self.tick);
+        self.tick += 1;
+        self.answered = false;
+        match serde_json::to_string(&state) {
+            Ok(line) => self.queue(&line),
+            Err(error) => eprintln!("Could not describe the match for the bot: {}", error),
+        }
+    }
+
+    // Act on one line from the bot, answering with an error if it makes no sense
+    fn handle_line(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        let command = match serde_json::from_str::<BotCommand>(line) {
+            Ok(command) => command,
+            Err(error) => {
+                let reply = serde_json::json!({ "error": error.to_string() });
+                self.queue(&reply.to_string());
+                return;
+            }
+        };
+        if let Some(lockstep) = command.lockstep {
+            self.lockstep = lockstep;
+        }
+        match command.direction {
+            Some(direction) if direction.is_finite() => {
+                // In lockstep, a move for an older state came too late to use
+                let stale =
+                    self.lockstep && matches!(command.tick, Some(tick) if tick + 1 < self.tick);
+                if !stale {
+                    self.direction = direction.clamp(-1.0, 1.0);
+                    self.answered = true;
+                }
+            }
+            Some(_) => self.queue("{\"error\":\"move must be a number\"}"),
+            None if command.lockstep.is_none() => {
+                self.queue("{\"error\":\"expected a move or lockstep\"}")
+            }
+            None => {}
+        }
+    }
+
+    // Queue a line for the bot and send as much as it will take. A bot that
+    // stops reading is dropped rather than letting its backlog grow forever
+    fn queue(&mut self, line: &str) {
+        let client = match &mut self.client {
+            Some(client) => client,
+            None => return,
+        };
+        if client.send_line(line).is_err() {
+            self.disconnect();
+        }
+    }
+
+    // Forget the bot, leaving its paddle still until another one connects
+    fn disconnect(&mut self) {
+        self.client = None;
+        self.direction = 0.0;
+        self.lockstep = false;
+        self.answered = true;
+    }
+}
+
+// Starting a bot match from the menu
+impl GameState {
+    // Start a match against an outside bot program, opening the bot server
+    // the first time. If the port can't be opened, say why on the menu
+    pub fn start_bot_match(&mut self) {
+        if self.bot.is_none() {
+            match BotServer::bind(BOT_SERVER_ADDRESS) {
+                Ok(server) => self.bot = Some(server),
+                Err(error) => {
+                    self.status = Some(format!(
+                        "Could not start the bot server on {}: {}",
+                        BOT_SERVER_ADDRESS, error
+                    ));
+                    return;
+                }
+            }
+
+            // Bot matches can always be watched, but the match goes ahead without it
+            self.open_spectator_port();
+        }
+        self.status = None;
+        self.start_match(GameMode::Bot);
+    }
+}
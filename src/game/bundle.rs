@@ -0,0 +1,306 @@
+// This file packs the player's setup (settings and lifetime stats) into one
+// file, so it can be copied to another computer and loaded there
+
+use crate::game::audio::{SoundCategory, SoundMix};
+use crate::game::constants::*;
+use crate::game::data_folder::data_file;
+use crate::game::input::PaddleInput;
+use crate::game::match_options::MatchOptions;
+use crate::game::state::GameState;
+use crate::game::stats::StatTotals;
+use crate::graphics::broadcast::HudLayout;
+use crate::graphics::theme::ThemePreset;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Every option on the settings screen
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)] // A misspelled option is reported, not ignored
+pub struct Settings {
+    #[serde(flatten)] // Written alongside the rest, as before they were kept together
+    pub options: MatchOptions, // AI, court, rules and physics for the next match
+    pub low_power: bool, // Lower frame rate, fewer effects
+    #[serde(default = "switched_on")]
+    pub screen_effects: bool, // Screen shake and hit-stop (on for older setup files)
+    pub theme: ThemePreset, // Color theme
+    #[serde(default = "full_volume")]
+    pub sound_volume: u32, // 0 (silent) to MAX_SOUND_VOLUME (full for older setup files)
+    #[serde(default)]
+    pub coach_tips: bool, // A tip after each lost point (off for older setup files)
+    #[serde(default)]
+    pub paddle_input: PaddleInput, // What steers player 1 (keys for older setup files)
+    #[serde(default)]
+    pub aspect_lock: bool, // Window keeps the play area's shape (off for older setup files)
+    #[serde(default)]
+    pub safe_margin: u32, // TV safe-area margin in percent (none for older setup files)
+    #[serde(default = "switched_on")]
+    pub holiday_events: bool, // Snow, fireworks and the like on holidays (on for older setup files)
+    #[serde(default)]
+    pub hud_layout: HudLayout, // Score line or broadcast scoreboard (the line for older files)
+    #[serde(default = "switched_on")]
+    pub point_replays: bool, // Slow-motion replay after each point (on for older setup files)
+    #[serde(default)]
+    pub sound_mix: SoundMix, // Volume of each kind of sound (all full for older setup files)
+}
+
+// Default for options that start out on
+fn switched_on() -> bool {
+    true
+}
+
+// Default for the volume, which starts out all the way up
+fn full_volume() -> u32 {
+    MAX_SOUND_VOLUME
+}
+
+impl Settings {
+    // Check every value is one the settings screen could have picked
+    pub fn validate(&self) -> Result<(), BundleError> {
+        let tuning = self.options.ai_tuning;
+        if !tuning.in_range() {
+            return Err(BundleError::Invalid(format!(
+                "AI speed and accuracy must be -{0} to {0}, not {1} and {2}",
+                AI_TUNING_STEPS, tuning.speed, tuning.accuracy
+            )));
+        }
+        let rules = &self.options.rules;
+        if !POINTS_TO_WIN_CHOICES.contains(&rules.points_to_win) {
+            return Err(BundleError::Invalid(format!(
+                "points to win must be one of {:?}, not {}",
+                POINTS_TO_WIN_CHOICES, rules.points_to_win
+            )));
+        }
+        if !BEST_OF_CHOICES.contains(&rules.best_of) {
+            return Err(BundleError::Invalid(format!(
+                "best of must be one of {:?}, not {}",
+                BEST_OF_CHOICES, rules.best_of
+            )));
+        }
+        if self.sound_volume > MAX_SOUND_VOLUME {
+            return Err(BundleError::Invalid(format!(
+                "sound volume must be 0 to {}, not {}",
+                MAX_SOUND_VOLUME, self.sound_volume
+            )));
+        }
+        for category in SoundCategory::ALL {
+            let volume = self.sound_mix.volume(category);
+            if volume > MAX_SOUND_VOLUME {
+                return Err(BundleError::Invalid(format!(
+                    "{} volume must be 0 to {}, not {}",
+                    category.name().to_lowercase(),
+                    MAX_SOUND_VOLUME,
+                    volume
+                )));
+            }
+        }
+        if self.safe_margin > MAX_SAFE_MARGIN {
+            return Err(BundleError::Invalid(format!(
+                "safe area must be 0 to {}%, not {}%",
+                MAX_SAFE_MARGIN, self.safe_margin
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Everything that moves between computers, tagged with the format version
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bundle {
+    pub version: u32,       // Which version of this format wrote the file
+    pub settings: Settings, // Game options
+    pub stats: StatTotals,  // Lifetime totals
+}
+
+// Why a bundle couldn't be loaded
+#[derive(Debug)]
+pub enum BundleError {
+    Io(io::Error),             // The file couldn't be read or written
+    Format(serde_json::Error), // The file isn't a setup bundle
+    Version(u32),              // Written by a version of the game we don't understand
+    Invalid(String),           // A value is out of range
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BundleError::Io(error) => write!(f, "could not access the file ({})", error),
+            BundleError::Format(error) => write!(f, "not a setup file ({})", error),
+            BundleError::Version(version) => {
+                write!(
+                    f,
+                    "made by a newer version of the game (format {})",
+                    version
+                )
+            }
+            BundleError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<io::Error> for BundleError {
+    fn from(error: io::Error) -> Self {
+        BundleError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for BundleError {
+    fn from(error: serde_json::Error) -> Self {
+        BundleError::Format(error)
+    }
+}
+
+impl Bundle {
+    // Pack up the current settings and stats
+    pub fn new(settings: Settings, stats: StatTotals) -> Self {
+        Bundle {
+            version: BUNDLE_VERSION,
+            settings,
+            stats,
+        }
+    }
+
+    // Write the bundle to a file, creating its folder if needed
+    pub fn export(&self, path: &Path) -> Result<(), BundleError> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // Read a bundle from a file, refusing anything that doesn't check out
+    pub fn import(path: &Path) -> Result<Self, BundleError> {
+        Bundle::parse(&fs::read_to_string(path)?)
+    }
+
+    // Turn the text of a bundle file into a bundle. The version is checked
+    // first, so a file from a newer game is reported as that rather than as
+    // fields we've never heard of. Format errors name the line and column
+    pub fn parse(text: &str) -> Result<Self, BundleError> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let version = value.get("version").and_then(|version| version.as_u64());
+        match version {
+            None => {
+                return Err(BundleError::Invalid(
+                    "missing a whole-number \"version\"".to_string(),
+                ))
+            }
+            Some(version) if version == 0 || version > BUNDLE_VERSION as u64 => {
+                return Err(BundleError::Version(version as u32))
+            }
+            Some(_) => {}
+        }
+
+        let bundle: Bundle = serde_json::from_str(text)?;
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    // Check the bundle was written by a version we understand and that every
+    // value is one the settings screen could have picked
+    pub fn validate(&self) -> Result<(), BundleError> {
+        if self.version == 0 || self.version > BUNDLE_VERSION {
+            return Err(BundleError::Version(self.version));
+        }
+        self.settings.validate()
+    }
+}
+
+// Where the setup bundle is exported to and imported from: the user data folder
+// for this game (None if the system doesn't have one)
+pub fn bundle_file() -> Option<PathBuf> {
+    data_file(BUNDLE_FILE_NAME)
+}
+
+// Exporting and importing the setup file from the settings screen
+impl GameState {
+    // Write the settings and lifetime stats to the setup file
+    pub fn export_setup(&mut self) {
+        let path = match self.bundle_path.clone() {
+            Some(path) => path,
+            None => {
+                self.status = Some("No place to save the setup on this system".to_string());
+                return;
+            }
+        };
+        let bundle = Bundle::new(self.settings(), self.career.lifetime.clone());
+        self.status = Some(match bundle.export(&path) {
+            Ok(()) => format!("Setup saved to {}", path.display()),
+            Err(error) => format!("Could not export setup: {}", error),
+        });
+    }
+
+    // Load settings and lifetime stats from the setup file. Nothing changes
+    // unless the whole file checks out
+    pub fn import_setup(&mut self) {
+        let path = match self.bundle_path.clone() {
+            Some(path) => path,
+            None => {
+                self.status = Some("No place to load a setup from on this system".to_string());
+                return;
+            }
+        };
+        self.status = Some(match Bundle::import(&path) {
+            Ok(bundle) => {
+                self.apply_settings(&bundle.settings);
+                self.career.lifetime = bundle.stats;
+                format!("Setup loaded from {}", path.display())
+            }
+            Err(error) => format!("Could not import setup: {}", error),
+        });
+    }
+}
+
+// Reading the game's options into settings, and switching to them
+impl GameState {
+    // The current options, as stored in a setup bundle. A continued match's
+    // own options don't count: the player's are kept
+    pub fn settings(&self) -> Settings {
+        let options = match (self.own_options, &self.tournament) {
+            (Some(own), _) => own,
+            // A tournament match's opponent doesn't change the difficulty picked
+            (None, Some(tournament)) => MatchOptions {
+                ai_difficulty: tournament.home_difficulty,
+                ..MatchOptions::of(&self.sim)
+            },
+            (None, None) => MatchOptions::of(&self.sim),
+        };
+        Settings {
+            options,
+            low_power: self.low_power,
+            screen_effects: self.juice.enabled,
+            theme: self.theme_preset,
+            sound_volume: self.sound_volume,
+            coach_tips: self.coach_tips,
+            paddle_input: self.paddle_input,
+            aspect_lock: self.aspect_lock,
+            safe_margin: self.safe_margin,
+            holiday_events: self.holiday_events,
+            hud_layout: self.hud_layout,
+            point_replays: self.point_replays,
+            sound_mix: self.sound_mix,
+        }
+    }
+
+    // Switch every option to the ones from a setup bundle
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        settings.options.apply(&mut self.sim);
+        self.low_power = settings.low_power;
+        self.juice.enabled = settings.screen_effects;
+        self.theme_preset = settings.theme;
+        self.holiday_events = settings.holiday_events;
+        self.hud_layout = settings.hud_layout;
+        self.refresh_theme();
+        self.sound_volume = settings.sound_volume;
+        self.sound_mix = settings.sound_mix;
+        self.coach_tips = settings.coach_tips;
+        self.point_replays = settings.point_replays;
+        self.paddle_input = settings.paddle_input;
+        self.aspect_lock = settings.aspect_lock;
+        self.safe_margin = settings.safe_margin;
+    }
+}
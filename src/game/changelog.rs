@@ -0,0 +1,40 @@
+// This file holds the list of changes in each version of the game, shown on
+// the "What's New" screen the first time a new version is started
+
+// The changes made in one version
+pub struct Release {
+    pub version: &'static str,            // Version number, matching Cargo.toml
+    pub changes: &'static [&'static str], // One short line per change
+}
+
+// Every release, newest first. Add an entry here whenever the version in
+// Cargo.toml goes up
+pub const CHANGELOG: &[Release] = &[Release {
+    version: "0.1.0",
+    changes: &[
+        "Local two-player mode, four AI difficulty levels and controller support",
+        "Settings: court layouts, points to win, win by two, best-of-N and lives mode",
+        "Practice mode, momentum paddle physics and a low-power mode",
+        "Color themes (press T), fullscreen (F11) and a resizable window",
+        "Live match stats (hold Tab) and a momentum bar under the score",
+        "Post-match analysis of where the ball hit your paddle (press A)",
+        "Lifetime stats saved between runs, and setup export and import",
+        "Countdown beeps and a goal jingle",
+    ],
+}];
+
+// The version of the game that is running
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+// Releases the player hasn't seen yet, newest first. `last_seen` is the version
+// they last started; if it isn't in the list (e.g. first run) just the newest
+// release is shown
+pub fn unseen_releases(last_seen: &str) -> &'static [Release] {
+    let seen = CHANGELOG
+        .iter()
+        .position(|release| release.version == last_seen)
+        .unwrap_or(1.min(CHANGELOG.len()));
+    &CHANGELOG[..seen]
+}
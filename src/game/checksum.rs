@@ -0,0 +1,10 @@
+// This file holds a small, fast hash used to spot when two things that should
+// match (two runs of the same match, or a typed code) don't
+
+// FNV-1a: not for security, but the same bytes always give the same number
+// and a change to any byte changes the result
+pub fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
@@ -0,0 +1,257 @@
+// This file reads the options given on the command line, e.g.
+// `ping_pong --difficulty hard --points 5 --seed 42`
+
+use crate::game::ai::AiDifficulty;
+use crate::game::constants::*;
+use crate::game::court::CourtPreset;
+use crate::game::simulation::Simulation;
+use std::fmt;
+use std::str::FromStr;
+
+// What `--help` prints
+pub const USAGE: &str = "\
+Usage: ping_pong [options]
+
+Options:
+  --width <pixels>           Window width (default 900)
+  --height <pixels>          Window height (default 600)
+  --vsync, --no-vsync        Wait for the screen to refresh before drawing (default on)
+  --points <n>               Points to win a game: 3, 5, 7 or 11
+  --difficulty <level>       AI difficulty: easy, medium, hard or impossible
+  --court <layout>           Court: classic, narrow, pulled-in, wide or tall
+  --seed <n>                 Make serves and AI mistakes repeatable
+  --headless-simulate <n>    Play n simulation steps of AI against AI without a
+                             window, print the result and exit
+  --bench-render <frames>    Play AI against AI in the window for that many frames,
+                             then print how long the frames took and exit
+  --spectate                 Let other copies of the game watch every match, not
+                             only bot matches
+  --watch <address>          Watch a match running in another copy of the game
+                             (its spectator port, e.g. 127.0.0.1:7879)
+  --capture <file>           Save the watched match to a file (with --watch)
+  --replay-capture <file>    Watch a match saved with --capture
+  -h, --help                 Show this message";
+
+// The streaming options `--help` adds when they're built in
+#[cfg(feature = "stream-hud")]
+const STREAM_HUD_USAGE: &str = "
+
+Streaming:
+  --stream-hud               Send every match to a HUD window
+  --hud-window               Open only the HUD window (scores and match clock on
+                             green, to be keyed out by streaming software), fed by
+                             a game started with --stream-hud";
+
+// Everything that can be set from the command line. Options left out keep
+// the game's defaults
+#[derive(Clone, PartialEq, Debug)]
+pub struct CliOptions {
+    pub width: f32,                       // Window width in pixels
+    pub height: f32,                      // Window height in pixels
+    pub vsync: bool,                      // Wait for the screen to refresh before drawing
+    pub points_to_win: Option<u32>,       // Points needed to take a game
+    pub difficulty: Option<AiDifficulty>, // How hard the AI plays
+    pub court: Option<CourtPreset>,       // Size and shape of the court
+    pub seed: Option<u64>,                // Seed for every random choice in the match
+    pub headless_ticks: Option<u64>,      // Simulation steps to run without a window
+    pub bench_frames: Option<usize>,      // Frames to time in a render benchmark
+    pub spectate: bool,                   // Open the spectator port for every match
+    pub watch: Option<String>,            // Spectator port of a game to watch
+    pub capture: Option<String>,          // File the watched match is saved to
+    pub replay_capture: Option<String>,   // Saved match to watch instead of a live one
+    #[cfg(feature = "stream-hud")]
+    pub stream_hud: bool, // Send every match to a HUD window
+    #[cfg(feature = "stream-hud")]
+    pub hud_window: bool, // Open only the HUD window instead of the game
+    pub help: bool,                       // Print the usage text and exit
+}
+
+// Why the command line couldn't be understood
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    Unknown(String),         // An option we don't have
+    MissingValue(String),    // An option given without its value
+    Invalid(String, String), // An option and the reason its value was refused
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Unknown(option) => write!(f, "unknown option {}", option),
+            CliError::MissingValue(option) => write!(f, "{} needs a value", option),
+            CliError::Invalid(option, reason) => write!(f, "{}: {}", option, reason),
+        }
+    }
+}
+
+impl Default for CliOptions {
+    // A normal windowed game
+    fn default() -> Self {
+        CliOptions {
+            width: SCREEN_WIDTH,
+            height: SCREEN_HEIGHT,
+            vsync: true,
+            points_to_win: None,
+            difficulty: None,
+            court: None,
+            seed: None,
+            headless_ticks: None,
+            bench_frames: None,
+            spectate: false,
+            watch: None,
+            capture: None,
+            replay_capture: None,
+            #[cfg(feature = "stream-hud")]
+            stream_hud: false,
+            #[cfg(feature = "stream-hud")]
+            hud_window: false,
+            help: false,
+        }
+    }
+}
+
+impl CliOptions {
+    // Read the options from the arguments after the program name. Values can
+    // follow their option as the next argument or after an `=`
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, CliError> {
+        let mut options = CliOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            // Flags take no value
+            match name.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--vsync" => options.vsync = true,
+                "--no-vsync" => options.vsync = false,
+                "--spectate" => options.spectate = true,
+                #[cfg(feature = "stream-hud")]
+                "--stream-hud" => options.stream_hud = true,
+                #[cfg(feature = "stream-hud")]
+                "--hud-window" => options.hud_window = true,
+                _ => {
+                    let value = match inline_value.or_else(|| args.next()) {
+                        Some(value) => value,
+                        None if is_option(&name) => return Err(CliError::MissingValue(name)),
+                        None => return Err(CliError::Unknown(name)),
+                    };
+                    options.set(&name, &value)?;
+                }
+            }
+        }
+        // Only a live match can be saved
+        if options.capture.is_some() && options.watch.is_none() {
+            return Err(CliError::Invalid(
+                "--capture".to_string(),
+                "needs --watch to say which match to save".to_string(),
+            ));
+        }
+        Ok(options)
+    }
+
+    // Put the match options (difficulty, court, points to win, seed) onto a match
+    pub fn apply(&self, sim: &mut Simulation) {
+        if let Some(difficulty) = self.difficulty {
+            sim.ai_difficulty = difficulty;
+        }
+        if let Some(court) = self.court {
+            sim.apply_court(court);
+        }
+        if let Some(points) = self.points_to_win {
+            sim.rules.points_to_win = points;
+            sim.rules.classic = false; // Classic rules would ignore the chosen score
+        }
+        if let Some(seed) = self.seed {
+            sim.seed(seed);
+        }
+    }
+
+    // Apply one option that takes a value
+    fn set(&mut self, name: &str, value: &str) -> Result<(), CliError> {
+        let invalid = |reason: String| CliError::Invalid(name.to_string(), reason);
+        match name {
+            "--width" => self.width = window_size(value).map_err(invalid)?,
+            "--height" => self.height = window_size(value).map_err(invalid)?,
+            "--points" => {
+                let points = number(value).map_err(invalid)?;
+                if !POINTS_TO_WIN_CHOICES.contains(&points) {
+                    return Err(invalid(format!(
+                        "must be one of {:?}, not {}",
+                        POINTS_TO_WIN_CHOICES, value
+                    )));
+                }
+                self.points_to_win = Some(points);
+            }
+            "--difficulty" => match AiDifficulty::from_name(value) {
+                Some(difficulty) => self.difficulty = Some(difficulty),
+                None => {
+                    return Err(invalid(format!(
+                        "expected easy, medium, hard or impossible, not {}",
+                        value
+                    )))
+                }
+            },
+            "--court" => match CourtPreset::from_name(value) {
+                Some(court) => self.court = Some(court),
+                None => {
+                    return Err(invalid(format!(
+                        "expected classic, narrow, pulled-in, wide or tall, not {}",
+                        value
+                    )))
+                }
+            },
+            "--seed" => self.seed = Some(number(value).map_err(invalid)?),
+            "--headless-simulate" => self.headless_ticks = Some(number(value).map_err(invalid)?),
+            "--bench-render" => self.bench_frames = Some(number(value).map_err(invalid)?),
+            "--watch" => self.watch = Some(value.to_string()),
+            "--capture" => self.capture = Some(value.to_string()),
+            "--replay-capture" => self.replay_capture = Some(value.to_string()),
+            _ => return Err(CliError::Unknown(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+// What `--help` prints, with the streaming options if they're built in
+pub fn usage() -> String {
+    #[cfg(feature = "stream-hud")]
+    return format!("{}{}", USAGE, STREAM_HUD_USAGE);
+    #[cfg(not(feature = "stream-hud"))]
+    USAGE.to_string()
+}
+
+// Check if `name` is one of the options that take a value
+fn is_option(name: &str) -> bool {
+    matches!(
+        name,
+        "--width"
+            | "--height"
+            | "--points"
+            | "--difficulty"
+            | "--court"
+            | "--seed"
+            | "--headless-simulate"
+            | "--bench-render"
+            | "--watch"
+            | "--capture"
+            | "--replay-capture"
+    )
+}
+
+// Read a whole number that can't be negative
+fn number<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a whole number, not {}", value))
+}
+
+// Read a window width or height, which has to be at least a few pixels
+fn window_size(value: &str) -> Result<f32, String> {
+    let pixels: u32 = number(value)?;
+    if (pixels as f32) < MIN_WINDOW_SIZE {
+        return Err(format!("must be at least {} pixels", MIN_WINDOW_SIZE));
+    }
+    Ok(pixels as f32)
+}
@@ -0,0 +1,68 @@
+// This file is the coach: after player 1 loses a point it picks a one-line
+// tip from how the ball got past their paddle (how far off they were, how
+// fast and steep the ball came) and how their last return of the rally went
+
+use crate::game::constants::*;
+use crate::game::events::{EventListener, GameEvent};
+use crate::game::recording::{Contact, Miss};
+
+// The tip for a ball that got past the player. The unusual causes (a fast or
+// steep ball, or a poor last return) come first, then where the paddle was
+pub fn tip_for(miss: &Miss, last_return: Option<&Contact>) -> &'static str {
+    if miss.speed >= COACH_FAST_BALL && miss.offset.abs() >= COACH_FAR_MISS {
+        "That one was fast - start moving as soon as it leaves their paddle"
+    } else if miss.angle.abs() >= COACH_STEEP_ANGLE {
+        "It came in steep - watch where it will bounce off the wall and get there early"
+    } else if last_return.is_some_and(Contact::is_mishit) {
+        "Your last return came off the edge of your paddle, which gave them an easy angle"
+    } else if miss.offset > 1.0 {
+        "You were too high - the ball passed below your paddle"
+    } else if miss.offset < -1.0 {
+        "You were too low - the ball passed above your paddle"
+    } else {
+        "It slipped past the end of your paddle - line up the middle with the ball"
+    }
+}
+
+// Watches the match for player 1's returns and misses, and holds the tip
+// for the last point they lost while it's on screen
+#[derive(Default)]
+pub struct Coach {
+    pub tip: Option<&'static str>, // Tip on screen, if any
+    pub tip_age: f32,              // Seconds of play the tip has been showing
+    last_return: Option<Contact>,  // Player 1's last return in the rally
+}
+
+impl Coach {
+    // Let `delta` seconds of play go by, taking the tip down once it has run its time
+    pub fn update(&mut self, delta: f32) {
+        if self.tip.is_some() {
+            self.tip_age += delta;
+            if self.tip_age >= COACH_TIP_DURATION {
+                self.tip = None;
+            }
+        }
+    }
+
+    // Forget the rally and the tip for a new match
+    pub fn reset(&mut self) {
+        *self = Coach::default();
+    }
+}
+
+impl EventListener for Coach {
+    // Remember player 1's returns, and pick a tip when the ball gets past them
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::PaddleHit { contact, .. } if contact.player == 1 => {
+                self.last_return = Some(contact);
+            }
+            GameEvent::BallMissed { miss } if miss.player == 1 => {
+                self.tip = Some(tip_for(&miss, self.last_return.as_ref()));
+                self.tip_age = 0.0;
+            }
+            GameEvent::PointScored { .. } => self.last_return = None, // The rally is over
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// This file holds a TCP connection that carries one line of JSON per message,
+// without ever making the game wait on the network. The bot server and the
+// spectator server both talk to their programs through it
+
+use crate::game::constants::*;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+// A connected program, with text waiting to be read or sent
+pub struct LineConnection {
+    stream: TcpStream,
+    incoming: Vec<u8>, // Received bytes not yet ending in a newline
+    outgoing: Vec<u8>, // Lines the other side hasn't taken yet
+}
+
+impl LineConnection {
+    // Take over a freshly connected stream, switching it to non-blocking
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        let _ = stream.set_nodelay(true);
+        Ok(LineConnection {
+            stream,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+        })
+    }
+
+    // Read everything available and return the complete lines.
+    // An error (or the other side closing the connection) means it is gone
+    pub fn receive(&mut self) -> io::Result<Vec<String>> {
+        let mut buffer = [0; 1024];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+                Ok(count) => self.incoming.extend_from_slice(&buffer[..count]),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.incoming.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            lines.push(String::from_utf8_lossy(&line).into_owned());
+        }
+
+        // A line that never ends is not a message
+        if self.incoming.len() > BOT_MAX_BACKLOG {
+            return Err(ErrorKind::InvalidData.into());
+        }
+        Ok(lines)
+    }
+
+    // Sleep until the other side sends something or `timeout` runs out
+    pub fn wait(&mut self, timeout: Duration) -> io::Result<()> {
+        self.stream.set_nonblocking(false)?;
+        self.stream.set_read_timeout(Some(timeout))?;
+        let mut buffer = [0; 1024];
+        let result = self.stream.read(&mut buffer);
+        self.stream.set_nonblocking(true)?;
+        match result {
+            Ok(0) => Err(ErrorKind::UnexpectedEof.into()),
+            Ok(count) => {
+                self.incoming.extend_from_slice(&buffer[..count]);
+                Ok(())
+            }
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+                ) =>
+            {
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    // Queue a line and send as much as the other side will take. One that
+    // stops reading gets an error rather than letting its backlog grow forever
+    pub fn send_line(&mut self, line: &str) -> io::Result<()> {
+        self.outgoing.extend_from_slice(line.as_bytes());
+        self.outgoing.push(b'\n');
+        self.flush()?;
+        if self.outgoing.len() > BOT_MAX_BACKLOG {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+        Ok(())
+    }
+
+    // Send as much of the waiting text as the connection takes right now
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(count) => {
+                    self.outgoing.drain(..count);
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,10 +3,20 @@
 // Screen dimensions (in pixels)
 pub const SCREEN_WIDTH: f32 = 900.0; // How wide the game window is
 pub const SCREEN_HEIGHT: f32 = 600.0; // How tall the game window is
+pub const MIN_WINDOW_SIZE: f32 = 100.0; // Smallest width or height the window can be given
+pub const MAX_SAFE_MARGIN: u32 = 10; // Widest TV safe-area margin (percent of the window per side)
 
 // Paddle settings
 pub const PADDLE_WIDTH: f32 = 15.0; // How wide each paddle is
 pub const PADDLE_HEIGHT: f32 = 100.0; // How tall each paddle is
+pub const SMALL_PADDLE_HEIGHT: f32 = 70.0; // How tall small paddles are
+pub const LARGE_PADDLE_HEIGHT: f32 = 140.0; // How tall large paddles are
+
+// Court presets
+pub const NARROW_COURT_WIDTH: f32 = 600.0; // How wide the narrow court is
+pub const WIDE_COURT_HEIGHT: f32 = 450.0; // How tall the wide court is (twice as wide as tall)
+pub const TALL_COURT_WIDTH: f32 = 450.0; // How wide the tall court is (taller than it is wide)
+pub const PULLED_IN_PADDLE_INSET: f32 = 60.0; // How far paddles stand from the walls when pulled in
 
 // Ball settings
 pub const BALL_RADIUS: f32 = 10.0; // How big the ball is
@@ -15,7 +25,440 @@ pub const BALL_RADIUS: f32 = 10.0; // How big the ball is
 pub const PLAYER_PADDLE_SPEED: f32 = 500.0; // How fast player's paddle moves
 pub const AI_PADDLE_SPEED: f32 = 300.0; // How fast computer's paddle moves
 pub const BALL_SPEED: f32 = 300.0; // How fast the ball moves
+pub const RALLY_SPEED_UP: f32 = 1.04; // Ball speeds up by this much on every paddle hit
+pub const MAX_BALL_SPEED: f32 = 750.0; // The ball never gets faster than this
+pub const SLOW_BALL_SPEED: f32 = 220.0; // Serve speed with the slow ball setting
+pub const FAST_BALL_SPEED: f32 = 380.0; // Serve speed with the fast ball setting
+
+// Controller settings
+pub const GAMEPAD_DEADZONE: f32 = 0.2; // Stick movement smaller than this is ignored, to start with
+pub const GAMEPAD_DEADZONE_MAX: f32 = 0.5; // Largest dead zone a player can pick
+pub const GAMEPAD_DEADZONE_STEP: f32 = 0.05; // How much one press changes the dead zone
+pub const MOUSE_FOLLOW_DISTANCE: f32 = 40.0; // Mouse this far off moves the paddle at full speed
+
+// Keyboard paddle ramping (seconds)
+pub const PADDLE_RAMP_UP_TIME: f32 = 0.15; // How long a held key takes to reach full speed
+pub const PADDLE_RAMP_DOWN_TIME: f32 = 0.08; // How long the paddle takes to stop after release
+
+// Momentum physics option (paddles have mass, input pushes them)
+pub const PADDLE_MASS: f32 = 2.0; // How heavy each paddle is
+pub const PADDLE_INPUT_FORCE: f32 = 4000.0; // How hard a held key pushes the paddle
+pub const PADDLE_FRICTION: f32 = 4.0; // How quickly a paddle slows down on its own
+pub const PADDLE_SPIN_TRANSFER: f32 = 0.3; // How much paddle speed carries into the ball
+
+// Ball spin (a moving paddle spins the ball, and the spin curves its path)
+pub const BALL_SPIN_TRANSFER: f32 = 0.004; // Spin given per unit of paddle speed
+pub const BALL_SPIN_DECAY: f32 = 1.0; // Share of the spin lost each second
+pub const BALL_SPIN_CURVE: f32 = 0.15; // How hard spin bends the path
+pub const BALL_SPIN_WALL_GRIP: f32 = 0.1; // How much spin changes a wall bounce angle
+pub const BALL_SPIN_MIN_BOUNCE_ANGLE: f32 = 0.1; // Flattest wall bounce spin can cause (radians)
 
 // Game mechanics
 pub const COLLISION_TOLERANCE: f32 = 1.0; // Helps prevent ball from getting stuck
+pub const PADDLE_HIT_COOLDOWN: f32 = 0.1; // Seconds a paddle ignores the ball after a return
+pub const MAX_DEFLECTION_ANGLE: f32 = std::f32::consts::PI / 3.0; // Steepest return angle (paddle edge)
 pub const COUNTDOWN_DURATION: f32 = 3.0; // How long the "3,2,1" countdown lasts
+pub const COUNTDOWN_RING_RADIUS: f32 = 40.0; // Size of the arc that winds down during the countdown
+pub const COUNTDOWN_TEXT_SIZE: f32 = 35.0; // Size of each number as it appears
+pub const COUNTDOWN_GROWTH: f32 = 1.8; // How many times bigger a number is as it fades out
+pub const COUNTDOWN_GO_DURATION: f32 = 0.5; // Seconds "GO!" flashes after a serve (0 = no flash)
+pub const SCORE_FLASH_DURATION: f32 = 3.0; // Seconds of play the scorer's score stays highlighted
+
+// Bounciness (1.0 = keeps its speed, above 1.0 = speeds up)
+pub const WALL_RESTITUTION: f32 = 1.0; // Top and bottom walls are perfectly bouncy
+pub const PADDLE_RESTITUTION: f32 = 1.02; // Paddles give the ball a small kick
+
+// Slow-motion replay after each point
+pub const REPLAY_LENGTH: f32 = 2.0; // Seconds of play before the point that are played back
+pub const REPLAY_MIN_LENGTH: f32 = 0.5; // Shorter points than this aren't played back (seconds)
+pub const REPLAY_SPEED: f32 = 0.5; // How fast the replay plays (1 = real time)
+
+// Ball trail
+pub const BALL_TRAIL_LENGTH: usize = 12; // How many past positions the trail shows
+pub const BALL_TRAIL_OPACITY: f32 = 0.5; // How see-through the newest part of the trail is (0 to 1)
+
+// Ball heat (color and glow as the ball speeds up)
+pub const BALL_HEAT_START_SPEED: f32 = BALL_SPEED; // Speed the ball starts warming up from
+pub const BALL_HEAT_FULL_SPEED: f32 = MAX_BALL_SPEED; // Speed it's fully heated up at
+pub const BALL_GLOW_SIZE: f32 = 2.2; // Halo size around a fully heated ball (times its radius)
+pub const BALL_GLOW_OPACITY: f32 = 0.35; // How strong the halo is at full heat (0 to 1)
+pub const BALL_GLOW_PULSE_RATE: f32 = 2.0; // Pulses per second
+pub const BALL_GLOW_PULSE_DEPTH: f32 = 0.3; // How much of the halo's size and strength pulses away
+pub const BALL_GLOW_LAYERS: usize = 3; // Circles stacked for the halo's soft edge
+
+// Court decoration
+pub const COURT_LINE_WIDTH: f32 = 2.0; // Thickness of the border and center lines
+pub const CENTER_LINE_DASH: f32 = 14.0; // Length of each dash in the center line
+pub const CENTER_LINE_GAP: f32 = 10.0; // Space between center line dashes
+pub const CENTER_CIRCLE_RADIUS: f32 = 60.0; // Size of the circle around the serve spot
+
+// Photo mode
+pub const PHOTO_MAX_ZOOM: f32 = 4.0; // Furthest the photo camera zooms in
+pub const PHOTO_ZOOM_STEP: f32 = 1.25; // How much one press of + or - zooms
+pub const PHOTO_PAN_SPEED: f32 = 400.0; // How fast the held arrows move the camera (on screen)
+pub const PHOTO_EXPORT_SCALE: u32 = 2; // Saved photos are this many times the play area's size
+
+// Coach tips after a lost point
+pub const COACH_TIP_DURATION: f32 = 4.0; // Seconds of play a tip stays on screen
+pub const COACH_FAST_BALL: f32 = 500.0; // A ball this fast (pixels per second) counts as fast
+pub const COACH_STEEP_ANGLE: f32 = 30.0; // A ball this steep (degrees from flat) counts as steep
+pub const COACH_FAR_MISS: f32 = 3.0; // A miss this many half paddle heights away counts as far
+
+// Menus
+pub const MENU_COMPACT_ITEMS: usize = 8; // Menus with more entries than this are drawn smaller
+pub const OPTION_LIST_ROWS: usize = 13; // Options shown at once; longer lists scroll
+pub const DEMO_VEIL_OPACITY: f32 = 0.75; // How much the menu background hides the demo match
+
+// Screen shake and hit-stop
+pub const SCREEN_SHAKE_DURATION: f32 = 0.12; // How long the picture shakes after a hit (seconds)
+pub const SCREEN_SHAKE_STRENGTH: f32 = 4.0; // Furthest the court moves in a shake (pixels)
+pub const SCREEN_SHAKE_SPEED: f32 = 90.0; // How quickly the shake wobbles (radians per second)
+pub const HIT_STOP_DURATION: f32 = 0.15; // How long everything holds still after a point (seconds)
+
+// AI speed and accuracy sliders
+pub const AI_TUNING_STEPS: i32 = 5; // Steps each slider goes in either direction
+pub const AI_SPEED_PER_STEP: f32 = 0.1; // Share of the paddle speed added per speed step
+pub const AI_ERROR_PER_STEP: f32 = 4.0; // Pixels of aiming error taken away per accuracy step
+pub const AI_HESITATION_PER_STEP: f64 = 0.04; // Hesitation chance taken away per accuracy step
+pub const AI_MAX_HESITATION: f64 = 0.9; // The sloppiest AI still moves sometimes
+
+// Adaptive AI (eases off when ahead, pushes harder when behind)
+pub const ADAPTIVE_MIN_SPEED: f32 = AI_PADDLE_SPEED - 120.0; // Slowest it gets when well ahead
+pub const ADAPTIVE_MAX_SPEED: f32 = AI_PADDLE_SPEED + 150.0; // Fastest it gets when well behind
+pub const ADAPTIVE_MIN_ERROR: f32 = 0.0; // Best aim it gets when well behind (pixels)
+pub const ADAPTIVE_MAX_ERROR: f32 = 30.0; // Worst aim it gets when well ahead (pixels)
+pub const ADAPTIVE_SCORE_SPAN: f32 = 3.0; // Points ahead or behind to reach the bounds
+pub const ADAPTIVE_CLOSE_RALLY: f32 = 8.0; // Hits per point at which play already counts as close
+pub const ADAPTIVE_RALLY_WINDOW: usize = 5; // How many recent points' rallies are looked at
+
+// Pausing
+pub const MATCH_PAUSE_LIMIT: f32 = 60.0; // Total pause time in a bot or watched match (seconds)
+
+// Quick restart
+pub const QUICK_RESTART_CONFIRM_TIME: f32 = 3.0; // Seconds to press G again to confirm a restart
+
+// Auto-pilot
+pub const AUTOPILOT_REMATCH_DELAY: f32 = 5.0; // Seconds on the game over screen before it plays again
+
+// Debug overlay
+pub const DEBUG_TARGET_MARK: f32 = 20.0; // Length of the line marking where an AI is aiming
+
+// Player profiles
+pub const MAX_PROFILES: usize = 8; // Most player profiles kept
+pub const PROFILE_NAME_MAX_LENGTH: usize = 16; // Most characters in a profile name
+pub const STICK_SENSITIVITY_MIN: f32 = 0.5; // Least touchy stick setting
+pub const STICK_SENSITIVITY_MAX: f32 = 2.0; // Most touchy stick setting
+pub const STICK_SENSITIVITY_STEP: f32 = 0.25; // How much one press changes the stick setting
+
+// Win streaks
+pub const WIN_STREAK_MILESTONES: [u32; 3] = [3, 5, 10]; // Streaks announced at the start of a match
+pub const STREAK_BANNER_TIME: f32 = 3.0; // Seconds a streak banner stays up
+
+// Input latency test (hidden screen, F9 on the main menu)
+pub const LATENCY_FLASH_TIME: f32 = 0.1; // Seconds the flash stays up once it's on screen
+pub const LATENCY_SAMPLES: usize = 20; // Most recent presses the results are worked out from
+
+// Low-power mode
+pub const LOW_POWER_FPS: f32 = 30.0; // Frame rate cap during a match
+pub const LOW_POWER_MENU_FPS: f32 = 10.0; // Frame rate cap on menus and pause screens
+
+// Sound cues
+pub const SOUND_SAMPLE_RATE: u32 = 22050; // Samples per second in the built-in tones
+pub const CUE_VOLUME: f32 = 0.4; // How loud cues play (0 to 1)
+pub const CUE_TIME_TOLERANCE: f32 = 1e-4; // Cues this close to due (seconds) play now
+pub const BEEP_PITCH: f32 = 440.0; // Countdown number beep (Hz)
+pub const BEEP_LENGTH: f32 = 0.12; // Countdown number beep (seconds)
+pub const GO_PITCH: f32 = 880.0; // Serve beep (Hz)
+pub const GO_LENGTH: f32 = 0.25; // Serve beep (seconds)
+pub const GOAL_JINGLE: [(f32, f32); 3] = [(523.0, 0.1), (659.0, 0.1), (784.0, 0.2)]; // Rising notes
+pub const HIT_PITCH: f32 = 220.0; // Paddle hit "tock" (Hz)
+pub const HIT_LENGTH: f32 = 0.05; // Paddle hit "tock" (seconds)
+pub const BOUNCE_PITCH: f32 = 330.0; // Wall or block bounce (Hz)
+pub const BOUNCE_LENGTH: f32 = 0.03; // Wall or block bounce (seconds)
+pub const CLICK_PITCH: f32 = 1200.0; // Menu click (Hz)
+pub const CLICK_LENGTH: f32 = 0.02; // Menu click (seconds)
+
+// Crowd ambience
+pub const CROWD_VOLUME: f32 = 0.12; // How loud the crowd murmurs at most (0 to 1)
+pub const CROWD_LOOP_LENGTH: f32 = 4.0; // Seconds of murmur before the loop repeats
+pub const CROWD_SMOOTHING: f32 = 0.08; // Share of the noise each sample takes (lower = deeper)
+
+// Background music
+pub const MAX_SOUND_VOLUME: u32 = 10; // Steps on the volume setting (0 = silent)
+pub const MUSIC_VOLUME: f32 = 0.15; // How loud each music layer plays at most (0 to 1)
+pub const MUSIC_SPEED_WINDOW: f32 = 4.0; // Seconds of ball speed the music follows
+pub const MUSIC_INTENSITY_RATE: f32 = 0.25; // Most the intensity changes per second
+pub const MUSIC_MAX_INTENSITY: f32 = 0.8; // Cap on the driving layer, so it never drowns the bass
+pub const MUSIC_BASS: [(f32, f32); 8] = [
+    (110.0, 0.25),
+    (110.0, 0.25),
+    (165.0, 0.25),
+    (147.0, 0.25),
+    (110.0, 0.25),
+    (110.0, 0.25),
+    (131.0, 0.25),
+    (147.0, 0.25),
+]; // Steady two-second bass line
+pub const MUSIC_DRIVE: [(f32, f32); 8] = [
+    (440.0, 0.125),
+    (554.0, 0.125),
+    (659.0, 0.125),
+    (554.0, 0.125),
+    (440.0, 0.125),
+    (659.0, 0.125),
+    (880.0, 0.125),
+    (659.0, 0.125),
+]; // Quick one-second arpeggio, twice to every bass line
+
+// Match analysis
+pub const MISHIT_OFFSET: f32 = 0.7; // Hits further than this from the paddle center are mishits
+pub const ANALYSIS_PADDLE_SCALE: f32 = 3.0; // How much bigger the paddle diagram is than a paddle
+pub const ANALYSIS_DOT_RADIUS: f32 = 5.0; // Size of each contact dot on the diagram
+
+// Pace graph on the game over screen
+pub const PACE_GRAPH_WIDTH: f32 = 360.0; // How wide the graph is (pixels)
+pub const PACE_GRAPH_HEIGHT: f32 = 90.0; // How tall the graph is (pixels)
+pub const PACE_GRAPH_TOP: f32 = 415.0; // Where the top of the graph sits on the screen
+
+// Pause screen
+pub const PAUSED_TITLE_TOP: f32 = 130.0; // Where the "Paused" title sits on the screen
+pub const PAUSE_CARD_TOP: f32 = 190.0; // Where the top of the match summary card sits
+pub const PAUSE_CARD_PADDING: f32 = 16.0; // Space between the card's edge and its text
+
+// Bot server
+pub const BOT_SERVER_ADDRESS: &str = "127.0.0.1:7878"; // Where bots connect (this computer only)
+pub const BOT_MAX_BACKLOG: usize = 256 * 1024; // Bytes a bot may leave unread before it is dropped
+pub const SPECTATOR_SERVER_ADDRESS: &str = "127.0.0.1:7879"; // Where spectators connect
+pub const BOT_LOCKSTEP_TIMEOUT: f32 = 0.5; // Longest a lockstep step waits for a move (seconds)
+
+// Streaming HUD window (only built with the stream-hud feature)
+#[cfg(feature = "stream-hud")]
+pub const STREAM_HUD_ADDRESS: &str = "127.0.0.1:7880"; // Where the HUD window gets the match from
+#[cfg(feature = "stream-hud")]
+pub const STREAM_HUD_WIDTH: f32 = 420.0; // HUD window size (pixels)
+#[cfg(feature = "stream-hud")]
+pub const STREAM_HUD_HEIGHT: f32 = 110.0;
+#[cfg(feature = "stream-hud")]
+pub const STREAM_HUD_RETRY: f32 = 1.0; // Seconds between tries to reach the game
+
+// Match codes
+pub const MATCH_CODE_VERSION: char = '2'; // First character of every code, for this code layout
+pub const MATCH_CODE_LENGTH: usize = 22; // Letters and digits in a code, not counting dashes
+pub const MATCH_CODE_MAX_LENGTH: usize = 25; // Most characters the code entry takes, dashes included
+
+// Saved statistics
+pub const STATS_FILE_NAME: &str = "stats.json"; // File name inside the user data folder
+pub const MATCH_HISTORY_LENGTH: usize = 20; // Most recent matches kept in the stats file
+
+// Remembered between runs
+pub const PREFERENCES_FILE_NAME: &str = "preferences.json"; // File name inside the user data folder
+
+// Setup export and import
+pub const BUNDLE_FILE_NAME: &str = "setup.json"; // File name inside the user data folder
+pub const BUNDLE_VERSION: u32 = 1; // Format version written into exported setups
+
+// Unfinished match kept for next time
+pub const SAVED_MATCH_FILE_NAME: &str = "saved_match.json"; // File name inside the user data folder
+pub const SAVED_MATCH_VERSION: u32 = 1; // Format version written into saved matches
+
+// Hidden window
+pub const HIDDEN_FPS: f32 = 5.0; // How often the game wakes up while minimized or hidden
+
+// Simulation timing
+pub const FIXED_TIMESTEP: f32 = 1.0 / 120.0; // Physics always moves in steps of this size (120 Hz)
+pub const MAX_FRAME_TIME: f32 = 0.25; // Longest frame we try to catch up on (e.g. after a window drag)
+
+// Obstacle (arcade) mode
+pub const OBSTACLE_WIDTH: f32 = 20.0; // How wide each block in the middle of the court is
+pub const OBSTACLE_HEIGHT: f32 = 80.0; // How tall each block is
+pub const OBSTACLE_SPEED: f32 = 100.0; // How fast the sliding blocks move (pixels per second)
+
+// Training mode (ball machine)
+pub const LAUNCHER_WIDTH: f32 = 16.0; // How deep the ball machine is
+pub const LAUNCHER_HEIGHT: f32 = 50.0; // How tall the ball machine is
+pub const LAUNCHER_MIN_SPEED: f32 = 250.0; // Slowest ball it fires (pixels per second)
+pub const LAUNCHER_MAX_SPEED: f32 = 450.0; // Fastest ball it fires (pixels per second)
+pub const LAUNCHER_MAX_ANGLE: f32 = 0.5; // Steepest ball it fires (radians from flat)
+
+// Practice drills (recorded shots for the ball machine)
+pub const DRILLS_FILE_NAME: &str = "drills.json"; // File name inside the user data folder
+pub const DRILL_MAX_SHOTS: usize = 20; // Most shots one drill records
+pub const DRILL_NAME_MAX_LENGTH: usize = 24; // Most characters in a drill's name
+pub const MAX_DRILLS: usize = 10; // Most drills kept, so the list fits on one screen
+
+// Arenas
+pub const ICE_PADDLE_FRICTION: f32 = 1.0; // Paddle friction on ice (PADDLE_FRICTION normally)
+pub const RUBBER_WALL_RESTITUTION: f32 = 1.1; // Rubber walls speed the ball up a little
+
+// Lives (arcade) mode
+pub const STARTING_LIVES: u32 = 5; // How many misses each player can afford
+pub const LIFE_ICON_RADIUS: f32 = 6.0; // How big each life icon is
+
+// Match rules
+pub const POINTS_TO_WIN_CHOICES: [u32; 4] = [3, 5, 7, 11]; // Points needed to win a game
+pub const BEST_OF_CHOICES: [u32; 3] = [1, 3, 5]; // How many games a match can be played over
+pub const CLASSIC_POINTS_TO_WIN: u32 = 11; // Points to take a game under classic table tennis rules
+pub const CLASSIC_SERVES_IN_A_ROW: u32 = 2; // Serves each player gets before it changes (classic)
+
+// Momentum bar
+pub const MOMENTUM_POINT_PUSH: f32 = 0.25; // How far a point won off the serve moves the bar (of 1)
+pub const MOMENTUM_LONG_RALLY: u32 = 10; // Hits in a rally whose point moves the bar twice as far
+pub const MOMENTUM_HALF_LIFE: f32 = 20.0; // Seconds of play for the bar's lean to fade by half
+pub const MOMENTUM_EASE: f32 = 3.0; // How quickly the bar slides to its new spot (per second)
+pub const MOMENTUM_BAR_WIDTH: f32 = 200.0; // Full width of the bar (pixels)
+pub const MOMENTUM_BAR_HEIGHT: f32 = 6.0; // Thickness of the bar (pixels)
+
+// Broadcast scoreboard (B)
+pub const BROADCAST_BAR_WIDTH: f32 = 560.0; // Width of the scoreboard bar (pixels)
+pub const BROADCAST_BAR_HEIGHT: f32 = 34.0; // Height of the scoreboard bar (pixels)
+pub const BROADCAST_BAR_TOP: f32 = 10.0; // Gap above the bar (pixels)
+pub const BROADCAST_SCORE_WIDTH: f32 = 48.0; // Width of each score box in the middle (pixels)
+
+// Holiday events (snow and fireworks)
+pub const SNOW_SWAY: f32 = 20.0; // Fastest a snowflake drifts sideways (pixels per second)
+pub const FIREWORK_GRAVITY: f32 = 120.0; // How quickly firework sparks speed up as they fall
+
+// Checks on the settings above, so a bad edit stops the build with a clear
+// message instead of breaking the game while it runs
+const _: () = {
+    assert!(
+        PLAYER_PADDLE_SPEED > 0.0,
+        "PLAYER_PADDLE_SPEED must be above zero"
+    );
+    assert!(AI_PADDLE_SPEED > 0.0, "AI_PADDLE_SPEED must be above zero");
+    assert!(BALL_SPEED > 0.0, "BALL_SPEED must be above zero");
+    assert!(
+        MAX_BALL_SPEED >= BALL_SPEED,
+        "MAX_BALL_SPEED must be at least BALL_SPEED"
+    );
+    assert!(
+        LARGE_PADDLE_HEIGHT < SCREEN_HEIGHT,
+        "paddles must fit on the screen"
+    );
+    assert!(
+        BALL_RADIUS * 2.0 < SMALL_PADDLE_HEIGHT,
+        "the ball must be smaller than a paddle"
+    );
+    assert!(
+        MAX_BALL_SPEED >= FAST_BALL_SPEED && SLOW_BALL_SPEED > 0.0,
+        "serve speeds must be above zero and at most MAX_BALL_SPEED"
+    );
+    assert!(
+        NARROW_COURT_WIDTH <= SCREEN_WIDTH,
+        "the narrow court must fit in the window"
+    );
+    assert!(
+        WIDE_COURT_HEIGHT <= SCREEN_HEIGHT && TALL_COURT_WIDTH <= SCREEN_WIDTH,
+        "the wide and tall courts must fit in the window"
+    );
+    assert!(
+        WIDE_COURT_HEIGHT > LARGE_PADDLE_HEIGHT * 2.0,
+        "the wide court must leave room to get past a large paddle"
+    );
+    assert!(
+        COUNTDOWN_DURATION >= 0.0,
+        "COUNTDOWN_DURATION can't be negative"
+    );
+    assert!(
+        COUNTDOWN_DURATION <= 3.0,
+        "the theme only has colors for a 3-2-1 countdown"
+    );
+    assert!(
+        COUNTDOWN_GO_DURATION >= 0.0,
+        "COUNTDOWN_GO_DURATION can't be negative"
+    );
+    assert!(FIXED_TIMESTEP > 0.0, "FIXED_TIMESTEP must be above zero");
+    assert!(
+        GAMEPAD_DEADZONE >= 0.0 && GAMEPAD_DEADZONE <= GAMEPAD_DEADZONE_MAX,
+        "GAMEPAD_DEADZONE must be 0 to GAMEPAD_DEADZONE_MAX"
+    );
+    assert!(
+        GAMEPAD_DEADZONE_MAX < 1.0 && GAMEPAD_DEADZONE_STEP > 0.0,
+        "GAMEPAD_DEADZONE_MAX must be below 1 and GAMEPAD_DEADZONE_STEP above zero"
+    );
+    assert!(
+        MOUSE_FOLLOW_DISTANCE > 0.0,
+        "MOUSE_FOLLOW_DISTANCE must be above zero"
+    );
+    assert!(
+        MISHIT_OFFSET > 0.0 && MISHIT_OFFSET < 1.0,
+        "MISHIT_OFFSET must be 0 to 1"
+    );
+    assert!(BALL_SPIN_DECAY >= 0.0, "BALL_SPIN_DECAY can't be negative");
+    assert!(
+        BALL_SPIN_MIN_BOUNCE_ANGLE < MAX_DEFLECTION_ANGLE,
+        "BALL_SPIN_MIN_BOUNCE_ANGLE must be flatter than MAX_DEFLECTION_ANGLE"
+    );
+    assert!(
+        AI_SPEED_PER_STEP * (AI_TUNING_STEPS as f32) < 1.0,
+        "the slowest AI speed slider step must still move the paddle"
+    );
+    assert!(
+        LAUNCHER_MIN_SPEED > 0.0 && LAUNCHER_MIN_SPEED <= LAUNCHER_MAX_SPEED,
+        "the ball machine's slowest ball must be above zero and no faster than its fastest"
+    );
+    assert!(
+        ADAPTIVE_MIN_SPEED > 0.0 && ADAPTIVE_MIN_SPEED <= ADAPTIVE_MAX_SPEED,
+        "ADAPTIVE_MIN_SPEED must be above zero and at most ADAPTIVE_MAX_SPEED"
+    );
+    assert!(
+        ADAPTIVE_MIN_ERROR >= 0.0 && ADAPTIVE_MIN_ERROR <= ADAPTIVE_MAX_ERROR,
+        "ADAPTIVE_MIN_ERROR can't be negative or above ADAPTIVE_MAX_ERROR"
+    );
+    assert!(
+        ADAPTIVE_SCORE_SPAN > 0.0 && ADAPTIVE_CLOSE_RALLY > 0.0,
+        "ADAPTIVE_SCORE_SPAN and ADAPTIVE_CLOSE_RALLY must be above zero"
+    );
+    assert!(
+        STICK_SENSITIVITY_MIN > 0.0
+            && STICK_SENSITIVITY_MIN <= 1.0
+            && STICK_SENSITIVITY_MAX >= 1.0
+            && STICK_SENSITIVITY_STEP > 0.0,
+        "the stick sensitivity range must include 1 (as pushed) and step upward"
+    );
+    assert!(
+        MOMENTUM_POINT_PUSH > 0.0 && MOMENTUM_POINT_PUSH <= 0.5,
+        "MOMENTUM_POINT_PUSH must be above zero and at most 0.5 (a long rally pushes twice that)"
+    );
+    assert!(
+        MOMENTUM_HALF_LIFE > 0.0 && MOMENTUM_LONG_RALLY > 0,
+        "MOMENTUM_HALF_LIFE and MOMENTUM_LONG_RALLY must be above zero"
+    );
+    assert!(
+        BROADCAST_SCORE_WIDTH * 2.0 < BROADCAST_BAR_WIDTH && BROADCAST_BAR_WIDTH <= SCREEN_WIDTH,
+        "the broadcast bar must fit on screen with room for names beside the score boxes"
+    );
+    assert!(
+        REPLAY_MIN_LENGTH > 0.0 && REPLAY_MIN_LENGTH <= REPLAY_LENGTH && REPLAY_SPEED > 0.0,
+        "replays must last longer than REPLAY_MIN_LENGTH and play at a speed above 0"
+    );
+    assert!(
+        BALL_HEAT_FULL_SPEED > BALL_HEAT_START_SPEED,
+        "BALL_HEAT_FULL_SPEED must be above BALL_HEAT_START_SPEED"
+    );
+    assert!(
+        BALL_GLOW_SIZE >= 1.0
+            && BALL_GLOW_OPACITY >= 0.0
+            && BALL_GLOW_OPACITY <= 1.0
+            && BALL_GLOW_PULSE_DEPTH >= 0.0
+            && BALL_GLOW_PULSE_DEPTH <= 1.0
+            && BALL_GLOW_LAYERS > 0,
+        "the ball glow must be at least the ball's size, with opacity and pulse depth 0 to 1"
+    );
+    assert!(
+        SNOW_SWAY >= 0.0 && FIREWORK_GRAVITY >= 0.0,
+        "SNOW_SWAY and FIREWORK_GRAVITY can't be negative"
+    );
+    assert!(
+        MAX_SAFE_MARGIN < 25,
+        "MAX_SAFE_MARGIN must leave at least half the window for the HUD"
+    );
+    assert!(
+        LATENCY_SAMPLES > 0,
+        "LATENCY_SAMPLES must keep at least one press"
+    );
+    assert!(
+        AI_TUNING_STEPS >= 1 && AI_TUNING_STEPS <= 7,
+        "AI_TUNING_STEPS must be 1 to 7 so a match code holds each slider in one digit"
+    );
+};
@@ -0,0 +1,75 @@
+// This file holds the paddle controllers: whatever decides how a paddle moves
+// each step, whether that is a person at the keyboard or the computer
+
+use crate::components::{ball::Ball, paddle::Paddle};
+use crate::game::constants::*;
+use rand_chacha::ChaCha12Rng;
+
+// The random numbers a match and its computer players use. It's the same
+// generator rand's StdRng is built on, used directly because it can say how
+// far along it is, so a saved match carries on with the same numbers
+pub type MatchRng = ChaCha12Rng;
+
+// What a controller gets to look at when deciding how to move its paddle
+pub struct GameView<'a> {
+    pub ball: &'a Ball,         // The ball, with its position and speed
+    pub paddle: &'a Paddle,     // The paddle being controlled
+    pub input: f32,             // Held direction from this paddle's player (-1 = up, 1 = down)
+    pub serving: bool,          // The countdown before a serve is running
+    pub momentum_physics: bool, // Paddles have mass and keep sliding when on
+    pub score_gap: i32,         // This paddle's points minus the other player's
+    pub recent_rally: f32,      // Average paddle hits per point over the last few points
+}
+
+// Something that can drive a paddle
+pub trait PaddleController {
+    // How far to move the paddle this step (pixels, negative = up)
+    fn desired_move(&mut self, view: &GameView, delta: f32) -> f32;
+
+    // Height the controller was last aiming the paddle's center at, for the
+    // debug overlay (None for people, or an AI that isn't chasing the ball)
+    fn target_y(&self) -> Option<f32> {
+        None
+    }
+
+    // How many random numbers the controller has used, so a saved match
+    // can carry on with the same mistakes (None for people)
+    fn rng_position(&self) -> Option<u64> {
+        None
+    }
+
+    // Skip the controller's random numbers ahead to a saved position
+    fn set_rng_position(&mut self, _position: u64) {}
+}
+
+// One controller for each paddle: player 1's, then player 2's
+pub type Controllers = [Box<dyn PaddleController>; 2];
+
+// A person steering with the keyboard or a controller
+pub struct HumanController;
+
+impl PaddleController for HumanController {
+    fn desired_move(&mut self, view: &GameView, delta: f32) -> f32 {
+        // Work on a copy so the move can be handed back instead of applied here
+        let mut paddle = view.paddle.clone();
+
+        if view.momentum_physics {
+            // Held keys push the paddle, which keeps sliding after release.
+            // The push follows the friction so the top speed stays the
+            // paddle's own, and grows with it for a quicker paddle
+            let force =
+                PADDLE_INPUT_FORCE * paddle.friction / PADDLE_FRICTION * paddle.speed_scale();
+            paddle.apply_force(view.input * force, delta);
+            return paddle.velocity * delta;
+        }
+
+        // Speed up gradually while a key is held, and slow down after release
+        let ramp_time = if view.input != 0.0 {
+            PADDLE_RAMP_UP_TIME
+        } else {
+            PADDLE_RAMP_DOWN_TIME
+        };
+        paddle.accelerate_towards(view.input * paddle.speed, paddle.speed / ramp_time, delta);
+        paddle.velocity * delta
+    }
+}
@@ -0,0 +1,125 @@
+// This file describes the playing area: how big it is and where the paddles stand
+
+use crate::game::constants::*;
+use serde::{Deserialize, Serialize};
+
+// The size of the court the ball plays in, separate from the window size
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Court {
+    pub width: f32,        // How wide the court is (pixels)
+    pub height: f32,       // How tall the court is (pixels)
+    pub paddle_inset: f32, // How far each paddle stands in from its end wall
+}
+
+// Ready-made court layouts to pick from before a game
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CourtPreset {
+    Classic,  // Fills the window, paddles against the walls
+    Narrow,   // A shorter court for quicker rallies
+    PulledIn, // Full size, but paddles stand away from the walls
+    Wide,     // Full width but shorter, for long flat rallies
+    Tall,     // Taller than it is wide, for steep bouncing angles
+}
+
+impl Court {
+    // Create a court with the given size and paddle distance from the walls
+    pub fn new(width: f32, height: f32, paddle_inset: f32) -> Self {
+        Court {
+            width,
+            height,
+            paddle_inset,
+        }
+    }
+
+    // Left edge of the left paddle
+    pub fn left_paddle_x(&self) -> f32 {
+        self.paddle_inset
+    }
+
+    // Left edge of the right paddle
+    pub fn right_paddle_x(&self) -> f32 {
+        self.width - PADDLE_WIDTH - self.paddle_inset
+    }
+
+    // Top edge of a paddle `paddle_height` tall sitting in the middle of the court
+    pub fn paddle_start_y(&self, paddle_height: f32) -> f32 {
+        (self.height - paddle_height) / 2.0
+    }
+
+    // Middle of the court, where the ball is served from
+    pub fn center(&self) -> (f32, f32) {
+        (self.width / 2.0, self.height / 2.0)
+    }
+
+    // Where the court's top-left corner sits so it is centered in the window
+    pub fn offset(&self) -> [f32; 2] {
+        [
+            (SCREEN_WIDTH - self.width) / 2.0,
+            (SCREEN_HEIGHT - self.height) / 2.0,
+        ]
+    }
+
+    // Check if the court fills the whole window
+    pub fn fills_window(&self) -> bool {
+        self.width >= SCREEN_WIDTH && self.height >= SCREEN_HEIGHT
+    }
+}
+
+impl CourtPreset {
+    // Every preset, in the order the settings screen cycles through them
+    pub const ALL: [CourtPreset; 5] = [
+        CourtPreset::Classic,
+        CourtPreset::Narrow,
+        CourtPreset::PulledIn,
+        CourtPreset::Wide,
+        CourtPreset::Tall,
+    ];
+
+    // Build the court for this preset
+    pub fn court(self) -> Court {
+        match self {
+            CourtPreset::Classic => Court::new(SCREEN_WIDTH, SCREEN_HEIGHT, 0.0),
+            CourtPreset::Narrow => Court::new(NARROW_COURT_WIDTH, SCREEN_HEIGHT, 0.0),
+            CourtPreset::PulledIn => {
+                Court::new(SCREEN_WIDTH, SCREEN_HEIGHT, PULLED_IN_PADDLE_INSET)
+            }
+            CourtPreset::Wide => Court::new(SCREEN_WIDTH, WIDE_COURT_HEIGHT, 0.0),
+            CourtPreset::Tall => Court::new(TALL_COURT_WIDTH, SCREEN_HEIGHT, 0.0),
+        }
+    }
+
+    // Move on to the next preset (wraps back to Classic)
+    pub fn next(self) -> Self {
+        match self {
+            CourtPreset::Classic => CourtPreset::Narrow,
+            CourtPreset::Narrow => CourtPreset::PulledIn,
+            CourtPreset::PulledIn => CourtPreset::Wide,
+            CourtPreset::Wide => CourtPreset::Tall,
+            CourtPreset::Tall => CourtPreset::Classic,
+        }
+    }
+
+    // Look up a preset by the name used on the command line, ignoring case
+    // (e.g. "wide" or "pulled-in")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "classic" => Some(CourtPreset::Classic),
+            "narrow" => Some(CourtPreset::Narrow),
+            "pulled-in" => Some(CourtPreset::PulledIn),
+            "wide" => Some(CourtPreset::Wide),
+            "tall" => Some(CourtPreset::Tall),
+            _ => None,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            CourtPreset::Classic => "Classic",
+            CourtPreset::Narrow => "Narrow",
+            CourtPreset::PulledIn => "Pulled-in paddles",
+            CourtPreset::Wide => "Wide",
+            CourtPreset::Tall => "Tall",
+        }
+    }
+}
@@ -0,0 +1,12 @@
+// This file finds where the game keeps the files it remembers between runs
+// (stats, preferences, drills, a saved match and the setup bundle): the user
+// data folder for this game
+
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+// Where the file called `name` lives in the user data folder
+// (None if the system doesn't have one, e.g. no home folder)
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    ProjectDirs::from("", "", "Ping Pong").map(|dirs| dirs.data_dir().join(name))
+}
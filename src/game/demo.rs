@@ -0,0 +1,42 @@
+// This file runs the demo match behind the main menu: two computer players
+// rallying silently, stepped like a real match and started over whenever
+// one side wins
+
+use crate::game::constants::*;
+use crate::game::scene::Scene;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+
+// A demo match between two computer players, already counting down to its first serve
+pub fn new_demo() -> Simulation {
+    let mut demo = Simulation::new(GameMode::Demo);
+    demo.start_match(GameMode::Demo);
+    demo
+}
+
+impl GameState {
+    // Keep the menu's demo match going, in fixed-size steps like a real match.
+    // It plays silently and starts over whenever one side wins
+    pub fn advance_demo(&mut self, frame_delta: f32) {
+        self.demo_accumulator += frame_delta.clamp(0.0, MAX_FRAME_TIME);
+        while self.demo_accumulator >= FIXED_TIMESTEP {
+            self.demo.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            self.demo_accumulator -= FIXED_TIMESTEP;
+        }
+        self.demo.audio.clear();
+        if self.demo.winner.is_some() {
+            self.demo.start_match(GameMode::Demo);
+        }
+    }
+
+    // Check if the demo match should be shown: only on the main menu
+    pub fn shows_demo(&self) -> bool {
+        self.scene == Scene::Menu
+    }
+
+    // Check if the demo match should keep playing. In low-power mode it would
+    // cost battery, so the menu shows it as a still frame instead
+    pub fn runs_demo(&self) -> bool {
+        self.shows_demo() && !self.low_power
+    }
+}
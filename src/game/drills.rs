@@ -0,0 +1,245 @@
+// This file holds practice drills: short runs of shots recorded in training
+// mode, which the ball machine fires again on loop. Drills are named by the
+// player and kept in a file in the user data folder
+
+use crate::components::ball::Ball;
+use crate::game::constants::*;
+use crate::game::court::Court;
+use crate::game::data_folder::data_file;
+use crate::game::scene::Scene;
+use crate::game::simulation::GameMode;
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// One ball for the ball machine to fire
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Shot {
+    pub height: f32, // Where it leaves from: 0 (top of the court) to 1 (bottom)
+    pub speed: f32,  // How fast it goes (pixels per second)
+    pub angle: f32,  // Radians from flat, positive = heading down
+}
+
+impl Shot {
+    // The shot a ball just sent back by the player makes, so the machine
+    // fires the same ball the other way. Heights are kept as a share of the
+    // court's height, so a drill fits any court
+    pub fn from_return(ball: &Ball, court: &Court) -> Self {
+        Shot {
+            height: (ball.y / court.height).clamp(0.0, 1.0),
+            speed: ball.speed().min(MAX_BALL_SPEED),
+            angle: (ball.dy / ball.dx.abs().max(f32::EPSILON)).atan(),
+        }
+    }
+}
+
+// A named run of shots
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Drill {
+    pub name: String,     // Shown on the drill list, e.g. "fast top-corner serves"
+    pub shots: Vec<Shot>, // Fired in this order, starting again after the last
+}
+
+impl Drill {
+    // Check the drill makes sense, e.g. after someone edited the file by hand
+    pub fn validate(&self) -> Result<(), String> {
+        let name_length = self.name.chars().count();
+        if name_length == 0 || name_length > DRILL_NAME_MAX_LENGTH {
+            return Err(format!(
+                "a drill's name must be 1 to {} characters",
+                DRILL_NAME_MAX_LENGTH
+            ));
+        }
+        if self.shots.is_empty() || self.shots.len() > DRILL_MAX_SHOTS {
+            return Err(format!(
+                "drill \"{}\" must have 1 to {} shots",
+                self.name, DRILL_MAX_SHOTS
+            ));
+        }
+        for shot in &self.shots {
+            let fits = (0.0..=1.0).contains(&shot.height)
+                && shot.speed > 0.0
+                && shot.speed <= MAX_BALL_SPEED
+                && shot.angle.abs() < std::f32::consts::FRAC_PI_2; // NaN fails every check
+            if !fits {
+                return Err(format!("drill \"{}\" has a shot it can't fire", self.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Every saved drill
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)] // Missing fields start empty; misspelled ones are an error
+pub struct DrillBook {
+    pub drills: Vec<Drill>, // In the order they were first saved
+}
+
+impl DrillBook {
+    // Read drills from a file. A missing file means none have been saved yet;
+    // a damaged one is an error naming the line and column (or the bad drill)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(DrillBook::default())
+            }
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        let book: DrillBook = serde_json::from_str(&text)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        if book.drills.len() > MAX_DRILLS {
+            return Err(format!(
+                "{}: at most {} drills can be kept",
+                path.display(),
+                MAX_DRILLS
+            ));
+        }
+        for drill in &book.drills {
+            drill
+                .validate()
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+        }
+        Ok(book)
+    }
+
+    // Write drills to a file, creating its folder if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    // Keep a drill, replacing any saved before under the same name. Once
+    // MAX_DRILLS are kept, a new name is turned down
+    pub fn add(&mut self, drill: Drill) -> Result<(), String> {
+        let full = self.drills.len() >= MAX_DRILLS;
+        match self
+            .drills
+            .iter_mut()
+            .find(|saved| saved.name == drill.name)
+        {
+            Some(saved) => *saved = drill,
+            None if full => {
+                return Err(format!(
+                    "only {} drills can be kept; save over one by using its name",
+                    MAX_DRILLS
+                ))
+            }
+            None => self.drills.push(drill),
+        }
+        Ok(())
+    }
+}
+
+// Where the drills file lives: the user data folder for this game
+// (None if the system doesn't have one)
+pub fn drills_file() -> Option<PathBuf> {
+    data_file(DRILLS_FILE_NAME)
+}
+
+// Picking, recording and saving drills from the game
+impl GameState {
+    // Entries on the drill list: random balls, every saved drill, then Back
+    pub fn drill_choices(&self) -> Vec<String> {
+        let mut choices = vec!["Random Balls".to_string()];
+        for drill in &self.drills.drills {
+            choices.push(format!("{} ({} shots)", drill.name, drill.shots.len()));
+        }
+        choices.push("Back".to_string());
+        choices
+    }
+
+    // Start training with the highlighted entry on the drill list
+    pub fn select_drill(&mut self) {
+        let selected = self.drill_cursor.selected;
+        if selected > self.drills.drills.len() {
+            self.scene = Scene::Menu; // Back
+            return;
+        }
+        self.sim.launcher.drill = selected
+            .checked_sub(1)
+            .map(|index| self.drills.drills[index].clone());
+        self.start_match(GameMode::Training);
+    }
+
+    // Start recording the player's returns as a drill, or stop and name it.
+    // Recording stops with the match paused while the name is typed
+    pub fn toggle_drill_recording(&mut self) {
+        if self.sim.mode != GameMode::Training {
+            return;
+        }
+        match &self.sim.launcher.recording {
+            None => self.sim.launcher.recording = Some(Vec::new()),
+            Some(shots) if shots.is_empty() => self.sim.launcher.recording = None,
+            Some(_) => {
+                self.pause();
+                if self.scene == Scene::Paused {
+                    self.drill_name.clear();
+                    self.scene = Scene::NameDrill;
+                }
+            }
+        }
+    }
+
+    // Add a typed character to the drill's name
+    pub fn type_drill_name_char(&mut self, character: char) {
+        if !character.is_control() && self.drill_name.chars().count() < DRILL_NAME_MAX_LENGTH {
+            self.drill_name.push(character);
+        }
+    }
+
+    // Keep the recorded shots under the typed name and save the drills file,
+    // going back to the paused match with the result on the pause screen
+    pub fn save_drill(&mut self) {
+        let name = self.drill_name.trim().to_string();
+        if name.is_empty() {
+            self.status = Some("Type a name for the drill first".to_string());
+            return;
+        }
+        let shots = self.sim.launcher.recording.take().unwrap_or_default();
+        self.status = Some(match self.drills.add(Drill { name, shots }) {
+            Ok(()) => match self.save_drills() {
+                Ok(()) => format!("Saved drill \"{}\"", self.drill_name.trim()),
+                Err(error) => format!("Could not save drills: {}", error),
+            },
+            Err(reason) => format!("Drill not kept: {}", reason),
+        });
+        self.scene = Scene::Paused;
+    }
+
+    // Throw away the recorded shots and go back to the paused match
+    pub fn discard_drill(&mut self) {
+        self.sim.launcher.recording = None;
+        self.scene = Scene::Paused;
+    }
+
+    // Load practice drills from a file, and save new ones back there. A
+    // damaged file is shown at startup and left alone, like the stats file
+    pub fn use_drills_file(&mut self, path: PathBuf) {
+        match DrillBook::load(&path) {
+            Ok(drills) => {
+                self.drills = drills;
+                self.drills_path = Some(path);
+            }
+            Err(error) => self.report_startup_error(format!(
+                "Could not read your drills. New drills won't be saved this run.\n{}",
+                error
+            )),
+        }
+    }
+
+    // Write the drills to their file, if there is one
+    pub fn save_drills(&self) -> io::Result<()> {
+        match &self.drills_path {
+            Some(path) => self.drills.save(path),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+// This file lists the things that happen during a match that other parts of
+// the game react to. The simulation only announces what happened; the match
+// stats, the sound cues, the paddle-hit recording and the screen effects each
+// listen for the events they care about, so a new effect doesn't have to be
+// wired into the collision code
+
+use crate::game::recording::{Contact, Miss};
+use crate::game::rules::MatchEnd;
+
+// Something that just happened in the match
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameEvent {
+    CountdownTick,                              // A number of the 3-2-1 countdown appears
+    Serve { server: u8, speed: f32 },           // The countdown ended and the ball is served
+    PaddleHit { contact: Contact, speed: f32 }, // A paddle returned the ball (speed after the hit)
+    WallBounce,                                 // The ball bounced off the top or bottom wall
+    ObstacleBounce,                             // The ball bounced off a block in obstacle mode
+    BallMissed { miss: Miss },                  // The ball got past a paddle, just before the point
+    PointScored { winner: u8 },                 // Someone won a point
+    GameOver { winner: u8, reason: MatchEnd },  // The match is over
+}
+
+// Anything that reacts to what happens in a match
+pub trait EventListener {
+    // Called for every event, in the order they happened
+    fn on_event(&mut self, event: &GameEvent);
+}
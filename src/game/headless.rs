@@ -0,0 +1,72 @@
+// This file plays matches without a window: two computer players rallying as
+// fast as the computer can simulate them, for quick checks and benchmarks
+
+use crate::game::constants::*;
+use crate::game::simulation::{GameMode, Simulation};
+use std::fmt;
+
+// What happened during a headless run
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct HeadlessReport {
+    pub ticks: u64,            // Simulation steps played
+    pub matches_won: [u32; 2], // Finished matches won by the left and right player
+    pub points_played: u32,    // Points finished across every match
+    pub longest_rally: u32,    // Most paddle hits in a single point
+    pub score: (u32, u32),     // Points in the game still going when the run ended
+    pub checksum: u32,         // Hash of the match state when the run ended
+}
+
+impl fmt::Display for HeadlessReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Simulated {} ticks ({:.1} seconds of play)",
+            self.ticks,
+            self.ticks as f32 * FIXED_TIMESTEP
+        )?;
+        writeln!(
+            f,
+            "Matches won: left {}, right {}",
+            self.matches_won[0], self.matches_won[1]
+        )?;
+        writeln!(
+            f,
+            "Points played: {}, longest rally {} hits",
+            self.points_played, self.longest_rally
+        )?;
+        writeln!(f, "Unfinished game: {}-{}", self.score.0, self.score.1)?;
+        write!(f, "State checksum: {:08X}", self.checksum)
+    }
+}
+
+// Play `ticks` fixed-size steps of computer against computer using the match
+// settings on `sim`, starting a new match whenever one is won
+pub fn run_headless(sim: &mut Simulation, ticks: u64) -> HeadlessReport {
+    let mut report = HeadlessReport {
+        ticks,
+        ..HeadlessReport::default()
+    };
+    sim.start_match(GameMode::Demo);
+    for _ in 0..ticks {
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        sim.audio.clear(); // Nothing to play the sounds on
+        if let Some(winner) = sim.winner {
+            report.matches_won[winner as usize - 1] += 1;
+            report.add_match(sim);
+            sim.opening_server = None; // Each match gets a fresh first serve
+            sim.start_match(GameMode::Demo);
+        }
+    }
+    report.add_match(sim);
+    report.score = (sim.score.player1, sim.score.player2);
+    report.checksum = sim.checksum();
+    report
+}
+
+impl HeadlessReport {
+    // Add the points from a match that just ended (or the run stopped in)
+    fn add_match(&mut self, sim: &Simulation) {
+        self.points_played += sim.stats.points_played;
+        self.longest_rally = self.longest_rally.max(sim.stats.longest_rally);
+    }
+}
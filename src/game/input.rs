@@ -0,0 +1,263 @@
+// This file turns keyboard, mouse and gamepad input into things the game
+// understands, like "move paddle 1 up" or "confirm the menu entry"
+
+use crate::game::constants::*;
+use crate::game::profiles::{ControlScheme, SteerKeys, StickCurve};
+use ggez::event::{Axis, Button};
+use ggez::input::gamepad::GamepadId;
+use ggez::input::keyboard::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// What steers player 1's paddle
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum PaddleInput {
+    #[default]
+    Keys, // Arrow keys or the first controller
+    Mouse, // The paddle follows the mouse pointer up and down
+}
+
+impl PaddleInput {
+    // Switch to the other way of steering
+    pub fn next(self) -> Self {
+        match self {
+            PaddleInput::Keys => PaddleInput::Mouse,
+            PaddleInput::Mouse => PaddleInput::Keys,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            PaddleInput::Keys => "Keys & Controller",
+            PaddleInput::Mouse => "Mouse",
+        }
+    }
+}
+
+// Things a player can ask for, whichever device they use
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Up,               // Menu selection up
+    Down,             // Menu selection down
+    Left,             // Menu value back
+    Right,            // Menu value forward
+    Confirm,          // Choose the highlighted entry
+    Back,             // Leave the current menu
+    Pause,            // Pause or resume the match
+    Restart,          // Start the match over
+    QuickRestart,     // Start only the current game over (asks first once points are played)
+    Resign(u8),       // Player 1 or 2 gives up the match
+    NextTheme,        // Switch to the next color theme
+    ToggleFullscreen, // Switch between fullscreen and a window
+    ToggleDebug,      // Show or hide the debug overlay
+    ToggleAutopilot,  // Let the computer play player 1's paddle, or take it back
+    ToggleBroadcast,  // Switch between the normal score line and the broadcast scoreboard
+    Analysis,         // Open the post-match analysis
+    PhotoMode,        // Open photo mode from the pause screen
+    ToggleHud,        // Show or hide the score and badges in photo mode
+    NextFilter,       // Switch to the next photo filter
+    ZoomIn,           // Move the photo camera closer
+    ZoomOut,          // Move the photo camera back
+    RecordDrill,      // Start or stop recording a drill in training
+    LatencyTest,      // Open the hidden input latency test from the main menu
+    Exit,             // Close the game
+}
+
+// What one controller is doing right now
+#[derive(Clone, Copy, Default)]
+struct PadState {
+    id: Option<GamepadId>, // Which controller is in this slot
+    stick_y: f32,          // Left stick, -1 (up) to 1 (down), as pushed
+    dpad_up: bool,         // D-pad up held
+    dpad_down: bool,       // D-pad down held
+    stats_held: bool,      // Stats button held
+}
+
+// Everything held down on the keyboard and the connected controllers
+pub struct InputState {
+    pub pressed_keys: HashSet<KeyCode>, // Keys currently held
+    pads: [PadState; 2],                // Controller for player 1 and player 2
+    pub mouse_y: Option<f32>,           // Pointer height in the play area (None = not moved yet)
+    pub schemes: [ControlScheme; 2],    // How player 1 and player 2 steer
+}
+
+impl InputState {
+    // Start with nothing pressed, no controllers assigned, and the usual controls
+    pub fn new() -> Self {
+        InputState {
+            pressed_keys: HashSet::new(),
+            pads: [PadState::default(); 2],
+            mouse_y: None,
+            schemes: [ControlScheme::for_player(1), ControlScheme::for_player(2)],
+        }
+    }
+
+    // Record a key press and return the action it stands for, if any
+    pub fn key_down(&mut self, key: KeyCode) -> Option<Action> {
+        self.pressed_keys.insert(key);
+        match key {
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Left => Some(Action::Left),
+            KeyCode::Right => Some(Action::Right),
+            KeyCode::Return => Some(Action::Confirm),
+            KeyCode::Escape => Some(Action::Back),
+            KeyCode::P => Some(Action::Pause),
+            KeyCode::R => Some(Action::Restart),
+            KeyCode::G => Some(Action::QuickRestart),
+            KeyCode::E => Some(Action::Exit),
+            KeyCode::T => Some(Action::NextTheme),
+            KeyCode::F11 => Some(Action::ToggleFullscreen),
+            KeyCode::F3 => Some(Action::ToggleDebug),
+            KeyCode::F9 => Some(Action::LatencyTest),
+            KeyCode::O => Some(Action::ToggleAutopilot),
+            KeyCode::B => Some(Action::ToggleBroadcast),
+            KeyCode::A => Some(Action::Analysis),
+            KeyCode::F => Some(Action::Resign(1)),
+            KeyCode::Q => Some(Action::Resign(2)),
+            KeyCode::C => Some(Action::PhotoMode),
+            KeyCode::K => Some(Action::RecordDrill),
+            KeyCode::H => Some(Action::ToggleHud),
+            KeyCode::L => Some(Action::NextFilter),
+            KeyCode::Equals | KeyCode::Plus | KeyCode::NumpadAdd => Some(Action::ZoomIn),
+            KeyCode::Minus | KeyCode::NumpadSubtract => Some(Action::ZoomOut),
+            _ => None,
+        }
+    }
+
+    // Record a key release
+    pub fn key_up(&mut self, key: KeyCode) {
+        self.pressed_keys.remove(&key);
+    }
+
+    // Check if a key is held down
+    pub fn is_key_held(&self, key: KeyCode) -> bool {
+        self.pressed_keys.contains(&key)
+    }
+
+    // Record a controller button press and return the action it stands for, if any
+    pub fn button_down(&mut self, button: Button, id: GamepadId) -> Option<Action> {
+        let slot = self.slot_for(id);
+        if let Some(slot) = slot {
+            let pad = &mut self.pads[slot];
+            match button {
+                Button::DPadUp => pad.dpad_up = true,
+                Button::DPadDown => pad.dpad_down = true,
+                Button::North => pad.stats_held = true,
+                _ => {}
+            }
+        }
+        match button {
+            Button::DPadUp => Some(Action::Up),
+            Button::DPadDown => Some(Action::Down),
+            Button::DPadLeft => Some(Action::Left),
+            Button::DPadRight => Some(Action::Right),
+            Button::South => Some(Action::Confirm),
+            Button::East => Some(Action::Back),
+            Button::Start => Some(Action::Pause),
+            Button::Select => Some(Action::Restart),
+            Button::RightThumb => Some(Action::QuickRestart), // Clicking the right stick
+            Button::LeftThumb => Some(Action::ToggleAutopilot), // Clicking the left stick
+            Button::West => slot.map(|slot| Action::Resign(slot as u8 + 1)),
+            _ => None,
+        }
+    }
+
+    // Record a controller button release
+    pub fn button_up(&mut self, button: Button, id: GamepadId) {
+        if let Some(slot) = self.slot_for(id) {
+            let pad = &mut self.pads[slot];
+            match button {
+                Button::DPadUp => pad.dpad_up = false,
+                Button::DPadDown => pad.dpad_down = false,
+                Button::North => pad.stats_held = false,
+                _ => {}
+            }
+        }
+    }
+
+    // Record a stick movement
+    pub fn axis_moved(&mut self, axis: Axis, value: f32, id: GamepadId) {
+        if axis == Axis::LeftStickY {
+            if let Some(slot) = self.slot_for(id) {
+                // Sticks report up as positive, but our paddles move down for positive
+                self.pads[slot].stick_y = -value;
+            }
+        }
+    }
+
+    // How player 1 or 2 wants their paddle to move: -1 (full up) to 1 (full
+    // down), from the keys and controller their control scheme steers with
+    pub fn paddle_direction(&self, player: u8) -> f32 {
+        let slot = if player == 1 { 0 } else { 1 };
+        let scheme = self.schemes[slot];
+        let (up_key, down_key) = match scheme.keys {
+            SteerKeys::Arrows => (KeyCode::Up, KeyCode::Down),
+            SteerKeys::Wasd => (KeyCode::W, KeyCode::S),
+        };
+        // A player who doesn't steer with a controller gets nothing from it
+        let pad = if scheme.gamepad {
+            self.pads[slot]
+        } else {
+            PadState::default()
+        };
+
+        let mut direction = 0.0;
+        if self.is_key_held(up_key) || pad.dpad_up {
+            direction -= 1.0;
+        }
+        if self.is_key_held(down_key) || pad.dpad_down {
+            direction += 1.0;
+        }
+        let stick = apply_deadzone(pad.stick_y, scheme.stick_deadzone(), scheme.curve);
+        (direction + stick * scheme.stick_scale()).clamp(-1.0, 1.0)
+    }
+
+    // Which way the held arrow keys point: (-1 left to 1 right, -1 up to 1 down)
+    pub fn arrow_direction(&self) -> (f32, f32) {
+        let axis = |back: KeyCode, forward: KeyCode| {
+            self.is_key_held(forward) as i32 as f32 - self.is_key_held(back) as i32 as f32
+        };
+        (
+            axis(KeyCode::Left, KeyCode::Right),
+            axis(KeyCode::Up, KeyCode::Down),
+        )
+    }
+
+    // Check if anyone is holding the show-stats key or button
+    pub fn stats_held(&self) -> bool {
+        self.is_key_held(KeyCode::Tab) || self.pads.iter().any(|pad| pad.stats_held)
+    }
+
+    // Find the slot for a controller, giving it the first free player slot
+    // the first time it is used
+    fn slot_for(&mut self, id: GamepadId) -> Option<usize> {
+        if let Some(slot) = self.pads.iter().position(|pad| pad.id == Some(id)) {
+            return Some(slot);
+        }
+        let free = self.pads.iter().position(|pad| pad.id.is_none())?;
+        self.pads[free].id = Some(id);
+        Some(free)
+    }
+}
+
+// How hard to steer a paddle whose middle is at `paddle_y` toward the mouse
+// pointer at `mouse_y`: full speed (-1 or 1) when the pointer is more than
+// MOUSE_FOLLOW_DISTANCE away, slowing down as the paddle gets close so it
+// settles on the pointer instead of shaking around it
+pub fn mouse_direction(mouse_y: f32, paddle_y: f32) -> f32 {
+    ((mouse_y - paddle_y) / MOUSE_FOLLOW_DISTANCE).clamp(-1.0, 1.0)
+}
+
+// Ignore stick movements smaller than `deadzone` near the center and rescale
+// the rest to 0..1 along the player's curve, so a stick resting slightly
+// off-center doesn't drift the paddle
+pub fn apply_deadzone(value: f32, deadzone: f32, curve: StickCurve) -> f32 {
+    let magnitude = value.abs();
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+    curve.bend(scaled) * value.signum()
+}
@@ -0,0 +1,155 @@
+// This file keeps the last couple of seconds of a match so each point can be
+// played back in slow motion once it's over, before the countdown to the next
+// serve. Every simulation step adds a frame saying where the ball and paddles
+// were; only the newest REPLAY_LENGTH seconds are kept. It only changes what
+// is shown, never the match itself
+
+use crate::game::constants::*;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+use std::collections::VecDeque;
+
+// Where everything was after one simulation step
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ReplayFrame {
+    pub ball: [f32; 2],    // Ball center
+    pub ball_speed: f32,   // How fast the ball was going, for its color
+    pub paddles: [f32; 2], // Top edges of the left and right paddles
+}
+
+impl ReplayFrame {
+    // Where everything is right now
+    pub fn new(sim: &Simulation) -> Self {
+        ReplayFrame {
+            ball: [sim.ball.x, sim.ball.y],
+            ball_speed: sim.ball.speed(),
+            paddles: [sim.player1.y, sim.player2.y],
+        }
+    }
+
+    // Part way from this frame to `next` (0 = this one, 1 = the next)
+    fn blend(&self, next: &ReplayFrame, amount: f32) -> ReplayFrame {
+        let mix = |from: f32, to: f32| from + (to - from) * amount;
+        ReplayFrame {
+            ball: [
+                mix(self.ball[0], next.ball[0]),
+                mix(self.ball[1], next.ball[1]),
+            ],
+            ball_speed: mix(self.ball_speed, next.ball_speed),
+            paddles: [
+                mix(self.paddles[0], next.paddles[0]),
+                mix(self.paddles[1], next.paddles[1]),
+            ],
+        }
+    }
+}
+
+// The newest frames of the point being played
+#[derive(Clone, Debug, Default)]
+pub struct ReplayBuffer {
+    frames: VecDeque<ReplayFrame>, // Oldest first
+}
+
+impl ReplayBuffer {
+    // Add where everything is now, dropping frames older than REPLAY_LENGTH
+    pub fn record(&mut self, sim: &Simulation) {
+        self.frames.push_back(ReplayFrame::new(sim));
+        while self.frames.len() > (REPLAY_LENGTH / FIXED_TIMESTEP) as usize {
+            self.frames.pop_front();
+        }
+    }
+
+    // Forget every frame, e.g. when a match is left
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    // Turn the frames kept so far into a replay and start afresh for the
+    // next point (None if there's too little to be worth showing)
+    pub fn take_replay(&mut self) -> Option<Replay> {
+        let frames: Vec<ReplayFrame> = self.frames.drain(..).collect();
+        match frames.len() as f32 * FIXED_TIMESTEP >= REPLAY_MIN_LENGTH {
+            true => Some(Replay { frames, clock: 0.0 }),
+            false => None,
+        }
+    }
+}
+
+// A point being played back in slow motion
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub frames: Vec<ReplayFrame>, // The point's last moments, oldest first
+    pub clock: f32,               // Seconds of the point played back so far
+}
+
+impl Replay {
+    // Seconds of play the replay covers
+    pub fn length(&self) -> f32 {
+        (self.frames.len() - 1) as f32 * FIXED_TIMESTEP
+    }
+
+    // Play on by `delta` seconds of real time, slowed down. Returns true
+    // while there's still more to show
+    pub fn advance(&mut self, delta: f32) -> bool {
+        self.clock = (self.clock + delta * REPLAY_SPEED).min(self.length());
+        !self.finished()
+    }
+
+    // Check if the whole replay has been shown
+    pub fn finished(&self) -> bool {
+        self.clock >= self.length()
+    }
+
+    // Where everything is at this point in the replay, blended between the
+    // two frames either side so slow motion stays smooth
+    pub fn frame(&self) -> ReplayFrame {
+        let position = self.clock / FIXED_TIMESTEP;
+        let index = (position as usize).min(self.frames.len() - 1);
+        match self.frames.get(index + 1) {
+            Some(next) => self.frames[index].blend(next, position - index as f32),
+            None => self.frames[index],
+        }
+    }
+
+    // Where the ball was over the steps just before this point in the
+    // replay, oldest first, for its trail
+    pub fn trail(&self) -> Vec<(f32, f32)> {
+        let index = ((self.clock / FIXED_TIMESTEP) as usize).min(self.frames.len() - 1);
+        let first = index.saturating_sub(BALL_TRAIL_LENGTH);
+        self.frames[first..index]
+            .iter()
+            .map(|frame| (frame.ball[0], frame.ball[1]))
+            .collect()
+    }
+}
+
+// Showing the replay after a point
+impl GameState {
+    // Play back the point that just ended in slow motion, unless replays are
+    // off, the match is over (the winning point goes straight to the results)
+    // or a bot or the ball machine would be kept waiting
+    pub fn start_replay(&mut self) {
+        let replay = self.replay_buffer.take_replay();
+        let replayed = matches!(
+            self.sim.mode,
+            GameMode::VsAi | GameMode::TwoPlayer | GameMode::Demo
+        );
+        if self.point_replays && replayed && self.sim.winner.is_none() {
+            self.replay = replay;
+        }
+    }
+
+    // Play the slow-motion replay on by a frame. Returns true while it's
+    // holding the match still
+    pub fn run_replay(&mut self, frame_delta: f32) -> bool {
+        let replay = match &mut self.replay {
+            Some(replay) => replay,
+            None => return false,
+        };
+        if replay.advance(frame_delta) {
+            return true;
+        }
+        self.replay = None;
+        false
+    }
+}
@@ -0,0 +1,74 @@
+// This file adds a little "juice" to the match: the picture shakes briefly
+// when a paddle hits the ball, and everything holds still for a moment when a
+// point is scored. It only changes what is shown, never the match itself
+
+use crate::game::constants::*;
+use crate::game::events::{EventListener, GameEvent};
+
+// The screen shake and hit-stop timers
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Juice {
+    pub enabled: bool, // Turned off on the settings screen
+    shake: f32,        // Seconds of screen shake left
+    shake_clock: f32,  // Seconds since the shake started, for its wobble
+    hit_stop: f32,     // Seconds left to hold the match still
+}
+
+impl Juice {
+    // Start with both effects switched on and nothing happening
+    pub fn new() -> Self {
+        Juice {
+            enabled: true,
+            ..Juice::default()
+        }
+    }
+
+    // Let a frame of real time pass. Returns true while hit-stop is holding
+    // the match still
+    pub fn update(&mut self, delta: f32) -> bool {
+        self.shake = (self.shake - delta).max(0.0);
+        self.shake_clock += delta;
+        if self.hit_stop > 0.0 {
+            self.hit_stop = (self.hit_stop - delta).max(0.0);
+            return true;
+        }
+        false
+    }
+
+    // How far to shift the court this frame. The shake wobbles on two
+    // different rhythms so it doesn't just slide back and forth, and fades out
+    pub fn camera_offset(&self) -> [f32; 2] {
+        if !self.enabled || self.shake <= 0.0 {
+            return [0.0, 0.0];
+        }
+        let strength = SCREEN_SHAKE_STRENGTH * self.shake / SCREEN_SHAKE_DURATION;
+        let time = self.shake_clock * SCREEN_SHAKE_SPEED;
+        [strength * time.sin(), strength * (time * 1.3 + 1.0).cos()]
+    }
+
+    // Stop any effect that is running, e.g. when a match is left
+    pub fn clear(&mut self) {
+        self.shake = 0.0;
+        self.hit_stop = 0.0;
+    }
+}
+
+impl EventListener for Juice {
+    // Start a shake for a paddle hit, or a hit-stop for a point
+    fn on_event(&mut self, event: &GameEvent) {
+        if !self.enabled {
+            return;
+        }
+        match event {
+            GameEvent::PointScored { .. } => {
+                self.hit_stop = HIT_STOP_DURATION;
+                self.shake = 0.0; // The freeze says it all
+            }
+            GameEvent::PaddleHit { .. } => {
+                self.shake = SCREEN_SHAKE_DURATION;
+                self.shake_clock = 0.0;
+            }
+            _ => {}
+        }
+    }
+}
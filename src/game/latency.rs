@@ -0,0 +1,129 @@
+// This file measures input latency for the hidden test screen (F9 on the
+// main menu). Every key pressed there flashes the screen, and the time from
+// the press to the frame with the flash being handed to the display is kept.
+// The screen also shows how much delay the game's own loop adds on average,
+// which helps when trying out vsync or a frame rate cap for competitive play.
+// The time the display itself takes to light up can't be seen from here, so
+// real latency is a little higher than what's shown
+
+use crate::game::constants::*;
+use crate::game::scene::Scene;
+use crate::game::state::GameState;
+use std::time::{Duration, Instant};
+
+// Key presses waiting to show up on screen and how long the last ones took
+#[derive(Clone, Default)]
+pub struct LatencyProbe {
+    pressed: Option<Instant>, // When the key was pressed, until its flash is on screen
+    shown: Option<Instant>,   // When the last flash reached the screen
+    pub samples: Vec<Duration>, // Press-to-screen times, oldest first
+}
+
+impl LatencyProbe {
+    // Start again with no results
+    pub fn reset(&mut self) {
+        *self = LatencyProbe::default();
+    }
+
+    // A key was pressed at `at`. A press while an earlier one hasn't shown up
+    // yet is part of the same flash, so it isn't timed on its own
+    pub fn press(&mut self, at: Instant) {
+        if self.pressed.is_none() {
+            self.pressed = Some(at);
+        }
+    }
+
+    // A frame was handed to the display at `at`. If it had the flash for a
+    // press, that press's time is kept (only the last LATENCY_SAMPLES)
+    pub fn presented(&mut self, at: Instant) {
+        if let Some(pressed) = self.pressed.take() {
+            self.samples.push(at.duration_since(pressed));
+            if self.samples.len() > LATENCY_SAMPLES {
+                self.samples.remove(0);
+            }
+            self.shown = Some(at);
+        }
+    }
+
+    // Whether the frame drawn at `now` should flash: from the press until
+    // LATENCY_FLASH_TIME after the flash first showed, so it can be seen
+    pub fn flashing(&self, now: Instant) -> bool {
+        self.pressed.is_some()
+            || self
+                .shown
+                .is_some_and(|shown| now.duration_since(shown).as_secs_f32() < LATENCY_FLASH_TIME)
+    }
+
+    // The time the latest press took (None before the first one)
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.last().copied()
+    }
+
+    // The average, quickest and slowest of the kept presses (None before the first one)
+    pub fn summary(&self) -> Option<(Duration, Duration, Duration)> {
+        let best = *self.samples.iter().min()?;
+        let worst = *self.samples.iter().max()?;
+        let average = self.samples.iter().sum::<Duration>() / self.samples.len() as u32;
+        Some((average, best, worst))
+    }
+}
+
+// How long the game's loop holds on to a key press on average before it can
+// be seen, with frames taking `frame_time`: half a frame waiting for the
+// press to be read, half a simulation step before the match moves for it,
+// then a whole frame being drawn and handed to the display
+pub fn pipeline_delay(frame_time: Duration) -> Duration {
+    frame_time.mul_f32(1.5) + Duration::from_secs_f32(FIXED_TIMESTEP / 2.0)
+}
+
+// A time in milliseconds for the screen, e.g. "16.7 ms"
+fn millis(time: Duration) -> String {
+    format!("{:.1} ms", time.as_secs_f64() * 1000.0)
+}
+
+// The lines of results shown on the test screen
+pub fn latency_text(probe: &LatencyProbe, frame_time: Duration, fps: f64, vsync: bool) -> String {
+    let mut lines = vec![
+        format!(
+            "Frame rate: {:.0} FPS ({} per frame), vsync {}",
+            fps,
+            millis(frame_time),
+            if vsync { "on" } else { "off" }
+        ),
+        format!(
+            "Game loop delay: about {}",
+            millis(pipeline_delay(frame_time))
+        ),
+    ];
+    match (probe.last(), probe.summary()) {
+        (Some(last), Some((average, best, worst))) => {
+            lines.push(format!("Last press: {}", millis(last)));
+            lines.push(format!(
+                "Presses timed: {} (average {}, best {}, worst {})",
+                probe.samples.len(),
+                millis(average),
+                millis(best),
+                millis(worst)
+            ));
+        }
+        _ => lines.push("Press any key to measure".to_string()),
+    }
+    lines.join("\n")
+}
+
+// Opening the latency test and timing its presses
+impl GameState {
+    // Open the input latency test with no presses timed yet
+    pub fn open_latency_test(&mut self) {
+        self.latency.reset();
+        self.scene = Scene::LatencyTest;
+    }
+
+    // On the latency test, a key or button pressed at `at` starts a flash
+    // that is timed until it reaches the screen
+    pub fn time_press(&mut self, at: Instant) {
+        if self.scene == Scene::LatencyTest {
+            self.latency.press(at);
+        }
+    }
+}
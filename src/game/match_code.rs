@@ -0,0 +1,236 @@
+// This file turns a finished match into a short code, like
+// 2A0001-001155-21FB6DF4-F9, that can be typed in on the menu to play the
+// same match again: the same settings, the same serves and the same AI
+// mistakes. Handy for bug reports and for challenging a friend to beat the
+// same match. Every code has the same layout: the layout version
+// (MATCH_CODE_VERSION), the mode letter and four digits of choices and
+// switches; then one digit each for the court, arena, ball speed, paddle size
+// and the two AI sliders; then the seed; then a check. A code from another
+// layout is turned away instead of being read the wrong way
+
+use crate::game::ai::{AiDifficulty, AiTuning};
+use crate::game::arena::Arena;
+use crate::game::checksum::fnv1a;
+use crate::game::constants::*;
+use crate::game::court::CourtPreset;
+use crate::game::match_feel::{BallPace, PaddleSize};
+use crate::game::match_options::MatchOptions;
+use crate::game::rules::MatchRules;
+use crate::game::scene::Scene;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+use std::fmt;
+
+// Everything needed to play a match again
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MatchCode {
+    pub mode: GameMode,        // Who controlled each paddle
+    pub seed: u32,             // Where the match's random numbers came from
+    pub options: MatchOptions, // AI, court, rules and physics the match was played with
+    pub opening_server: u8,    // Who served first (1 or 2)
+}
+
+impl MatchCode {
+    // The code for the match `sim` is playing (or just finished)
+    pub fn for_match(sim: &Simulation) -> Self {
+        MatchCode {
+            mode: sim.mode,
+            seed: sim.match_seed,
+            options: MatchOptions::of(sim),
+            opening_server: sim.opening_server.unwrap_or(1),
+        }
+    }
+
+    // Switch `sim` to this match's settings and seed, ready for start_match
+    pub fn apply(&self, sim: &mut Simulation) {
+        self.options.apply(sim);
+        sim.opening_server = Some(self.opening_server);
+        sim.next_seed = Some(self.seed);
+    }
+
+    // Read a code typed by the player. Case, spaces and dashes don't matter
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let wrong_shape = format!("a match code has {} letters and digits", MATCH_CODE_LENGTH);
+        if !text.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(wrong_shape);
+        }
+        // Codes from before the layout had a version start with the mode letter
+        match text.chars().next() {
+            Some(version) if version != MATCH_CODE_VERSION => {
+                return Err("that code is from another version of the game".to_string())
+            }
+            _ => {}
+        }
+        if text.len() != MATCH_CODE_LENGTH {
+            return Err(wrong_shape);
+        }
+        let (body, check) = text.split_at(text.len() - 2);
+        if hex(check)? != checksum(body) as u32 {
+            return Err("that code has a typo in it".to_string());
+        }
+
+        let mode = match &body[1..2] {
+            "A" => GameMode::VsAi,
+            "T" => GameMode::TwoPlayer,
+            "B" => GameMode::Bot,
+            "D" => GameMode::Demo,
+            "M" => GameMode::Training,
+            _ => return Err("unknown game mode".to_string()),
+        };
+        let setup = hex(&body[2..6])?;
+        let pick = |shift: u32| setup >> shift & 0b11;
+        let flag = |shift: u32| setup >> shift & 1 == 1;
+        let digit = |at: usize| hex(&body[at..at + 1]);
+        let tuning = |at: usize| digit(at).map(|value| value as i32 - AI_TUNING_STEPS);
+        let ai_tuning = AiTuning {
+            speed: tuning(10)?,
+            accuracy: tuning(11)?,
+        };
+        if !ai_tuning.in_range() {
+            return Err("settings this version doesn't have".to_string());
+        }
+        Ok(MatchCode {
+            mode,
+            seed: hex(&body[12..20])?,
+            options: MatchOptions {
+                ai_difficulty: choose(&AiDifficulty::ALL, pick(0))?,
+                ai_tuning,
+                adaptive_ai: flag(13),
+                court: choose(&CourtPreset::ALL, digit(6)?)?,
+                arena: choose(&Arena::ALL, digit(7)?)?,
+                rules: MatchRules {
+                    points_to_win: choose(&POINTS_TO_WIN_CHOICES, pick(2))?,
+                    win_by_two: flag(6),
+                    best_of: choose(&BEST_OF_CHOICES, pick(4))?,
+                    classic: flag(11),
+                },
+                lives_mode: flag(7),
+                obstacle_mode: flag(12),
+                end_wall_bounce: flag(8),
+                momentum_physics: flag(9),
+                ball_pace: choose(&BallPace::ALL, digit(8)?)?,
+                paddle_size: choose(&PaddleSize::ALL, digit(9)?)?,
+            },
+            opening_server: if flag(10) { 2 } else { 1 },
+        })
+    }
+
+    // The choices and switches packed into one number, two bits per choice
+    // and one per switch
+    fn setup_bits(&self) -> u32 {
+        let options = &self.options;
+        position(&AiDifficulty::ALL, &options.ai_difficulty)
+            | position(&POINTS_TO_WIN_CHOICES, &options.rules.points_to_win) << 2
+            | position(&BEST_OF_CHOICES, &options.rules.best_of) << 4
+            | (options.rules.win_by_two as u32) << 6
+            | (options.lives_mode as u32) << 7
+            | (options.end_wall_bounce as u32) << 8
+            | (options.momentum_physics as u32) << 9
+            | ((self.opening_server == 2) as u32) << 10
+            | (options.rules.classic as u32) << 11
+            | (options.obstacle_mode as u32) << 12
+            | (options.adaptive_ai as u32) << 13
+    }
+}
+
+impl fmt::Display for MatchCode {
+    // Version, mode letter and switches, then one digit for each of the
+    // court, arena, ball speed, paddle size and AI sliders, then the seed,
+    // then a check so typos are caught
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self.mode {
+            GameMode::VsAi => 'A',
+            GameMode::TwoPlayer => 'T',
+            GameMode::Bot => 'B',
+            GameMode::Demo => 'D',
+            GameMode::Training => 'M',
+        };
+        let options = &self.options;
+        let slider = |steps: i32| (steps + AI_TUNING_STEPS) as u32;
+        let body = format!(
+            "{}{}{:04X}{:X}{:X}{:X}{:X}{:X}{:X}{:08X}",
+            MATCH_CODE_VERSION,
+            mode,
+            self.setup_bits(),
+            position(&CourtPreset::ALL, &options.court),
+            position(&Arena::ALL, &options.arena),
+            position(&BallPace::ALL, &options.ball_pace),
+            position(&PaddleSize::ALL, &options.paddle_size),
+            slider(options.ai_tuning.speed),
+            slider(options.ai_tuning.accuracy),
+            self.seed
+        );
+        write!(
+            f,
+            "{}-{}-{}-{:02X}",
+            &body[..6],
+            &body[6..12],
+            &body[12..],
+            checksum(&body)
+        )
+    }
+}
+
+// The choice at `index` in `choices`, if this version of the game has it
+fn choose<T: Copy>(choices: &[T], index: u32) -> Result<T, String> {
+    choices
+        .get(index as usize)
+        .copied()
+        .ok_or_else(|| "settings this version doesn't have".to_string())
+}
+
+// Where `choice` is in `choices` (the first place if it isn't there)
+fn position<T: PartialEq>(choices: &[T], choice: &T) -> u32 {
+    choices.iter().position(|c| c == choice).unwrap_or(0) as u32
+}
+
+// Read hexadecimal digits
+fn hex(digits: &str) -> Result<u32, String> {
+    u32::from_str_radix(digits, 16).map_err(|_| format!("{} isn't a valid part of a code", digits))
+}
+
+// One byte worked out from the rest of the code, so a mistyped code is
+// turned away instead of quietly playing a different match
+fn checksum(body: &str) -> u8 {
+    let hash = fnv1a(body.as_bytes());
+    (hash ^ hash >> 8 ^ hash >> 16 ^ hash >> 24) as u8
+}
+
+// Typing in a match code on the menu and playing it
+impl GameState {
+    // Add a typed character to the match code, ignoring anything that can't be in one
+    pub fn type_code_char(&mut self, character: char) {
+        if (character.is_ascii_alphanumeric() || character == '-')
+            && self.code_entry.len() < MATCH_CODE_MAX_LENGTH
+        {
+            self.code_entry.push(character.to_ascii_uppercase());
+        }
+    }
+
+    // Play the match from the typed code again: same settings, serves and AI
+    // mistakes. A code that doesn't check out is explained on the screen
+    pub fn replay_match_code(&mut self) {
+        let code = match MatchCode::parse(&self.code_entry) {
+            Ok(code) => code,
+            Err(reason) => {
+                self.status = Some(format!("Can't use that code: {}", reason));
+                return;
+            }
+        };
+        code.apply(&mut self.sim);
+        self.status = None;
+        if code.mode == GameMode::Bot {
+            self.start_bot_match();
+            if self.scene != Scene::Playing {
+                self.sim.next_seed = None; // The bot server didn't start
+            }
+        } else {
+            self.start_match(code.mode);
+        }
+    }
+}
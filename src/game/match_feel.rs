@@ -0,0 +1,80 @@
+// This file holds the ball speed and paddle size choices on the settings
+// screen. They make a match easier or harder for everyone at once, unlike the
+// AI sliders, which only change the computer player
+
+use crate::game::constants::*;
+use serde::{Deserialize, Serialize};
+
+// How fast the ball is served
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum BallPace {
+    Slow, // Easier to follow while learning
+    #[default]
+    Normal, // The usual serve
+    Fast, // Rallies start out heated
+}
+
+// How tall both paddles are
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum PaddleSize {
+    Small, // Harder to reach the ball
+    #[default]
+    Normal, // The usual paddle
+    Large, // Easier to reach the ball
+}
+
+impl BallPace {
+    // Every pace, slowest first, as the settings screen slides through them
+    pub const ALL: [BallPace; 3] = [BallPace::Slow, BallPace::Normal, BallPace::Fast];
+
+    // How fast the ball leaves on a serve, across and up or down (pixels per second)
+    pub fn serve_speed(self) -> f32 {
+        match self {
+            BallPace::Slow => SLOW_BALL_SPEED,
+            BallPace::Normal => BALL_SPEED,
+            BallPace::Fast => FAST_BALL_SPEED,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            BallPace::Slow => "Slow",
+            BallPace::Normal => "Normal",
+            BallPace::Fast => "Fast",
+        }
+    }
+}
+
+impl PaddleSize {
+    // Every size, smallest first, as the settings screen slides through them
+    pub const ALL: [PaddleSize; 3] = [PaddleSize::Small, PaddleSize::Normal, PaddleSize::Large];
+
+    // How tall a paddle of this size is
+    pub fn height(self) -> f32 {
+        match self {
+            PaddleSize::Small => SMALL_PADDLE_HEIGHT,
+            PaddleSize::Normal => PADDLE_HEIGHT,
+            PaddleSize::Large => LARGE_PADDLE_HEIGHT,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            PaddleSize::Small => "Small",
+            PaddleSize::Normal => "Normal",
+            PaddleSize::Large => "Large",
+        }
+    }
+}
+
+// The choice `by` steps along from `current` in `choices` (wrapping at the ends)
+pub fn slide<T: Copy + PartialEq>(choices: &[T], current: T, by: i32) -> T {
+    let len = choices.len() as i32;
+    let index = choices
+        .iter()
+        .position(|&choice| choice == current)
+        .unwrap_or(0) as i32;
+    choices[(index + by).rem_euclid(len) as usize]
+}
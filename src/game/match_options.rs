@@ -0,0 +1,75 @@
+// This file holds the options a match is played with: the AI, the court, the
+// rules and the physics switches. The settings screen's options, a match code
+// and a saved match each carry one, and it's always copied as a whole, so an
+// option added here travels with all of them
+
+use crate::game::ai::{AiDifficulty, AiTuning};
+use crate::game::arena::Arena;
+use crate::game::constants::*;
+use crate::game::court::CourtPreset;
+use crate::game::match_feel::{BallPace, PaddleSize};
+use crate::game::rules::MatchRules;
+use crate::game::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+
+// Everything that changes how a match plays
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)] // A misspelled option is reported, not ignored
+pub struct MatchOptions {
+    pub ai_difficulty: AiDifficulty, // How hard the AI is
+    #[serde(default)]
+    pub ai_tuning: AiTuning, // AI speed and accuracy sliders (centered for older files)
+    #[serde(default)]
+    pub adaptive_ai: bool, // AI keeps the match close (off for older files)
+    pub court: CourtPreset,          // Court layout
+    #[serde(default)]
+    pub arena: Arena, // Court look and twist (the standard court for older files)
+    pub rules: MatchRules,           // Points to win, win-by-two, best-of-N
+    pub lives_mode: bool,            // Lives instead of points
+    #[serde(default)]
+    pub obstacle_mode: bool, // Blocks in the middle of the court (off for older files)
+    pub end_wall_bounce: bool,       // Practice mode
+    pub momentum_physics: bool,      // Paddles with mass
+    #[serde(default)]
+    pub ball_pace: BallPace, // Serve speed (normal for older files)
+    #[serde(default)]
+    pub paddle_size: PaddleSize, // Paddle height (normal for older files)
+}
+
+impl MatchOptions {
+    // The options `sim` is playing with
+    pub fn of(sim: &Simulation) -> Self {
+        MatchOptions {
+            ai_difficulty: sim.ai_difficulty,
+            ai_tuning: sim.ai_tuning,
+            adaptive_ai: sim.adaptive_ai,
+            court: sim.court_preset,
+            arena: sim.arena,
+            rules: sim.rules,
+            lives_mode: sim.lives_mode,
+            obstacle_mode: sim.obstacle_mode,
+            end_wall_bounce: sim.end_wall_bounce,
+            momentum_physics: sim.momentum_physics,
+            ball_pace: sim.ball_pace,
+            paddle_size: sim.paddle_size,
+        }
+    }
+
+    // Switch `sim` to these options. The obstacles and paddle size are set
+    // before the court, which puts the blocks out and sizes the paddles
+    pub fn apply(&self, sim: &mut Simulation) {
+        sim.ai_difficulty = self.ai_difficulty;
+        sim.ai_tuning = self.ai_tuning;
+        sim.adaptive_ai = self.adaptive_ai;
+        sim.obstacle_mode = self.obstacle_mode;
+        sim.paddle_size = self.paddle_size;
+        sim.apply_court(self.court);
+        sim.ball_pace = self.ball_pace;
+        sim.arena = self.arena;
+        sim.rules = self.rules;
+        sim.lives_mode = self.lives_mode;
+        sim.lives.reset(STARTING_LIVES);
+        sim.end_wall_bounce = self.end_wall_bounce;
+        sim.momentum_physics = self.momentum_physics;
+    }
+}
@@ -1,4 +1,42 @@
 // This file organizes the main parts of our game into modules
 
+pub mod ai; // Computer opponents and their difficulty levels
+pub mod arena; // Arenas that pair a court look with a gameplay twist
+pub mod audio; // Sound cues scheduled against game time
+pub mod bench; // Frame times of a render benchmark, summed up into percentiles
+pub mod bot_server; // Local socket that lets outside programs drive a paddle
+pub mod bundle; // Settings and stats packed into one file to move between computers
+pub mod changelog; // What changed in each version, for the What's New screen
+pub mod checksum; // Small hash for spotting runs that should match but don't
+pub mod coach; // One-line tips after a lost point, from how the ball got past
+pub mod cli; // Options given on the command line
+pub mod connection; // TCP connections that carry one line of JSON per message
 pub mod constants; // Game settings like speeds and sizes
+pub mod controller; // Whatever drives each paddle: a person or the computer
+pub mod court; // Playing area size and paddle placement
+pub mod data_folder; // Where files kept between runs are saved
+pub mod demo; // The computer players rallying behind the main menu
+pub mod drills; // Recorded shots the ball machine fires on loop in training
+pub mod events; // Things that happen in a match, for stats, sounds and effects to react to
+pub mod headless; // Matches played without a window, for testing and benchmarks
+pub mod input; // Keyboard and gamepad input turned into game actions
+pub mod instant_replay; // The last moments of each point, played back in slow motion
+pub mod juice; // Screen shake on hits and hit-stop on points
+pub mod latency; // Hidden screen that measures how long a key press takes to show up
+pub mod match_code; // Short codes that replay a match with the same seed and settings
+pub mod match_feel; // Ball speed and paddle size choices for every player
+pub mod match_options; // Everything that changes how a match plays, copied as one
+pub mod momentum; // Which player has been winning recent points, for the bar above the court
+pub mod preferences; // Small things remembered between runs
+pub mod profiles; // Player names with the controls each player likes
+pub mod recording; // Every paddle contact in a match, for the analysis screen
+pub mod rules; // Points to win, win-by-two and best-of-N matches
+pub mod saved_match; // A match left unfinished when the game closed, to carry on next time
+pub mod scene; // Screens the game moves between (menu, playing, paused, game over)
+pub mod settings_screen; // What each entry on the settings screen shows and does
+pub mod simulation; // The match itself, runnable without a window
+pub mod sounds; // Built-in tones that play the sound cues
+pub mod spectator; // Local socket that lets other copies of the game watch a match
 pub mod state; // Current game state (ball position, scores, etc.)
+pub mod stats; // Match statistics (serves won, rally lengths, streaks)
+pub mod tournament; // Brackets of computer opponents that get harder each round
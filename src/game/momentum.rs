@@ -0,0 +1,58 @@
+// This file works out the momentum meter above the court: a lean toward
+// whichever player has been winning recent points. Every point pushes it
+// toward the winner, further after a long rally, and the lean fades back
+// toward even as play goes on, so older points count for less and less.
+// It only drives the bar on screen; nothing in the match depends on it
+
+use crate::game::constants::*;
+use crate::game::events::{EventListener, GameEvent};
+
+// How the match has been leaning lately
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct Momentum {
+    pub value: f32, // -1 (all player 1) to 1 (all player 2), 0 when even
+    rally: u32,     // Paddle hits so far in the current point
+}
+
+impl Momentum {
+    // Start a match even
+    pub fn new() -> Self {
+        Momentum::default()
+    }
+
+    // Back to even for a new match
+    pub fn reset(&mut self) {
+        *self = Momentum::default();
+    }
+
+    // Let the lean fade over `delta` seconds of play, halving every
+    // MOMENTUM_HALF_LIFE seconds
+    pub fn update(&mut self, delta: f32) {
+        self.value *= 0.5_f32.powf(delta / MOMENTUM_HALF_LIFE);
+    }
+
+    // Push the meter toward `winner` for a point that took `rally` hits. A
+    // rally of MOMENTUM_LONG_RALLY hits or more pushes twice as far as a
+    // point won straight off the serve
+    pub fn point_won(&mut self, winner: u8, rally: u32) {
+        let length = rally.min(MOMENTUM_LONG_RALLY) as f32 / MOMENTUM_LONG_RALLY as f32;
+        let push = MOMENTUM_POINT_PUSH * (1.0 + length);
+        let toward = if winner == 1 { -1.0 } else { 1.0 };
+        self.value = (self.value + push * toward).clamp(-1.0, 1.0);
+    }
+}
+
+impl EventListener for Momentum {
+    // Count the hits in each point, and push the meter when it's won
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::Serve { .. } => self.rally = 0,
+            GameEvent::PaddleHit { .. } => self.rally += 1,
+            GameEvent::PointScored { winner } => {
+                self.point_won(winner, self.rally);
+                self.rally = 0;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// This file remembers small things about the player between runs of the game,
+// kept in a file in the user data folder
+
+use crate::game::bundle::Settings;
+use crate::game::changelog::{current_version, unseen_releases};
+use crate::game::constants::*;
+use crate::game::data_folder::data_file;
+use crate::game::profiles::ProfileBook;
+use crate::game::scene::Scene;
+use crate::game::state::GameState;
+use crate::game::tournament::TournamentRecord;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Everything remembered between runs
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)] // Missing fields start empty; misspelled ones are an error
+pub struct Preferences {
+    pub last_seen_version: String, // Version whose "What's New" screen was last shown
+    pub settings: Option<Settings>, // Options last picked on the settings screen
+    pub profiles: ProfileBook,     // Player profiles and who picked which
+    pub tournament: TournamentRecord, // Furthest round reached in each size of bracket
+}
+
+impl Preferences {
+    // Read preferences from a file. A missing file means a first run; a damaged
+    // one is an error naming the line and column
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(Preferences::default())
+            }
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        serde_json::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    // Write preferences to a file, creating its folder if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+// Where the preferences file lives: the user data folder for this game
+// (None if the system doesn't have one)
+pub fn preferences_file() -> Option<PathBuf> {
+    data_file(PREFERENCES_FILE_NAME)
+}
+
+// Loading and saving the preferences file
+impl GameState {
+    // Load preferences from a file, switch to the saved settings, and show
+    // What's New if this version hasn't been started before. It is only
+    // shown once: the version is saved right away
+    pub fn use_preferences_file(&mut self, path: PathBuf) {
+        match Preferences::load(&path) {
+            Ok(preferences) => self.preferences = preferences,
+            Err(error) => {
+                self.report_startup_error(format!(
+                    "Could not read your preferences; they won't be saved this run.\n{}",
+                    error
+                ));
+                return;
+            }
+        }
+        self.preferences_path = Some(path);
+        self.apply_profiles(); // Each player steers the way their profile says
+
+        // Start with the options picked last time, if they still make sense
+        if let Some(settings) = self.preferences.settings {
+            match settings.validate() {
+                Ok(()) => self.apply_settings(&settings),
+                Err(error) => self.report_startup_error(format!(
+                    "Your saved settings were reset to the defaults.\n{}",
+                    error
+                )),
+            }
+        }
+
+        if self.preferences.last_seen_version != current_version() {
+            self.whats_new = unseen_releases(&self.preferences.last_seen_version);
+            self.preferences.last_seen_version = current_version().to_string();
+            if let Err(error) = self.save_preferences() {
+                eprintln!("Could not save preferences: {}", error);
+            }
+            if self.scene == Scene::Menu && !self.whats_new.is_empty() {
+                self.scene = Scene::WhatsNew;
+            }
+        }
+    }
+
+    // Write preferences to their file, if there is one
+    pub fn save_preferences(&self) -> io::Result<()> {
+        match &self.preferences_path {
+            Some(path) => self.preferences.save(path),
+            None => Ok(()),
+        }
+    }
+
+    // Save the current options to the preferences file, so the next run
+    // starts with them
+    pub fn remember_settings(&mut self) {
+        self.preferences.settings = Some(self.settings());
+        if let Err(error) = self.save_preferences() {
+            eprintln!("Could not save preferences: {}", error);
+        }
+    }
+}
@@ -0,0 +1,399 @@
+// This file holds player profiles: a name with the controls that player
+// likes (which keys steer, whether a controller does too, and how touchy the
+// stick is, how far it must move before it counts, and how the push turns
+// into paddle speed). Each of the two local players can pick a profile on the
+// settings screen, and its controls are used for their paddle from then on.
+// Players without a profile keep guest controls, which are remembered too.
+// A profile also keeps its run of matches won in a row
+
+use crate::game::constants::*;
+use crate::game::scene::Scene;
+use crate::game::simulation::GameMode;
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+
+// Which keys steer a paddle up and down
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SteerKeys {
+    Arrows, // Up and Down arrow keys
+    Wasd,   // W and S
+}
+
+impl SteerKeys {
+    // Switch to the other pair of keys
+    pub fn next(self) -> Self {
+        match self {
+            SteerKeys::Arrows => SteerKeys::Wasd,
+            SteerKeys::Wasd => SteerKeys::Arrows,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            SteerKeys::Arrows => "Arrow Keys",
+            SteerKeys::Wasd => "W/S",
+        }
+    }
+}
+
+// How a stick push (past the dead zone) turns into paddle speed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum StickCurve {
+    #[default]
+    Linear, // Speed grows evenly with the push
+    Gentle, // Small pushes move slowly, for careful aiming
+    Quick,  // Small pushes already move fast
+}
+
+impl StickCurve {
+    // Switch to the next curve, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            StickCurve::Linear => StickCurve::Gentle,
+            StickCurve::Gentle => StickCurve::Quick,
+            StickCurve::Quick => StickCurve::Linear,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            StickCurve::Linear => "Linear",
+            StickCurve::Gentle => "Gentle",
+            StickCurve::Quick => "Quick",
+        }
+    }
+
+    // Bend a push of 0 to 1 along the curve (0 and 1 stay where they are)
+    pub fn bend(self, push: f32) -> f32 {
+        match self {
+            StickCurve::Linear => push,
+            StickCurve::Gentle => push * push,
+            StickCurve::Quick => push.sqrt(),
+        }
+    }
+}
+
+// How one player steers their paddle
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ControlScheme {
+    pub keys: SteerKeys,  // Keys that steer
+    pub gamepad: bool,    // The player's controller steers too
+    pub sensitivity: f32, // How far the paddle moves for a stick push (1 = as pushed)
+    #[serde(default = "usual_deadzone")] // Profiles saved before this was kept get the usual one
+    pub deadzone: f32, // Stick movement smaller than this is ignored
+    #[serde(default)] // Profiles saved before this was kept steer evenly
+    pub curve: StickCurve, // How a stick push turns into paddle speed
+}
+
+// Dead zone for controls saved before players could set their own
+fn usual_deadzone() -> f32 {
+    GAMEPAD_DEADZONE
+}
+
+impl ControlScheme {
+    // The usual controls for player 1 (arrow keys) or player 2 (W/S), each
+    // with their own controller
+    pub fn for_player(player: u8) -> Self {
+        ControlScheme {
+            keys: if player == 1 {
+                SteerKeys::Arrows
+            } else {
+                SteerKeys::Wasd
+            },
+            gamepad: true,
+            sensitivity: 1.0,
+            deadzone: GAMEPAD_DEADZONE,
+            curve: StickCurve::Linear,
+        }
+    }
+
+    // Move the stick sensitivity `by` steps (wrapping at the ends)
+    pub fn nudge_sensitivity(&mut self, by: i32) {
+        let steps = ((STICK_SENSITIVITY_MAX - STICK_SENSITIVITY_MIN) / STICK_SENSITIVITY_STEP)
+            .round() as i32
+            + 1;
+        let current =
+            ((self.stick_scale() - STICK_SENSITIVITY_MIN) / STICK_SENSITIVITY_STEP).round() as i32;
+        let step = (current + by).rem_euclid(steps);
+        self.sensitivity = STICK_SENSITIVITY_MIN + step as f32 * STICK_SENSITIVITY_STEP;
+    }
+
+    // The sensitivity to steer with, kept to what the settings screen offers
+    // in case the preferences file was edited by hand
+    pub fn stick_scale(&self) -> f32 {
+        self.sensitivity
+            .clamp(STICK_SENSITIVITY_MIN, STICK_SENSITIVITY_MAX)
+    }
+
+    // Move the dead zone `by` steps (wrapping at the ends)
+    pub fn nudge_deadzone(&mut self, by: i32) {
+        let steps = (GAMEPAD_DEADZONE_MAX / GAMEPAD_DEADZONE_STEP).round() as i32 + 1;
+        let current = (self.stick_deadzone() / GAMEPAD_DEADZONE_STEP).round() as i32;
+        let step = (current + by).rem_euclid(steps);
+        self.deadzone = step as f32 * GAMEPAD_DEADZONE_STEP;
+    }
+
+    // The dead zone to steer with, kept to what the settings screen offers
+    // in case the preferences file was edited by hand
+    pub fn stick_deadzone(&self) -> f32 {
+        self.deadzone.clamp(0.0, GAMEPAD_DEADZONE_MAX)
+    }
+}
+
+// A player's name and the controls they use
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub name: String,            // Shown on the settings screen
+    pub controls: ControlScheme, // Applied when the profile is picked
+    #[serde(default)] // Profiles saved before streaks were kept start on none
+    pub win_streak: u32, // Matches won in a row, across runs of the game
+}
+
+// Every saved profile and which one each player has picked
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)] // Missing parts start empty
+pub struct ProfileBook {
+    pub profiles: Vec<PlayerProfile>, // Saved profiles, oldest first
+    pub chosen: [Option<String>; 2],  // Profile name picked by player 1 and 2 (None = guest)
+    pub guests: [ControlScheme; 2],   // Controls for a player without a profile
+}
+
+impl Default for ProfileBook {
+    // No profiles, and both players using the usual controls
+    fn default() -> Self {
+        ProfileBook {
+            profiles: Vec::new(),
+            chosen: [None, None],
+            guests: [ControlScheme::for_player(1), ControlScheme::for_player(2)],
+        }
+    }
+}
+
+impl ProfileBook {
+    // Save a new profile with the usual controls. Names must be different,
+    // and only MAX_PROFILES are kept
+    pub fn add(&mut self, name: &str) -> Result<(), String> {
+        if self.profiles.iter().any(|profile| profile.name == name) {
+            return Err(format!("there is already a profile called \"{}\"", name));
+        }
+        if self.profiles.len() >= MAX_PROFILES {
+            return Err(format!("only {} profiles can be kept", MAX_PROFILES));
+        }
+        self.profiles.push(PlayerProfile {
+            name: name.to_string(),
+            controls: ControlScheme::for_player(1),
+            win_streak: 0,
+        });
+        Ok(())
+    }
+
+    // Where in the saved profiles player 1's or 2's pick is (None = guest,
+    // or a profile that is no longer saved)
+    fn chosen_index(&self, player: u8) -> Option<usize> {
+        let name = self.chosen[player as usize - 1].as_ref()?;
+        self.profiles
+            .iter()
+            .position(|profile| &profile.name == name)
+    }
+
+    // Check if both players have picked the same profile
+    pub fn shared(&self) -> bool {
+        self.chosen_index(1).is_some() && self.chosen_index(1) == self.chosen_index(2)
+    }
+
+    // Name of the profile player 1 or 2 is using ("Guest" without one)
+    pub fn chosen_name(&self, player: u8) -> &str {
+        match self.chosen_index(player) {
+            Some(index) => &self.profiles[index].name,
+            None => "Guest",
+        }
+    }
+
+    // Pick the profile `by` steps along for player 1 or 2, with Guest
+    // before the first profile (wrapping at the ends)
+    pub fn slide_choice(&mut self, player: u8, by: i32) {
+        let choices = self.profiles.len() as i32 + 1;
+        let current = self
+            .chosen_index(player)
+            .map_or(0, |index| index as i32 + 1);
+        let next = (current + by).rem_euclid(choices) as usize;
+        self.chosen[player as usize - 1] = next
+            .checked_sub(1)
+            .map(|index| self.profiles[index].name.clone());
+    }
+
+    // The controls player 1 or 2 steers with: their profile's, or their
+    // guest controls
+    pub fn controls(&self, player: u8) -> ControlScheme {
+        match self.chosen_index(player) {
+            Some(index) => self.profiles[index].controls,
+            None => self.guests[player as usize - 1],
+        }
+    }
+
+    // After player 1 or 2 picks a profile or changes keys, give the other
+    // player the other pair of keys if they both ended up on the same ones,
+    // so one keyboard never drives both paddles
+    pub fn keep_keys_apart(&mut self, player: u8) {
+        let other = 3 - player;
+        if self.controls(player).keys == self.controls(other).keys && !self.shared() {
+            let controls = self.controls_mut(other);
+            controls.keys = controls.keys.next();
+        }
+    }
+
+    // The controls both players steer with. If they are still on the same
+    // keys (both picked one profile, or the file was edited by hand), player
+    // 2 steers with the other pair
+    pub fn steering(&self) -> [ControlScheme; 2] {
+        let mut schemes = [self.controls(1), self.controls(2)];
+        if schemes[0].keys == schemes[1].keys {
+            schemes[1].keys = schemes[1].keys.next();
+        }
+        schemes
+    }
+
+    // The win streak of player 1's or 2's profile (guests don't keep one)
+    pub fn win_streak(&self, player: u8) -> u32 {
+        self.chosen_index(player)
+            .map_or(0, |index| self.profiles[index].win_streak)
+    }
+
+    // Carry on player 1's or 2's streak after a match they won, or break it
+    // after one they lost or resigned. Returns whether a profile changed, so
+    // it only needs saving then
+    pub fn record_result(&mut self, player: u8, won: bool) -> bool {
+        let profile = match self.chosen_index(player) {
+            Some(index) => &mut self.profiles[index],
+            None => return false,
+        };
+        let streak = if won { profile.win_streak + 1 } else { 0 };
+        let changed = streak != profile.win_streak;
+        profile.win_streak = streak;
+        changed
+    }
+
+    // The streak milestone player 1's or 2's profile is on, if it's one of
+    // the WIN_STREAK_MILESTONES
+    pub fn streak_milestone(&self, player: u8) -> Option<u32> {
+        let streak = self.win_streak(player);
+        WIN_STREAK_MILESTONES.contains(&streak).then_some(streak)
+    }
+
+    // A banner line for each of `players` whose profile has just reached a
+    // streak milestone (None if nobody has)
+    pub fn streak_banner(&self, players: &[u8]) -> Option<String> {
+        let lines: Vec<String> = players
+            .iter()
+            .filter_map(|&player| {
+                self.streak_milestone(player).map(|streak| {
+                    format!(
+                        "{} is on a {}-win streak!",
+                        self.chosen_name(player),
+                        streak
+                    )
+                })
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    // The controls player 1 or 2 steers with, to change them
+    pub fn controls_mut(&mut self, player: u8) -> &mut ControlScheme {
+        match self.chosen_index(player) {
+            Some(index) => &mut self.profiles[index].controls,
+            None => &mut self.guests[player as usize - 1],
+        }
+    }
+}
+
+// Naming profiles, steering with them, and keeping their win streaks
+impl GameState {
+    // Put up a banner for anyone whose profile is on a milestone win streak
+    pub fn announce_streaks(&mut self) {
+        self.streak_banner = self
+            .preferences
+            .profiles
+            .streak_banner(self.streak_players())
+            .map(|banner| (banner, 0.0));
+    }
+
+    // Players whose profile keeps a win streak in this match: whoever is
+    // playing against the computer, or both players in a two-player match.
+    // When both sides use the same profile it wins and loses the match at
+    // once, so its streak is left alone
+    fn streak_players(&self) -> &'static [u8] {
+        match self.sim.mode {
+            GameMode::VsAi => &[1],
+            GameMode::TwoPlayer if self.preferences.profiles.shared() => &[],
+            GameMode::TwoPlayer => &[1, 2],
+            GameMode::Demo | GameMode::Bot | GameMode::Training => &[],
+        }
+    }
+
+    // Take the win streak banner down once it has been up long enough
+    pub fn expire_streak_banner(&mut self, frame_delta: f32) {
+        if let Some((banner, shown)) = self.streak_banner.take() {
+            let shown = shown + frame_delta;
+            self.streak_banner = (shown < STREAK_BANNER_TIME).then_some((banner, shown));
+        }
+    }
+
+    // Carry on the winner's streak and break the loser's (a resignation
+    // counts as a loss). A match the auto-pilot helped with leaves them alone
+    pub fn record_win_streaks(&mut self, winner: u8) {
+        if self.sim.autopilot_used {
+            return;
+        }
+        let mut changed = false;
+        for &player in self.streak_players() {
+            changed |= self
+                .preferences
+                .profiles
+                .record_result(player, player == winner);
+        }
+        if changed {
+            if let Err(error) = self.save_preferences() {
+                eprintln!("Could not save preferences: {}", error);
+            }
+        }
+    }
+
+    // Ask for the name of a new player profile, from the settings screen
+    pub fn open_profile_naming(&mut self) {
+        self.profile_name.clear();
+        self.status = None;
+        self.scene = Scene::NameProfile;
+    }
+
+    // Add a typed character to the new profile's name
+    pub fn type_profile_name_char(&mut self, character: char) {
+        if !character.is_control() && self.profile_name.chars().count() < PROFILE_NAME_MAX_LENGTH {
+            self.profile_name.push(character);
+        }
+    }
+
+    // Save a profile under the typed name, with the usual controls, and go
+    // back to the settings screen with the result there
+    pub fn save_profile(&mut self) {
+        let name = self.profile_name.trim().to_string();
+        if name.is_empty() {
+            self.status = Some("Type a name for the profile first".to_string());
+            return;
+        }
+        self.status = Some(match self.preferences.profiles.add(&name) {
+            Ok(()) => format!("Saved profile \"{}\"; pick it for a player above", name),
+            Err(reason) => format!("Profile not kept: {}", reason),
+        });
+        self.remember_settings();
+        self.scene = Scene::Settings;
+    }
+
+    // Steer with the controls from the players' profiles (or guest controls),
+    // never both on the same keys
+    pub fn apply_profiles(&mut self) {
+        self.input.schemes = self.preferences.profiles.steering();
+    }
+}
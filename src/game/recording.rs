@@ -0,0 +1,101 @@
+// This file records every paddle contact in a match, so players can look back
+// at where the ball hit their paddle and how it came off, and every ball that
+// got past a paddle, with how far from the paddle it went by
+
+use crate::game::constants::*;
+use crate::game::events::{EventListener, GameEvent};
+
+// One time a paddle touched the ball
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Contact {
+    pub player: u8,     // Whose paddle it was (1 or 2)
+    pub offset: f32,    // Where on the paddle: -1 (top edge), 0 (center), 1 (bottom edge)
+    pub angle_in: f32,  // Direction the ball arrived from (degrees, positive = heading down)
+    pub angle_out: f32, // Direction the ball left in (degrees, positive = heading down)
+    pub speed: f32,     // How fast the ball arrived (pixels per second)
+}
+
+impl Contact {
+    // Check if the ball caught the outer part of the paddle instead of the middle
+    pub fn is_mishit(&self) -> bool {
+        self.offset.abs() > MISHIT_OFFSET
+    }
+}
+
+// One time the ball got past a paddle
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Miss {
+    pub player: u8,  // Whose paddle it got past (1 or 2)
+    pub offset: f32, // Half paddle heights from the paddle's middle it passed (+ = below)
+    pub angle: f32,  // Direction it was travelling (degrees, positive = heading down)
+    pub speed: f32,  // How fast it was going (pixels per second)
+}
+
+// Every paddle contact and miss in the current match, in the order they happened
+#[derive(Default)]
+pub struct MatchRecording {
+    pub contacts: Vec<Contact>,
+    pub misses: Vec<Miss>,
+}
+
+impl MatchRecording {
+    // Create an empty recording for a fresh match
+    pub fn new() -> Self {
+        MatchRecording::default()
+    }
+
+    // Add one paddle contact
+    pub fn record(&mut self, contact: Contact) {
+        self.contacts.push(contact);
+    }
+
+    // All contacts made by player 1 or 2
+    pub fn for_player(&self, player: u8) -> impl Iterator<Item = &Contact> {
+        self.contacts
+            .iter()
+            .filter(move |contact| contact.player == player)
+    }
+
+    // How many of a player's contacts were mishits
+    pub fn mishits(&self, player: u8) -> usize {
+        self.for_player(player)
+            .filter(|contact| contact.is_mishit())
+            .count()
+    }
+
+    // Average speed the ball arrived at a player's paddle (0 if they never touched it)
+    pub fn average_speed(&self, player: u8) -> f32 {
+        let speeds: Vec<f32> = self
+            .for_player(player)
+            .map(|contact| contact.speed)
+            .collect();
+        if speeds.is_empty() {
+            0.0
+        } else {
+            speeds.iter().sum::<f32>() / speeds.len() as f32
+        }
+    }
+
+    // Forget everything for a new match
+    pub fn reset(&mut self) {
+        self.contacts.clear();
+        self.misses.clear();
+    }
+}
+
+impl EventListener for MatchRecording {
+    // Keep every paddle contact and miss the match announces
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::PaddleHit { contact, .. } => self.record(*contact),
+            GameEvent::BallMissed { miss } => self.misses.push(*miss),
+            _ => {}
+        }
+    }
+}
+
+// The direction a ball is travelling in degrees, measured from flat so both
+// sides read the same way: 0 is straight across, positive is heading down
+pub fn travel_angle(dx: f32, dy: f32) -> f32 {
+    dy.atan2(dx.abs()).to_degrees()
+}
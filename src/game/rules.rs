@@ -0,0 +1,162 @@
+// This file holds the rules that decide who serves, and when a game and a match are won
+
+use crate::game::constants::*;
+use serde::{Deserialize, Serialize};
+
+// How a match came to an end
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum MatchEnd {
+    ScoreReached, // The winner took enough games under the match rules
+    LivesLost,    // The loser ran out of lives in lives mode
+    Forfeit,      // The loser resigned from the pause screen
+}
+
+impl MatchEnd {
+    // Short explanation shown on the game over screen. `loser` is who lost as
+    // the player reading it sees them, e.g. "You", "Opponent" or "Player 2"
+    pub fn describe(self, loser: &str) -> String {
+        match self {
+            MatchEnd::ScoreReached => "Winning score reached".to_string(),
+            MatchEnd::LivesLost => format!("{} ran out of lives", loser),
+            MatchEnd::Forfeit => format!("{} resigned", loser),
+        }
+    }
+}
+
+// How many points win a game and how many games make up a match
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatchRules {
+    pub points_to_win: u32, // Points needed to take a game
+    pub win_by_two: bool,   // A game must be won by a two-point lead
+    pub best_of: u32,       // Number of games in the match (1 = a single game)
+    #[serde(default)]
+    pub classic: bool, // Table tennis rules: to 11 by two, serve changes every 2 points
+}
+
+impl Default for MatchRules {
+    // Classic rules: a single game to 3 points
+    fn default() -> Self {
+        MatchRules {
+            points_to_win: POINTS_TO_WIN_CHOICES[0],
+            win_by_two: false,
+            best_of: BEST_OF_CHOICES[0],
+            classic: false,
+        }
+    }
+}
+
+impl MatchRules {
+    // Points needed to take a game (classic rules always play to 11)
+    pub fn points_needed(&self) -> u32 {
+        if self.classic {
+            CLASSIC_POINTS_TO_WIN
+        } else {
+            self.points_to_win
+        }
+    }
+
+    // Check if a player with `score` points has won the game against `opponent_score`
+    pub fn game_won(&self, score: u32, opponent_score: u32) -> bool {
+        if score < self.points_needed() {
+            return false;
+        }
+        !(self.win_by_two || self.classic) || score >= opponent_score + 2
+    }
+
+    // Who serves the next point (1 or 2), given who served first in the match,
+    // the games finished so far, the points in this game and who won the last point.
+    // Normally the player who lost the last point serves. Under classic rules the
+    // serve changes every 2 points, and every point once it's deuce (10-10); the
+    // first serve of each game goes to the players in turn
+    pub fn server(
+        &self,
+        opening_server: u8,
+        games_played: u32,
+        points: (u32, u32),
+        last_winner: Option<u8>,
+    ) -> u8 {
+        let other = |player: u8| if player == 1 { 2 } else { 1 };
+        if !self.classic {
+            return match last_winner {
+                Some(winner) => other(winner),
+                None => opening_server,
+            };
+        }
+
+        let game_server = if games_played.is_multiple_of(2) {
+            opening_server
+        } else {
+            other(opening_server)
+        };
+        let played = points.0 + points.1;
+        let deuce_at = 2 * (CLASSIC_POINTS_TO_WIN - 1); // Points played when it's 10-10
+        let turns = if played < deuce_at {
+            played / CLASSIC_SERVES_IN_A_ROW
+        } else {
+            deuce_at / CLASSIC_SERVES_IN_A_ROW + (played - deuce_at)
+        };
+        if turns.is_multiple_of(2) {
+            game_server
+        } else {
+            other(game_server)
+        }
+    }
+
+    // Games needed to take the match (more than half of best-of)
+    pub fn games_to_win(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    // Move on to the next points-to-win choice (wraps back to the first).
+    // Picking a score leaves classic rules, which always play to 11
+    pub fn next_points_to_win(&mut self) {
+        if self.classic {
+            self.classic = false;
+            return;
+        }
+        self.points_to_win = next_choice(&POINTS_TO_WIN_CHOICES, self.points_to_win);
+    }
+
+    // Switch win-by-two on or off (leaving classic rules, which always need it)
+    pub fn toggle_win_by_two(&mut self) {
+        if self.classic {
+            self.classic = false;
+            self.win_by_two = false;
+            return;
+        }
+        self.win_by_two = !self.win_by_two;
+    }
+
+    // Move on to the next best-of choice (wraps back to a single game)
+    pub fn next_best_of(&mut self) {
+        self.best_of = next_choice(&BEST_OF_CHOICES, self.best_of);
+    }
+
+    // Describe the rules in a short sentence for the menu
+    pub fn describe(&self) -> String {
+        let mut text = if self.classic {
+            format!(
+                "Classic: first to {} by two, serve changes every {} points",
+                CLASSIC_POINTS_TO_WIN, CLASSIC_SERVES_IN_A_ROW
+            )
+        } else {
+            format!("First to score {}", self.points_to_win)
+        };
+        if self.win_by_two && !self.classic {
+            text.push_str(", win by two");
+        }
+        if self.best_of > 1 {
+            text.push_str(&format!(", best of {} games", self.best_of));
+        } else if !self.classic {
+            text.push_str(" wins");
+        }
+        text
+    }
+}
+
+// Pick the entry after `current` in a list of choices
+fn next_choice(choices: &[u32], current: u32) -> u32 {
+    let index = choices.iter().position(|&c| c == current).unwrap_or(0);
+    choices[(index + 1) % choices.len()]
+}
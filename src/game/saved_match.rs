@@ -0,0 +1,216 @@
+// This file keeps a match that was still going when the game closed, so it
+// can be carried on from the main menu next time. Only what the match needs
+// to pick up where it stopped is kept: the options it was played with, the
+// score, where the ball and paddles were, how far along its random numbers
+// were and how long it had been going. Its statistics start afresh when it's
+// continued. The options are only for that match: the player's own settings
+// come back once it's over
+
+use crate::game::constants::*;
+use crate::game::data_folder::data_file;
+use crate::game::match_options::MatchOptions;
+use crate::game::scene::Scene;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// A match left part way through
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SavedMatch {
+    pub version: u32,   // SAVED_MATCH_VERSION when it was saved
+    pub mode: GameMode, // Against the computer or two players
+    #[serde(flatten)] // Written alongside the rest, as before they were kept together
+    pub options: MatchOptions, // AI, court, rules and physics the match is played with
+    pub score: [u32; 2], // Points in the current game: [left, right]
+    pub games: [u32; 2], // Games won: [left, right]
+    pub lives: [u32; 2], // Lives left: [left, right]
+    pub ball: [f32; 5], // Ball center, speed and spin: x, y, dx, dy, spin
+    pub paddles: [f32; 2], // Top edges of the left and right paddles
+    pub countdown: Option<f32>, // Seconds into the countdown before a serve, if running
+    pub last_winner: Option<u8>, // Who won the last point
+    pub opening_server: Option<u8>, // Who served first
+    pub match_seed: u32, // Seed the match's random numbers started from
+    pub ticks: u64,     // Simulation steps played, for the match clock
+    #[serde(default)] // Older saves carry on with fresh random numbers
+    pub rng: [Option<u64>; 3], // Random numbers used by the match and each paddle's AI
+}
+
+impl SavedMatch {
+    // Describe the match as it stands
+    pub fn new(sim: &Simulation) -> Self {
+        SavedMatch {
+            version: SAVED_MATCH_VERSION,
+            mode: sim.mode,
+            options: MatchOptions::of(sim),
+            score: [sim.score.player1, sim.score.player2],
+            games: [sim.score.games_player1, sim.score.games_player2],
+            lives: [sim.lives.player1, sim.lives.player2],
+            ball: [
+                sim.ball.x,
+                sim.ball.y,
+                sim.ball.dx,
+                sim.ball.dy,
+                sim.ball.spin,
+            ],
+            paddles: [sim.player1.y, sim.player2.y],
+            countdown: sim.countdown,
+            last_winner: sim.last_winner,
+            opening_server: sim.opening_server,
+            match_seed: sim.match_seed,
+            ticks: sim.ticks,
+            rng: sim.rng_positions(),
+        }
+    }
+
+    // Check if a match can be saved and carried on later: only ordinary
+    // matches against the computer or a friend that nobody has won yet
+    pub fn can_save(sim: &Simulation) -> bool {
+        matches!(sim.mode, GameMode::VsAi | GameMode::TwoPlayer) && sim.winner.is_none()
+    }
+
+    // Switch a match to the options this one was played with, ready for it
+    // to be started with its own seed
+    pub fn apply_options(&self, sim: &mut Simulation) {
+        self.options.apply(sim);
+        sim.next_seed = Some(self.match_seed);
+    }
+
+    // Put the score, ball and paddles of a just-started match back where
+    // this one had got to
+    pub fn apply_progress(&self, sim: &mut Simulation) {
+        sim.score.player1 = self.score[0];
+        sim.score.player2 = self.score[1];
+        sim.score.games_player1 = self.games[0];
+        sim.score.games_player2 = self.games[1];
+        sim.lives.player1 = self.lives[0];
+        sim.lives.player2 = self.lives[1];
+        let [x, y, dx, dy, spin] = self.ball;
+        sim.ball.x = x;
+        sim.ball.y = y;
+        sim.ball.dx = dx;
+        sim.ball.dy = dy;
+        sim.ball.spin = spin;
+        sim.player1.y = self.paddles[0];
+        sim.player2.y = self.paddles[1];
+        sim.prev_ball = (x, y);
+        sim.prev_paddle_y = (self.paddles[0], self.paddles[1]);
+        sim.countdown = self.countdown;
+        sim.last_winner = self.last_winner;
+        sim.opening_server = self.opening_server;
+        sim.ticks = self.ticks;
+        sim.set_rng_positions(self.rng);
+    }
+
+    // Read a saved match from a file. No file means there's nothing to carry
+    // on; a damaged one, or one from another version of the game, is an error
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        let saved: SavedMatch = serde_json::from_str(&text)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        if saved.version != SAVED_MATCH_VERSION {
+            return Err(format!(
+                "{}: saved by another version of the game (format {}, this one reads {})",
+                path.display(),
+                saved.version,
+                SAVED_MATCH_VERSION
+            ));
+        }
+        Ok(Some(saved))
+    }
+
+    // Write the match to a file, creating its folder if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+// Remove a saved match's file once it's been carried on (no file is fine)
+pub fn forget_saved_match(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+// Where the saved match lives: the user data folder for this game
+// (None if the system doesn't have one)
+pub fn saved_match_file() -> Option<PathBuf> {
+    data_file(SAVED_MATCH_FILE_NAME)
+}
+
+// The game's side of a saved match: finding it at startup, saving the one
+// being played as the game closes, and carrying it on from the menu
+impl GameState {
+    // Pick up the match left unfinished last time, if there is one, and save
+    // the match being played to the same file if the game closes part way
+    // through. A damaged file is shown at startup; the next unfinished match
+    // is saved over it
+    pub fn use_saved_match_file(&mut self, path: PathBuf) {
+        match SavedMatch::load(&path) {
+            Ok(saved) => self.saved_match = saved,
+            Err(error) => self.report_startup_error(format!(
+                "Your unfinished match can't be continued.\n{}",
+                error
+            )),
+        }
+        self.saved_match_path = Some(path);
+    }
+
+    // The match being played, if it could be carried on later were the game
+    // closed right now (tournament matches are played in one go)
+    pub fn unfinished_match(&self) -> Option<SavedMatch> {
+        let in_match = matches!(self.scene, Scene::Playing | Scene::Paused | Scene::Photo)
+            || (self.scene == Scene::Settings && self.settings_in_match);
+        let can_save = in_match && self.tournament.is_none() && SavedMatch::can_save(&self.sim);
+        can_save.then(|| SavedMatch::new(&self.sim))
+    }
+
+    // Write the match being played to the saved match file, if it's
+    // unfinished and there is a file (an older saved match is left alone otherwise)
+    pub fn save_unfinished_match(&self) -> io::Result<()> {
+        match (&self.saved_match_path, self.unfinished_match()) {
+            (Some(path), Some(saved)) => saved.save(path),
+            _ => Ok(()),
+        }
+    }
+
+    // Carry on the match left unfinished last time, paused so the players
+    // can get ready. Its file is removed so it's only carried on once
+    pub fn continue_match(&mut self) {
+        let saved = match self.saved_match.take() {
+            Some(saved) => saved,
+            None => return,
+        };
+        if let Some(path) = &self.saved_match_path {
+            if let Err(error) = forget_saved_match(path) {
+                eprintln!("Could not remove the saved match: {}", error);
+            }
+        }
+        // The match is played with its own options, and the player's come
+        // back once it's over
+        let own = self.settings().options;
+        saved.apply_options(&mut self.sim);
+        self.start_match(saved.mode);
+        self.own_options = Some(own);
+        saved.apply_progress(&mut self.sim);
+        self.pause();
+    }
+
+    // Go back to the player's own options once a continued match is over
+    pub fn restore_own_settings(&mut self) {
+        if let Some(own) = self.own_options.take() {
+            own.apply(&mut self.sim);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// This file holds the screens the game moves between (menu, playing, paused, game over)
+// and the entries shown on the menu screens
+
+use crate::game::audio::SoundCategory;
+
+// Which screen the game is currently showing
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scene {
+    Menu,         // Main menu: pick a mode or open settings
+    Settings,     // Change game options before playing
+    Playing,      // A match is in progress
+    Paused,       // A match is frozen until resumed
+    GameOver,     // Someone has won the match
+    Analysis,     // Where each paddle hit the ball in the finished match
+    StartupError, // Files that couldn't be loaded when the game started
+    WhatsNew,     // Changes in the new version, shown once after an update
+    EnterCode,    // Typing in a match code to play that match again
+    Spectating,   // Watching a match running in another copy of the game
+    Photo,        // A paused match framed with a free camera to save a picture of it
+    Drills,       // Picking random balls or a saved drill before training
+    NameDrill,    // Typing a name for the drill just recorded in training
+    LatencyTest,  // Hidden screen that times how long a key press takes to show up
+    NameProfile,  // Typing a name for a new player profile
+    Bracket,      // Tournament progress, shown before and between its matches
+}
+
+// Entries on the main menu
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuItem {
+    Continue, // Carry on the match left unfinished last time (only listed when there is one)
+    SinglePlayer,
+    TwoPlayer,
+    Tournament,
+    BotMatch,
+    Training,
+    EnterCode,
+    Settings,
+    WhatsNew,
+    Exit,
+}
+
+// Entries on the settings screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsItem {
+    Difficulty,
+    AiSpeed,
+    AiAccuracy,
+    AdaptiveAi,
+    Court,
+    Arena,
+    BallSpeed,
+    PaddleSize,
+    PointsToWin,
+    WinByTwo,
+    BestOf,
+    ClassicRules,
+    LivesMode,
+    Obstacles,
+    Practice,
+    Momentum,
+    PaddleInput,
+    PlayerProfile(u8),    // Profile picked by player 1 or 2
+    PlayerKeys(u8),       // Keys that steer player 1's or 2's paddle
+    PlayerController(u8), // Whether player 1's or 2's controller steers too
+    StickSensitivity(u8), // How touchy player 1's or 2's stick is
+    StickDeadzone(u8),    // How far player 1's or 2's stick moves before it counts
+    StickCurve(u8),       // How player 1's or 2's stick push turns into speed
+    NewProfile,
+    Theme,
+    Volume,
+    SoundVolume(SoundCategory), // How loud one kind of sound plays
+    LowPower,
+    ScreenEffects,
+    CoachTips,
+    HolidayEvents,
+    HudLayout,
+    PointReplays,
+    WindowSize,
+    AspectLock,
+    SafeArea,
+    ExportSetup,
+    ImportSetup,
+    Back,
+}
+
+impl MenuItem {
+    // The main menu entries that are always listed, top to bottom
+    pub const ALL: [MenuItem; 9] = [
+        MenuItem::SinglePlayer,
+        MenuItem::TwoPlayer,
+        MenuItem::Tournament,
+        MenuItem::BotMatch,
+        MenuItem::Training,
+        MenuItem::EnterCode,
+        MenuItem::Settings,
+        MenuItem::WhatsNew,
+        MenuItem::Exit,
+    ];
+
+    // Text shown for this entry
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuItem::Continue => "Continue Match",
+            MenuItem::SinglePlayer => "Single Player",
+            MenuItem::TwoPlayer => "Local 2 Players",
+            MenuItem::Tournament => "Tournament",
+            MenuItem::BotMatch => "Play a Bot",
+            MenuItem::Training => "Training (Ball Machine)",
+            MenuItem::EnterCode => "Replay a Match Code",
+            MenuItem::Settings => "Settings",
+            MenuItem::WhatsNew => "What's New",
+            MenuItem::Exit => "Exit",
+        }
+    }
+}
+
+impl SettingsItem {
+    // Every settings entry, top to bottom
+    pub const ALL: [SettingsItem; 48] = [
+        SettingsItem::Difficulty,
+        SettingsItem::AiSpeed,
+        SettingsItem::AiAccuracy,
+        SettingsItem::AdaptiveAi,
+        SettingsItem::Court,
+        SettingsItem::Arena,
+        SettingsItem::BallSpeed,
+        SettingsItem::PaddleSize,
+        SettingsItem::PointsToWin,
+        SettingsItem::WinByTwo,
+        SettingsItem::BestOf,
+        SettingsItem::ClassicRules,
+        SettingsItem::LivesMode,
+        SettingsItem::Obstacles,
+        SettingsItem::Practice,
+        SettingsItem::Momentum,
+        SettingsItem::PaddleInput,
+        SettingsItem::PlayerProfile(1),
+        SettingsItem::PlayerKeys(1),
+        SettingsItem::PlayerController(1),
+        SettingsItem::StickSensitivity(1),
+        SettingsItem::StickDeadzone(1),
+        SettingsItem::StickCurve(1),
+        SettingsItem::PlayerProfile(2),
+        SettingsItem::PlayerKeys(2),
+        SettingsItem::PlayerController(2),
+        SettingsItem::StickSensitivity(2),
+        SettingsItem::StickDeadzone(2),
+        SettingsItem::StickCurve(2),
+        SettingsItem::NewProfile,
+        SettingsItem::Theme,
+        SettingsItem::Volume,
+        SettingsItem::SoundVolume(SoundCategory::Hits),
+        SettingsItem::SoundVolume(SoundCategory::Ui),
+        SettingsItem::SoundVolume(SoundCategory::Announcer),
+        SettingsItem::SoundVolume(SoundCategory::Crowd),
+        SettingsItem::LowPower,
+        SettingsItem::ScreenEffects,
+        SettingsItem::CoachTips,
+        SettingsItem::HolidayEvents,
+        SettingsItem::HudLayout,
+        SettingsItem::PointReplays,
+        SettingsItem::WindowSize,
+        SettingsItem::AspectLock,
+        SettingsItem::SafeArea,
+        SettingsItem::ExportSetup,
+        SettingsItem::ImportSetup,
+        SettingsItem::Back,
+    ];
+
+    // The entries that can be changed from the pause screen without
+    // changing the match being played
+    pub const IN_MATCH: [SettingsItem; 16] = [
+        SettingsItem::Theme,
+        SettingsItem::Volume,
+        SettingsItem::SoundVolume(SoundCategory::Hits),
+        SettingsItem::SoundVolume(SoundCategory::Ui),
+        SettingsItem::SoundVolume(SoundCategory::Announcer),
+        SettingsItem::SoundVolume(SoundCategory::Crowd),
+        SettingsItem::LowPower,
+        SettingsItem::ScreenEffects,
+        SettingsItem::CoachTips,
+        SettingsItem::HolidayEvents,
+        SettingsItem::HudLayout,
+        SettingsItem::PointReplays,
+        SettingsItem::WindowSize,
+        SettingsItem::AspectLock,
+        SettingsItem::SafeArea,
+        SettingsItem::Back,
+    ];
+}
+
+// Remembers which entry of a menu is highlighted
+#[derive(Clone, Copy, Default)]
+pub struct MenuCursor {
+    pub selected: usize, // Index of the highlighted entry
+}
+
+impl MenuCursor {
+    // Move the highlight up one entry (wraps to the bottom)
+    pub fn up(&mut self, len: usize) {
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    // Move the highlight down one entry (wraps to the top)
+    pub fn down(&mut self, len: usize) {
+        self.selected = (self.selected + 1) % len;
+    }
+}
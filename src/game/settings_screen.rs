@@ -0,0 +1,326 @@
+// This file runs the settings screen: which entries it shows, what each one
+// says, and what changing one does. Opened from the pause screen it only
+// has the options that don't change the match itself
+
+use crate::game::ai::AiTuning;
+use crate::game::constants::*;
+use crate::game::match_feel::{slide, BallPace, PaddleSize};
+use crate::game::scene::{MenuCursor, Scene, SettingsItem};
+use crate::game::state::GameState;
+use crate::graphics::viewport::WindowPreset;
+
+impl GameState {
+    // Open the settings screen on top of the paused match, with only the
+    // options that don't change the match itself
+    pub fn open_match_settings(&mut self) {
+        if self.scene == Scene::Paused {
+            self.settings_in_match = true;
+            self.settings_cursor = MenuCursor::default();
+            self.status = None;
+            self.scene = Scene::Settings;
+        }
+    }
+
+    // Go back to wherever the settings screen was opened from
+    pub fn leave_settings(&mut self) {
+        if self.scene != Scene::Settings {
+            return;
+        }
+        self.scene = if self.settings_in_match {
+            Scene::Paused
+        } else {
+            Scene::Menu
+        };
+        self.settings_in_match = false;
+    }
+
+    // The entries on the settings screen, top to bottom
+    pub fn settings_items(&self) -> &'static [SettingsItem] {
+        if self.settings_in_match {
+            &SettingsItem::IN_MATCH
+        } else {
+            &SettingsItem::ALL
+        }
+    }
+
+    // Text shown for a settings entry, including its current value
+    pub fn settings_label(&self, item: SettingsItem) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+        match item {
+            SettingsItem::Difficulty => {
+                let tuned = if self.sim.ai_tuning.is_custom() {
+                    " (Tuned)"
+                } else {
+                    ""
+                };
+                format!("AI Difficulty: {}{}", self.sim.ai_difficulty.name(), tuned)
+            }
+            SettingsItem::AiSpeed => format!("AI Speed: {}", slider(self.sim.ai_tuning.speed)),
+            SettingsItem::AiAccuracy => {
+                format!("AI Accuracy: {}", slider(self.sim.ai_tuning.accuracy))
+            }
+            SettingsItem::AdaptiveAi => format!("Adaptive AI: {}", on_off(self.sim.adaptive_ai)),
+            SettingsItem::Court => format!("Court: {}", self.sim.court_preset.name()),
+            SettingsItem::Arena => format!("Arena: {}", self.sim.arena.name()),
+            SettingsItem::BallSpeed => format!("Ball Speed: {}", self.sim.ball_pace.name()),
+            SettingsItem::PaddleSize => format!("Paddle Size: {}", self.sim.paddle_size.name()),
+            SettingsItem::PointsToWin => {
+                format!("Points to Win: {}", self.sim.rules.points_needed())
+            }
+            SettingsItem::WinByTwo => {
+                let on = self.sim.rules.win_by_two || self.sim.rules.classic;
+                format!("Win by Two: {}", on_off(on))
+            }
+            SettingsItem::BestOf => format!("Best of {} Games", self.sim.rules.best_of),
+            SettingsItem::ClassicRules => {
+                format!("Classic Table Tennis: {}", on_off(self.sim.rules.classic))
+            }
+            SettingsItem::LivesMode => format!("Lives Mode: {}", on_off(self.sim.lives_mode)),
+            SettingsItem::Obstacles => {
+                format!("Arcade Obstacles: {}", on_off(self.sim.obstacle_mode))
+            }
+            SettingsItem::Practice => {
+                format!(
+                    "End-Wall Bounce Practice: {}",
+                    on_off(self.sim.end_wall_bounce)
+                )
+            }
+            SettingsItem::Momentum => {
+                format!("Momentum Physics: {}", on_off(self.sim.momentum_physics))
+            }
+            SettingsItem::PaddleInput => {
+                format!("Player 1 Controls: {}", self.paddle_input.name())
+            }
+            SettingsItem::PlayerProfile(player) => format!(
+                "Player {} Profile: {}",
+                player,
+                self.preferences.profiles.chosen_name(player)
+            ),
+            SettingsItem::PlayerKeys(player) => format!(
+                "Player {} Keys: {}",
+                player,
+                self.preferences.profiles.steering()[player as usize - 1]
+                    .keys
+                    .name()
+            ),
+            SettingsItem::PlayerController(player) => format!(
+                "Player {} Controller: {}",
+                player,
+                on_off(self.preferences.profiles.controls(player).gamepad)
+            ),
+            SettingsItem::StickSensitivity(player) => format!(
+                "Player {} Stick Sensitivity: {:.2}x",
+                player,
+                self.preferences.profiles.controls(player).stick_scale()
+            ),
+            SettingsItem::StickDeadzone(player) => format!(
+                "Player {} Stick Dead Zone: {:.0}%",
+                player,
+                self.preferences.profiles.controls(player).stick_deadzone() * 100.0
+            ),
+            SettingsItem::StickCurve(player) => format!(
+                "Player {} Stick Curve: {}",
+                player,
+                self.preferences.profiles.controls(player).curve.name()
+            ),
+            SettingsItem::NewProfile => "New Player Profile".to_string(),
+            SettingsItem::Theme => format!("Theme: {}", self.theme_preset.name()),
+            SettingsItem::Volume => {
+                if self.sound_volume == 0 {
+                    "Volume: Off".to_string()
+                } else {
+                    format!("Volume: {}", volume_bar(self.sound_volume))
+                }
+            }
+            SettingsItem::SoundVolume(category) => match self.sound_mix.volume(category) {
+                0 => format!("{}: Off", category.name()),
+                volume => format!("{}: {}", category.name(), volume_bar(volume)),
+            },
+            SettingsItem::LowPower => format!("Low Power Mode: {}", on_off(self.low_power)),
+            SettingsItem::ScreenEffects => {
+                format!("Screen Shake & Hit-Stop: {}", on_off(self.juice.enabled))
+            }
+            SettingsItem::CoachTips => {
+                format!("Coach Tips After Lost Points: {}", on_off(self.coach_tips))
+            }
+            SettingsItem::PointReplays => {
+                format!("Slow-Motion Replays: {}", on_off(self.point_replays))
+            }
+            SettingsItem::HolidayEvents => match (self.holiday_events, self.season) {
+                (true, Some(event)) => format!("Holiday Events: On ({})", event.name),
+                (shown, _) => format!("Holiday Events: {}", on_off(shown)),
+            },
+            SettingsItem::HudLayout => format!("Score Display: {}", self.hud_layout.name()),
+            SettingsItem::WindowSize => {
+                let (width, height) = self.window_size;
+                let name = match WindowPreset::matching(width, height) {
+                    Some(preset) => preset.name(),
+                    None => "Custom",
+                };
+                format!("Window Size: {} ({} x {})", name, width, height)
+            }
+            SettingsItem::AspectLock => {
+                format!("Lock Window Shape: {}", on_off(self.aspect_lock))
+            }
+            SettingsItem::SafeArea => {
+                if self.safe_margin == 0 {
+                    "TV Safe Area: Off".to_string()
+                } else {
+                    format!("TV Safe Area: {}%", self.safe_margin)
+                }
+            }
+            SettingsItem::ExportSetup => "Export Setup".to_string(),
+            SettingsItem::ImportSetup => "Import Setup".to_string(),
+            SettingsItem::Back => "Back".to_string(),
+        }
+    }
+
+    // Change the highlighted setting to its next value
+    pub fn change_setting(&mut self, item: SettingsItem) {
+        match item {
+            // Picking a difficulty puts the sliders back to how it plays on its own
+            SettingsItem::Difficulty => {
+                self.sim.ai_difficulty = self.sim.ai_difficulty.next();
+                self.sim.ai_tuning = AiTuning::default();
+            }
+            SettingsItem::AiSpeed | SettingsItem::AiAccuracy => self.slide_setting(item, 1),
+            SettingsItem::AdaptiveAi => self.sim.adaptive_ai = !self.sim.adaptive_ai,
+            SettingsItem::Court => self.sim.apply_court(self.sim.court_preset.next()),
+            SettingsItem::Arena => self.sim.arena = self.sim.arena.next(),
+            SettingsItem::BallSpeed
+            | SettingsItem::PaddleSize
+            | SettingsItem::Volume
+            | SettingsItem::SoundVolume(_) => self.slide_setting(item, 1),
+            SettingsItem::PointsToWin => self.sim.rules.next_points_to_win(),
+            SettingsItem::WinByTwo => self.sim.rules.toggle_win_by_two(),
+            SettingsItem::BestOf => self.sim.rules.next_best_of(),
+            SettingsItem::ClassicRules => self.sim.rules.classic = !self.sim.rules.classic,
+            SettingsItem::LivesMode => {
+                self.sim.lives_mode = !self.sim.lives_mode;
+                self.sim.lives.reset(STARTING_LIVES);
+            }
+            // Setting the court again puts the blocks out (or clears them away)
+            SettingsItem::Obstacles => {
+                self.sim.obstacle_mode = !self.sim.obstacle_mode;
+                self.sim.apply_court(self.sim.court_preset);
+            }
+            SettingsItem::Practice => self.sim.end_wall_bounce = !self.sim.end_wall_bounce,
+            SettingsItem::Momentum => self.sim.momentum_physics = !self.sim.momentum_physics,
+            SettingsItem::PaddleInput => self.paddle_input = self.paddle_input.next(),
+            SettingsItem::PlayerProfile(_)
+            | SettingsItem::StickSensitivity(_)
+            | SettingsItem::StickDeadzone(_) => self.slide_setting(item, 1),
+            // Changing a player's controls changes their profile's (or guest) controls
+            SettingsItem::PlayerKeys(player) => {
+                let controls = self.preferences.profiles.controls_mut(player);
+                controls.keys = controls.keys.next();
+                self.preferences.profiles.keep_keys_apart(player);
+                self.apply_profiles();
+            }
+            SettingsItem::PlayerController(player) => {
+                let controls = self.preferences.profiles.controls_mut(player);
+                controls.gamepad = !controls.gamepad;
+                self.apply_profiles();
+            }
+            SettingsItem::StickCurve(player) => {
+                let controls = self.preferences.profiles.controls_mut(player);
+                controls.curve = controls.curve.next();
+                self.apply_profiles();
+            }
+            SettingsItem::NewProfile => self.open_profile_naming(),
+            SettingsItem::Theme => self.next_theme(),
+            SettingsItem::LowPower => self.low_power = !self.low_power,
+            SettingsItem::ScreenEffects => self.juice.enabled = !self.juice.enabled,
+            SettingsItem::CoachTips => self.coach_tips = !self.coach_tips,
+            SettingsItem::PointReplays => self.point_replays = !self.point_replays,
+            SettingsItem::HolidayEvents => {
+                self.holiday_events = !self.holiday_events;
+                self.refresh_theme();
+            }
+            SettingsItem::HudLayout => self.hud_layout = self.hud_layout.next(),
+            SettingsItem::WindowSize | SettingsItem::SafeArea => self.slide_setting(item, 1),
+            SettingsItem::AspectLock => {
+                self.aspect_lock = !self.aspect_lock;
+                self.snap_window_shape();
+            }
+            SettingsItem::ExportSetup => self.export_setup(),
+            SettingsItem::ImportSetup => self.import_setup(),
+            SettingsItem::Back => self.leave_settings(),
+        }
+    }
+
+    // Move a slider setting `by` steps (wrapping at the ends); any other
+    // setting just changes to its next value
+    pub fn slide_setting(&mut self, item: SettingsItem, by: i32) {
+        let tuning = &mut self.sim.ai_tuning;
+        match item {
+            SettingsItem::AiSpeed => tuning.speed = AiTuning::nudge(tuning.speed, by),
+            SettingsItem::AiAccuracy => tuning.accuracy = AiTuning::nudge(tuning.accuracy, by),
+            SettingsItem::BallSpeed => {
+                self.sim.ball_pace = slide(&BallPace::ALL, self.sim.ball_pace, by)
+            }
+            // Setting the court again resizes the paddles
+            SettingsItem::PaddleSize => {
+                self.sim.paddle_size = slide(&PaddleSize::ALL, self.sim.paddle_size, by);
+                self.sim.apply_court(self.sim.court_preset);
+            }
+            SettingsItem::PlayerProfile(player) => {
+                self.preferences.profiles.slide_choice(player, by);
+                self.preferences.profiles.keep_keys_apart(player);
+                self.apply_profiles();
+            }
+            SettingsItem::StickSensitivity(player) => {
+                let controls = self.preferences.profiles.controls_mut(player);
+                controls.nudge_sensitivity(by);
+                self.apply_profiles();
+            }
+            SettingsItem::StickDeadzone(player) => {
+                let controls = self.preferences.profiles.controls_mut(player);
+                controls.nudge_deadzone(by);
+                self.apply_profiles();
+            }
+            SettingsItem::Volume => {
+                let steps = MAX_SOUND_VOLUME as i32 + 1;
+                self.sound_volume = (self.sound_volume as i32 + by).rem_euclid(steps) as u32;
+            }
+            SettingsItem::SoundVolume(category) => self.sound_mix.slide(category, by),
+            SettingsItem::SafeArea => {
+                let steps = MAX_SAFE_MARGIN as i32 + 1;
+                self.safe_margin = (self.safe_margin as i32 + by).rem_euclid(steps) as u32;
+            }
+            // A window of some other size moves to the first or last preset.
+            // Picking a size leaves fullscreen
+            SettingsItem::WindowSize => {
+                let (width, height) = self.window_size;
+                let preset = match (WindowPreset::matching(width, height), by > 0) {
+                    (Some(current), _) => slide(&WindowPreset::ALL, current, by),
+                    (None, true) => WindowPreset::ALL[0],
+                    (None, false) => WindowPreset::ALL[WindowPreset::ALL.len() - 1],
+                };
+                self.fullscreen = false;
+                self.resize_request = Some(preset.size());
+            }
+            _ => self.change_setting(item),
+        }
+    }
+}
+
+// A slider drawn in text, e.g. "[-------|---] +2" for two steps up
+fn slider(steps: i32) -> String {
+    let track: String = (-AI_TUNING_STEPS..=AI_TUNING_STEPS)
+        .map(|step| if step == steps { '|' } else { '-' })
+        .collect();
+    if steps == 0 {
+        format!("[{}] Normal", track)
+    } else {
+        format!("[{}] {:+}", track, steps)
+    }
+}
+
+// A volume bar, e.g. "[#######---] 7"
+fn volume_bar(volume: u32) -> String {
+    let bar: String = (1..=MAX_SOUND_VOLUME)
+        .map(|step| if step <= volume { '#' } else { '-' })
+        .collect();
+    format!("[{}] {}", bar, volume)
+}
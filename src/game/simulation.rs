@@ -0,0 +1,831 @@
+// This file holds the match itself: ball, paddles, scoring and the AI.
+// It doesn't need a window, so tests can play thousands of steps without one
+
+use crate::components::launcher::BallLauncher;
+use crate::components::obstacle::{self, Obstacle};
+use crate::components::{ball::Ball, lives::Lives, paddle::Paddle, score::Score};
+use crate::game::ai::{self, AiDifficulty, AiTuning};
+use crate::game::arena::Arena;
+use crate::game::audio::AudioScheduler;
+use crate::game::checksum::fnv1a;
+use crate::game::coach::Coach;
+use crate::game::constants::*;
+use crate::game::controller::{Controllers, GameView, HumanController, MatchRng};
+use crate::game::court::{Court, CourtPreset};
+use crate::game::events::{EventListener, GameEvent};
+use crate::game::match_feel::{BallPace, PaddleSize};
+use crate::game::momentum::Momentum;
+use crate::game::recording::{travel_angle, Contact, MatchRecording, Miss};
+use crate::game::rules::{MatchEnd, MatchRules};
+use crate::game::stats::MatchStats;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// Who controls each paddle
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum GameMode {
+    VsAi,      // Play against the computer
+    TwoPlayer, // A second person plays with W/S on the same keyboard
+    Demo,      // Two computer players, shown behind the main menu
+    Bot,       // A program outside the game drives the right paddle over the bot server
+    Training,  // Return balls fired by a ball machine at the right end
+}
+
+// Everything that moves or keeps score during a match
+pub struct Simulation {
+    pub mode: GameMode,               // Whether the right paddle is AI or human
+    pub ai_difficulty: AiDifficulty,  // How hard the computer opponent plays
+    pub ai_tuning: AiTuning,          // Speed and accuracy sliders on top of the difficulty
+    pub adaptive_ai: bool,            // The AI opponent eases off or pushes to keep it close
+    pub court_preset: CourtPreset,    // Which court layout is selected
+    pub court: Court,                 // Size of the playing area and paddle positions
+    pub player1: Paddle,              // Player 1's paddle (controlled by the user)
+    pub player2: Paddle,              // Player 2's paddle (AI or second player)
+    pub ball: Ball,                   // The ball used in the game
+    pub controllers: Controllers,     // What drives each paddle: a person or the computer
+    pub score: Score,                 // Tracks the scores of both players
+    pub stats: MatchStats,            // Live statistics for the current match
+    pub recording: MatchRecording,    // Every paddle contact this match
+    pub audio: AudioScheduler,        // Sound cues timed against the match clock
+    pub events: Vec<GameEvent>,       // What happened during the latest step
+    pub lives: Lives,                 // Remaining lives in arcade mode
+    pub lives_mode: bool,             // Play with lives instead of racing to a score
+    pub end_wall_bounce: bool,        // Practice rule: missed balls bounce instead of scoring
+    pub obstacle_mode: bool,          // Arcade rule: blocks in the middle of the court
+    pub arena: Arena,                 // Court look and twist (ice, rubber, space)
+    pub ball_pace: BallPace,          // How fast the ball is served
+    pub paddle_size: PaddleSize,      // How tall both paddles are
+    pub obstacles: Vec<Obstacle>,     // The blocks in play (none unless obstacle mode is on)
+    pub launcher: BallLauncher,       // The ball machine and its streak in training mode
+    pub coach: Coach,                 // Tips for player 1 after a lost point
+    pub momentum: Momentum,           // Who has been winning recent points, for the bar
+    pub momentum_physics: bool,       // Paddles have mass and push the ball when on
+    pub rules: MatchRules,            // Points to win and games per match
+    pub last_winner: Option<u8>,      // The last player to score a point (1 or 2)
+    pub opening_server: Option<u8>,   // Who served first this match (kept for rematches)
+    pub countdown: Option<f32>,       // Seconds into the countdown before a serve, if running
+    pub go_flash: Option<f32>,        // Seconds since the countdown ended, while "GO!" shows
+    pub point_scored: bool,           // Indicates if a point was scored
+    pub winner: Option<u8>,           // Stores the winner of the match (1 or 2)
+    pub end_reason: Option<MatchEnd>, // How the match was decided
+    pub prev_ball: (f32, f32),        // Ball position before the latest step
+    pub prev_paddle_y: (f32, f32),    // Paddle heights before the latest step
+    pub rng: MatchRng,                // Random numbers for serves and the AI
+    pub match_seed: u32,              // Seed this match's random numbers started from
+    pub next_seed: Option<u32>,       // Seed for the next match, when replaying a match code
+    pub ticks: u64,                   // Simulation steps played this match
+    pub autopilot: bool,              // The computer plays player 1's paddle
+    pub autopilot_used: bool,         // The computer played player 1 at some point this match
+}
+
+impl Simulation {
+    // Create a match for the chosen mode with the default settings
+    pub fn new(mode: GameMode) -> Self {
+        let mut rng = MatchRng::from_entropy(); // Different every run unless seeded
+        let controllers = controllers_for(
+            mode,
+            AiDifficulty::Medium,
+            AiTuning::default(),
+            false,
+            &mut rng,
+        );
+        let mut sim = Simulation {
+            mode,
+            ai_difficulty: AiDifficulty::Medium, // Balanced opponent by default
+            ai_tuning: AiTuning::default(),      // Sliders left where the difficulty puts them
+            adaptive_ai: false,                  // The AI plays its difficulty the whole match
+            court_preset: CourtPreset::Classic,  // Court fills the window by default
+            court: CourtPreset::Classic.court(), // Playing area size
+            player1: Paddle::new(0.0, 0.0),      // Placed on the court below
+            player2: Paddle::new(0.0, 0.0),      // Placed on the court below
+            ball: Ball::new(),                   // Initialize the ball
+            controllers,                         // People or AI (Medium, like ai_difficulty)
+            score: Score::new(),                 // Initialize the score tracker
+            stats: MatchStats::new(),            // No stats recorded yet
+            recording: MatchRecording::new(),    // No contacts recorded yet
+            audio: AudioScheduler::new(),        // No sounds waiting
+            events: Vec::new(),                  // Nothing has happened yet
+            lives: Lives::new(STARTING_LIVES),   // Full lives for arcade mode
+            lives_mode: false,                   // Classic scoring by default
+            end_wall_bounce: false,              // Missed balls score by default
+            obstacle_mode: false,                // An empty court by default
+            arena: Arena::Standard,              // The normal court by default
+            ball_pace: BallPace::Normal,         // The usual serve speed
+            paddle_size: PaddleSize::Normal,     // The usual paddles
+            obstacles: Vec::new(),               // Set out on the court below
+            launcher: BallLauncher::new(),       // Nothing returned yet
+            coach: Coach::default(),             // No tip yet
+            momentum: Momentum::new(),           // Even to start
+            momentum_physics: false,             // Classic paddle movement by default
+            rules: MatchRules::default(),        // A single game to 3 points
+            last_winner: None,                   // No points scored yet
+            opening_server: None,                // First serve of a new match is random
+            countdown: None,                     // Countdown is not running
+            go_flash: None,                      // Nothing to flash yet
+            point_scored: false,                 // No points scored initially
+            winner: None,                        // No winner initially
+            end_reason: None,                    // Match not decided yet
+            prev_ball: (0.0, 0.0),               // Filled in by apply_court below
+            prev_paddle_y: (0.0, 0.0),
+            rng,
+            match_seed: 0,         // Picked when a match starts
+            next_seed: None,       // Next match gets a fresh seed
+            ticks: 0,              // Nothing played yet
+            autopilot: false,      // Player 1 plays for themselves
+            autopilot_used: false, // Nobody has helped yet
+        };
+
+        // Put the paddles and ball in their starting spots on the court
+        sim.apply_court(sim.court_preset);
+        sim
+    }
+
+    // Switch to a court layout and move paddles and ball to their starting spots
+    pub fn apply_court(&mut self, preset: CourtPreset) {
+        self.court_preset = preset;
+        self.court = preset.court();
+
+        let height = self.paddle_size.height();
+        let start_y = self.court.paddle_start_y(height);
+        self.player1.x = self.court.left_paddle_x();
+        self.player2.x = self.court.right_paddle_x();
+        for paddle in [&mut self.player1, &mut self.player2] {
+            paddle.height = height;
+            paddle.y = start_y;
+            paddle.court_height = self.court.height;
+        }
+        self.obstacles = if self.obstacle_mode {
+            obstacle::layout(&self.court)
+        } else {
+            Vec::new()
+        };
+
+        let (center_x, center_y) = self.court.center();
+        self.ball.x = center_x;
+        self.ball.y = center_y;
+        self.ball.stop();
+        self.snap_previous_positions();
+    }
+
+    // A hash of everything that moves or keeps score. Two runs of the same
+    // match (same seed, settings and inputs) give the same number at the same
+    // tick, so the first tick where they differ shows where determinism broke
+    pub fn checksum(&self) -> u32 {
+        let mut bytes = Vec::with_capacity(128);
+        let floats = [
+            self.ball.x,
+            self.ball.y,
+            self.ball.dx,
+            self.ball.dy,
+            self.ball.spin,
+            self.player1.y,
+            self.player1.velocity,
+            self.player1.acceleration,
+            self.player1.hit_cooldown,
+            self.player2.y,
+            self.player2.velocity,
+            self.player2.acceleration,
+            self.player2.hit_cooldown,
+            self.countdown.unwrap_or(-1.0),
+        ];
+        for value in floats {
+            bytes.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        let counts = [
+            self.score.player1,
+            self.score.player2,
+            self.score.games_player1,
+            self.score.games_player2,
+            self.lives.player1,
+            self.lives.player2,
+            self.stats.current_rally,
+            self.match_seed,
+        ];
+        for value in counts {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.ticks.to_le_bytes());
+        fnv1a(&bytes)
+    }
+
+    // Paddle hits so far in the current rally (starts again after each point)
+    pub fn rally(&self) -> u32 {
+        self.stats.current_rally
+    }
+
+    // Who is serving: the player who served the point in play or, while the
+    // countdown runs, the one about to serve (None if that is still to be
+    // picked at random)
+    pub fn serving_player(&self) -> Option<u8> {
+        if self.countdown.is_none() {
+            return self.stats.server;
+        }
+        self.opening_server
+            .map(|opening_server| self.server_after(opening_server))
+    }
+
+    // Who the match rules say serves the next point
+    fn server_after(&self, opening_server: u8) -> u8 {
+        self.rules.server(
+            opening_server,
+            self.score.games_player1 + self.score.games_player2,
+            (self.score.player1, self.score.player2),
+            self.last_winner,
+        )
+    }
+
+    // Make every random choice from here on (serves and AI mistakes) come from
+    // `seed`, so the same seed and the same inputs play out the same match
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = MatchRng::seed_from_u64(seed);
+        self.controllers = controllers_for(
+            self.mode,
+            self.ai_difficulty,
+            self.ai_tuning,
+            self.adaptive_ai,
+            &mut self.rng,
+        );
+    }
+
+    // Start a fresh match in the chosen mode, beginning with a countdown.
+    // Each match reseeds the random numbers, so its seed alone replays it
+    pub fn start_match(&mut self, mode: GameMode) {
+        self.match_seed = match self.next_seed.take() {
+            Some(seed) => seed,
+            None => self.rng.gen(),
+        };
+        self.rng = MatchRng::seed_from_u64(self.match_seed as u64);
+        self.reset();
+        self.mode = mode;
+        self.controllers = controllers_for(
+            mode,
+            self.ai_difficulty,
+            self.ai_tuning,
+            self.adaptive_ai,
+            &mut self.rng,
+        );
+        self.autopilot_used = false;
+        self.set_autopilot(self.autopilot);
+        self.start_countdown();
+    }
+
+    // How many random numbers the match has used: its own, then each
+    // paddle controller's (None for a person)
+    pub fn rng_positions(&self) -> [Option<u64>; 3] {
+        [
+            Some(self.rng.get_word_pos() as u64),
+            self.controllers[0].rng_position(),
+            self.controllers[1].rng_position(),
+        ]
+    }
+
+    // Skip the match's random numbers ahead to where a match started with
+    // the same seed had got to, so it carries on with the same serves and
+    // AI mistakes
+    pub fn set_rng_positions(&mut self, positions: [Option<u64>; 3]) {
+        if let Some(position) = positions[0] {
+            self.rng.set_word_pos(position as u128);
+        }
+        for (controller, position) in self.controllers.iter_mut().zip(&positions[1..]) {
+            if let Some(position) = *position {
+                controller.set_rng_position(position);
+            }
+        }
+    }
+
+    // Hand player 1's paddle to the computer (at the AI difficulty) or back to
+    // the player, straight away, even in the middle of a rally
+    pub fn set_autopilot(&mut self, on: bool) {
+        self.autopilot = on;
+        if self.mode == GameMode::Demo {
+            return; // The computer already plays both sides
+        }
+        self.controllers[0] = if on {
+            self.autopilot_used = true;
+            // Seeded from the match so turning it on doesn't use up the
+            // match's own random numbers
+            ai::controller_for(
+                self.ai_difficulty,
+                self.ai_tuning,
+                self.match_seed as u64 ^ self.ticks,
+            )
+        } else {
+            Box::new(HumanController)
+        };
+    }
+
+    // Put scores, paddles and ball back to the start, keeping the chosen settings
+    pub fn reset(&mut self) {
+        self.score.reset();
+        self.stats.reset();
+        self.recording.reset();
+        self.audio.clear();
+        self.events.clear();
+        self.lives.reset(STARTING_LIVES);
+        self.launcher.reset();
+        self.coach.reset();
+        self.momentum.reset();
+        self.winner = None;
+        self.end_reason = None;
+        self.ticks = 0;
+
+        // Reset ball and paddle positions
+        self.apply_court(self.court_preset);
+        self.player1.velocity = 0.0;
+        self.player1.acceleration = 0.0;
+        self.player2.velocity = 0.0;
+        self.player2.acceleration = 0.0;
+
+        self.point_scored = false;
+        self.countdown = None;
+        self.go_flash = None;
+        self.last_winner = None;
+    }
+
+    // Move the match forward by one step. `directions` is how players 1 and 2
+    // want their paddles to move (-1 = up, 1 = down); they are ignored for
+    // paddles the computer is driving
+    pub fn step(&mut self, delta: f32, directions: [f32; 2]) {
+        self.events.clear(); // Only this step's events are kept
+        if self.winner.is_some() {
+            return; // Nothing moves once the match is over
+        }
+        self.snap_previous_positions();
+        self.ticks += 1;
+        self.audio.advance(delta); // Let due sound cues play
+        self.score.update(delta); // Fade the scorer's highlight
+        self.coach.update(delta); // Take down an old tip
+        self.momentum.update(delta); // Let old points count for less
+        self.update_go_flash(delta);
+
+        // Handle countdown if it is active
+        if self.countdown.is_some() {
+            self.handle_countdown(delta);
+        }
+
+        self.steer_paddles(directions, delta); // Let each controller move its paddle
+        for obstacle in &mut self.obstacles {
+            obstacle.update(delta); // Sliding blocks keep moving, even between points
+        }
+
+        if self.countdown.is_none() {
+            self.ball.update(delta); // Move the ball
+            self.player1.update_cooldown(delta); // Let paddles accept hits again
+            self.player2.update_cooldown(delta);
+            self.handle_collisions(); // Check for collisions
+        }
+    }
+
+    // Begin the 3-2-1 countdown before a serve, with the first number showing
+    // straight away
+    fn start_countdown(&mut self) {
+        self.countdown = Some(0.0);
+        if COUNTDOWN_DURATION as u32 > 0 {
+            self.emit(GameEvent::CountdownTick);
+        }
+    }
+
+    // Count the time "GO!" has been showing, and take it down once it's done
+    pub fn update_go_flash(&mut self, delta: f32) {
+        self.go_flash = match self.go_flash {
+            Some(since) if since + delta < COUNTDOWN_GO_DURATION => Some(since + delta),
+            _ => None,
+        };
+    }
+
+    // Handle the countdown timer before starting a new point
+    fn handle_countdown(&mut self, delta: f32) {
+        let before = self.countdown.unwrap_or(0.0);
+        let elapsed = before + delta;
+        self.countdown = Some(elapsed);
+
+        // Start the ball movement after the countdown ends
+        if elapsed >= COUNTDOWN_DURATION {
+            self.countdown = None; // Reset countdown timer
+            self.go_flash = Some(0.0);
+            self.start_ball(); // Launch the ball
+            return;
+        }
+
+        // The next number appears each time a whole second has gone by. A tiny
+        // allowance so rounding in the step sizes can't make it one step late
+        let second = |time: f32| (time + CUE_TIME_TOLERANCE) as u32;
+        if second(elapsed) > second(before) && second(elapsed) < COUNTDOWN_DURATION as u32 {
+            self.emit(GameEvent::CountdownTick);
+        }
+    }
+
+    // Start the ball movement in a random direction
+    fn start_ball(&mut self) {
+        // In training the ball machine serves every ball
+        if self.mode == GameMode::Training {
+            self.fire_launcher();
+            return;
+        }
+
+        // The opening server starts the match (picked at random if not chosen yet),
+        // then the match rules decide who serves each point. The coin is tossed
+        // every time so a replayed match, which already knows its opening
+        // server, draws the same random numbers as the original
+        let coin_toss = if self.rng.gen_bool(0.5) { 1 } else { 2 };
+        let opening_server = self.opening_server.unwrap_or(coin_toss);
+        self.opening_server = Some(opening_server);
+        let server = self.server_after(opening_server);
+
+        // Serving from player 1 means moving right
+        let speed = self.ball_pace.serve_speed();
+        self.ball.dx = if server == 1 { speed } else { -speed };
+
+        // Set the vertical direction of the ball randomly
+        self.ball.dy = if self.rng.gen_bool(0.5) {
+            speed
+        } else {
+            -speed
+        };
+
+        self.emit(GameEvent::Serve {
+            server,
+            speed: self.ball.speed(),
+        });
+        self.point_scored = false; // Reset the point scored flag
+    }
+
+    // Have the ball machine fire the next ball in training mode
+    fn fire_launcher(&mut self) {
+        self.launcher
+            .fire(&mut self.ball, &self.court, &mut self.rng);
+        self.snap_previous_positions(); // Don't draw the ball sliding over to the machine
+        self.point_scored = false;
+        self.emit(GameEvent::Serve {
+            server: 2,
+            speed: self.ball.speed(),
+        });
+    }
+
+    // Ask each paddle's controller how far to move, then move it
+    fn steer_paddles(&mut self, directions: [f32; 2], delta: f32) {
+        // On ice the paddles always slide, whatever the momentum setting
+        let friction = self.arena.rules().paddle_friction;
+        let recent_rally = self.stats.recent_rally_length(ADAPTIVE_RALLY_WINDOW);
+        let lead = self.score.player1 as i32 - self.score.player2 as i32;
+        let paddles = [&mut self.player1, &mut self.player2];
+        for (((paddle, controller), input), score_gap) in paddles
+            .into_iter()
+            .zip(self.controllers.iter_mut())
+            .zip(directions)
+            .zip([lead, -lead])
+        {
+            paddle.friction = friction.unwrap_or(PADDLE_FRICTION);
+            let view = GameView {
+                ball: &self.ball,
+                paddle,
+                input,
+                serving: self.countdown.is_some(),
+                momentum_physics: self.momentum_physics || friction.is_some(),
+                score_gap,
+                recent_rally,
+            };
+            let distance = controller.desired_move(&view, delta);
+
+            // Remember how fast the paddle went, so it can put spin on the ball
+            if delta > 0.0 {
+                paddle.velocity = distance / delta;
+            }
+            paddle.move_by(distance);
+        }
+    }
+
+    // Check if any player has won the game
+    pub fn check_winner(&mut self, player: u8) {
+        let (score, opponent_score) = if player == 1 {
+            (self.score.player1, self.score.player2)
+        } else {
+            (self.score.player2, self.score.player1)
+        };
+        let opponent = if player == 1 { 2 } else { 1 };
+
+        // In lives mode you win by knocking out the opponent's last life,
+        // otherwise by winning enough games under the match rules
+        let has_won = if self.lives_mode {
+            self.lives.is_out(opponent)
+        } else if self.rules.game_won(score, opponent_score) {
+            self.score.win_game(player);
+            let games = if player == 1 {
+                self.score.games_player1
+            } else {
+                self.score.games_player2
+            };
+            games >= self.rules.games_to_win()
+        } else {
+            false
+        };
+
+        // Declare the game over if the winning condition is met
+        if has_won {
+            let reason = if self.lives_mode {
+                MatchEnd::LivesLost
+            } else {
+                MatchEnd::ScoreReached
+            };
+            self.finish_match(player, reason);
+        }
+    }
+
+    // Play the current game again from 0-0, keeping the games already won and
+    // every setting. The rally in progress is dropped and the game's first
+    // server serves again; points already played still count in the stats
+    pub fn restart_game(&mut self) {
+        if self.winner.is_some() {
+            return; // Nothing left to restart
+        }
+        self.score.reset_points();
+        if self.lives_mode {
+            self.lives.reset(STARTING_LIVES);
+        }
+        self.last_winner = None;
+        self.audio.clear();
+        self.stats.current_rally = 0;
+        self.reset_ball();
+    }
+
+    // Give the match to the opponent of the player who resigned
+    pub fn resign(&mut self, player: u8) {
+        // The AI never resigns, so only people can give up
+        let human = match self.mode {
+            GameMode::VsAi | GameMode::Bot => player == 1,
+            GameMode::TwoPlayer => true,
+            GameMode::Demo | GameMode::Training => false, // Training just stops at the menu
+        };
+        if !human {
+            return;
+        }
+        let opponent = if player == 1 { 2 } else { 1 };
+        self.finish_match(opponent, MatchEnd::Forfeit);
+    }
+
+    // End the match with a winner and the reason it ended
+    fn finish_match(&mut self, player: u8, reason: MatchEnd) {
+        self.winner = Some(player); // Set the winner (this stops the match)
+        self.end_reason = Some(reason); // Remember how it ended
+        self.last_winner = None; // Reset last winner
+        self.emit(GameEvent::GameOver {
+            winner: player,
+            reason,
+        });
+    }
+
+    // Tell the match stats, the sound cues and the recording about something
+    // that just happened, and keep it for effects outside the match to see
+    fn emit(&mut self, event: GameEvent) {
+        self.stats.on_event(&event);
+        self.audio.on_event(&event);
+        self.recording.on_event(&event);
+        self.launcher.on_event(&event);
+        self.coach.on_event(&event);
+        self.momentum.on_event(&event);
+        self.events.push(event);
+    }
+
+    // Handle collisions between the ball and game objects (walls, paddles)
+    fn handle_collisions(&mut self) {
+        // How bouncy the walls are and whether spin grips them depends on the arena
+        let arena = self.arena.rules();
+        let top_wall = self.ball.y - BALL_RADIUS <= COLLISION_TOLERANCE;
+        let bottom_wall = self.ball.y + BALL_RADIUS >= self.court.height - COLLISION_TOLERANCE;
+
+        // Ball bounces off the top wall
+        if top_wall {
+            self.ball.y = BALL_RADIUS + COLLISION_TOLERANCE;
+            self.ball.dy = self.ball.dy.abs() * arena.wall_restitution;
+        }
+        // Ball bounces off the bottom wall
+        else if bottom_wall {
+            self.ball.y = self.court.height - BALL_RADIUS - COLLISION_TOLERANCE;
+            self.ball.dy = -self.ball.dy.abs() * arena.wall_restitution;
+        }
+        if top_wall || bottom_wall {
+            if arena.wall_restitution > 1.0 {
+                self.ball.scale_speed(1.0); // Bouncy walls still can't push it past top speed
+            }
+            if arena.wall_spin_grip {
+                self.ball.spin_off_wall(top_wall);
+            }
+            self.emit(GameEvent::WallBounce);
+        }
+
+        // In obstacle mode the ball bounces off any block it runs into
+        let mut hit_obstacle = false;
+        for obstacle in &self.obstacles {
+            hit_obstacle |= obstacle::bounce_off_rect(&mut self.ball, obstacle.rect());
+        }
+        if hit_obstacle {
+            self.emit(GameEvent::ObstacleBounce);
+        }
+
+        // Ball hits Player 1's paddle while heading left (ignored briefly
+        // right after a return)
+        if self.player1.can_hit()
+            && self.ball.dx < 0.0
+            && self.ball.x - BALL_RADIUS <= self.player1.x + PADDLE_WIDTH
+            && self.ball.x + BALL_RADIUS >= self.player1.x
+            && self.ball.y >= self.player1.y
+            && self.ball.y <= self.player1.y + self.player1.height
+        {
+            let incoming = (self.ball.dx, self.ball.dy);
+
+            // Send the ball back at an angle set by where it hit the paddle
+            let angle = self.player1.deflection_angle(self.ball.y);
+            self.ball
+                .launch(self.ball.speed() * PADDLE_RESTITUTION, angle, 1.0);
+            // Rallies get faster, and a moving paddle spins the ball (and drags
+            // it along with momentum physics)
+            self.ball.scale_speed(RALLY_SPEED_UP);
+            self.ball
+                .take_spin(self.player1.velocity, self.momentum_physics);
+            self.paddle_hit(1, incoming);
+            self.player1.start_hit_cooldown();
+            self.launcher.record_return(&self.ball, &self.court); // Kept if recording a drill
+        }
+
+        // Ball hits Player 2's paddle while heading right (ignored briefly
+        // right after a return; there is no right paddle in training)
+        if self.mode != GameMode::Training
+            && self.player2.can_hit()
+            && self.ball.dx > 0.0
+            && self.ball.x + BALL_RADIUS >= self.player2.x
+            && self.ball.x - BALL_RADIUS <= self.player2.x + PADDLE_WIDTH
+            && self.ball.y >= self.player2.y
+            && self.ball.y <= self.player2.y + self.player2.height
+        {
+            let incoming = (self.ball.dx, self.ball.dy);
+
+            // Send the ball back at an angle set by where it hit the paddle
+            let angle = self.player2.deflection_angle(self.ball.y);
+            self.ball
+                .launch(self.ball.speed() * PADDLE_RESTITUTION, angle, -1.0);
+            // Rallies get faster, and a moving paddle spins the ball (and drags
+            // it along with momentum physics)
+            self.ball.scale_speed(RALLY_SPEED_UP);
+            self.ball
+                .take_spin(self.player2.velocity, self.momentum_physics);
+            self.paddle_hit(2, incoming);
+            self.player2.start_hit_cooldown();
+        }
+
+        // In training the ball machine takes back every ball that reaches it
+        // and fires the next one straight away. A ball that gets past the
+        // player ends the streak, and the machine fires again
+        if self.mode == GameMode::Training {
+            let reached_machine = self.ball.dx > 0.0
+                && self.ball.x + BALL_RADIUS >= self.court.width - LAUNCHER_WIDTH;
+            if self.ball.x - BALL_RADIUS <= 0.0 {
+                self.ball_missed(1);
+                self.emit(GameEvent::PointScored { winner: 2 });
+                self.fire_launcher();
+            } else if reached_machine {
+                self.fire_launcher();
+            }
+            return;
+        }
+
+        // In practice, balls that get past a paddle bounce off the end wall,
+        // as bouncy as the arena's other walls
+        if self.end_wall_bounce {
+            let left_wall = self.ball.x - BALL_RADIUS <= 0.0;
+            let right_wall = self.ball.x + BALL_RADIUS >= self.court.width;
+            if left_wall {
+                self.ball.x = BALL_RADIUS;
+                self.ball.dx = self.ball.dx.abs() * arena.wall_restitution;
+            } else if right_wall {
+                self.ball.x = self.court.width - BALL_RADIUS;
+                self.ball.dx = -self.ball.dx.abs() * arena.wall_restitution;
+            }
+            if (left_wall || right_wall) && arena.wall_restitution > 1.0 {
+                self.ball.scale_speed(1.0); // Bouncy walls still can't push it past top speed
+            }
+            return;
+        }
+
+        // Ball goes past Player 1 (Player 2 scores)
+        if self.ball.x - BALL_RADIUS <= 0.0 {
+            self.ball_missed(1);
+            self.score.increment_player2();
+            self.emit(GameEvent::PointScored { winner: 2 });
+            if self.lives_mode {
+                self.lives.lose_life(1);
+            }
+            self.last_winner = Some(2);
+            self.check_winner(2);
+            self.reset_ball();
+        }
+        // Ball goes past Player 2 (Player 1 scores)
+        else if self.ball.x + BALL_RADIUS >= self.court.width {
+            self.ball_missed(2);
+            self.score.increment_player1();
+            self.emit(GameEvent::PointScored { winner: 1 });
+            if self.lives_mode {
+                self.lives.lose_life(2);
+            }
+            self.last_winner = Some(1);
+            self.check_winner(1);
+            self.reset_ball();
+        }
+    }
+
+    // Player 1's or player 2's paddle
+    fn paddle(&self, player: u8) -> &Paddle {
+        if player == 1 {
+            &self.player1
+        } else {
+            &self.player2
+        }
+    }
+
+    // How far from `player`'s paddle's middle the ball is: -1 at its top
+    // end, 1 at its bottom end (beyond those when the ball misses)
+    fn offset_on_paddle(&self, player: u8) -> f32 {
+        let paddle = self.paddle(player);
+        let half = paddle.height / 2.0;
+        (self.ball.y - paddle.y - half) / half
+    }
+
+    // Announce a paddle hit: where on the paddle the ball hit, the ball's
+    // velocity before the hit, and where it went afterwards
+    fn paddle_hit(&mut self, player: u8, incoming: (f32, f32)) {
+        let contact = Contact {
+            player,
+            offset: self.offset_on_paddle(player).clamp(-1.0, 1.0),
+            angle_in: travel_angle(incoming.0, incoming.1),
+            angle_out: travel_angle(self.ball.dx, self.ball.dy),
+            speed: incoming.0.hypot(incoming.1),
+        };
+        self.emit(GameEvent::PaddleHit {
+            contact,
+            speed: self.ball.speed(),
+        });
+    }
+
+    // Announce the ball getting past `player`'s paddle, and how far from the
+    // paddle's middle it went by
+    fn ball_missed(&mut self, player: u8) {
+        let miss = Miss {
+            player,
+            offset: self.offset_on_paddle(player),
+            angle: travel_angle(self.ball.dx, self.ball.dy),
+            speed: self.ball.speed(),
+        };
+        self.emit(GameEvent::BallMissed { miss });
+    }
+
+    // Remember where the ball and paddles are before they move
+    fn snap_previous_positions(&mut self) {
+        self.prev_ball = (self.ball.x, self.ball.y);
+        self.prev_paddle_y = (self.player1.y, self.player2.y);
+    }
+
+    // Reset the ball to the center of the court after a point
+    fn reset_ball(&mut self) {
+        let (center_x, center_y) = self.court.center();
+        self.ball.x = center_x;
+        self.ball.y = center_y;
+        self.ball.stop();
+        self.point_scored = true;
+        self.snap_previous_positions(); // Don't draw the ball sliding back to the center
+
+        // Start a countdown for the next point if the match is not over
+        if self.winner.is_none() {
+            self.start_countdown();
+        }
+    }
+}
+
+// The controllers for each paddle in a mode: people where someone is playing,
+// the computer everywhere else. Each AI gets its own seed drawn from `rng`.
+// With `adaptive` on, the opponent in a match against the AI keeps it close
+fn controllers_for(
+    mode: GameMode,
+    difficulty: AiDifficulty,
+    tuning: AiTuning,
+    adaptive: bool,
+    rng: &mut impl Rng,
+) -> Controllers {
+    match mode {
+        GameMode::VsAi if adaptive => [
+            Box::new(HumanController),
+            ai::adaptive_controller_for(difficulty, tuning, rng.gen()),
+        ],
+        GameMode::VsAi => [
+            Box::new(HumanController),
+            ai::controller_for(difficulty, tuning, rng.gen()),
+        ],
+        // A bot steers like a person would, only its directions come over the
+        // network. In training the right paddle is left out of the match
+        GameMode::TwoPlayer | GameMode::Bot | GameMode::Training => {
+            [Box::new(HumanController), Box::new(HumanController)]
+        }
+        GameMode::Demo => [
+            ai::controller_for(difficulty, tuning, rng.gen()),
+            ai::controller_for(difficulty, tuning, rng.gen()),
+        ],
+    }
+}
@@ -0,0 +1,190 @@
+// This file plays the sound cues, the crowd and the background music. The
+// game ships without sound files, so each cue is a short tone built in memory
+// as a WAV clip when the game starts, the music is two looping layers built the
+// same way, and the crowd is a loop of softened noise
+
+use crate::game::audio::{Cue, SoundCategory, SoundMix};
+use crate::game::constants::*;
+use ggez::audio::{SoundData, SoundSource, Source};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// One loaded sound for each cue
+pub struct Sounds {
+    beep: Source,        // Countdown numbers
+    go: Source,          // Serve
+    goal: Source,        // Point scored
+    hit: Source,         // Paddle hit
+    bounce: Source,      // Wall or block bounce
+    click: Source,       // Menu click
+    crowd: Source,       // Crowd murmur that loops during a match
+    music_bass: Source,  // Music layer that always plays during a match
+    music_drive: Source, // Music layer turned up as the ball speeds up
+    pub volume: f32,     // How loud everything plays (0 to 1), from the volume setting
+    pub mix: SoundMix,   // How loud each kind of sound plays, from the settings
+}
+
+impl Sounds {
+    // Build every cue's sound. Fails if the system has no audio output
+    pub fn new(ctx: &ggez::Context) -> ggez::GameResult<Self> {
+        Ok(Sounds {
+            beep: load(ctx, &[(BEEP_PITCH, BEEP_LENGTH)])?,
+            go: load(ctx, &[(GO_PITCH, GO_LENGTH)])?,
+            goal: load(ctx, &GOAL_JINGLE)?,
+            hit: load(ctx, &[(HIT_PITCH, HIT_LENGTH)])?,
+            bounce: load(ctx, &[(BOUNCE_PITCH, BOUNCE_LENGTH)])?,
+            click: load(ctx, &[(CLICK_PITCH, CLICK_LENGTH)])?,
+            crowd: repeating(ctx, crowd_wav(CROWD_LOOP_LENGTH))?,
+            music_bass: load_loop(ctx, &MUSIC_BASS)?,
+            music_drive: load_loop(ctx, &MUSIC_DRIVE)?,
+            volume: 1.0,
+            mix: SoundMix::default(),
+        })
+    }
+
+    // Keep the crowd murmuring while `playing` (paused otherwise)
+    pub fn set_crowd(&mut self, ctx: &ggez::Context, playing: bool) -> ggez::GameResult {
+        let level = self.mix.level(SoundCategory::Crowd);
+        if !playing || level == 0.0 {
+            if self.crowd.playing() {
+                self.crowd.pause();
+            }
+            return Ok(());
+        }
+        if self.crowd.paused() {
+            self.crowd.resume();
+        } else if self.crowd.stopped() {
+            self.crowd.play(ctx)?;
+        }
+        self.crowd.set_volume(CROWD_VOLUME * self.volume * level);
+        Ok(())
+    }
+
+    // Keep the music going while `playing` (paused otherwise), with the driving
+    // layer turned up to `intensity` (0 to 1). Both layers start together so
+    // they stay in time with each other
+    pub fn set_music(
+        &mut self,
+        ctx: &ggez::Context,
+        playing: bool,
+        intensity: f32,
+    ) -> ggez::GameResult {
+        if !playing {
+            if self.music_bass.playing() {
+                self.music_bass.pause();
+                self.music_drive.pause();
+            }
+            return Ok(());
+        }
+        if self.music_bass.paused() {
+            self.music_bass.resume();
+            self.music_drive.resume();
+        } else if self.music_bass.stopped() {
+            self.music_bass.play(ctx)?;
+            self.music_drive.play(ctx)?;
+        }
+        self.music_bass.set_volume(MUSIC_VOLUME * self.volume);
+        self.music_drive
+            .set_volume(MUSIC_VOLUME * self.volume * intensity.clamp(0.0, 1.0));
+        Ok(())
+    }
+
+    // Start playing a cue, on top of anything already playing
+    pub fn play(&mut self, ctx: &ggez::Context, cue: Cue) -> ggez::GameResult {
+        let source = match cue {
+            Cue::CountdownBeep => &mut self.beep,
+            Cue::CountdownGo => &mut self.go,
+            Cue::Goal => &mut self.goal,
+            Cue::PaddleHit => &mut self.hit,
+            Cue::Bounce => &mut self.bounce,
+            Cue::MenuClick => &mut self.click,
+        };
+        // Kinds of sound turned off aren't played at all
+        let level = self.mix.level(cue.category());
+        if level == 0.0 {
+            return Ok(());
+        }
+        // Each cue plays on a fresh channel, so its volume is set every time
+        source.set_volume(CUE_VOLUME * self.volume * level);
+        source.play_detached(ctx)
+    }
+}
+
+// Turn a list of notes into a playable sound
+fn load(ctx: &ggez::Context, notes: &[(f32, f32)]) -> ggez::GameResult<Source> {
+    let data = SoundData::from_bytes(&tone_wav(notes));
+    let mut source = Source::from_data(ctx, data)?;
+    source.set_volume(CUE_VOLUME);
+    Ok(source)
+}
+
+// Turn a list of notes into a music layer that repeats until paused
+fn load_loop(ctx: &ggez::Context, notes: &[(f32, f32)]) -> ggez::GameResult<Source> {
+    repeating(ctx, tone_wav(notes))
+}
+
+// Turn a WAV file into a sound that repeats until paused
+fn repeating(ctx: &ggez::Context, wav: Vec<u8>) -> ggez::GameResult<Source> {
+    let data = SoundData::from_bytes(&wav);
+    let mut source = Source::from_data(ctx, data)?;
+    source.set_volume(MUSIC_VOLUME);
+    source.set_repeat(true);
+    Ok(source)
+}
+
+// Build a mono 16-bit WAV file playing each (pitch in Hz, length in seconds) note in turn.
+// Every note fades out so it ends without a click
+pub fn tone_wav(notes: &[(f32, f32)]) -> Vec<u8> {
+    let mut samples: Vec<i16> = Vec::new();
+    for &(pitch, length) in notes {
+        let count = (length * SOUND_SAMPLE_RATE as f32) as usize;
+        for index in 0..count {
+            let time = index as f32 / SOUND_SAMPLE_RATE as f32;
+            let fade = 1.0 - index as f32 / count as f32;
+            let wave = (time * pitch * std::f32::consts::TAU).sin();
+            samples.push((wave * fade * i16::MAX as f32) as i16);
+        }
+    }
+    wav(&samples)
+}
+
+// Build a mono 16-bit WAV file of crowd murmur `length` seconds long: random
+// noise smoothed until only the low rumble is left, swelling and settling
+// once over the loop. The noise is always the same so the loop sounds the
+// same every time the game starts
+pub fn crowd_wav(length: f32) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let count = (length * SOUND_SAMPLE_RATE as f32) as usize;
+    let mut level = 0.0;
+    let mut samples: Vec<i16> = Vec::with_capacity(count);
+    for index in 0..count {
+        level += (rng.gen_range(-1.0..1.0) - level) * CROWD_SMOOTHING;
+        // Louder in the middle of the loop, quiet where it joins up again
+        let swell = 0.6 + 0.4 * (index as f32 / count as f32 * std::f32::consts::PI).sin();
+        samples.push((level * swell * i16::MAX as f32) as i16);
+    }
+    wav(&samples)
+}
+
+// Put 16-bit mono samples into a WAV file
+fn wav(samples: &[i16]) -> Vec<u8> {
+    // 44-byte WAV header followed by the samples
+    let data_size = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // Size of the format section
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Plain PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&SOUND_SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SOUND_SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
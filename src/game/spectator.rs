@@ -0,0 +1,370 @@
+// This file lets other copies of the game watch a match. While a bot match
+// is running (or any match, if the game was started with --spectate), the
+// game also listens on a second local port; anyone who connects there gets a
+// snapshot of the match as one line of JSON every simulation step.
+// Spectators can't send moves: anything they send is ignored.
+// Start the game with --watch to connect to that port and show the match.
+// Adding --capture also saves every line received to a file, and
+// --replay-capture plays such a file back later, one line per simulation
+// step, without the other game running
+
+use crate::components::obstacle::Obstacle;
+use crate::game::arena::Arena;
+use crate::game::connection::LineConnection;
+use crate::game::constants::*;
+use crate::game::court::CourtPreset;
+use crate::game::match_feel::PaddleSize;
+use crate::game::scene::Scene;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+
+// Everything needed to draw the match as it stands
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub tick: u64,               // Simulation steps sent so far
+    pub court: CourtPreset,      // Court layout, which places the paddles
+    pub ball: [f32; 4],          // Ball center and speed: x, y, dx, dy
+    pub paddles: [f32; 2],       // Top edges of the left and right paddles
+    pub score: [u32; 2],         // Points in the current game: [left, right]
+    pub games: [u32; 2],         // Games won this match: [left, right]
+    pub best_of: u32,            // Games in the match (1 = a single game)
+    pub lives: Option<[u32; 2]>, // Lives left in lives mode: [left, right]
+    pub countdown: Option<f32>,  // Seconds into the countdown before a serve
+    pub winner: Option<u8>,      // 1 (left) or 2 (right) once the match is over
+    #[serde(default)]
+    pub obstacles: Vec<[f32; 4]>, // Blocks in obstacle mode: x, y, width, height
+    #[serde(default)]
+    pub paused: Option<f32>, // Pause time the player has left, while the match is paused
+    #[serde(default)]
+    pub arena: Arena, // Court look and twist, so the court is drawn the same
+    #[serde(default)]
+    pub paddle_size: PaddleSize, // How tall the paddles are drawn
+    #[serde(default)]
+    pub clock: f32, // Seconds of the match played so far
+}
+
+// Listens for spectators and sends every one of them each snapshot
+pub struct SpectatorServer {
+    listener: TcpListener,
+    spectators: Vec<LineConnection>,
+    tick: u64, // Snapshots sent so far
+}
+
+// Where a spectator's snapshots come from
+enum Feed {
+    Live(LineConnection), // Another game's spectator port
+    Saved {
+        lines: Vec<String>, // Every line of a capture file
+        shown: usize,       // Lines played back so far
+        clock: f32,         // Seconds of the capture played back so far
+    },
+}
+
+// A connection to another game's spectator port (or a capture saved from
+// one), keeping the newest snapshot
+pub struct SpectatorClient {
+    feed: Feed,
+    pub address: String, // Where the match is watched from (or the capture file)
+    pub latest: Option<Snapshot>, // The most recent snapshot received
+    capture: Option<File>, // File every line received is also saved to, if any
+}
+
+impl Snapshot {
+    // Describe the match as it stands
+    pub fn new(sim: &Simulation, tick: u64) -> Self {
+        Snapshot {
+            tick,
+            court: sim.court_preset,
+            arena: sim.arena,
+            paddle_size: sim.paddle_size,
+            ball: [sim.ball.x, sim.ball.y, sim.ball.dx, sim.ball.dy],
+            paddles: [sim.player1.y, sim.player2.y],
+            score: [sim.score.player1, sim.score.player2],
+            games: [sim.score.games_player1, sim.score.games_player2],
+            best_of: sim.rules.best_of,
+            lives: if sim.lives_mode {
+                Some([sim.lives.player1, sim.lives.player2])
+            } else {
+                None
+            },
+            countdown: sim.countdown,
+            winner: sim.winner,
+            obstacles: sim
+                .obstacles
+                .iter()
+                .map(|block| [block.x, block.y, block.width, block.height])
+                .collect(),
+            paused: None,
+            clock: sim.ticks as f32 * FIXED_TIMESTEP,
+        }
+    }
+
+    // Copy the snapshot onto a match so it can be drawn like a local one.
+    // Nothing is simulated: the next snapshot simply replaces this one
+    pub fn apply(&self, sim: &mut Simulation) {
+        sim.mode = GameMode::Bot;
+        if sim.court_preset != self.court {
+            sim.apply_court(self.court);
+        }
+        sim.arena = self.arena;
+        sim.paddle_size = self.paddle_size;
+        sim.player1.height = self.paddle_size.height();
+        sim.player2.height = self.paddle_size.height();
+        let [x, y, dx, dy] = self.ball;
+        sim.ball.x = x;
+        sim.ball.y = y;
+        sim.ball.dx = dx;
+        sim.ball.dy = dy;
+        sim.prev_ball = (x, y);
+        sim.player1.y = self.paddles[0];
+        sim.player2.y = self.paddles[1];
+        sim.prev_paddle_y = (self.paddles[0], self.paddles[1]);
+        sim.score.player1 = self.score[0];
+        sim.score.player2 = self.score[1];
+        sim.score.games_player1 = self.games[0];
+        sim.score.games_player2 = self.games[1];
+        sim.rules.best_of = self.best_of;
+        sim.lives_mode = self.lives.is_some();
+        if let Some([left, right]) = self.lives {
+            sim.lives.player1 = left;
+            sim.lives.player2 = right;
+        }
+        sim.countdown = self.countdown;
+        sim.winner = self.winner;
+        sim.ticks = (self.clock / FIXED_TIMESTEP).round() as u64;
+        sim.obstacles = self
+            .obstacles
+            .iter()
+            .map(|&[x, y, width, height]| Obstacle::fixed(x, y, width, height))
+            .collect();
+    }
+}
+
+impl SpectatorServer {
+    // Start listening, e.g. on "127.0.0.1:7879"
+    pub fn bind(address: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(SpectatorServer {
+            listener,
+            spectators: Vec::new(),
+            tick: 0,
+        })
+    }
+
+    // The address spectators should connect to
+    pub fn address(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // How many spectators are watching
+    pub fn count(&self) -> usize {
+        self.spectators.len()
+    }
+
+    // Pick up newly connected spectators (any number can watch)
+    pub fn poll(&mut self) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if let Ok(spectator) = LineConnection::new(stream) {
+                self.spectators.push(spectator);
+            }
+        }
+    }
+
+    // Send the match to every spectator, dropping any that have gone away
+    // or stopped reading. `paused` is the pause time left if it is paused
+    pub fn broadcast(&mut self, sim: &Simulation, paused: Option<f32>) {
+        self.poll();
+        if self.spectators.is_empty() {
+            return;
+        }
+        let snapshot = Snapshot {
+            paused,
+            ..Snapshot::new(sim, self.tick)
+        };
+        let line = match serde_json::to_string(&snapshot) {
+            Ok(line) => line,
+            Err(error) => {
+                eprintln!("Could not describe the match for spectators: {}", error);
+                return;
+            }
+        };
+        self.tick += 1;
+        self.spectators
+            .retain_mut(|spectator| spectator.send_line(&line).is_ok());
+    }
+}
+
+impl SpectatorClient {
+    // Connect to a game's spectator port, e.g. "127.0.0.1:7879"
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        Ok(SpectatorClient {
+            feed: Feed::Live(LineConnection::new(stream)?),
+            address: address.to_string(),
+            latest: None,
+            capture: None,
+        })
+    }
+
+    // Play back a capture saved while watching. A file without a single
+    // snapshot in it is an error
+    pub fn open_capture(path: &Path) -> io::Result<Self> {
+        let lines: Vec<String> = fs::read_to_string(path)?
+            .lines()
+            .map(String::from)
+            .collect();
+        if !lines
+            .iter()
+            .any(|line| serde_json::from_str::<Snapshot>(line).is_ok())
+        {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "there is no saved match in this file",
+            ));
+        }
+        Ok(SpectatorClient {
+            feed: Feed::Saved {
+                lines,
+                shown: 0,
+                clock: 0.0,
+            },
+            address: path.display().to_string(),
+            latest: None,
+            capture: None,
+        })
+    }
+
+    // Also save every line received from now on to a file, exactly as it
+    // arrived, so the match can be played back later
+    pub fn capture_to(&mut self, path: &Path) -> io::Result<()> {
+        self.capture = Some(File::create(path)?);
+        Ok(())
+    }
+
+    // Check if this is a saved capture rather than a live match
+    pub fn is_replay(&self) -> bool {
+        matches!(self.feed, Feed::Saved { .. })
+    }
+
+    // Check if a saved capture has been played to the end
+    pub fn finished(&self) -> bool {
+        match &self.feed {
+            Feed::Live(_) => false,
+            Feed::Saved { lines, shown, .. } => *shown == lines.len(),
+        }
+    }
+
+    // Read whatever snapshots have arrived and keep the newest. Lines that
+    // aren't snapshots are skipped. A live match gives an error once the game
+    // being watched is gone; a capture plays on by `frame_delta` seconds and
+    // holds its last snapshot once it reaches the end
+    pub fn poll(&mut self, frame_delta: f32) -> io::Result<()> {
+        let lines = match &mut self.feed {
+            Feed::Live(connection) => connection.receive()?,
+            Feed::Saved {
+                lines,
+                shown,
+                clock,
+            } => {
+                *clock += frame_delta.min(MAX_FRAME_TIME);
+                let due = ((*clock / FIXED_TIMESTEP) as usize + 1).min(lines.len());
+                let played = lines[*shown..due].to_vec();
+                *shown = due;
+                played
+            }
+        };
+        for line in &lines {
+            if let Ok(snapshot) = serde_json::from_str::<Snapshot>(line) {
+                self.latest = Some(snapshot);
+            }
+        }
+
+        // Saving stops if the file can't be written to, but watching goes on
+        if let Some(file) = &mut self.capture {
+            let saved = lines
+                .iter()
+                .try_for_each(|line| writeln!(file, "{}", line.trim_end()));
+            if let Err(error) = saved {
+                eprintln!("Stopped saving the match: {}", error);
+                self.capture = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+// The game's side of spectating: letting others watch, and watching another game
+impl GameState {
+    // Start listening for spectators, who then see every match played from
+    // here on. If the port can't be opened, the game carries on without them
+    pub fn open_spectator_port(&mut self) {
+        if self.spectators.is_some() {
+            return;
+        }
+        match SpectatorServer::bind(SPECTATOR_SERVER_ADDRESS) {
+            Ok(server) => self.spectators = Some(server),
+            Err(error) => eprintln!(
+                "Could not let spectators watch on {}: {}",
+                SPECTATOR_SERVER_ADDRESS, error
+            ),
+        }
+    }
+
+    // Watch a match running in another copy of the game, at the address of
+    // its spectator port
+    pub fn watch(&mut self, address: &str) -> io::Result<()> {
+        self.start_watching(SpectatorClient::connect(address)?);
+        Ok(())
+    }
+
+    // Play back a match saved with --capture, drawn the same way as watching
+    // it live
+    pub fn watch_capture(&mut self, path: &Path) -> io::Result<()> {
+        self.start_watching(SpectatorClient::open_capture(path)?);
+        Ok(())
+    }
+
+    // Show the spectating screen for a live or saved match
+    fn start_watching(&mut self, watching: SpectatorClient) {
+        self.watching = Some(watching);
+        self.reset_match();
+        self.sim.mode = GameMode::Bot;
+        self.scene = Scene::Spectating;
+    }
+
+    // Show the newest snapshot of the match being watched, `frame_delta`
+    // seconds on. If the other game has gone away, go back to the menu and say so
+    pub fn follow_watched_match(&mut self, frame_delta: f32) {
+        let watching = match &mut self.watching {
+            Some(watching) => watching,
+            None => return,
+        };
+        match watching.poll(frame_delta) {
+            Ok(()) => {
+                if let Some(snapshot) = &watching.latest {
+                    snapshot.apply(&mut self.sim);
+                }
+            }
+            Err(_) => {
+                self.status = Some(format!(
+                    "The game at {} stopped sending its match",
+                    watching.address
+                ));
+                self.stop_watching();
+            }
+        }
+    }
+
+    // Stop watching and return to the main menu
+    pub fn stop_watching(&mut self) {
+        self.watching = None;
+        self.reset_match();
+        self.scene = Scene::Menu;
+    }
+}
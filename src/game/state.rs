@@ -1,301 +1,1620 @@
-// Importing components used in the game such as Ball (game ball), Paddle (player paddles), and Score (score tracker)
-// Importing modules and components from the crate
-// Ball, Paddle, and Score are game components likely representing game objects
-use crate::components::{ball::Ball, paddle::Paddle, score::Score};
+// Importing the match itself: ball, paddles, scoring and the AI
+use crate::game::simulation::{GameMode, Simulation};
 
 // Importing constants used in the game logic, such as screen dimensions or movement speeds
 use crate::game::constants::*;
 
+// Importing the options a match is played with
+use crate::game::match_options::MatchOptions;
+
+// Importing the changelog and the file that remembers which version was last seen
+use crate::game::changelog::{Release, CHANGELOG};
+use crate::game::preferences::Preferences;
+
+// Importing the totals kept across matches and saved between runs, with how each match ended
+use crate::game::rules::MatchEnd;
+use crate::game::stats::CareerStats;
+
+// Importing the match left unfinished last time, to carry on
+use crate::game::saved_match::SavedMatch;
+
+// Importing the demo match behind the main menu
+use crate::game::demo::new_demo;
+
+// Importing the socket server that lets outside programs play the right paddle
+use crate::game::bot_server::BotServer;
+
+// Importing the spectator port, and the connection used to watch another game
+use crate::game::spectator::{SpectatorClient, SpectatorServer};
+
+// Importing the frame timer for render benchmarks
+use crate::game::bench::FrameTimes;
+
+// Importing the codes that replay a match with the same seed and settings
+use crate::game::match_code::MatchCode;
+
+// Importing the practice drills the ball machine can fire
+use crate::game::drills::DrillBook;
+
+// Importing the screen shake and hit-stop effects, which react to match events
+use crate::game::events::{EventListener, GameEvent};
+use crate::game::juice::Juice;
+
+// Importing the slow-motion replay shown after each point
+use crate::game::instant_replay::{Replay, ReplayBuffer};
+
+// Importing the hidden input latency test
+use crate::game::latency::{latency_text, LatencyProbe};
+
+// Importing the sounds played for countdown and scoring cues, and the music's intensity
+use crate::game::audio::{Cue, MusicIntensity, SoundMix};
+use crate::game::sounds::Sounds;
+
+// Importing tournament mode's brackets
+use crate::game::tournament::Tournament;
+
+// Importing the screens the game moves between and their menu entries
+use crate::game::scene::{MenuCursor, MenuItem, Scene};
+
 // Importing the renderer for rendering game graphics
+use crate::graphics::mesh_cache::MeshCache;
 use crate::graphics::renderer::GameRenderer;
+use crate::graphics::seasonal::{event_on, CalendarDay, ParticleField, SeasonalEvent};
+
+// Importing the menu, analysis screen, pace graph and debug overlay drawing helpers
+use crate::graphics::analysis::draw_analysis;
+use crate::graphics::broadcast::{HudLayout, Scoreboard};
+use crate::graphics::debug_overlay::draw_debug_overlay;
+use crate::graphics::hud::{Anchor, HudLine, HudText};
+use crate::graphics::menu::draw_menu;
+use crate::graphics::pace::draw_pace_graph;
+use crate::graphics::pause_card::draw_pause_card;
+use crate::graphics::photo::PhotoMode;
+use crate::graphics::ui::option_list::draw_option_list;
+
+// Importing the colors used to draw the court
+use crate::graphics::theme::{Theme, ThemePreset};
+
+// Importing the helper that fits the play area into the window
+use crate::graphics::viewport::{court_cover, letterbox, lock_aspect, safe_area};
 
 // Importing traits and types required for event handling
+use ggez::conf::FullscreenType;
 use ggez::event::EventHandler;
 
 // Importing graphics utilities from the ggez library
-use ggez::graphics::{Canvas, Color, DrawParam, Text, TextFragment};
+use ggez::graphics::{Canvas, DrawMode, DrawParam, Mesh, Text, TextFragment};
 
-// Importing keyboard input utilities for capturing player actions
+// Importing keyboard and controller input types for capturing player actions
+use crate::game::input::{mouse_direction, Action, InputState, PaddleInput};
+use ggez::event::{Axis, Button};
+use ggez::input::gamepad::GamepadId;
 use ggez::input::keyboard::{KeyCode, KeyInput};
 
-// Importing random number generation functionality
-use rand::Rng;
-
-// Importing HashSet for managing collections of unique items
-use std::collections::HashSet;
+// Importing Instant and Duration for handling timing-related operations
+use std::time::{Duration, Instant};
 
-// Importing Instant for handling timing-related operations
-use std::time::Instant;
+// Importing file paths for the files the game saves to
+use std::path::PathBuf;
 
-// Struct to represent the state of the game
+// Struct to represent the state of the game: the match plus menus, input and timing
 pub struct GameState {
-    pub player1: Paddle,                // Player 1's paddle (controlled by the user)
-    pub player2: Paddle,                // Player 2's paddle (controlled by AI)
-    pub ball: Ball,                     // The ball used in the game
-    pub score: Score,                   // Tracks the scores of both players
-    pub game_running: bool,             // Indicates if the game is currently running
-    pub pressed_keys: HashSet<KeyCode>, // Stores the keys currently being pressed
-    pub last_winner: Option<u8>,        // The last player to score a point (1 or 2)
-    pub countdown_start: Option<Instant>, // Timer for the countdown before starting a new point
-    pub point_scored: bool,             // Indicates if a point was scored
-    pub should_exit: bool,              // Flag to indicate if the game should exit
-    pub game_over: bool,                // Indicates if the game is over
-    pub winner: Option<u8>,             // Stores the winner of the game (1 or 2)
+    pub sim: Simulation,               // The match itself (ball, paddles, scores)
+    pub demo: Simulation,              // Two computer players rallying behind the main menu
+    pub scene: Scene,                  // Which screen is showing (menu, playing, ...)
+    pub menu_cursor: MenuCursor,       // Highlighted entry on the main menu
+    pub settings_cursor: MenuCursor,   // Highlighted entry on the settings screen
+    pub resume_countdown: Option<f32>, // Seconds into the countdown after unpausing a rally
+    pub accumulator: f32,              // Frame time not yet used up by simulation steps
+    pub demo_accumulator: f32,         // Frame time not yet used up by demo steps
+    pub momentum_shown: f32, // Where the momentum bar is drawn, sliding toward the real value
+    pub input: InputState,   // Keys and controller buttons currently held
+    pub theme_preset: ThemePreset, // Which color theme is picked
+    pub theme: Theme,        // Colors for everything on screen
+    pub low_power: bool,     // Save battery: lower frame rate, fewer effects
+    pub hidden: bool,        // Window is minimized or hidden, so nothing is drawn
+    pub window_size: (f32, f32), // Current window size, used to scale the play area
+    pub fullscreen: bool,    // Whether the window fills the whole screen
+    pub aspect_lock: bool,   // Resizing the window keeps the play area's shape
+    pub safe_margin: u32,    // TV safe area around the HUD, in percent of the window per side
+    pub resize_request: Option<(f32, f32)>, // Window size to switch to on the next update
+    pub debug_overlay: bool, // Show frame rate and physics numbers (F3)
+    pub vsync: bool,         // The window waits for the screen to refresh before drawing
+    pub latency: LatencyProbe, // Key presses timed on the latency test screen
+    pub juice: Juice,        // Screen shake on hits and hit-stop on points
+    pub point_replays: bool, // Play each point back in slow motion before the next serve
+    pub replay_buffer: ReplayBuffer, // The last moments of the point being played
+    pub replay: Option<Replay>, // The point being played back, while it is
+    pub game_over_time: f32, // Seconds the game over screen has been up
+    pub restart_prompt: Option<f32>, // Seconds the quick-restart question has been up, if asked
+    pub streak_banner: Option<(String, f32)>, // Win streak milestones announced, and seconds up
+    pub pause_used: f32,     // Seconds spent paused this match (limited in networked matches)
+    pub last_frame: Instant, // When the last frame finished drawing
+    pub career: CareerStats, // Session and lifetime totals
+    pub sounds: Option<Sounds>, // Sound cues, if the system has audio output
+    pub music: MusicIntensity, // How hard the background music drives, from the ball speed
+    pub sound_volume: u32,   // How loud cues and music play, 0 to MAX_SOUND_VOLUME
+    pub sound_mix: SoundMix, // How loud each kind of sound plays, under the main volume
+    pub ui_cues: Vec<Cue>,   // Menu clicks waiting to be played
+    pub settings_in_match: bool, // The settings screen was opened from the pause screen
+    pub photo: PhotoMode,    // Camera, filter and HUD choice in photo mode
+    pub coach_tips: bool,    // Show the coach's tip after each lost point
+    pub holiday_events: bool, // Show the holiday look when one is on (the opt-out setting)
+    pub season: Option<&'static SeasonalEvent>, // Holiday event running today, if any
+    pub particles: Option<ParticleField>, // Snow or fireworks for today's event
+    pub hud_layout: HudLayout, // Normal score line or the broadcast scoreboard (B)
+    pub paddle_input: PaddleInput, // Keys or the mouse steer player 1's paddle
+    pub stats_path: Option<PathBuf>, // File the lifetime totals are saved to, if any
+    pub bundle_path: Option<PathBuf>, // File the setup is exported to and imported from
+    pub preferences: Preferences, // Things remembered between runs
+    pub preferences_path: Option<PathBuf>, // File the preferences are saved to, if any
+    pub drills: DrillBook,   // Practice drills recorded in training
+    pub drills_path: Option<PathBuf>, // File the drills are saved to, if any
+    pub saved_match: Option<SavedMatch>, // Match left unfinished last time, to carry on
+    pub saved_match_path: Option<PathBuf>, // File an unfinished match is saved to, if any
+    pub own_options: Option<MatchOptions>, // Player's own options, while a continued match plays
+    pub drill_cursor: MenuCursor, // Highlighted entry on the drill list
+    pub drill_name: String,  // Name being typed for a just-recorded drill
+    pub profile_name: String, // Name being typed for a new player profile
+    pub whats_new: &'static [Release], // Releases listed on the What's New screen
+    pub startup_errors: Vec<String>, // Problems found while loading files at startup
+    pub bot: Option<BotServer>, // Lets outside bot programs play; opened on the first bot match
+    pub spectators: Option<SpectatorServer>, // Lets other copies of the game watch matches
+    pub watching: Option<SpectatorClient>, // Another game's match being watched (--watch)
+    pub hud_feed: Option<SpectatorServer>, // Sends every match to a streaming HUD window
+    pub bench: Option<FrameTimes>, // Frame times of a render benchmark (--bench-render)
+    pub status: Option<String>, // Last export/import result, or why a bot match failed
+    pub code_entry: String,  // Match code being typed on the replay screen
+    pub tournament: Option<Tournament>, // Tournament being played, if any
+    pub meshes: MeshCache,   // Paddle and ball shapes kept between frames
+    pub hud: HudText,        // Lines of HUD text kept between frames
+    pub should_exit: bool,   // Flag to indicate if the game should exit
 }
 
 impl GameState {
-    // Constructor to create a new game state
-    pub fn new() -> Self {
-        // Initialize the game state with default values
-        let mut state = GameState {
-            player1: Paddle::new(0.0, (SCREEN_HEIGHT - PADDLE_HEIGHT) / 2.0), // Left paddle at center
-            player2: Paddle::new(
-                SCREEN_WIDTH - PADDLE_WIDTH,
-                (SCREEN_HEIGHT - PADDLE_HEIGHT) / 2.0,
-            ), // Right paddle at center
-            ball: Ball::new(),                                                // Initialize the ball
-            score: Score::new(),          // Initialize the score tracker
-            game_running: false,          // Game is not running initially
-            pressed_keys: HashSet::new(), // No keys are pressed initially
-            last_winner: None,            // No points scored yet
-            countdown_start: None,        // Countdown timer is not active
-            point_scored: false,          // No points scored initially
-            should_exit: false,           // Game should not exit initially
-            game_over: false,             // Game is not over initially
-            winner: None,                 // No winner initially
+    // Constructor to create a new game state for the chosen mode
+    pub fn new(mode: GameMode) -> Self {
+        GameState {
+            sim: Simulation::new(mode),             // Default match settings
+            demo: new_demo(),                       // Starts playing straight away
+            scene: Scene::Menu,                     // Start on the main menu
+            menu_cursor: MenuCursor::default(),     // First menu entry highlighted
+            settings_cursor: MenuCursor::default(), // First settings entry highlighted
+            resume_countdown: None,                 // No resume countdown running
+            accumulator: 0.0,                       // No time waiting to be simulated
+            demo_accumulator: 0.0,                  // No demo time waiting either
+            momentum_shown: 0.0,                    // Momentum bar starts centered
+            input: InputState::new(),               // No keys are pressed initially
+            theme_preset: ThemePreset::Classic,     // Original dark look
+            theme: ThemePreset::Classic.theme(),
+            low_power: false,                           // Full frame rate by default
+            hidden: false,                              // Window starts visible
+            window_size: (SCREEN_WIDTH, SCREEN_HEIGHT), // Window opens at the play area's size
+            fullscreen: false,                          // Start in a window
+            aspect_lock: false,                         // Any window shape, with bars
+            safe_margin: 0,                             // HUD right out to the edges
+            resize_request: None,                       // Keep the window's size
+            debug_overlay: false,                       // Hidden until F3 is pressed
+            vsync: true,                                // The window's default
+            latency: LatencyProbe::default(),           // Nothing timed yet
+            juice: Juice::new(),                        // Effects on, nothing happening yet
+            game_over_time: 0.0,                        // No match over yet
+            restart_prompt: None,                       // Nobody has asked to restart
+            streak_banner: None,                        // No streak to announce yet
+            pause_used: 0.0,                            // No time paused yet
+            last_frame: Instant::now(),
+            career: CareerStats::default(), // No matches played yet
+            sounds: None,                   // Silent until sounds are loaded
+            music: MusicIntensity::new(),   // Music starts calm
+            sound_volume: MAX_SOUND_VOLUME, // All the way up
+            sound_mix: SoundMix::default(), // Every kind all the way up
+            ui_cues: Vec::new(),            // Nothing clicked yet
+            settings_in_match: false,       // Opened from the main menu
+            photo: PhotoMode::default(),    // Looking at the whole court, no filter
+            coach_tips: false,              // Only for players who ask for them
+            point_replays: true,            // Shown unless turned off
+            replay_buffer: ReplayBuffer::default(), // Nothing played yet
+            replay: None,                   // Nothing to play back
+            holiday_events: true,           // Shown unless turned off
+            season: None,                   // The date is checked at startup
+            particles: None,                // Nothing to show without an event
+            hud_layout: HudLayout::Normal,  // The usual score line
+            paddle_input: PaddleInput::Keys, // Arrow keys and the first controller
+            stats_path: None,               // Nothing is saved unless a file is chosen
+            bundle_path: None,              // No export file unless one is chosen
+            preferences: Preferences::default(), // Nothing remembered yet
+            preferences_path: None,         // Nothing is saved unless a file is chosen
+            drills: DrillBook::default(),   // No drills recorded yet
+            drills_path: None,              // Nothing is saved unless a file is chosen
+            saved_match: None,              // Nothing to carry on
+            saved_match_path: None,         // Nothing is saved unless a file is chosen
+            own_options: None,              // Playing with the player's own options
+            drill_cursor: MenuCursor::default(), // Random balls highlighted
+            drill_name: String::new(),      // Nothing typed yet
+            profile_name: String::new(),    // Nothing typed yet
+            whats_new: &[],                 // Nothing new to show
+            startup_errors: Vec::new(),     // No problems yet
+            bot: None,                      // Not listening until a bot match is chosen
+            spectators: None,               // Opened with --spectate or the bot server
+            watching: None,                 // Not watching anyone
+            hud_feed: None,                 // Only opened with --stream-hud
+            bench: None,                    // Not benchmarking
+            status: None,                   // Nothing to report yet
+            code_entry: String::new(),      // Nothing typed yet
+            tournament: None,               // Not in a tournament
+            meshes: MeshCache::new(),       // Shapes are built on the first frame
+            hud: HudText::new(),            // So are the lines of text
+            should_exit: false,             // Game should not exit initially
+        }
+    }
+
+    // Check if a match is in progress (playing, paused or framed for a photo)
+    pub fn in_match(&self) -> bool {
+        matches!(self.scene, Scene::Playing | Scene::Paused | Scene::Photo)
+    }
+
+    // Start a fresh match in the chosen mode
+    pub fn start_match(&mut self, mode: GameMode) {
+        self.restore_own_settings();
+        self.reset_match();
+        self.sim.start_match(mode);
+        self.scene = Scene::Playing;
+        self.announce_streaks();
+    }
+
+    // Put scores, paddles and ball back to the start, keeping the chosen settings
+    pub fn reset_match(&mut self) {
+        self.sim.reset();
+        self.resume_countdown = None;
+        self.accumulator = 0.0;
+        self.momentum_shown = 0.0;
+        self.juice.clear();
+        self.replay_buffer.clear();
+        self.replay = None;
+        self.restart_prompt = None;
+        self.pause_used = 0.0;
+        self.music.reset();
+    }
+
+    // Freeze the match (unless it has used up its pause time)
+    pub fn pause(&mut self) {
+        if self.scene == Scene::Playing && self.pause_time_left() != Some(0.0) {
+            self.status = None; // Only news from this pause is shown on it
+            self.scene = Scene::Paused;
+        }
+    }
+
+    // Pause time left this match, so a bot or anyone watching over the
+    // network isn't kept waiting forever. Matches played only on this
+    // computer have nobody else waiting, so they have no limit
+    pub fn pause_time_left(&self) -> Option<f32> {
+        let networked = match self.sim.mode {
+            GameMode::Bot => true,
+            GameMode::VsAi | GameMode::TwoPlayer | GameMode::Training => self.spectators.is_some(),
+            GameMode::Demo => false,
         };
+        networked.then(|| (MATCH_PAUSE_LIMIT - self.pause_used).max(0.0))
+    }
 
-        // Set the ball's initial position and velocity
-        state.ball.x = SCREEN_WIDTH / 2.0;
-        state.ball.y = SCREEN_HEIGHT / 2.0;
-        state.ball.dx = 0.0;
-        state.ball.dy = 0.0;
-        state
+    // Count down the pause clock while a match is paused, carrying on by
+    // itself once the time runs out. Spectators keep getting the frozen match
+    // with the time left, so they can see why nothing is moving. The clock
+    // stands still while the window is hidden, so nobody comes back to a
+    // match that went on without them; a bot is left waiting for nobody, so
+    // its clock keeps going
+    pub fn run_pause_clock(&mut self, frame_delta: f32) {
+        let paused = matches!(self.scene, Scene::Paused | Scene::Photo) || self.settings_in_match;
+        let stopped = self.hidden && self.sim.mode != GameMode::Bot;
+        if !paused || stopped || self.pause_time_left().is_none() {
+            return;
+        }
+        self.pause_used += frame_delta;
+        let left = self.pause_time_left();
+        if let Some(spectators) = &mut self.spectators {
+            spectators.broadcast(&self.sim, left);
+        }
+        if left == Some(0.0) {
+            self.leave_settings();
+            self.leave_photo_mode();
+            self.resume();
+        }
     }
 
-    // Handle the countdown timer before starting a new point
-    fn handle_countdown(&mut self, countdown_start: Instant, delta: f32) {
-        let elapsed = countdown_start.elapsed().as_secs_f32(); // Calculate elapsed time
+    // Carry on with the match. A serve countdown picks up where it stopped;
+    // a rally waits for a fresh 3-2-1 so the ball doesn't surprise the player
+    pub fn resume(&mut self) {
+        if self.scene == Scene::Paused {
+            if self.sim.countdown.is_none() {
+                self.resume_countdown = Some(0.0);
+            }
+            self.scene = Scene::Playing;
+        }
+    }
 
-        // Move the AI paddle towards the middle of the screen during the countdown
-        let middle_position = (SCREEN_HEIGHT - PADDLE_HEIGHT) / 2.0;
-        let distance_to_middle = middle_position - self.player2.y;
+    // Start the current match over in the same mode
+    pub fn restart(&mut self) {
+        self.start_match(self.sim.mode);
+    }
 
-        if distance_to_middle.abs() > 1.0 {
-            let direction = distance_to_middle.signum(); // Determine direction to move
-            self.player2.move_by(direction * AI_PADDLE_SPEED * delta);
+    // Start the current game over from 0-0 with the same settings, keeping the
+    // games already won. Once the game is under way, the first press only
+    // asks; pressing again within a few seconds does it
+    pub fn quick_restart(&mut self) {
+        let under_way =
+            self.sim.score.player1 + self.sim.score.player2 > 0 || self.sim.countdown.is_none();
+        if under_way && self.restart_prompt.is_none() {
+            self.restart_prompt = Some(0.0);
+            return;
         }
+        self.restart_prompt = None;
+        self.sim.restart_game();
+        self.resume_countdown = None;
+        self.accumulator = 0.0;
+        self.juice.clear();
+        self.scene = Scene::Playing;
+    }
 
-        // Start the ball movement after the countdown ends
-        if elapsed >= COUNTDOWN_DURATION {
-            self.countdown_start = None; // Reset countdown timer
-            self.start_ball(); // Launch the ball
+    // Let the quick-restart question go away if it isn't answered in time
+    pub fn expire_restart_prompt(&mut self, frame_delta: f32) {
+        if let Some(shown) = self.restart_prompt {
+            let shown = shown + frame_delta;
+            self.restart_prompt = (shown < QUICK_RESTART_CONFIRM_TIME).then_some(shown);
         }
     }
 
-    // Start the ball movement in a random direction
-    fn start_ball(&mut self) {
-        let mut rng = rand::thread_rng(); // Random number generator
+    // Run the countdown after unpausing by `frame_delta` seconds, and check
+    // if the match is still frozen by it
+    pub fn run_resume_countdown(&mut self, frame_delta: f32) -> bool {
+        if let Some(elapsed) = self.resume_countdown {
+            let elapsed = elapsed + frame_delta;
+            if elapsed < COUNTDOWN_DURATION {
+                self.resume_countdown = Some(elapsed);
+                return true;
+            }
+            self.resume_countdown = None; // Countdown over, play on
+            self.sim.go_flash = Some(0.0);
+        }
+        false
+    }
 
-        // Set the horizontal direction of the ball based on the last winner
-        self.ball.dx = match self.last_winner {
-            Some(2) => BALL_SPEED,  // If Player 2 scored, move right
-            Some(1) => -BALL_SPEED, // If Player 1 scored, move left
-            Some(_) | None => {
-                // Randomize the direction if no prior winner
-                if rng.gen_bool(0.5) {
-                    BALL_SPEED
-                } else {
-                    -BALL_SPEED
-                }
+    // Leave the current match and go back to the main menu
+    pub fn quit_to_menu(&mut self) {
+        self.restore_own_settings();
+        self.reset_match();
+        // Leaving halfway through a tournament ends it, and the opponents'
+        // difficulty goes back to the one picked in the settings
+        if let Some(tournament) = self.tournament.take() {
+            self.sim.ai_difficulty = tournament.home_difficulty;
+        }
+        self.sim.opening_server = None; // A match started from the menu gets a random first serve
+        self.scene = Scene::Menu;
+    }
+
+    // Play again with the same mode and settings, with the other player serving first
+    pub fn rematch(&mut self) {
+        self.sim.opening_server = match self.sim.opening_server {
+            Some(1) => Some(2),
+            Some(_) => Some(1),
+            None => None,
+        };
+        self.start_match(self.sim.mode);
+    }
+
+    // The main menu's entries, with Continue at the top when there's a match
+    // to carry on
+    pub fn menu_items(&self) -> Vec<MenuItem> {
+        let continuing = self.saved_match.as_ref().map(|_| MenuItem::Continue);
+        continuing.into_iter().chain(MenuItem::ALL).collect()
+    }
+
+    // Act on the highlighted main menu entry
+    fn select_menu_item(&mut self) {
+        match self.menu_items()[self.menu_cursor.selected] {
+            MenuItem::Continue => self.continue_match(),
+            MenuItem::SinglePlayer => self.start_match(GameMode::VsAi),
+            MenuItem::Tournament => self.open_tournament(),
+            MenuItem::TwoPlayer => self.start_match(GameMode::TwoPlayer),
+            MenuItem::Training => {
+                self.drill_cursor = MenuCursor::default();
+                self.status = None;
+                self.scene = Scene::Drills;
             }
+            MenuItem::BotMatch => self.start_bot_match(),
+            MenuItem::EnterCode => {
+                self.code_entry.clear();
+                self.status = None;
+                self.scene = Scene::EnterCode;
+            }
+            MenuItem::WhatsNew => {
+                self.whats_new = &CHANGELOG[..1]; // The latest release
+                self.scene = Scene::WhatsNew;
+            }
+            MenuItem::Settings => {
+                self.status = None; // Forget old export/import messages
+                self.settings_in_match = false;
+                self.scene = Scene::Settings;
+            }
+            MenuItem::Exit => self.should_exit = true,
+        }
+    }
+
+    // Who won a finished match and how it ended, as the player at the
+    // keyboard sees it, e.g. "You Lost!" and "You resigned" against the computer
+    pub fn result_text(&self) -> String {
+        let winner = match self.sim.winner {
+            Some(winner) => winner,
+            None => return String::new(),
+        };
+        let headline = match (self.sim.mode, winner) {
+            (GameMode::VsAi, 1) => "You Won!".to_string(),
+            (GameMode::VsAi, _) => "You Lost!".to_string(),
+            (GameMode::TwoPlayer | GameMode::Demo, player) => format!("Player {} Wins!", player),
+            (GameMode::Bot, 1) => "You Beat the Bot!".to_string(),
+            (GameMode::Bot, _) => "The Bot Won!".to_string(),
+            (GameMode::Training, _) => "Training Over".to_string(),
+        };
+        // The loser, named the same way as the headline names the winner
+        let loser = match (self.sim.mode, winner) {
+            (GameMode::VsAi | GameMode::Bot, 2) => "You".to_string(),
+            (GameMode::VsAi, _) => "Opponent".to_string(),
+            (GameMode::Bot, _) => "The bot".to_string(),
+            (_, player) => format!("Player {}", 3 - player),
         };
+        let reason = self
+            .sim
+            .end_reason
+            .map_or(String::new(), |end| end.describe(&loser));
+        format!("{}\n{}", headline, reason)
+    }
 
-        // Set the vertical direction of the ball randomly
-        self.ball.dy = if rng.gen_bool(0.5) {
-            BALL_SPEED
+    // Describe how the current rules decide the winner
+    fn rules_text(&self) -> String {
+        if self.sim.end_wall_bounce {
+            "Practice: missed balls bounce off the end walls".to_string()
+        } else if self.sim.lives_mode {
+            format!(
+                "Lives mode: last one with lives left wins ({} each)",
+                STARTING_LIVES
+            )
         } else {
-            -BALL_SPEED
+            self.sim.rules.describe()
+        }
+    }
+
+    // Move on to the next color theme
+    pub fn next_theme(&mut self) {
+        self.theme_preset = self.theme_preset.next();
+        self.refresh_theme();
+    }
+
+    // Build the colors for the picked theme, with today's holiday colors
+    // swapped in (unless they're turned off)
+    pub fn refresh_theme(&mut self) {
+        self.theme = match self.shown_event() {
+            Some(event) => event.apply(self.theme_preset.theme()),
+            None => self.theme_preset.theme(),
         };
-        self.point_scored = false; // Reset the point scored flag
     }
 
-    // Handle user input to move the paddle
-    fn handle_input(&mut self, delta: f32) {
-        if self.pressed_keys.contains(&KeyCode::Up) {
-            // Move paddle up if 'Up' key is pressed
-            self.player1.move_by(-PLAYER_PADDLE_SPEED * delta);
+    // Look up which holiday event runs on `day` and get its particles going
+    pub fn set_calendar_day(&mut self, day: CalendarDay) {
+        self.season = event_on(day);
+        self.particles = self
+            .season
+            .map(|event| ParticleField::new(event.particles, rand::random()));
+        self.refresh_theme();
+    }
+
+    // The broadcast scoreboard for the match on screen
+    pub fn scoreboard(&self) -> Scoreboard {
+        Scoreboard::new(&self.sim, [self.broadcast_name(1), self.broadcast_name(2)])
+    }
+
+    // Name shown for player 1 or 2 on the broadcast scoreboard: their
+    // profile's, the computer opponent's, or which side they're on
+    fn broadcast_name(&self, player: u8) -> String {
+        let profile = self.preferences.profiles.chosen_name(player);
+        let human = player == 1 || self.sim.mode == GameMode::TwoPlayer;
+        if human && self.scene != Scene::Spectating && profile != "Guest" {
+            return profile.to_string();
+        }
+        match (player, self.sim.mode) {
+            (2, GameMode::VsAi) => {
+                // A tournament opponent goes by their name
+                let opponent = self.tournament.as_ref().and_then(|tournament| {
+                    tournament
+                        .opponents()
+                        .iter()
+                        .find(|opponent| opponent.difficulty == self.sim.ai_difficulty)
+                });
+                match opponent {
+                    Some(opponent) => opponent.name.to_string(),
+                    None => format!("CPU ({})", self.sim.ai_difficulty.name()),
+                }
+            }
+            (2, GameMode::Bot) => "Bot".to_string(),
+            (_, GameMode::Demo) => "CPU".to_string(),
+            (player, _) => format!("Player {}", player),
+        }
+    }
+
+    // Today's holiday event, if there is one and it isn't turned off
+    pub fn shown_event(&self) -> Option<&'static SeasonalEvent> {
+        self.season.filter(|_| self.holiday_events)
+    }
+
+    // The snow or fireworks to draw, if any. Low-power mode skips them to
+    // save drawing work
+    pub fn shown_particles(&self) -> Option<&ParticleField> {
+        match self.shown_event().is_some() && !self.low_power {
+            true => self.particles.as_ref(),
+            false => None,
+        }
+    }
+
+    // React to a menu or match action from the keyboard or a controller
+    pub fn handle_action(&mut self, action: Action) {
+        // Exit the game from any screen
+        if action == Action::Exit {
+            self.should_exit = true;
+            return;
+        }
+
+        // Switch color theme from any screen
+        if action == Action::NextTheme {
+            self.next_theme();
+            self.remember_settings();
+            return;
         }
-        if self.pressed_keys.contains(&KeyCode::Down) {
-            // Move paddle down if 'Down' key is pressed
-            self.player1.move_by(PLAYER_PADDLE_SPEED * delta);
+
+        // Switch between the score line and the broadcast scoreboard from any screen
+        if action == Action::ToggleBroadcast {
+            self.hud_layout = self.hud_layout.next();
+            self.remember_settings();
+            return;
+        }
+
+        // Go fullscreen or back to a window from any screen
+        if action == Action::ToggleFullscreen {
+            self.fullscreen = !self.fullscreen;
+            return;
         }
+
+        // Show or hide the debug overlay from any screen
+        if action == Action::ToggleDebug {
+            self.debug_overlay = !self.debug_overlay;
+            return;
+        }
+
+        // Hand player 1's paddle to the computer or take it back, from any screen
+        if action == Action::ToggleAutopilot {
+            self.sim.set_autopilot(!self.sim.autopilot);
+            return;
+        }
+
+        // Moving through the menus clicks (when there's audio to click with)
+        let menu_key = matches!(
+            action,
+            Action::Up
+                | Action::Down
+                | Action::Left
+                | Action::Right
+                | Action::Confirm
+                | Action::Back
+        );
+        if menu_key && self.scene != Scene::Playing && self.sounds.is_some() {
+            self.ui_cues.push(Cue::MenuClick);
+        }
+
+        match self.scene {
+            Scene::Menu => match action {
+                Action::Up => self.menu_cursor.up(self.menu_items().len()),
+                Action::Down => self.menu_cursor.down(self.menu_items().len()),
+                Action::Confirm => self.select_menu_item(),
+                Action::LatencyTest => self.open_latency_test(), // Not listed on the menu
+                _ => {}
+            },
+            Scene::Settings => {
+                let items = self.settings_items();
+                let item = items[self.settings_cursor.selected];
+                match action {
+                    Action::Up => self.settings_cursor.up(items.len()),
+                    Action::Down => self.settings_cursor.down(items.len()),
+                    Action::Left => self.slide_setting(item, -1),
+                    Action::Confirm | Action::Right => self.change_setting(item),
+                    Action::Back => self.leave_settings(),
+                    _ => {}
+                }
+                // Every change is kept for next time straight away
+                if matches!(action, Action::Left | Action::Right | Action::Confirm) {
+                    self.remember_settings();
+                }
+            }
+            Scene::Playing | Scene::Paused => match action {
+                Action::Pause if self.scene == Scene::Playing => self.pause(),
+                Action::Pause => self.resume(),
+                Action::Confirm if self.scene == Scene::Paused => self.open_match_settings(),
+                Action::Restart => self.restart(),
+                Action::QuickRestart => self.quick_restart(),
+                Action::Resign(player) if self.scene == Scene::Paused => self.resign(player),
+                Action::PhotoMode if self.scene == Scene::Paused => self.open_photo_mode(),
+                Action::RecordDrill => self.toggle_drill_recording(),
+                Action::Back => self.quit_to_menu(), // Abandon the match
+                _ => {}
+            },
+            Scene::Photo => match action {
+                Action::Confirm => self.photo.save_requested = true,
+                Action::ToggleHud => self.photo.hide_hud = !self.photo.hide_hud,
+                Action::NextFilter => self.photo.filter = self.photo.filter.next(),
+                Action::ZoomIn => self.photo.camera.zoom_by(1),
+                Action::ZoomOut => self.photo.camera.zoom_by(-1),
+                Action::PhotoMode | Action::Back => self.leave_photo_mode(),
+                _ => {}
+            },
+            Scene::GameOver => match action {
+                Action::Confirm if self.tournament.is_some() => self.scene = Scene::Bracket,
+                Action::Confirm => self.rematch(),
+                Action::Analysis => self.scene = Scene::Analysis,
+                Action::Restart | Action::Back => self.quit_to_menu(),
+                _ => {}
+            },
+            Scene::StartupError => match action {
+                Action::Confirm | Action::Back => self.leave_startup_error(),
+                _ => {}
+            },
+            Scene::WhatsNew => match action {
+                Action::Confirm | Action::Back => self.scene = Scene::Menu,
+                _ => {}
+            },
+            Scene::Analysis => match action {
+                Action::Analysis | Action::Back | Action::Confirm => self.scene = Scene::GameOver,
+                _ => {}
+            },
+            Scene::EnterCode => match action {
+                Action::Confirm => self.replay_match_code(),
+                Action::Back => self.scene = Scene::Menu,
+                _ => {}
+            },
+            Scene::Drills => {
+                let choices = self.drills.drills.len() + 2;
+                match action {
+                    Action::Up => self.drill_cursor.up(choices),
+                    Action::Down => self.drill_cursor.down(choices),
+                    Action::Confirm => self.select_drill(),
+                    Action::Back => self.scene = Scene::Menu,
+                    _ => {}
+                }
+            }
+            Scene::Bracket => match action {
+                Action::Left | Action::Right => {
+                    if let Some(tournament) = &mut self.tournament {
+                        tournament.switch_size();
+                    }
+                }
+                Action::Confirm if self.tournament.as_ref().is_some_and(Tournament::over) => {
+                    self.quit_to_menu()
+                }
+                Action::Confirm => self.play_tournament_round(),
+                Action::Back => self.quit_to_menu(), // Leave the tournament
+                _ => {}
+            },
+            Scene::NameProfile => match action {
+                Action::Confirm => self.save_profile(),
+                Action::Back => self.scene = Scene::Settings,
+                _ => {}
+            },
+            Scene::NameDrill => match action {
+                Action::Confirm => self.save_drill(),
+                Action::Back => self.discard_drill(),
+                _ => {}
+            },
+            Scene::Spectating => {
+                if action == Action::Back {
+                    self.stop_watching();
+                }
+            }
+            Scene::LatencyTest => {
+                if action == Action::Back {
+                    self.scene = Scene::Menu;
+                }
+            }
+        }
+    }
+
+    // Give the match to the opponent of the player who resigned
+    pub fn resign(&mut self, player: u8) {
+        self.sim.resign(player);
+        self.check_game_over();
     }
 
-    // Check if any player has won the game
-    fn check_winner(&mut self, player: u8) {
-        let score = if player == 1 {
-            self.score.player1
+    // Show the game over screen once the match has a winner,
+    // adding the match to the session and lifetime totals
+    fn check_game_over(&mut self) {
+        if let (true, Some(winner)) = (self.in_match(), self.sim.winner) {
+            self.scene = Scene::GameOver;
+            self.game_over_time = 0.0;
+            // A match the auto-pilot helped with doesn't count as a win or a loss
+            let won = match self.sim.mode {
+                GameMode::VsAi if !self.sim.autopilot_used => Some(winner == 1),
+                GameMode::VsAi
+                | GameMode::TwoPlayer
+                | GameMode::Demo
+                | GameMode::Bot
+                | GameMode::Training => None,
+            };
+            // A winner always comes with a reason; without one it was won on score
+            let end = self.sim.end_reason.unwrap_or(MatchEnd::ScoreReached);
+            self.career.record_match(&self.sim.stats, won, end);
+            self.record_tournament_match(winner == 1);
+            self.record_win_streaks(winner);
+        }
+    }
+
+    // Where to go once the startup problems have been read: What's New if
+    // there is something to show, otherwise the main menu
+    fn leave_startup_error(&mut self) {
+        self.scene = if self.whats_new.is_empty() {
+            Scene::Menu
         } else {
-            self.score.player2
+            Scene::WhatsNew
         };
+    }
+
+    // Remember a problem found while starting up and show it before the menu
+    pub fn report_startup_error(&mut self, message: String) {
+        self.startup_errors.push(message);
+        self.scene = Scene::StartupError;
+    }
 
-        // Declare the game over if the score reaches the winning threshold
-        if score >= 3 {
-            self.game_over = true;
-            self.winner = Some(player); // Set the winner
-            self.game_running = false; // Stop the game
-            self.ball.color = Color::from_rgb(30, 30, 30); // Dim the ball color
-            self.last_winner = None; // Reset last winner
+    // Run as many fixed-size simulation steps as the frame time allows,
+    // keeping the leftover time for the next frame
+    pub fn advance(&mut self, frame_delta: f32) {
+        // Time spent waiting on a lockstep bot is left out, so a slow bot
+        // slows the match down instead of piling up steps to catch up on
+        let waited = match (self.sim.mode, &mut self.bot) {
+            (GameMode::Bot, Some(bot)) => bot.take_waited(),
+            _ => 0.0,
+        };
+        self.accumulator += (frame_delta - waited).clamp(0.0, MAX_FRAME_TIME);
+        while self.accumulator >= FIXED_TIMESTEP && self.scene == Scene::Playing {
+            self.step(FIXED_TIMESTEP);
+            self.accumulator -= FIXED_TIMESTEP;
         }
+
+        // Slide the momentum bar smoothly toward the latest value
+        let target = self.sim.momentum.value;
+        let blend = (MOMENTUM_EASE * frame_delta).min(1.0);
+        self.momentum_shown += (target - self.momentum_shown) * blend;
     }
 
-    // Handle collisions between the ball and game objects (walls, paddles)
-    fn handle_collisions(&mut self) {
-        // Ball bounces off the top wall
-        if self.ball.y - BALL_RADIUS <= COLLISION_TOLERANCE {
-            self.ball.y = BALL_RADIUS + COLLISION_TOLERANCE;
-            self.ball.dy = self.ball.dy.abs();
+    // Move the match forward by one simulation step using the held keys and sticks
+    // (or, in a bot match, the bot's latest command for the right paddle)
+    pub fn step(&mut self, delta: f32) {
+        let mut directions = [
+            self.input.paddle_direction(1), // Arrow keys or the first controller
+            self.input.paddle_direction(2), // W/S or the second controller
+        ];
+        // With mouse steering, player 1's paddle chases the pointer instead
+        // (once it has moved), never faster than the keys would move it
+        if let (PaddleInput::Mouse, Some(mouse_y)) = (self.paddle_input, self.input.mouse_y) {
+            let paddle = &self.sim.player1;
+            let court_y = mouse_y - self.sim.court.offset()[1];
+            directions[0] = mouse_direction(court_y, paddle.y + paddle.height / 2.0);
         }
-        // Ball bounces off the bottom wall
-        else if self.ball.y + BALL_RADIUS >= SCREEN_HEIGHT - COLLISION_TOLERANCE {
-            self.ball.y = SCREEN_HEIGHT - BALL_RADIUS - COLLISION_TOLERANCE;
-            self.ball.dy = -self.ball.dy.abs();
+        // Keep the last moments of the point for its replay (the ball sitting
+        // still through a countdown isn't worth keeping)
+        if self.sim.countdown.is_none() {
+            self.replay_buffer.record(&self.sim);
         }
 
-        // Ball hits Player 1's paddle
-        if self.ball.x - BALL_RADIUS <= PADDLE_WIDTH
-            && self.ball.y >= self.player1.y
-            && self.ball.y <= self.player1.y + PADDLE_HEIGHT
-        {
-            self.ball.dx = self.ball.dx.abs();
+        let bot = match (self.sim.mode, &mut self.bot) {
+            (GameMode::Bot, Some(bot)) => Some(bot),
+            _ => None,
+        };
+        if let Some(bot) = bot {
+            bot.receive_move(Duration::from_secs_f32(BOT_LOCKSTEP_TIMEOUT));
+            directions[1] = bot.direction;
+            self.sim.step(delta, directions);
+            bot.send(&self.sim);
+        } else {
+            self.sim.step(delta, directions);
         }
-
-        // Ball hits Player 2's paddle
-        if self.ball.x + BALL_RADIUS >= SCREEN_WIDTH - PADDLE_WIDTH
-            && self.ball.y >= self.player2.y
-            && self.ball.y <= self.player2.y + PADDLE_HEIGHT
-        {
-            self.ball.dx = -self.ball.dx.abs();
+        if let Some(spectators) = &mut self.spectators {
+            spectators.broadcast(&self.sim, None);
+        }
+        if let Some(feed) = &mut self.hud_feed {
+            feed.broadcast(&self.sim, None);
         }
 
-        // Ball goes past Player 1 (Player 2 scores)
-        if self.ball.x - BALL_RADIUS <= 0.0 {
-            self.score.increment_player2();
-            self.last_winner = Some(2);
-            self.check_winner(2);
-            self.reset_ball();
+        // Shake for new hits, hit-stop for new points
+        for event in &self.sim.events {
+            self.juice.on_event(event);
         }
-        // Ball goes past Player 2 (Player 1 scores)
-        else if self.ball.x + BALL_RADIUS >= SCREEN_WIDTH {
-            self.score.increment_player1();
-            self.last_winner = Some(1);
-            self.check_winner(1);
-            self.reset_ball();
+        let point_over = self
+            .sim
+            .events
+            .iter()
+            .any(|event| matches!(event, GameEvent::PointScored { .. }));
+        if point_over {
+            self.start_replay();
         }
+        self.check_game_over();
     }
 
-    // Reset the ball to the center of the screen after a point
-    fn reset_ball(&mut self) {
-        self.ball.x = SCREEN_WIDTH / 2.0;
-        self.ball.y = SCREEN_HEIGHT / 2.0;
-        self.ball.dx = 0.0;
-        self.ball.dy = 0.0;
-        self.point_scored = true;
+    // Count time on the game over screen and, with the auto-pilot on, start
+    // the next match after a while so the game keeps playing by itself
+    pub fn wait_for_rematch(&mut self, frame_delta: f32) {
+        self.game_over_time += frame_delta;
+        self.sim.score.update(frame_delta); // The last point's highlight still fades
+        if self.sim.autopilot && self.game_over_time >= AUTOPILOT_REMATCH_DELAY {
+            self.rematch();
+        }
+    }
 
-        // Start a countdown for the next point if the game is not over
-        if self.game_running && !self.game_over {
-            self.countdown_start = Some(Instant::now());
+    // Shortest time a frame should take in low-power mode (None = no cap).
+    // Menus and pauses barely change, so they run even slower
+    pub fn frame_cap(&self) -> Option<Duration> {
+        // Barely tick over while nobody can see the window
+        if self.hidden {
+            return Some(Duration::from_secs_f32(1.0 / HIDDEN_FPS));
         }
+        if !self.low_power {
+            return None;
+        }
+        let fps = if self.scene == Scene::Playing {
+            LOW_POWER_FPS
+        } else {
+            LOW_POWER_MENU_FPS
+        };
+        Some(Duration::from_secs_f32(1.0 / fps))
     }
 
-    // Update AI paddle position to follow the ball
-    fn update_ai_paddle(&mut self, delta: f32) {
-        if self.ball.dx > 0.0 {
-            let paddle_center = self.player2.y + PADDLE_HEIGHT / 2.0; // Get the center of the AI paddle
-            let mut rng = rand::thread_rng(); // Random number generator
+    // Note that the window was minimized/hidden or shown again.
+    // Hiding it pauses a running match so nobody loses a point they couldn't see
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+        if hidden {
+            self.pause();
+        }
+    }
 
-            let reaction_speed = AI_PADDLE_SPEED - 10.0; // Adjust reaction speed
-            let hesitation = if rng.gen_bool(0.12) { 0.0 } else { 1.0 }; // Simulate hesitation
-            let error_margin: f32 = rng.gen_range(-3.0..3.0); // Add random error to movement
+    // Remember the new window size. A zero size (minimized on some systems) is
+    // ignored so the play area keeps its last shape
+    pub fn set_window_size(&mut self, width: f32, height: f32) {
+        if width > 0.0 && height > 0.0 {
+            self.window_size = (width, height);
+        }
+    }
 
-            // Move the AI paddle up or down based on the ball's position
-            if self.ball.y + error_margin > paddle_center {
-                self.player2.move_by(reaction_speed * hesitation * delta);
-            } else if self.ball.y + error_margin < paddle_center {
-                self.player2.move_by(-reaction_speed * hesitation * delta);
-            }
+    // With the window's shape locked, ask for the nearest size with the play
+    // area's shape whenever the window has been dragged to some other shape.
+    // A fullscreen window is left to fill the screen
+    pub fn snap_window_shape(&mut self) {
+        if !self.aspect_lock || self.fullscreen {
+            return;
+        }
+        let (width, height) = self.window_size;
+        let locked = lock_aspect(width, height);
+        if (locked.0 - width).abs() >= 1.0 || (locked.1 - height).abs() >= 1.0 {
+            self.resize_request = Some(locked);
         }
     }
 
-    // Display game instructions and messages
-    fn draw_instructions(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> ggez::GameResult {
-        if self.game_over {
-            // Display "Game Over" message based on who won
-            let winner_text = if self.winner.unwrap() == 1 {
-                "You Won!\n\nGame Over".to_string()
-            } else {
-                "You Lost!\n\nGame Over".to_string()
-            };
+    // Remember where the mouse pointer is, from its spot in the window. Only
+    // its height in the play area matters, for steering with the mouse
+    pub fn mouse_moved(&mut self, window_y: f32) {
+        let frame = letterbox(self.window_size.0, self.window_size.1);
+        self.input.mouse_y = Some(frame.y + window_y / self.window_size.1 * frame.h);
+    }
 
-            let text_fragment = TextFragment::new(winner_text)
-                .scale(24.0)
-                .color(Color::WHITE);
-            let game_over_text = Text::new(text_fragment);
-            let dims = game_over_text.measure(ctx)?;
+    // How far the current frame is between the last step and the next (0 to 1),
+    // used to draw smooth motion between steps
+    pub fn interpolation(&self) -> f32 {
+        self.accumulator / FIXED_TIMESTEP
+    }
 
-            // Center the "Game Over" text on the screen
+    // Draw the latest status message, if any, just above the instructions
+    fn draw_status(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> ggez::GameResult {
+        if let Some(status) = &self.status {
+            let status_text = Text::new(
+                TextFragment::new(status.as_str())
+                    .scale(16.0)
+                    .color(self.theme.text_dim),
+            );
+            let dims = status_text.measure(ctx)?;
             canvas.draw(
-                &game_over_text,
+                &status_text,
                 DrawParam::default().dest([
                     SCREEN_WIDTH / 2.0 - dims.x / 2.0,
-                    SCREEN_HEIGHT / 2.0 - dims.y,
+                    SCREEN_HEIGHT - dims.y - 50.0,
                 ]),
             );
-        } else if !self.game_running {
-            // Display the "First to score 3 wins" message when game is not running
-            let start_text = "First to score 3 wins";
-            let text_fragment = TextFragment::new(start_text)
-                .scale(24.0)
-                .color(Color::WHITE);
-            let start_game_text = Text::new(text_fragment);
-            let dims = start_game_text.measure(ctx)?;
+        }
+        Ok(())
+    }
+
+    // Show a tournament's rounds, who is met in each and how they went, with
+    // the best ever reached in a bracket of that size
+    fn draw_bracket(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        tournament: &Tournament,
+    ) -> ggez::GameResult {
+        let title = Text::new(
+            TextFragment::new("Tournament")
+                .scale(32.0)
+                .color(self.theme.text),
+        );
+        let dims = title.measure(ctx)?;
+        canvas.draw(
+            &title,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 80.0]),
+        );
+
+        let summary = Text::new(
+            TextFragment::new(tournament.summary())
+                .scale(22.0)
+                .color(self.theme.highlight),
+        );
+        let dims = summary.measure(ctx)?;
+        canvas.draw(
+            &summary,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 140.0]),
+        );
+
+        // One line per round, first round at the top
+        let mut y = 210.0;
+        for line in tournament.lines() {
+            let round = Text::new(TextFragment::new(line).scale(20.0).color(self.theme.text));
+            let dims = round.measure(ctx)?;
+            canvas.draw(
+                &round,
+                DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, y]),
+            );
+            y += dims.y + 16.0;
+        }
+
+        let best = Text::new(
+            TextFragment::new(self.preferences.tournament.describe(tournament.size))
+                .scale(16.0)
+                .color(self.theme.text_dim),
+        );
+        let dims = best.measure(ctx)?;
+        canvas.draw(
+            &best,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, y + 24.0]),
+        );
+        Ok(())
+    }
+
+    // Display menus, game instructions and messages
+    fn draw_instructions(&self, canvas: &mut Canvas, ctx: &mut ggez::Context) -> ggez::GameResult {
+        match self.scene {
+            Scene::Menu => {
+                let items: Vec<String> = self
+                    .menu_items()
+                    .iter()
+                    .map(|item| item.label().to_string())
+                    .collect();
+                draw_menu(
+                    canvas,
+                    ctx,
+                    "Ping Pong",
+                    &items,
+                    self.menu_cursor.selected,
+                    &self.theme,
+                )?;
+
+                // Remind the player of the current rules and theme under the menu
+                let rules = Text::new(
+                    TextFragment::new(format!(
+                        "{}  |  Theme: {}",
+                        self.rules_text(),
+                        self.theme_preset.name()
+                    ))
+                    .scale(16.0)
+                    .color(self.theme.text_dim),
+                );
+                let dims = rules.measure(ctx)?;
+                canvas.draw(
+                    &rules,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - dims.x / 2.0,
+                        SCREEN_HEIGHT - dims.y - 70.0,
+                    ]),
+                );
+
+                // Why a bot match couldn't start, if it didn't
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::Settings => {
+                // Opened from the pause screen, the match stays dimmed behind
+                let title = if self.settings_in_match {
+                    let dim = Mesh::new_rectangle(
+                        ctx,
+                        DrawMode::fill(),
+                        court_cover(self.safe_margin),
+                        self.theme.overlay,
+                    )?;
+                    canvas.draw(&dim, DrawParam::default());
+                    "Settings (Match Paused)"
+                } else {
+                    "Settings"
+                };
+                let items: Vec<String> = self
+                    .settings_items()
+                    .iter()
+                    .map(|item| self.settings_label(*item))
+                    .collect();
+                draw_option_list(
+                    canvas,
+                    ctx,
+                    title,
+                    &items,
+                    self.settings_cursor.selected,
+                    &self.theme,
+                )?;
+
+                // Result of the last export, import or new profile
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::NameProfile => {
+                let title = Text::new(
+                    TextFragment::new("Name the New Profile")
+                        .scale(32.0)
+                        .color(self.theme.text),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 150.0]),
+                );
+
+                // What has been typed so far, with a cursor after it
+                let entry = Text::new(
+                    TextFragment::new(format!("{}_", self.profile_name))
+                        .scale(32.0)
+                        .color(self.theme.highlight),
+                );
+                let dims = entry.measure(ctx)?;
+                canvas.draw(
+                    &entry,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 240.0]),
+                );
+
+                // Asked for a name when none was typed
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::Paused => {
+                // Dim the frozen court behind the pause message
+                let dim = Mesh::new_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    court_cover(self.safe_margin),
+                    self.theme.overlay,
+                )?;
+                canvas.draw(&dim, DrawParam::default());
+
+                let paused_text = Text::new(
+                    TextFragment::new("Paused")
+                        .scale(32.0)
+                        .color(self.theme.text),
+                );
+                let dims = paused_text.measure(ctx)?;
+                canvas.draw(
+                    &paused_text,
+                    DrawParam::default()
+                        .dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, PAUSED_TITLE_TOP]),
+                );
+
+                // How much longer the pause can go on
+                if let Some(left) = self.pause_time_left() {
+                    let clock = Text::new(
+                        TextFragment::new(pause_clock(left))
+                            .scale(16.0)
+                            .color(self.theme.highlight),
+                    );
+                    let dims = clock.measure(ctx)?;
+                    canvas.draw(
+                        &clock,
+                        DrawParam::default()
+                            .dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, PAUSED_TITLE_TOP - 30.0]),
+                    );
+                }
+
+                // The settings that can be changed without leaving the match
+                let hint = Text::new(
+                    TextFragment::new("Enter for Settings, C for Photo Mode")
+                        .scale(14.0)
+                        .color(self.theme.text_dim),
+                );
+                let hint_width = hint.measure(ctx)?.x;
+                canvas.draw(
+                    &hint,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - hint_width / 2.0,
+                        PAUSED_TITLE_TOP + dims.y + 4.0,
+                    ]),
+                );
+
+                // Where the match stands, for whoever comes back to it
+                draw_pause_card(canvas, ctx, &self.sim, &self.theme)?;
+
+                // Whether the drill just recorded was saved
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::GameOver => {
+                // Display "Game Over" message based on who won
+                let winner_text = format!("{}\n\nGame Over", self.result_text());
+
+                let text_fragment = TextFragment::new(winner_text)
+                    .scale(24.0)
+                    .color(self.theme.text);
+                let game_over_text = Text::new(text_fragment);
+                let dims = game_over_text.measure(ctx)?;
+
+                // Center the "Game Over" text on the screen
+                canvas.draw(
+                    &game_over_text,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - dims.x / 2.0,
+                        SCREEN_HEIGHT / 2.0 - dims.y,
+                    ]),
+                );
+
+                // The longest rally of the match, just under the result
+                let longest = Text::new(
+                    TextFragment::new(format!("Longest rally: {}", self.sim.stats.longest_rally))
+                        .scale(18.0)
+                        .color(self.theme.highlight),
+                );
+                let longest_dims = longest.measure(ctx)?;
+                canvas.draw(
+                    &longest,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - longest_dims.x / 2.0,
+                        SCREEN_HEIGHT / 2.0 + 10.0,
+                    ]),
+                );
+
+                // Session and lifetime totals underneath
+                let career = Text::new(
+                    TextFragment::new(self.career_text())
+                        .scale(16.0)
+                        .color(self.theme.text_dim),
+                );
+                let career_dims = career.measure(ctx)?;
+                canvas.draw(
+                    &career,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - career_dims.x / 2.0,
+                        SCREEN_HEIGHT / 2.0 + 40.0,
+                    ]),
+                );
+
+                // How the ball speed and rally lengths went over the match
+                draw_pace_graph(canvas, ctx, &self.sim.stats.pace, &self.theme)?;
+
+                // The code that plays this match again, for bug reports and challenges
+                let code = Text::new(
+                    TextFragment::new(format!("Match code: {}", MatchCode::for_match(&self.sim)))
+                        .scale(16.0)
+                        .color(self.theme.text_dim),
+                );
+                let code_dims = code.measure(ctx)?;
+                canvas.draw(
+                    &code,
+                    DrawParam::default().dest([
+                        SCREEN_WIDTH / 2.0 - code_dims.x / 2.0,
+                        SCREEN_HEIGHT - code_dims.y - 50.0,
+                    ]),
+                );
+            }
+            Scene::StartupError => {
+                let title = Text::new(
+                    TextFragment::new("Problems Loading Files")
+                        .scale(32.0)
+                        .color(self.theme.warning),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 60.0]),
+                );
+
+                // Each problem on its own, wrapped to fit the screen
+                let mut errors = Text::new(
+                    TextFragment::new(self.startup_errors.join("\n\n"))
+                        .scale(18.0)
+                        .color(self.theme.text),
+                );
+                errors.set_bounds([SCREEN_WIDTH - 100.0, SCREEN_HEIGHT]);
+                canvas.draw(&errors, DrawParam::default().dest([50.0, 130.0]));
+            }
+            Scene::WhatsNew => {
+                let title = Text::new(
+                    TextFragment::new("What's New")
+                        .scale(32.0)
+                        .color(self.theme.text),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 60.0]),
+                );
+
+                // Each release's version, then one line per change
+                let mut lines = Vec::new();
+                for release in self.whats_new {
+                    lines.push(format!("Version {}", release.version));
+                    for change in release.changes {
+                        lines.push(format!("  - {}", change));
+                    }
+                    lines.push(String::new());
+                }
+                let mut changes = Text::new(
+                    TextFragment::new(lines.join("\n"))
+                        .scale(18.0)
+                        .color(self.theme.text),
+                );
+                changes.set_bounds([SCREEN_WIDTH - 100.0, SCREEN_HEIGHT]);
+                canvas.draw(&changes, DrawParam::default().dest([50.0, 130.0]));
+            }
+            Scene::EnterCode => {
+                let title = Text::new(
+                    TextFragment::new("Replay a Match Code")
+                        .scale(32.0)
+                        .color(self.theme.text),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 150.0]),
+                );
+
+                let hint = Text::new(
+                    TextFragment::new("Type the code from the end of a match to play it again")
+                        .scale(18.0)
+                        .color(self.theme.text_dim),
+                );
+                let dims = hint.measure(ctx)?;
+                canvas.draw(
+                    &hint,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 220.0]),
+                );
+
+                // What has been typed so far, with a cursor after it
+                let entry = Text::new(
+                    TextFragment::new(format!("{}_", self.code_entry))
+                        .scale(32.0)
+                        .color(self.theme.highlight),
+                );
+                let dims = entry.measure(ctx)?;
+                canvas.draw(
+                    &entry,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 280.0]),
+                );
+
+                // Why the last code didn't work, if it didn't
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::Drills => {
+                draw_menu(
+                    canvas,
+                    ctx,
+                    "Training",
+                    &self.drill_choices(),
+                    self.drill_cursor.selected,
+                    &self.theme,
+                )?;
+            }
+            Scene::Bracket => {
+                if let Some(tournament) = &self.tournament {
+                    self.draw_bracket(canvas, ctx, tournament)?;
+                }
+            }
+            Scene::NameDrill => {
+                // The paused match stays dimmed behind the name
+                let dim = Mesh::new_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    court_cover(self.safe_margin),
+                    self.theme.overlay,
+                )?;
+                canvas.draw(&dim, DrawParam::default());
+
+                let shots = self.sim.launcher.recording.as_ref().map_or(0, Vec::len);
+                let title = Text::new(
+                    TextFragment::new(format!("Name This Drill ({} shots)", shots))
+                        .scale(32.0)
+                        .color(self.theme.text),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 150.0]),
+                );
+
+                // What has been typed so far, with a cursor after it
+                let entry = Text::new(
+                    TextFragment::new(format!("{}_", self.drill_name))
+                        .scale(32.0)
+                        .color(self.theme.highlight),
+                );
+                let dims = entry.measure(ctx)?;
+                canvas.draw(
+                    &entry,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 240.0]),
+                );
+
+                // Asked for a name when none was typed
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::Photo => {
+                // What the camera and filter are set to, left out once the HUD is hidden
+                if !self.photo.hide_hud {
+                    let settings = Text::new(
+                        TextFragment::new(format!(
+                            "Photo Mode  |  Zoom: {:.1}x  |  Filter: {}",
+                            self.photo.camera.zoom,
+                            self.photo.filter.name()
+                        ))
+                        .scale(16.0)
+                        .color(self.theme.highlight),
+                    );
+                    let dims = settings.measure(ctx)?;
+                    canvas.draw(
+                        &settings,
+                        DrawParam::default().dest([
+                            SCREEN_WIDTH / 2.0 - dims.x / 2.0,
+                            SCREEN_HEIGHT - dims.y - 70.0,
+                        ]),
+                    );
+                }
+
+                // Where the last photo was saved, or why it couldn't be
+                self.draw_status(canvas, ctx)?;
+            }
+            Scene::Analysis => {
+                draw_analysis(canvas, ctx, &self.sim.recording, self.sim.mode, &self.theme)?;
+            }
+            Scene::LatencyTest => {
+                // The whole screen lights up from a press until a moment after
+                // it has been seen
+                if self.latency.flashing(Instant::now()) {
+                    let flash = Mesh::new_rectangle(
+                        ctx,
+                        DrawMode::fill(),
+                        court_cover(self.safe_margin),
+                        self.theme.text,
+                    )?;
+                    canvas.draw(&flash, DrawParam::default());
+                }
+
+                let title = Text::new(
+                    TextFragment::new("Input Latency Test")
+                        .scale(32.0)
+                        .color(self.theme.highlight),
+                );
+                let dims = title.measure(ctx)?;
+                canvas.draw(
+                    &title,
+                    DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 60.0]),
+                );
+
+                // Frame timing and the press-to-screen times so far
+                let results = Text::new(
+                    TextFragment::new(latency_text(
+                        &self.latency,
+                        ctx.time.delta(),
+                        ctx.time.fps(),
+                        self.vsync,
+                    ))
+                    .scale(18.0)
+                    .color(self.theme.highlight),
+                );
+                canvas.draw(&results, DrawParam::default().dest([50.0, 150.0]));
+            }
+            Scene::Spectating => {
+                // Until the other game starts a match there is nothing to show
+                if let Some(watching) = self.watching.as_ref().filter(|w| w.latest.is_none()) {
+                    let waiting = Text::new(
+                        TextFragment::new(format!("Waiting for a match at {}", watching.address))
+                            .scale(16.0)
+                            .color(self.theme.text_dim),
+                    );
+                    let dims = waiting.measure(ctx)?;
+                    canvas.draw(
+                        &waiting,
+                        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 100.0]),
+                    );
+                }
+
+                // A saved match holds its last moment once it has played out
+                if self
+                    .watching
+                    .as_ref()
+                    .is_some_and(SpectatorClient::finished)
+                {
+                    let ended = Text::new(
+                        TextFragment::new("End of the saved match")
+                            .scale(16.0)
+                            .color(self.theme.highlight),
+                    );
+                    let dims = ended.measure(ctx)?;
+                    canvas.draw(
+                        &ended,
+                        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 100.0]),
+                    );
+                }
+
+                // The other game's player has paused; show their pause clock
+                let paused = self
+                    .watching
+                    .as_ref()
+                    .and_then(|w| w.latest.as_ref()?.paused);
+                if let Some(left) = paused {
+                    let clock = Text::new(
+                        TextFragment::new(format!("Paused - {}", pause_clock(left)))
+                            .scale(16.0)
+                            .color(self.theme.highlight),
+                    );
+                    let dims = clock.measure(ctx)?;
+                    canvas.draw(
+                        &clock,
+                        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 100.0]),
+                    );
+                }
+            }
+            Scene::Playing => {
+                // Until a bot connects, say where it should connect to (and
+                // where spectators can watch); otherwise, how many are watching
+                let spectators = self.spectators.as_ref();
+                let waiting_bot = match (self.sim.mode, &self.bot) {
+                    (GameMode::Bot, Some(bot)) if !bot.connected() => Some(bot),
+                    _ => None,
+                };
+                if let Some(bot) = waiting_bot {
+                    let address = bot
+                        .address()
+                        .map_or(BOT_SERVER_ADDRESS.to_string(), |address| {
+                            address.to_string()
+                        });
+                    let mut message = format!("Waiting for a bot on {}", address);
+                    if let Some(Ok(address)) = spectators.map(SpectatorServer::address) {
+                        message.push_str(&format!("\nSpectators can watch on {}", address));
+                    }
+                    let waiting = Text::new(
+                        TextFragment::new(message)
+                            .scale(16.0)
+                            .color(self.theme.text_dim),
+                    );
+                    let dims = waiting.measure(ctx)?;
+                    canvas.draw(
+                        &waiting,
+                        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 100.0]),
+                    );
+                } else if let Some(count) = spectators.map(SpectatorServer::count) {
+                    if count > 0 {
+                        let watching = Text::new(
+                            TextFragment::new(format!("{} watching", count))
+                                .scale(14.0)
+                                .color(self.theme.text_dim),
+                        );
+                        canvas.draw(&watching, DrawParam::default().dest([10.0, 8.0]));
+                    }
+                }
+            }
+        }
+
+        // A win streak milestone announced as the match starts
+        if let (Some((banner, _)), Scene::Playing | Scene::Paused) =
+            (&self.streak_banner, self.scene)
+        {
+            let banner = Text::new(
+                TextFragment::new(banner.as_str())
+                    .scale(24.0)
+                    .color(self.theme.highlight),
+            );
+            let dims = banner.measure(ctx)?;
+            canvas.draw(
+                &banner,
+                DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 140.0]),
+            );
+        }
 
+        // Waiting for a second press before restarting the game
+        if self.restart_prompt.is_some() && matches!(self.scene, Scene::Playing | Scene::Paused) {
+            let prompt = Text::new(
+                TextFragment::new("Restart this game from 0-0? Press G again")
+                    .scale(18.0)
+                    .color(self.theme.highlight),
+            );
+            let dims = prompt.measure(ctx)?;
             canvas.draw(
-                &start_game_text,
+                &prompt,
                 DrawParam::default().dest([
                     SCREEN_WIDTH / 2.0 - dims.x / 2.0,
-                    SCREEN_HEIGHT / 2.0 - dims.y / 0.5,
+                    SCREEN_HEIGHT - dims.y - 50.0,
                 ]),
             );
         }
 
-        // Display game instructions (dynamic based on game state)
-        let instructions = if !self.game_over {
-            "Press S to Start, R to Reset, E to Exit"
-        } else {
-            "Press R to Restart, E to Exit"
+        // Display game instructions (dynamic based on the current screen)
+        let instructions = match self.scene {
+            Scene::Menu => "Up/Down to Choose, Enter to Select, T to Change Theme, E to Exit",
+            Scene::Settings => "Up/Down to Choose, Left/Right or Enter to Change, Esc to Go Back",
+            Scene::Playing if self.sim.launcher.recording.is_some() => {
+                "Recording a Drill: Your Returns Become Its Shots - K to Stop and Name It"
+            }
+            Scene::Playing if self.sim.mode == GameMode::Training => {
+                "Press P to Pause, K to Record a Drill, R to Restart, Esc to Quit to Menu, \
+                 E to Exit"
+            }
+            Scene::Playing => {
+                "Press P to Pause, R to Restart, G to Restart Game, Esc to Quit to Menu, E to Exit"
+            }
+            Scene::Paused if self.sim.mode == GameMode::Training => {
+                "Paused - P to Resume, K to Record a Drill, R to Restart, G to Restart Game, \
+                 Esc to Quit to Menu, E to Exit"
+            }
+            Scene::Paused if self.sim.mode == GameMode::TwoPlayer => {
+                "Paused - P to Resume, R to Restart, G to Restart Game, F/Q to Resign, \
+                 Esc to Quit to Menu, E to Exit"
+            }
+            Scene::Paused => {
+                "Paused - P to Resume, R to Restart, G to Restart Game, F to Resign, \
+                 Esc to Quit to Menu, E to Exit"
+            }
+            Scene::GameOver if self.tournament.is_some() => {
+                "Press Enter for the Bracket, A for Analysis, R to Leave the Tournament, E to Exit"
+            }
+            Scene::GameOver => {
+                "Press Enter for a Rematch, A for Analysis, R to Return to Menu, E to Exit"
+            }
+            Scene::Analysis => "Esc to Go Back, E to Exit",
+            Scene::EnterCode => "Enter to Play, Backspace to Erase, Esc to Go Back",
+            Scene::Drills => "Up/Down to Choose, Enter to Start Training, Esc to Go Back",
+            Scene::NameDrill => "Enter to Save the Drill, Backspace to Erase, Esc to Throw It Away",
+            Scene::NameProfile => "Enter to Save the Profile, Backspace to Erase, Esc to Go Back",
+            Scene::Bracket if self.tournament.as_ref().is_some_and(Tournament::over) => {
+                "Enter to Return to Menu"
+            }
+            Scene::Bracket if !self.tournament.as_ref().is_some_and(Tournament::started) => {
+                "Left/Right for 4 or 8 Players, Enter to Play, Esc to Go Back"
+            }
+            Scene::Bracket => "Enter to Play the Next Match, Esc to Leave the Tournament",
+            Scene::Photo if self.photo.hide_hud => return Ok(()), // Nothing over the picture
+            Scene::Photo => {
+                "Arrows to Move, +/- to Zoom, L for Filter, H to Hide HUD, Enter to Save Photo, \
+                 Esc to Go Back"
+            }
+            Scene::Spectating
+                if self
+                    .watching
+                    .as_ref()
+                    .is_some_and(SpectatorClient::is_replay) =>
+            {
+                "Playing back a saved match - Esc to Stop Watching, E to Exit"
+            }
+            Scene::Spectating => "Watching a match - Esc to Stop Watching, E to Exit",
+            Scene::StartupError | Scene::WhatsNew => "Enter to Continue, E to Exit",
+            Scene::LatencyTest => "Press Any Key or Button to Flash the Screen, Esc to Go Back",
         };
 
-        let text_fragment = TextFragment::new(instructions)
-            .scale(14.0)
-            .color(Color::WHITE);
-        let instruction_text = Text::new(text_fragment);
-        let dims = instruction_text.measure(ctx)?;
-
         // Position the instructions near the bottom of the screen
-        canvas.draw(
-            &instruction_text,
-            DrawParam::default().dest([
-                SCREEN_WIDTH / 2.0 - dims.x / 2.0,
-                SCREEN_HEIGHT - dims.y - 20.0,
-            ]),
-        );
-
-        Ok(())
+        let line = HudLine::new(instructions, 14.0, self.theme.text).at(Anchor::BottomCenter, 20.0);
+        self.hud.draw(canvas, ctx, "instructions", &line)
     }
 }
 
+// The pause clock in a match, e.g. "Pause time left: 0:42"
+fn pause_clock(seconds_left: f32) -> String {
+    let seconds = seconds_left.ceil() as u32;
+    format!("Pause time left: {}:{:02}", seconds / 60, seconds % 60)
+}
+
 impl EventHandler for GameState {
     // Update game state for each frame
     fn update(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
@@ -304,20 +1623,100 @@ impl EventHandler for GameState {
             return Ok(());
         }
 
+        // Notice when the window gets minimized or hidden (a zero-sized window counts too)
+        let window = ctx.gfx.window();
+        let size = window.inner_size();
+        let hidden = window.is_minimized() == Some(true)
+            || window.is_visible() == Some(false)
+            || size.width == 0
+            || size.height == 0;
+        if hidden != self.hidden {
+            self.set_hidden(hidden);
+        }
+
         let delta = ctx.time.delta().as_secs_f32(); // Time since last frame
+        self.run_bench(delta);
+
+        // Resizing the window needs the context, which the settings screen
+        // can't reach. A shape-lock snap waits here too, until the resize that
+        // caused it has been handled
+        if let Some((width, height)) = self.resize_request.take() {
+            ctx.gfx.set_fullscreen(FullscreenType::Windowed)?;
+            ctx.gfx.set_drawable_size(width, height)?;
+        }
+
+        // The computer players keep rallying behind the main menu
+        if self.runs_demo() && !self.hidden {
+            self.advance_demo(delta);
+        }
+
+        // The held arrows move the photo camera around the frozen court
+        if self.scene == Scene::Photo {
+            let (dx, dy) = self.input.arrow_direction();
+            let step = PHOTO_PAN_SPEED * delta;
+            self.photo.camera.pan(dx * step, dy * step);
+        }
+
+        // A watched match comes from the other game; nothing is simulated here
+        if self.scene == Scene::Spectating {
+            self.follow_watched_match(delta);
+        }
+
+        // With auto-pilot on, the game keeps playing itself like a screensaver
+        if self.scene == Scene::GameOver {
+            self.wait_for_rematch(delta);
+        }
+        self.expire_restart_prompt(delta);
+        if self.scene == Scene::Playing {
+            self.expire_streak_banner(delta);
+        }
+        self.run_pause_clock(delta);
+
+        // Screen shake fades in real time; hit-stop holds the match still for
+        // a moment after a point
+        let holding = self.juice.update(delta);
 
-        // Handle countdown if it is active
-        if let Some(countdown_start) = self.countdown_start {
-            self.handle_countdown(countdown_start, delta);
+        // Holiday snow and fireworks keep moving on every screen
+        if self.shown_particles().is_some() && !self.hidden {
+            if let Some(field) = &mut self.particles {
+                field.update(delta);
+            }
         }
 
-        if self.game_running {
-            self.handle_input(delta); // Process user input
+        // The music and the crowd play only while the match is under way, the
+        // music driving harder the faster the ball has been going
+        if let Some(sounds) = &mut self.sounds {
+            sounds.volume = self.sound_volume as f32 / MAX_SOUND_VOLUME as f32;
+            sounds.mix = self.sound_mix;
+            let playing = self.scene == Scene::Playing && !self.hidden;
+            sounds.set_music(ctx, playing, self.music.level())?;
+            sounds.set_crowd(ctx, playing)?;
+        }
 
-            if self.countdown_start.is_none() {
-                self.ball.update(delta); // Move the ball
-                self.handle_collisions(); // Check for collisions
-                self.update_ai_paddle(delta); // Update AI paddle movement
+        // Menu clicks play on every screen
+        for cue in self.ui_cues.drain(..) {
+            if let Some(sounds) = &mut self.sounds {
+                sounds.play(ctx, cue)?;
+            }
+        }
+
+        // Only the match itself moves; menus, pauses and replays leave everything still
+        if self.scene != Scene::Playing
+            || self.run_resume_countdown(delta)
+            || holding
+            || self.run_replay(delta)
+        {
+            return Ok(());
+        }
+
+        // Step the physics at a fixed rate so it behaves the same at any frame rate
+        self.advance(delta);
+        self.music.update(delta, self.sim.ball.speed());
+
+        // Play the sound cues that came due during those steps
+        for cue in self.sim.audio.take_ready() {
+            if let Some(sounds) = &mut self.sounds {
+                sounds.play(ctx, cue)?;
             }
         }
 
@@ -326,70 +1725,242 @@ impl EventHandler for GameState {
 
     // Draw the game state on the screen
     fn draw(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
-        let mut canvas = Canvas::from_frame(ctx, Color::from_rgb(30, 30, 30)); // Clear the screen with a dark background
-        let mut renderer = GameRenderer::new(ctx); // Initialize the renderer
-        renderer.render(&mut canvas, self)?; // Render the game objects
-        self.draw_instructions(&mut canvas, ctx)?; // Draw game instructions
-        canvas.finish(ctx)?; // Display the frame
+        // Skip drawing entirely while the window can't be seen
+        if !self.hidden {
+            // Clear the screen with the theme's background
+            let mut canvas = Canvas::from_frame(ctx, self.theme.background);
+
+            // Draw in play-area coordinates and let ggez scale them to the window.
+            // The HUD is drawn a little smaller when there's a TV safe area
+            let frame = letterbox(self.window_size.0, self.window_size.1);
+            let hud = safe_area(frame, self.safe_margin);
+            canvas.set_screen_coordinates(frame);
+
+            // Holiday snow or fireworks go behind everything else
+            GameRenderer::new(ctx, &self.meshes).render_particles(&mut canvas, self)?;
+
+            // The main menu sits on top of the demo match, the other menus, the
+            // analysis and the startup screens are drawn on their own, and
+            // everything else shows the court (so do settings opened mid-match)
+            if self.shows_demo() {
+                let mut renderer = GameRenderer::new(ctx, &self.meshes);
+                renderer.render_demo(&mut canvas, &self.demo, &self.theme, !self.low_power)?;
+            } else if self.scene == Scene::Photo {
+                self.draw_photo(&mut canvas, ctx, frame, hud)?;
+            } else if !matches!(
+                self.scene,
+                Scene::Menu
+                    | Scene::Settings
+                    | Scene::Drills
+                    | Scene::Analysis
+                    | Scene::StartupError
+                    | Scene::WhatsNew
+                    | Scene::EnterCode
+                    | Scene::LatencyTest
+                    | Scene::NameProfile
+                    | Scene::Bracket
+            ) || self.settings_in_match
+            {
+                let mut renderer = GameRenderer::new(ctx, &self.meshes); // Initialize the renderer
+                renderer.render(&mut canvas, self, hud)?; // Render the game objects
+            }
+
+            // Menus and messages stay inside the safe area too
+            canvas.set_screen_coordinates(hud);
+            self.draw_instructions(&mut canvas, ctx)?; // Draw game instructions
+
+            // Debug numbers on top of everything, for the demo behind the menu
+            // or the current match
+            if self.debug_overlay {
+                let sim = if self.shows_demo() {
+                    &self.demo
+                } else {
+                    &self.sim
+                };
+                draw_debug_overlay(&mut canvas, ctx, sim, &self.theme)?;
+            }
+            canvas.finish(ctx)?; // Display the frame
+
+            // A flash for a key press on the latency test has now reached the screen
+            if self.scene == Scene::LatencyTest {
+                self.latency.presented(Instant::now());
+            }
+
+            // A photo asked for in photo mode is drawn again, larger, and saved
+            if self.photo.save_requested {
+                self.photo.save_requested = false;
+                self.save_photo(ctx)?;
+            }
+        }
+
+        // In low-power mode or while hidden, wait out the rest of the frame
+        // instead of drawing again right away
+        if let Some(frame_time) = self.frame_cap() {
+            let spent = self.last_frame.elapsed();
+            if spent < frame_time {
+                std::thread::sleep(frame_time - spent);
+            }
+        }
+        self.last_frame = Instant::now();
         Ok(())
     }
 
     // Handle key press events
     fn key_down_event(
         &mut self,
-        _ctx: &mut ggez::Context,
+        ctx: &mut ggez::Context,
         input: KeyInput,
-        _repeat: bool,
+        repeat: bool,
     ) -> ggez::GameResult {
         if let Some(keycode) = input.keycode {
-            match keycode {
-                KeyCode::S => {
-                    // Start the game when 'S' is pressed
-                    if !self.game_running && !self.game_over {
-                        self.game_running = true;
-                        self.countdown_start = Some(Instant::now());
+            // Letters are typed into the code rather than acting as shortcuts
+            // (the characters themselves arrive in text_input_event)
+            if self.scene == Scene::EnterCode {
+                match keycode {
+                    KeyCode::Back => {
+                        self.code_entry.pop();
                     }
+                    KeyCode::Return => self.replay_match_code(),
+                    KeyCode::Escape => self.scene = Scene::Menu,
+                    _ => {}
                 }
-                KeyCode::E => {
-                    // Exit the game when 'E' is pressed
-                    self.should_exit = true;
+                return Ok(());
+            }
+            if self.scene == Scene::NameDrill {
+                match keycode {
+                    KeyCode::Back => {
+                        self.drill_name.pop();
+                    }
+                    KeyCode::Return => self.save_drill(),
+                    KeyCode::Escape => self.discard_drill(),
+                    _ => {}
                 }
-                KeyCode::R => {
-                    // Reset the game when 'R' is pressed
-                    self.score.reset();
-                    self.game_running = false;
-                    self.game_over = false;
-                    self.winner = None;
-
-                    // Reset ball and paddle positions
-                    self.ball.x = SCREEN_WIDTH / 2.0;
-                    self.ball.y = SCREEN_HEIGHT / 2.0;
-                    self.ball.dx = 0.0;
-                    self.ball.dy = 0.0;
-                    self.ball.color = Color::from_rgb(255, 255, 0);
-
-                    let middle_y = (SCREEN_HEIGHT - PADDLE_HEIGHT) / 2.0;
-                    self.player1.y = middle_y;
-                    self.player2.y = middle_y;
-
-                    self.point_scored = false;
-                    self.countdown_start = None;
-                    self.last_winner = None;
+                return Ok(());
+            }
+            if self.scene == Scene::NameProfile {
+                match keycode {
+                    KeyCode::Back => {
+                        self.profile_name.pop();
+                    }
+                    KeyCode::Return => self.save_profile(),
+                    KeyCode::Escape => self.scene = Scene::Settings,
+                    _ => {}
                 }
-                _ => {
-                    // Add any other pressed key to the set of active keys
-                    self.pressed_keys.insert(keycode);
+                return Ok(());
+            }
+            if !repeat {
+                self.time_press(Instant::now());
+            }
+            if let Some(action) = self.input.key_down(keycode) {
+                self.handle_action(action);
+
+                // Switching fullscreen needs the window, which handle_action can't reach
+                if action == Action::ToggleFullscreen {
+                    let mode = if self.fullscreen {
+                        FullscreenType::Desktop
+                    } else {
+                        FullscreenType::Windowed
+                    };
+                    ctx.gfx.set_fullscreen(mode)?;
                 }
             }
         }
         Ok(())
     }
 
+    // Handle typed characters, used for the match code entry
+    fn text_input_event(&mut self, _ctx: &mut ggez::Context, character: char) -> ggez::GameResult {
+        match self.scene {
+            Scene::EnterCode => self.type_code_char(character),
+            Scene::NameDrill => self.type_drill_name_char(character),
+            Scene::NameProfile => self.type_profile_name_char(character),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Save lifetime stats as the game closes. A failed save is reported but
+    // doesn't stop the game from closing
+    fn quit_event(&mut self, _ctx: &mut ggez::Context) -> Result<bool, ggez::GameError> {
+        if let Some(bench) = &self.bench {
+            println!("{}", bench.report());
+        }
+        if let Err(error) = self.save_stats() {
+            eprintln!("Could not save stats: {}", error);
+        }
+        if let Err(error) = self.save_unfinished_match() {
+            eprintln!("Could not save the unfinished match: {}", error);
+        }
+        Ok(false) // false = go ahead and close
+    }
+
+    // Handle the window being resized (by the player or by going fullscreen)
+    fn resize_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        width: f32,
+        height: f32,
+    ) -> ggez::GameResult {
+        self.set_window_size(width, height);
+        self.snap_window_shape();
+        Ok(())
+    }
+
+    // Handle the mouse moving over the window
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        _x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> ggez::GameResult {
+        self.mouse_moved(y);
+        Ok(())
+    }
+
     // Handle key release events
     fn key_up_event(&mut self, _ctx: &mut ggez::Context, input: KeyInput) -> ggez::GameResult {
         if let Some(keycode) = input.keycode {
-            self.pressed_keys.remove(&keycode); // Remove the released key from the set
+            self.input.key_up(keycode); // Remove the released key from the set
         }
         Ok(())
     }
+
+    // Handle controller button presses
+    fn gamepad_button_down_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        btn: Button,
+        id: GamepadId,
+    ) -> ggez::GameResult {
+        self.time_press(Instant::now());
+        if let Some(action) = self.input.button_down(btn, id) {
+            self.handle_action(action);
+        }
+        Ok(())
+    }
+
+    // Handle controller button releases
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        btn: Button,
+        id: GamepadId,
+    ) -> ggez::GameResult {
+        self.input.button_up(btn, id);
+        Ok(())
+    }
+
+    // Handle controller stick movement
+    fn gamepad_axis_event(
+        &mut self,
+        _ctx: &mut ggez::Context,
+        axis: Axis,
+        value: f32,
+        id: GamepadId,
+    ) -> ggez::GameResult {
+        self.input.axis_moved(axis, value, id);
+        Ok(())
+    }
 }
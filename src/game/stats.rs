@@ -0,0 +1,292 @@
+// This file keeps track of match statistics, like who wins their serves and how long rallies last,
+// plus totals across matches that are saved to a file between runs
+
+use crate::game::constants::*;
+use crate::game::data_folder::data_file;
+use crate::game::events::{EventListener, GameEvent};
+use crate::game::rules::MatchEnd;
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Points won by one player, split by who served
+#[derive(Clone, Copy, Default)]
+pub struct PlayerStats {
+    pub points_on_serve: u32,  // Points won when this player served
+    pub points_on_return: u32, // Points won when the other player served
+}
+
+// How one finished point went, for the pace graph after the match
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PaceSample {
+    pub rally: u32,     // Paddle hits in the point
+    pub top_speed: f32, // Fastest the ball went during the point (pixels per second)
+}
+
+// Live statistics for the current match
+#[derive(Default)]
+pub struct MatchStats {
+    pub player1: PlayerStats,      // Your (left player) stats
+    pub player2: PlayerStats,      // AI (right player) stats
+    pub server: Option<u8>,        // Who served the current point (1 or 2)
+    pub current_rally: u32,        // Paddle hits so far in the current point
+    pub total_rally_hits: u32,     // Paddle hits across all finished points
+    pub longest_rally: u32,        // Most paddle hits in a single finished point
+    pub points_played: u32,        // How many points have finished
+    pub streak_player: Option<u8>, // Who won the most recent points in a row
+    pub streak: u32,               // How many points in a row they won
+    pub forfeited_by: Option<u8>,  // Who resigned the match, if anyone
+    pub rally_top_speed: f32,      // Fastest ball speed so far in the current point
+    pub pace: Vec<PaceSample>,     // One sample per finished point, in order
+}
+
+impl MatchStats {
+    // Create empty stats for a fresh match
+    pub fn new() -> Self {
+        MatchStats::default()
+    }
+
+    // Remember who served when a new point begins
+    pub fn start_point(&mut self, server: u8) {
+        self.server = Some(server);
+        self.current_rally = 0;
+        self.rally_top_speed = 0.0;
+    }
+
+    // Count a paddle return in the current rally
+    pub fn record_hit(&mut self) {
+        self.current_rally += 1;
+    }
+
+    // Note the ball's speed after a serve or a return
+    pub fn record_ball_speed(&mut self, speed: f32) {
+        self.rally_top_speed = self.rally_top_speed.max(speed);
+    }
+
+    // Record the end of a point won by `winner`
+    pub fn record_point(&mut self, winner: u8) {
+        let served = self.server == Some(winner);
+        let player = if winner == 1 {
+            &mut self.player1
+        } else {
+            &mut self.player2
+        };
+        if served {
+            player.points_on_serve += 1;
+        } else {
+            player.points_on_return += 1;
+        }
+
+        // Keep the current streak going, or start a new one
+        if self.streak_player == Some(winner) {
+            self.streak += 1;
+        } else {
+            self.streak_player = Some(winner);
+            self.streak = 1;
+        }
+
+        self.pace.push(PaceSample {
+            rally: self.current_rally,
+            top_speed: self.rally_top_speed,
+        });
+        self.rally_top_speed = 0.0;
+
+        self.total_rally_hits += self.current_rally;
+        self.longest_rally = self.longest_rally.max(self.current_rally);
+        self.points_played += 1;
+        self.current_rally = 0;
+        self.server = None;
+    }
+
+    // Record a player resigning; the point in progress is not counted
+    pub fn record_forfeit(&mut self, player: u8) {
+        self.forfeited_by = Some(player);
+        self.current_rally = 0;
+        self.server = None;
+    }
+
+    // Average number of paddle hits per finished point
+    pub fn average_rally_length(&self) -> f32 {
+        if self.points_played == 0 {
+            0.0
+        } else {
+            self.total_rally_hits as f32 / self.points_played as f32
+        }
+    }
+
+    // Average number of paddle hits per point over the last `points` finished points
+    pub fn recent_rally_length(&self, points: usize) -> f32 {
+        let recent = &self.pace[self.pace.len().saturating_sub(points)..];
+        if recent.is_empty() {
+            0.0
+        } else {
+            recent.iter().map(|sample| sample.rally).sum::<u32>() as f32 / recent.len() as f32
+        }
+    }
+
+    // Clear all stats for a new match
+    pub fn reset(&mut self) {
+        *self = MatchStats::default();
+    }
+}
+
+impl EventListener for MatchStats {
+    // Count serves, returns and points as the match announces them
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::Serve { server, speed } => {
+                self.start_point(server);
+                self.record_ball_speed(speed);
+            }
+            GameEvent::PaddleHit { speed, .. } => {
+                self.record_hit();
+                self.record_ball_speed(speed);
+            }
+            GameEvent::PointScored { winner } => self.record_point(winner),
+            GameEvent::GameOver {
+                winner,
+                reason: MatchEnd::Forfeit,
+            } => self.record_forfeit(if winner == 1 { 2 } else { 1 }),
+            _ => {}
+        }
+    }
+}
+
+// One finished match, as kept in the match history
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatchRecord {
+    pub won: Option<bool>, // Whether you won (None if it didn't count as a win or a loss)
+    pub end: MatchEnd,     // How the match came to an end
+    pub points_played: u32, // Points played in the match
+    pub longest_rally: u32, // Most paddle hits in a single point
+}
+
+// Totals added up over many matches
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+// Missing fields (from an older file) start at zero; misspelled ones are an error
+#[serde(default, deny_unknown_fields)]
+pub struct StatTotals {
+    pub wins: u32,                 // Matches you won against the AI
+    pub losses: u32,               // Matches you lost against the AI
+    pub longest_rally: u32,        // Most paddle hits in a single point
+    pub total_points: u32,         // Points played in every match
+    pub history: Vec<MatchRecord>, // Latest matches, oldest first
+}
+
+impl StatTotals {
+    // Add one finished match that ended for `end`. `won` is None for
+    // two-player matches, which count toward rallies and points but not
+    // wins or losses
+    pub fn add_match(&mut self, stats: &MatchStats, won: Option<bool>, end: MatchEnd) {
+        match won {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => {}
+        }
+        self.longest_rally = self.longest_rally.max(stats.longest_rally);
+        self.total_points += stats.points_played;
+
+        // Only the latest matches are kept, so the file doesn't keep growing
+        self.history.push(MatchRecord {
+            won,
+            end,
+            points_played: stats.points_played,
+            longest_rally: stats.longest_rally,
+        });
+        let extra = self.history.len().saturating_sub(MATCH_HISTORY_LENGTH);
+        self.history.drain(..extra);
+    }
+}
+
+// Totals for this run of the game and for every run so far
+#[derive(Default, Debug)]
+pub struct CareerStats {
+    pub session: StatTotals,  // Since the game was started
+    pub lifetime: StatTotals, // Since the stats file was first written
+}
+
+impl CareerStats {
+    // Add one finished match to both the session and lifetime totals
+    pub fn record_match(&mut self, stats: &MatchStats, won: Option<bool>, end: MatchEnd) {
+        self.session.add_match(stats, won, end);
+        self.lifetime.add_match(stats, won, end);
+    }
+
+    // Read lifetime totals from a file. A missing file just means no matches
+    // have been saved yet; a damaged one is an error that says what is wrong
+    // and where, so it isn't quietly replaced with zeros
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::from("{}"),
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        let lifetime = serde_json::from_str(&text)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Ok(CareerStats {
+            session: StatTotals::default(),
+            lifetime,
+        })
+    }
+
+    // Write lifetime totals to a file, creating its folder if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let text = serde_json::to_string_pretty(&self.lifetime)?;
+        fs::write(path, text)
+    }
+}
+
+// Where the stats file lives: the user data folder for this game
+// (None if the system doesn't have one, e.g. no home folder)
+pub fn stats_file() -> Option<PathBuf> {
+    data_file(STATS_FILE_NAME)
+}
+
+// Loading, saving and showing the totals kept across matches
+impl GameState {
+    // Load lifetime totals from a file, and save them back there when the game closes
+    // If the file is damaged, the problem is shown at startup and the file is left
+    // alone (nothing is saved over it) so it can be fixed by hand
+    pub fn use_stats_file(&mut self, path: PathBuf) {
+        match CareerStats::load(&path) {
+            Ok(career) => {
+                self.career = career;
+                self.stats_path = Some(path);
+            }
+            Err(error) => self.report_startup_error(format!(
+                "Could not read your stats. This run's stats won't be saved.\n{}",
+                error
+            )),
+        }
+    }
+
+    // Write lifetime totals to the stats file, if there is one
+    pub fn save_stats(&self) -> io::Result<()> {
+        match &self.stats_path {
+            Some(path) => self.career.save(path),
+            None => Ok(()),
+        }
+    }
+
+    // Session and lifetime totals shown on the game over screen
+    pub fn career_text(&self) -> String {
+        let session = &self.career.session;
+        let lifetime = &self.career.lifetime;
+        format!(
+            "This session: {} won, {} lost\n\
+             All time: {} won, {} lost, longest rally {}, {} points played",
+            session.wins,
+            session.losses,
+            lifetime.wins,
+            lifetime.losses,
+            lifetime.longest_rally,
+            lifetime.total_points
+        )
+    }
+}
@@ -0,0 +1,276 @@
+// This file holds tournament mode: the player works through a bracket of 4 or
+// 8 players, meeting a harder computer opponent each round, until they lose a
+// match or win the final. The furthest round reached in each size of bracket
+// is kept between runs
+
+use crate::game::ai::AiDifficulty;
+use crate::game::scene::Scene;
+use crate::game::simulation::GameMode;
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+
+// How many players are in the bracket
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BracketSize {
+    #[default]
+    Four, // Semifinal and final
+    Eight, // Quarterfinal, semifinal and final
+}
+
+impl BracketSize {
+    // Switch to the other size
+    pub fn next(self) -> Self {
+        match self {
+            BracketSize::Four => BracketSize::Eight,
+            BracketSize::Eight => BracketSize::Four,
+        }
+    }
+
+    // Players in the bracket
+    pub fn players(self) -> u32 {
+        match self {
+            BracketSize::Four => 4,
+            BracketSize::Eight => 8,
+        }
+    }
+
+    // Matches needed to win the tournament
+    pub fn rounds(self) -> usize {
+        match self {
+            BracketSize::Four => 2,
+            BracketSize::Eight => 3,
+        }
+    }
+}
+
+// A computer player met in the bracket
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Opponent {
+    pub name: &'static str,       // Name shown on the bracket screen
+    pub difficulty: AiDifficulty, // How hard they play
+}
+
+// Everyone the player can meet, easiest first. Smaller brackets skip the
+// easiest ones, so the final is always against the hardest opponent
+const OPPONENTS: [Opponent; 3] = [
+    Opponent {
+        name: "Rookie Rita",
+        difficulty: AiDifficulty::Easy,
+    },
+    Opponent {
+        name: "Steady Sam",
+        difficulty: AiDifficulty::Medium,
+    },
+    Opponent {
+        name: "Ace Alex",
+        difficulty: AiDifficulty::Hard,
+    },
+];
+
+// Name of a round, from how many rounds are left including it
+pub fn round_name(rounds_left: usize) -> &'static str {
+    match rounds_left {
+        0 | 1 => "Final",
+        2 => "Semifinal",
+        _ => "Quarterfinal",
+    }
+}
+
+// A tournament being played
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tournament {
+    pub size: BracketSize,             // Players in the bracket
+    pub results: Vec<bool>,            // Whether the player won each round played so far
+    pub home_difficulty: AiDifficulty, // Difficulty picked in the settings, put back afterwards
+}
+
+impl Tournament {
+    // Start a tournament with no matches played
+    pub fn new(size: BracketSize, home_difficulty: AiDifficulty) -> Self {
+        Tournament {
+            size,
+            results: Vec::new(),
+            home_difficulty,
+        }
+    }
+
+    // At least one match has been played
+    pub fn started(&self) -> bool {
+        !self.results.is_empty()
+    }
+
+    // Switch to the other size of bracket (only before the first match)
+    pub fn switch_size(&mut self) {
+        if !self.started() {
+            self.size = self.size.next();
+        }
+    }
+
+    // The opponent in each round, first round first
+    pub fn opponents(&self) -> &'static [Opponent] {
+        &OPPONENTS[OPPONENTS.len() - self.size.rounds()..]
+    }
+
+    // Name of round `index` (0 = the first round)
+    pub fn round_name(&self, index: usize) -> &'static str {
+        round_name(self.size.rounds() - index)
+    }
+
+    // The player lost a match and is out
+    pub fn eliminated(&self) -> bool {
+        self.results.contains(&false)
+    }
+
+    // The player won every round
+    pub fn champion(&self) -> bool {
+        self.results.len() == self.size.rounds() && !self.eliminated()
+    }
+
+    // No more matches to play
+    pub fn over(&self) -> bool {
+        self.eliminated() || self.champion()
+    }
+
+    // Who the player meets next (None once the tournament is over)
+    pub fn next_opponent(&self) -> Option<Opponent> {
+        match self.over() {
+            true => None,
+            false => Some(self.opponents()[self.results.len()]),
+        }
+    }
+
+    // Keep the result of the match just played
+    pub fn record(&mut self, won: bool) {
+        if !self.over() {
+            self.results.push(won);
+        }
+    }
+
+    // Furthest the player has got: 1 for the first round, up to one past
+    // the final for the champion
+    pub fn reached(&self) -> u32 {
+        self.results.iter().filter(|&&won| won).count() as u32 + 1
+    }
+
+    // One line per round for the bracket screen, with its result or which
+    // match is next
+    pub fn lines(&self) -> Vec<String> {
+        self.opponents()
+            .iter()
+            .enumerate()
+            .map(|(index, opponent)| {
+                let result = match self.results.get(index) {
+                    Some(true) => "  -  Won",
+                    Some(false) => "  -  Lost",
+                    None if index == self.results.len() && !self.over() => "  -  Next Match",
+                    None => "",
+                };
+                format!(
+                    "{}: {} ({}){}",
+                    self.round_name(index),
+                    opponent.name,
+                    opponent.difficulty.name(),
+                    result
+                )
+            })
+            .collect()
+    }
+
+    // How the tournament stands, under the bracket
+    pub fn summary(&self) -> String {
+        if self.champion() {
+            "Champion!".to_string()
+        } else if self.eliminated() {
+            format!(
+                "Knocked out in the {}",
+                self.round_name(self.results.len() - 1)
+            )
+        } else {
+            format!("{} Players", self.size.players())
+        }
+    }
+}
+
+// The furthest round reached in each size of bracket, kept between runs
+// (0 = never played, see `Tournament::reached`)
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TournamentRecord {
+    pub four: u32,  // Best in a bracket of 4
+    pub eight: u32, // Best in a bracket of 8
+}
+
+impl TournamentRecord {
+    // Best reached in a bracket of this size
+    pub fn best(&self, size: BracketSize) -> u32 {
+        match size {
+            BracketSize::Four => self.four,
+            BracketSize::Eight => self.eight,
+        }
+    }
+
+    // Keep how far a tournament got, if it beats the best so far. Returns
+    // whether it did
+    pub fn note(&mut self, tournament: &Tournament) -> bool {
+        let best = match tournament.size {
+            BracketSize::Four => &mut self.four,
+            BracketSize::Eight => &mut self.eight,
+        };
+        let reached = tournament.reached();
+        if reached > *best {
+            *best = reached;
+            return true;
+        }
+        false
+    }
+
+    // The best in a bracket of this size, for the bracket screen
+    pub fn describe(&self, size: BracketSize) -> String {
+        let best = self.best(size) as usize;
+        let rounds = size.rounds();
+        if best == 0 {
+            "Best: not played yet".to_string()
+        } else if best > rounds {
+            "Best: Champion".to_string()
+        } else {
+            format!("Best: reached the {}", round_name(rounds + 1 - best))
+        }
+    }
+}
+
+// Playing a tournament from the menu, one match at a time
+impl GameState {
+    // Start a tournament and show its bracket, where the size can be picked
+    pub fn open_tournament(&mut self) {
+        self.tournament = Some(Tournament::new(
+            BracketSize::default(),
+            self.sim.ai_difficulty,
+        ));
+        self.scene = Scene::Bracket;
+    }
+
+    // Play the tournament's next match, against that round's opponent
+    pub fn play_tournament_round(&mut self) {
+        let opponent = match self.tournament.as_ref().and_then(Tournament::next_opponent) {
+            Some(opponent) => opponent,
+            None => return,
+        };
+        self.sim.ai_difficulty = opponent.difficulty;
+        self.start_match(GameMode::VsAi);
+    }
+
+    // Move the tournament on after one of its matches, and keep a new
+    // furthest round reached for next time
+    pub fn record_tournament_match(&mut self, won: bool) {
+        let tournament = match &mut self.tournament {
+            Some(tournament) if self.sim.mode == GameMode::VsAi => tournament,
+            _ => return,
+        };
+        tournament.record(won);
+        if self.preferences.tournament.note(tournament) {
+            if let Err(error) = self.save_preferences() {
+                eprintln!("Could not save preferences: {}", error);
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// This file draws the post-match analysis screen: a big diagram of each paddle
+// with a dot for every place the ball touched it
+
+use crate::game::constants::*;
+use crate::game::recording::MatchRecording;
+use crate::game::simulation::GameMode;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+
+// Draw both paddle diagrams side by side with a short summary under each
+pub fn draw_analysis(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    recording: &MatchRecording,
+    mode: GameMode,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let title = Text::new(
+        TextFragment::new("Match Analysis")
+            .scale(32.0)
+            .color(theme.text),
+    );
+    let dims = title.measure(ctx)?;
+    canvas.draw(
+        &title,
+        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 30.0]),
+    );
+
+    let player2_name = match mode {
+        GameMode::VsAi | GameMode::Demo => "AI",
+        GameMode::TwoPlayer => "Player 2",
+        GameMode::Bot => "Bot",
+        GameMode::Training => "Ball machine",
+    };
+    draw_paddle_diagram(canvas, ctx, recording, 1, "You", SCREEN_WIDTH / 3.0, theme)?;
+    draw_paddle_diagram(
+        canvas,
+        ctx,
+        recording,
+        2,
+        player2_name,
+        SCREEN_WIDTH * 2.0 / 3.0,
+        theme,
+    )?;
+    Ok(())
+}
+
+// Draw one player's paddle, scaled up and centered on `center_x`, with their contacts on it.
+// Dots sit higher or lower by where the ball hit and lean left or right by the angle it
+// left at, so a cluster near either end shows a habit of mishitting
+fn draw_paddle_diagram(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    recording: &MatchRecording,
+    player: u8,
+    name: &str,
+    center_x: f32,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let width = PADDLE_WIDTH * ANALYSIS_PADDLE_SCALE;
+    let height = PADDLE_HEIGHT * ANALYSIS_PADDLE_SCALE;
+    let top = 100.0;
+    let paddle_color = if player == 1 {
+        theme.paddle_left
+    } else {
+        theme.paddle_right
+    };
+
+    // The paddle, with its mishit zones at each end shaded
+    let paddle = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        Rect::new(center_x - width / 2.0, top, width, height),
+        paddle_color,
+    )?;
+    canvas.draw(&paddle, DrawParam::default());
+    let zone = height / 2.0 * (1.0 - MISHIT_OFFSET);
+    for zone_top in [top, top + height - zone] {
+        let shade = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(center_x - width / 2.0, zone_top, width, zone),
+            theme.overlay,
+        )?;
+        canvas.draw(&shade, DrawParam::default());
+    }
+
+    // One dot per contact
+    let dot = Mesh::new_circle(
+        ctx,
+        DrawMode::fill(),
+        [0.0, 0.0],
+        ANALYSIS_DOT_RADIUS,
+        0.1,
+        theme.text,
+    )?;
+    for contact in recording.for_player(player) {
+        let lean = contact.angle_out.to_radians().sin() * width;
+        let color = if contact.is_mishit() {
+            theme.warning
+        } else {
+            theme.text
+        };
+        canvas.draw(
+            &dot,
+            DrawParam::default()
+                .dest([
+                    center_x + lean,
+                    top + height / 2.0 + contact.offset * height / 2.0,
+                ])
+                .color(color),
+        );
+    }
+
+    // Summary under the diagram
+    let contacts = recording.for_player(player).count();
+    let summary = format!(
+        "{}\n{} hits, {} mishits\nAverage ball speed: {:.0}",
+        name,
+        contacts,
+        recording.mishits(player),
+        recording.average_speed(player)
+    );
+    let text = Text::new(TextFragment::new(summary).scale(18.0).color(theme.text));
+    let dims = text.measure(ctx)?;
+    canvas.draw(
+        &text,
+        DrawParam::default().dest([center_x - dims.x / 2.0, top + height + 20.0]),
+    );
+    Ok(())
+}
@@ -0,0 +1,200 @@
+// This file draws the broadcast scoreboard: a bar across the top of the screen
+// like the ones on televised matches, with both players' names and scores, a
+// dot by whoever is serving, and the games won and the hits in the rally
+// under it. It's meant for streaming a match, and B switches between it and
+// the normal score line
+
+use crate::game::constants::*;
+use crate::game::simulation::Simulation;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use serde::{Deserialize, Serialize};
+
+// How the score is shown during a match
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum HudLayout {
+    #[default]
+    Normal, // One line of text at the top
+    Broadcast, // The scoreboard bar
+}
+
+impl HudLayout {
+    // Switch to the other layout
+    pub fn next(self) -> Self {
+        match self {
+            HudLayout::Normal => HudLayout::Broadcast,
+            HudLayout::Broadcast => HudLayout::Normal,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            HudLayout::Normal => "Normal",
+            HudLayout::Broadcast => "Broadcast",
+        }
+    }
+}
+
+// What the scoreboard shows
+#[derive(Clone, PartialEq, Debug)]
+pub struct Scoreboard {
+    pub names: [String; 2],      // Left and right player
+    pub scores: [u32; 2],        // Points in the game being played
+    pub games: Option<[u32; 2]>, // Games won, in a best-of match
+    pub serving: Option<u8>,     // Player 1 or 2 serving (None = still to be picked)
+    pub rally: u32,              // Paddle hits in the rally so far
+    pub flash: Option<u8>,       // Whoever just scored, while their score is highlighted
+}
+
+impl Scoreboard {
+    // Read the scoreboard off a match, with the names to show for each side
+    pub fn new(sim: &Simulation, names: [String; 2]) -> Self {
+        let score = &sim.score;
+        Scoreboard {
+            names,
+            scores: [score.player1, score.player2],
+            games: (sim.rules.best_of > 1).then_some([score.games_player1, score.games_player2]),
+            serving: sim.serving_player(),
+            rally: sim.rally(),
+            flash: match score.flashing() {
+                true => score.flash.borrow().winner,
+                false => None,
+            },
+        }
+    }
+
+    // The small line under the bar: games won and the rally, whichever apply
+    // (None when there's neither)
+    pub fn tab_text(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some([left, right]) = self.games {
+            parts.push(format!("GAMES {} - {}", left, right));
+        }
+        if self.rally > 0 {
+            parts.push(format!("RALLY {}", self.rally));
+        }
+        match parts.is_empty() {
+            true => None,
+            false => Some(parts.join("   |   ")),
+        }
+    }
+}
+
+// Draw the scoreboard bar centered at the top of the screen
+pub fn draw_scoreboard(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    board: &Scoreboard,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let center = SCREEN_WIDTH / 2.0;
+    let left = center - BROADCAST_BAR_WIDTH / 2.0;
+    let top = BROADCAST_BAR_TOP;
+    let height = BROADCAST_BAR_HEIGHT;
+
+    // The bar, with a strip of each paddle's color at its ends
+    let bar = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        Rect::new(left, top, BROADCAST_BAR_WIDTH, height),
+        theme.overlay,
+    )?;
+    canvas.draw(&bar, DrawParam::default());
+    for (x, color) in [
+        (left, theme.paddle_left),
+        (left + BROADCAST_BAR_WIDTH - 6.0, theme.paddle_right),
+    ] {
+        let strip =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), Rect::new(x, top, 6.0, height), color)?;
+        canvas.draw(&strip, DrawParam::default());
+    }
+
+    for side in 0..2 {
+        let player = side as u8 + 1;
+
+        // Score in a box either side of the middle, lit up after a point
+        let box_x = match side {
+            0 => center - BROADCAST_SCORE_WIDTH - 1.0,
+            _ => center + 1.0,
+        };
+        let score_box = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(box_x, top, BROADCAST_SCORE_WIDTH, height),
+            theme.momentum_track,
+        )?;
+        canvas.draw(&score_box, DrawParam::default());
+        let score_color = match board.flash == Some(player) {
+            true => theme.highlight,
+            false => theme.text,
+        };
+        let score = Text::new(
+            TextFragment::new(board.scores[side].to_string())
+                .scale(22.0)
+                .color(score_color),
+        );
+        let dims = score.measure(ctx)?;
+        canvas.draw(
+            &score,
+            DrawParam::default().dest([
+                box_x + (BROADCAST_SCORE_WIDTH - dims.x) / 2.0,
+                top + (height - dims.y) / 2.0,
+            ]),
+        );
+
+        // Name out toward the ends, with the serve dot on its inside
+        let name = Text::new(
+            TextFragment::new(board.names[side].to_uppercase())
+                .scale(16.0)
+                .color(theme.text),
+        );
+        let dims = name.measure(ctx)?;
+        let name_y = top + (height - dims.y) / 2.0;
+        let (name_x, dot_x) = match side {
+            0 => {
+                let x = left + 18.0;
+                (x, x + dims.x + 12.0)
+            }
+            _ => {
+                let x = left + BROADCAST_BAR_WIDTH - 18.0 - dims.x;
+                (x, x - 12.0)
+            }
+        };
+        canvas.draw(&name, DrawParam::default().dest([name_x, name_y]));
+        if board.serving == Some(player) {
+            let dot = Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                [dot_x, top + height / 2.0],
+                4.0,
+                0.1,
+                theme.highlight,
+            )?;
+            canvas.draw(&dot, DrawParam::default());
+        }
+    }
+
+    // Games and rally in a small tab hanging under the middle
+    if let Some(tab) = board.tab_text() {
+        let text = Text::new(TextFragment::new(tab).scale(13.0).color(theme.text_dim));
+        let dims = text.measure(ctx)?;
+        let tab = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(
+                center - dims.x / 2.0 - 10.0,
+                top + height,
+                dims.x + 20.0,
+                dims.y + 6.0,
+            ),
+            theme.overlay,
+        )?;
+        canvas.draw(&tab, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([center - dims.x / 2.0, top + height + 3.0]),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// This file works out how the countdown before a serve looks at any moment:
+// each number starts at its normal size and grows while it fades out over
+// its second, and a short "GO!" flashes the same way once the ball is off.
+// Everything is worked out from game time, so the animation holds still
+// while the game is paused
+
+use crate::game::constants::*;
+
+// What to draw for the countdown at one moment
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CountdownFrame {
+    pub count: usize, // Number showing (3, 2, 1), or 0 for "GO!"
+    pub size: f32,    // Text size, growing as the number goes on
+    pub alpha: f32,   // 1 (solid) to 0 (faded out)
+}
+
+impl CountdownFrame {
+    // Text to draw
+    pub fn label(&self) -> String {
+        match self.count {
+            0 => "GO!".to_string(),
+            count => count.to_string(),
+        }
+    }
+}
+
+// Grow and fade over `progress` (0 at the start, 1 at the end)
+fn animate(count: usize, progress: f32) -> CountdownFrame {
+    let progress = progress.clamp(0.0, 1.0);
+    CountdownFrame {
+        count,
+        size: COUNTDOWN_TEXT_SIZE * (1.0 + (COUNTDOWN_GROWTH - 1.0) * progress),
+        // Stays easy to read at first, then fades away faster
+        alpha: 1.0 - progress * progress,
+    }
+}
+
+// The number `elapsed` seconds into the countdown (None once it's over)
+pub fn countdown_frame(elapsed: f32) -> Option<CountdownFrame> {
+    if !(0.0..COUNTDOWN_DURATION).contains(&elapsed) {
+        return None;
+    }
+    let count = (COUNTDOWN_DURATION - elapsed.floor()) as usize;
+    Some(animate(count, elapsed.fract()))
+}
+
+// The "GO!" flash `since` seconds after the countdown ended (None once it's
+// over, or always with COUNTDOWN_GO_DURATION at 0)
+pub fn go_frame(since: f32) -> Option<CountdownFrame> {
+    if !(0.0..COUNTDOWN_GO_DURATION).contains(&since) {
+        return None;
+    }
+    Some(animate(0, since / COUNTDOWN_GO_DURATION))
+}
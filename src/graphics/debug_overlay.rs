@@ -0,0 +1,89 @@
+// This file draws the debug overlay (toggled with F3): frame rate, timing and
+// physics numbers in the top-left corner, plus a mark where each AI is aiming
+
+use crate::game::constants::*;
+use crate::game::simulation::Simulation;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use std::time::Duration;
+
+// The lines of numbers shown on the overlay
+pub fn debug_text(sim: &Simulation, fps: f64, frame_time: Duration) -> String {
+    let targets: Vec<String> = sim
+        .controllers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, controller)| {
+            controller
+                .target_y()
+                .map(|target| format!("P{} {:.0}", index + 1, target))
+        })
+        .collect();
+    let targets = if targets.is_empty() {
+        "none".to_string()
+    } else {
+        targets.join(", ")
+    };
+    format!(
+        "FPS: {:.0}\n\
+         Frame time: {:.2} ms\n\
+         Ball velocity: ({:.0}, {:.0})  speed {:.0}\n\
+         Ball spin: {:.2}\n\
+         Rally: {} hits\n\
+         AI target: {}\n\
+         Tick {}  state {:08X}",
+        fps,
+        frame_time.as_secs_f64() * 1000.0,
+        sim.ball.dx,
+        sim.ball.dy,
+        sim.ball.speed(),
+        sim.ball.spin,
+        sim.rally(),
+        targets,
+        sim.ticks,
+        sim.checksum()
+    )
+}
+
+// Draw the overlay for a match on top of whatever is on screen
+pub fn draw_debug_overlay(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    sim: &Simulation,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let text = debug_text(sim, ctx.time.fps(), ctx.time.delta());
+    let overlay = Text::new(TextFragment::new(text).scale(14.0).color(theme.warning));
+    let dims = overlay.measure(ctx)?;
+
+    // A dark box behind the numbers so they can be read over the court
+    let backing = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        Rect::new(4.0, 4.0, dims.x + 12.0, dims.y + 12.0),
+        Color::new(0.0, 0.0, 0.0, 0.6),
+    )?;
+    canvas.draw(&backing, DrawParam::default());
+    canvas.draw(&overlay, DrawParam::default().dest([10.0, 10.0]));
+
+    // A short line just in front of each AI paddle at the height it is heading for
+    let [x, y] = sim.court.offset();
+    let paddles = [&sim.player1, &sim.player2];
+    for (paddle, controller) in paddles.iter().zip(sim.controllers.iter()) {
+        if let Some(target) = controller.target_y() {
+            let mark_x = if paddle.x < sim.court.width / 2.0 {
+                paddle.x + PADDLE_WIDTH
+            } else {
+                paddle.x - DEBUG_TARGET_MARK
+            };
+            let mark = Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                Rect::new(x + mark_x, y + target - 1.0, DEBUG_TARGET_MARK, 2.0),
+                theme.warning,
+            )?;
+            canvas.draw(&mark, DrawParam::default());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// This file works out the glow around a fast ball. At serve speed there is
+// none; as a rally speeds the ball up, a soft halo in the ball's color grows
+// around it and gets brighter, gently pulsing in and out. It's worked out from
+// game time, so the pulse holds still while the game is paused
+
+use crate::game::constants::*;
+use crate::graphics::theme::ball_heat;
+use std::f32::consts::TAU;
+
+// How the glow looks at one moment
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GlowFrame {
+    pub radius: f32, // Outer edge of the halo, in pixels
+    pub alpha: f32,  // How strong the halo is at its brightest (0 to 1)
+}
+
+// The glow for a ball moving at `speed`, `time` seconds into the match (None
+// while the ball is too slow to glow)
+pub fn ball_glow(speed: f32, time: f32) -> Option<GlowFrame> {
+    let heat = ball_heat(speed);
+    if heat <= 0.0 {
+        return None;
+    }
+    let pulse = 0.5 + 0.5 * (time * TAU * BALL_GLOW_PULSE_RATE).sin(); // 0 to 1
+    let grow = heat * (1.0 - BALL_GLOW_PULSE_DEPTH * (1.0 - pulse));
+    Some(GlowFrame {
+        radius: BALL_RADIUS * (1.0 + (BALL_GLOW_SIZE - 1.0) * grow),
+        alpha: BALL_GLOW_OPACITY * heat * (1.0 - BALL_GLOW_PULSE_DEPTH * (1.0 - pulse)),
+    })
+}
@@ -0,0 +1,157 @@
+// This file lays out lines of HUD text and keeps each one between frames.
+// Building a Text and measuring it lays out every letter, so a line is only
+// built again when its words or size change; its color is given when it's
+// drawn, so a line fading in and out doesn't count as a change. Each line is
+// placed by an anchor (top center, the middle or bottom center of the play
+// area) and a gap from it, instead of the drawing code working out positions
+
+use crate::game::constants::*;
+use ggez::graphics::{Canvas, Color, DrawParam, Text, TextFragment};
+use ggez::GameResult;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Where a line of text sits on screen, always centered across
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Anchor {
+    TopCenter,    // `gap` pixels down from the top
+    Center,       // In the middle, moved `gap` pixels down
+    BottomCenter, // `gap` pixels up from the bottom
+}
+
+impl Anchor {
+    // Top-left corner for text `size` big (width, height)
+    pub fn place(self, size: [f32; 2], gap: f32) -> [f32; 2] {
+        let x = SCREEN_WIDTH / 2.0 - size[0] / 2.0;
+        let y = match self {
+            Anchor::TopCenter => gap,
+            Anchor::Center => SCREEN_HEIGHT / 2.0 - size[1] / 2.0 + gap,
+            Anchor::BottomCenter => SCREEN_HEIGHT - size[1] - gap,
+        };
+        [x, y]
+    }
+}
+
+// One line of HUD text and where it goes
+#[derive(Clone, PartialEq, Debug)]
+pub struct HudLine {
+    pub text: String,   // Words to show
+    pub size: f32,      // Text size the line is built at
+    pub scale: f32,     // How many times its built size it's drawn (1 = as built)
+    pub color: Color,   // Color it's drawn in
+    pub anchor: Anchor, // Which edge (or the middle) it's placed from
+    pub gap: f32,       // Pixels in from the anchor
+}
+
+impl HudLine {
+    // A line at the top center, drawn at the size it's built
+    pub fn new(text: impl Into<String>, size: f32, color: Color) -> Self {
+        HudLine {
+            text: text.into(),
+            size,
+            scale: 1.0,
+            color,
+            anchor: Anchor::TopCenter,
+            gap: 0.0,
+        }
+    }
+
+    // Place the line `gap` pixels in from `anchor`
+    pub fn at(self, anchor: Anchor, gap: f32) -> Self {
+        HudLine {
+            anchor,
+            gap,
+            ..self
+        }
+    }
+
+    // Draw the line `scale` times the size it's built at, for text that
+    // grows or shrinks without being built again each frame
+    pub fn scaled(self, scale: f32) -> Self {
+        HudLine { scale, ..self }
+    }
+}
+
+// Things kept by slot name, built again only when the key they were built
+// from changes
+#[derive(Debug)]
+pub struct SlotCache<K, T> {
+    slots: HashMap<&'static str, (K, T)>, // Each slot's key and what was built from it
+}
+
+impl<K, T> Default for SlotCache<K, T> {
+    // Start with nothing built
+    fn default() -> Self {
+        SlotCache {
+            slots: HashMap::new(),
+        }
+    }
+}
+
+impl<K: PartialEq, T> SlotCache<K, T> {
+    // What's in `slot`, built with `build` first if the slot is empty or was
+    // built from a different key
+    pub fn get_or_build(
+        &mut self,
+        slot: &'static str,
+        key: K,
+        build: impl FnOnce() -> GameResult<T>,
+    ) -> GameResult<&T> {
+        let fresh = matches!(self.slots.get(slot), Some((kept, _)) if *kept == key);
+        if !fresh {
+            let built = build()?;
+            self.slots.insert(slot, (key, built));
+        }
+        Ok(&self.slots[slot].1)
+    }
+}
+
+// A line's text, built and measured
+struct BuiltLine {
+    text: Text,     // Ready to draw
+    dims: [f32; 2], // Width and height at the size it was built
+}
+
+// The HUD's lines of text, kept between frames by their words and size.
+// Drawing only reads the game state, so they sit behind a RefCell
+#[derive(Default)]
+pub struct HudText {
+    lines: RefCell<SlotCache<(String, f32), BuiltLine>>,
+}
+
+impl HudText {
+    // Start with nothing built
+    pub fn new() -> Self {
+        HudText::default()
+    }
+
+    // Draw `line` in its place, using the text kept in `slot` if the line
+    // hasn't changed since it was built
+    pub fn draw(
+        &self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        slot: &'static str,
+        line: &HudLine,
+    ) -> GameResult {
+        let mut lines = self.lines.borrow_mut();
+        let built = lines.get_or_build(slot, (line.text.clone(), line.size), || {
+            // No color on the text itself, so the one given when drawing is used
+            let text = Text::new(TextFragment::new(line.text.as_str()).scale(line.size));
+            let dims = text.measure(ctx)?;
+            Ok(BuiltLine {
+                text,
+                dims: [dims.x, dims.y],
+            })
+        })?;
+        let shown = [built.dims[0] * line.scale, built.dims[1] * line.scale];
+        canvas.draw(
+            &built.text,
+            DrawParam::default()
+                .dest(line.anchor.place(shown, line.gap))
+                .scale([line.scale, line.scale])
+                .color(line.color),
+        );
+        Ok(())
+    }
+}
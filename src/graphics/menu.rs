@@ -0,0 +1,51 @@
+// This file draws simple list menus (main menu, settings) in the middle of the screen
+
+use crate::game::constants::*;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawParam, Text, TextFragment};
+
+// Draw a title with a list of entries below it, highlighting the selected one
+pub fn draw_menu(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    title: &str,
+    items: &[String],
+    selected: usize,
+    theme: &Theme,
+) -> ggez::GameResult {
+    // Long lists start near the top with smaller, tighter entries so they
+    // still fit above the instructions
+    let compact = items.len() > MENU_COMPACT_ITEMS;
+    let (item_scale, item_gap) = if compact { (18.0, 6.0) } else { (22.0, 12.0) };
+
+    // Title at the top third of the screen
+    let title_text = Text::new(TextFragment::new(title).scale(36.0).color(theme.text));
+    let title_dims = title_text.measure(ctx)?;
+    let mut y = if compact {
+        40.0
+    } else {
+        SCREEN_HEIGHT / 3.0 - title_dims.y
+    };
+    canvas.draw(
+        &title_text,
+        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - title_dims.x / 2.0, y]),
+    );
+    y += title_dims.y + 30.0;
+
+    // One line per entry, the selected one highlighted with a marker
+    for (index, item) in items.iter().enumerate() {
+        let (label, color) = if index == selected {
+            (format!("> {} <", item), theme.menu_selected)
+        } else {
+            (item.clone(), theme.text)
+        };
+        let item_text = Text::new(TextFragment::new(label).scale(item_scale).color(color));
+        let dims = item_text.measure(ctx)?;
+        canvas.draw(
+            &item_text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, y]),
+        );
+        y += dims.y + item_gap;
+    }
+    Ok(())
+}
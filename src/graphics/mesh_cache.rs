@@ -0,0 +1,87 @@
+// This file keeps the paddle and ball shapes between frames. Building a mesh
+// sends new vertices to the graphics card, so instead of doing that for every
+// paddle and ball each frame, each shape is built once and moved into place
+// (and scaled) when it's drawn. A paddle shape is only built again when the
+// paddle changes size, since its rounded corners would stretch if it were
+// scaled
+
+use crate::components::ball::Ball;
+use crate::components::paddle::Paddle;
+use crate::game::constants::*;
+use ggez::graphics::Mesh;
+use ggez::GameResult;
+use std::cell::RefCell;
+
+// How many sizes of shape are kept before the oldest is dropped (two paddles
+// can be different heights, so there's room for both)
+const KEPT_SIZES: usize = 2;
+
+// Shapes kept by their width and height
+#[derive(Clone, Debug)]
+pub struct SizedCache<T> {
+    entries: Vec<([f32; 2], T)>, // Each size and the shape built for it, oldest first
+}
+
+impl<T> Default for SizedCache<T> {
+    // Start with nothing built
+    fn default() -> Self {
+        SizedCache {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> SizedCache<T> {
+    // The shape for this size, built with `build` only if it isn't kept yet
+    pub fn get_or_build(
+        &mut self,
+        size: [f32; 2],
+        build: impl FnOnce() -> GameResult<T>,
+    ) -> GameResult<T> {
+        if let Some((_, shape)) = self.entries.iter().find(|(kept, _)| *kept == size) {
+            return Ok(shape.clone());
+        }
+        let shape = build()?;
+        if self.entries.len() == KEPT_SIZES {
+            self.entries.remove(0);
+        }
+        self.entries.push((size, shape.clone()));
+        Ok(shape)
+    }
+}
+
+// The meshes the renderer reuses. Drawing only reads the game state, so the
+// shapes sit behind a RefCell to be filled in the first time they're needed
+// (a Mesh is cheap to clone, it shares the same vertices)
+#[derive(Default)]
+pub struct MeshCache {
+    paddles: RefCell<SizedCache<Mesh>>, // Paddle shapes, with their top left corner at 0, 0
+    ball: RefCell<Option<Mesh>>,        // A circle of radius 1 around 0, 0
+}
+
+impl MeshCache {
+    // Start with nothing built
+    pub fn new() -> Self {
+        MeshCache::default()
+    }
+
+    // The shape for this paddle's size
+    pub fn paddle(&self, ctx: &mut ggez::Context, paddle: &Paddle) -> GameResult<Mesh> {
+        self.paddles
+            .borrow_mut()
+            .get_or_build([PADDLE_WIDTH, paddle.height], || paddle.get_mesh(ctx))
+    }
+
+    // The ball's circle, to be scaled up to its radius when drawn
+    pub fn ball(&self, ctx: &mut ggez::Context, ball: &Ball) -> GameResult<Mesh> {
+        let mut kept = self.ball.borrow_mut();
+        match &*kept {
+            Some(mesh) => Ok(mesh.clone()),
+            None => {
+                let mesh = ball.get_mesh(ctx)?;
+                *kept = Some(mesh.clone());
+                Ok(mesh)
+            }
+        }
+    }
+}
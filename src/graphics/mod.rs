@@ -1,2 +1,19 @@
 // This file contains the code that draws the game graphics on screen
+pub mod analysis;
+pub mod broadcast;
+pub mod countdown;
+pub mod debug_overlay;
+pub mod glow;
+pub mod hud;
+pub mod menu;
+pub mod mesh_cache;
+pub mod pace;
+pub mod pause_card;
+pub mod photo;
 pub mod renderer;
+pub mod seasonal;
+#[cfg(feature = "stream-hud")]
+pub mod stream_hud;
+pub mod theme;
+pub mod ui;
+pub mod viewport;
@@ -0,0 +1,115 @@
+// This file draws the pace graph on the game over screen: how fast the ball
+// went and how long the rallies were, point by point through the match
+
+use crate::game::constants::*;
+use crate::game::stats::PaceSample;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+
+// Spread values evenly across `area` from left to right, with `max` at the top
+// edge and zero at the bottom. A single value sits in the middle
+pub fn graph_points(values: &[f32], max: f32, area: Rect) -> Vec<[f32; 2]> {
+    let gaps = values.len().saturating_sub(1);
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let x = if gaps == 0 {
+                area.x + area.w / 2.0
+            } else {
+                area.x + area.w * index as f32 / gaps as f32
+            };
+            let height = if max > 0.0 {
+                (value / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            [x, area.y + area.h * (1.0 - height)]
+        })
+        .collect()
+}
+
+// Draw the graph centered under the game over text: top ball speed per point in
+// the ball's color, and hits per rally in the highlight color
+pub fn draw_pace_graph(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    pace: &[PaceSample],
+    theme: &Theme,
+) -> ggez::GameResult {
+    if pace.is_empty() {
+        return Ok(()); // Nothing to show if no points were finished
+    }
+    let area = Rect::new(
+        SCREEN_WIDTH / 2.0 - PACE_GRAPH_WIDTH / 2.0,
+        PACE_GRAPH_TOP,
+        PACE_GRAPH_WIDTH,
+        PACE_GRAPH_HEIGHT,
+    );
+
+    // Baseline along the bottom
+    let axis = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        Rect::new(area.x, area.y + area.h, area.w, 1.0),
+        theme.text_dim,
+    )?;
+    canvas.draw(&axis, DrawParam::default());
+
+    let speeds: Vec<f32> = pace.iter().map(|sample| sample.top_speed).collect();
+    let rallies: Vec<f32> = pace.iter().map(|sample| sample.rally as f32).collect();
+    let longest = rallies.iter().copied().fold(1.0, f32::max);
+    draw_series(
+        canvas,
+        ctx,
+        &graph_points(&speeds, MAX_BALL_SPEED, area),
+        theme.ball,
+    )?;
+    draw_series(
+        canvas,
+        ctx,
+        &graph_points(&rallies, longest, area),
+        theme.highlight,
+    )?;
+
+    // Labels above the graph in each line's color
+    let mut labels = Text::new(
+        TextFragment::new("Ball speed")
+            .scale(14.0)
+            .color(theme.ball),
+    );
+    labels.add(
+        TextFragment::new("   /   ")
+            .scale(14.0)
+            .color(theme.text_dim),
+    );
+    labels.add(
+        TextFragment::new(format!("Rally length (up to {:.0} hits)", longest))
+            .scale(14.0)
+            .color(theme.highlight),
+    );
+    let dims = labels.measure(ctx)?;
+    canvas.draw(
+        &labels,
+        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, area.y - dims.y - 6.0]),
+    );
+    Ok(())
+}
+
+// Draw one line of the graph with a dot on every point
+fn draw_series(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    points: &[[f32; 2]],
+    color: Color,
+) -> ggez::GameResult {
+    if points.len() >= 2 {
+        let line = Mesh::new_line(ctx, points, 2.0, color)?;
+        canvas.draw(&line, DrawParam::default());
+    }
+    let dot = Mesh::new_circle(ctx, DrawMode::fill(), [0.0, 0.0], 3.0, 0.1, color)?;
+    for point in points {
+        canvas.draw(&dot, DrawParam::default().dest(*point));
+    }
+    Ok(())
+}
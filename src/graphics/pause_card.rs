@@ -0,0 +1,162 @@
+// This file draws the card on the pause screen that sums up where the match
+// stands: score, who serves, rally numbers, time played and the rules in play
+
+use crate::game::arena::Arena;
+use crate::game::constants::*;
+use crate::game::court::CourtPreset;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+
+// The lines shown on the card
+pub fn pause_card_text(sim: &Simulation) -> String {
+    let (player1, player2) = match sim.mode {
+        GameMode::VsAi => ("You", "AI"),
+        GameMode::TwoPlayer => ("Player 1", "Player 2"),
+        GameMode::Bot => ("You", "Bot"),
+        GameMode::Demo => ("AI 1", "AI 2"),
+        GameMode::Training => ("You", "Ball machine"),
+    };
+    let match_type = match sim.mode {
+        GameMode::VsAi => format!("Against the AI ({})", sim.ai_difficulty.name()),
+        GameMode::TwoPlayer => "Two players".to_string(),
+        GameMode::Bot => "Against a bot".to_string(),
+        GameMode::Demo => "Demo".to_string(),
+        GameMode::Training => "Training against the ball machine".to_string(),
+    };
+
+    let mut score = format!(
+        "{} {} - {} {}",
+        player1, sim.score.player1, sim.score.player2, player2
+    );
+    if sim.mode == GameMode::Training {
+        score = format!(
+            "{} returned in a row (best {})",
+            sim.launcher.streak, sim.launcher.best_streak
+        );
+    } else if sim.rules.best_of > 1 && !sim.lives_mode {
+        score.push_str(&format!(
+            "  (games {} - {})",
+            sim.score.games_player1, sim.score.games_player2
+        ));
+    }
+    if sim.lives_mode {
+        score.push_str(&format!(
+            "  (lives {} - {})",
+            sim.lives.player1, sim.lives.player2
+        ));
+    }
+    let serve = match sim.serving_player() {
+        Some(1) => player1,
+        Some(_) => player2,
+        None => "Picked at random",
+    };
+
+    let stats = &sim.stats;
+    let seconds = (sim.ticks as f32 * FIXED_TIMESTEP) as u32;
+    let mutators = active_mutators(sim);
+    let mutators = if mutators.is_empty() {
+        "None".to_string()
+    } else {
+        mutators.join(", ")
+    };
+    format!(
+        "{}\n\
+         Score:    {}\n\
+         Serve:    {}\n\
+         Rally:    {} hits now, longest {}, average {:.1}\n\
+         Points:   {} played\n\
+         Time:     {}:{:02}\n\
+         Rules:    {}",
+        match_type,
+        score,
+        serve,
+        sim.rally(),
+        stats.longest_rally,
+        stats.average_rally_length(),
+        stats.points_played,
+        seconds / 60,
+        seconds % 60,
+        mutators
+    )
+}
+
+// Names of the options that change how this match plays, in settings order
+pub fn active_mutators(sim: &Simulation) -> Vec<String> {
+    let mut mutators = Vec::new();
+    let ai_playing = matches!(sim.mode, GameMode::VsAi | GameMode::Demo) || sim.autopilot;
+    if ai_playing && sim.ai_tuning.speed != 0 {
+        mutators.push(format!("AI speed {:+}", sim.ai_tuning.speed));
+    }
+    if ai_playing && sim.ai_tuning.accuracy != 0 {
+        mutators.push(format!("AI accuracy {:+}", sim.ai_tuning.accuracy));
+    }
+    if sim.court_preset != CourtPreset::Classic {
+        mutators.push(format!("{} court", sim.court_preset.name()));
+    }
+    if sim.rules.classic {
+        mutators.push("Classic table tennis".to_string());
+    } else if sim.rules.win_by_two {
+        mutators.push("Win by two".to_string());
+    }
+    if sim.lives_mode {
+        mutators.push("Lives mode".to_string());
+    }
+    if sim.arena != Arena::Standard {
+        mutators.push(format!("{} arena", sim.arena.name()));
+    }
+    if sim.obstacle_mode {
+        mutators.push("Arcade obstacles".to_string());
+    }
+    if sim.end_wall_bounce {
+        mutators.push("End-wall bounce".to_string());
+    }
+    if sim.momentum_physics {
+        mutators.push("Momentum physics".to_string());
+    }
+    if sim.autopilot && sim.mode != GameMode::Demo {
+        mutators.push("Auto-pilot".to_string());
+    }
+    mutators
+}
+
+// Draw the card centered below the "Paused" title
+pub fn draw_pause_card(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    sim: &Simulation,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let card = Text::new(
+        TextFragment::new(pause_card_text(sim))
+            .scale(18.0)
+            .color(theme.text),
+    );
+    let dims = card.measure(ctx)?;
+    let x = SCREEN_WIDTH / 2.0 - dims.x / 2.0;
+
+    // A solid panel with a thin border so the card stands out from the dimmed court
+    let panel = Rect::new(
+        x - PAUSE_CARD_PADDING,
+        PAUSE_CARD_TOP,
+        dims.x + PAUSE_CARD_PADDING * 2.0,
+        dims.y + PAUSE_CARD_PADDING * 2.0,
+    );
+    let backing = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        panel,
+        Color {
+            a: 0.9,
+            ..theme.background
+        },
+    )?;
+    let border = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), panel, theme.text_dim)?;
+    canvas.draw(&backing, DrawParam::default());
+    canvas.draw(&border, DrawParam::default());
+    canvas.draw(
+        &card,
+        DrawParam::default().dest([x, PAUSE_CARD_TOP + PAUSE_CARD_PADDING]),
+    );
+    Ok(())
+}
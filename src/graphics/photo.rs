@@ -0,0 +1,214 @@
+// This file holds photo mode: a free camera that can pan and zoom around the
+// frozen court, color filters laid over the picture, and the file name a
+// saved photo gets. The drawing itself goes through the normal renderer
+
+use crate::game::constants::*;
+use crate::game::scene::Scene;
+use crate::game::simulation::Simulation;
+use crate::game::state::GameState;
+use crate::graphics::renderer::GameRenderer;
+use ggez::graphics::{
+    BlendMode, Canvas, Color, DrawMode, DrawParam, Image, ImageEncodingFormat, ImageFormat, Mesh,
+    Rect,
+};
+
+// Color filters that can be laid over a photo
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PhotoFilter {
+    #[default]
+    None, // The picture as it is
+    Sepia, // Warm, old-photo brown
+    Cool,  // Icy blue
+    Dusk,  // Dim purple evening light
+}
+
+impl PhotoFilter {
+    // Move on to the next filter (wraps back to None)
+    pub fn next(self) -> Self {
+        match self {
+            PhotoFilter::None => PhotoFilter::Sepia,
+            PhotoFilter::Sepia => PhotoFilter::Cool,
+            PhotoFilter::Cool => PhotoFilter::Dusk,
+            PhotoFilter::Dusk => PhotoFilter::None,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            PhotoFilter::None => "None",
+            PhotoFilter::Sepia => "Sepia",
+            PhotoFilter::Cool => "Cool",
+            PhotoFilter::Dusk => "Dusk",
+        }
+    }
+
+    // The color every pixel is multiplied by (None = left alone)
+    pub fn tint(self) -> Option<Color> {
+        match self {
+            PhotoFilter::None => None,
+            PhotoFilter::Sepia => Some(Color::from_rgb(255, 215, 160)),
+            PhotoFilter::Cool => Some(Color::from_rgb(170, 210, 255)),
+            PhotoFilter::Dusk => Some(Color::from_rgb(190, 150, 220)),
+        }
+    }
+}
+
+// Where the photo camera looks: the point in the middle of the picture and
+// how far it is zoomed in (1 = the whole play area)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PhotoCamera {
+    pub x: f32,    // Middle of the picture, across the play area
+    pub y: f32,    // Middle of the picture, down the play area
+    pub zoom: f32, // 1 (everything) to PHOTO_MAX_ZOOM
+}
+
+impl Default for PhotoCamera {
+    // Looking at the whole play area
+    fn default() -> Self {
+        PhotoCamera {
+            x: SCREEN_WIDTH / 2.0,
+            y: SCREEN_HEIGHT / 2.0,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl PhotoCamera {
+    // Slide the camera along. The distance is on screen, so the camera moves
+    // slower across the court the further it is zoomed in
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        self.x += dx / self.zoom;
+        self.y += dy / self.zoom;
+    }
+
+    // Zoom in (positive steps) or out (negative steps)
+    pub fn zoom_by(&mut self, steps: i32) {
+        self.zoom = (self.zoom * PHOTO_ZOOM_STEP.powi(steps)).clamp(1.0, PHOTO_MAX_ZOOM);
+    }
+
+    // The part of `frame` (what's shown when not zoomed) in the picture. The
+    // camera can't look past the edges of the frame, and is kept inside them
+    pub fn view(&mut self, frame: Rect) -> Rect {
+        let width = frame.w / self.zoom;
+        let height = frame.h / self.zoom;
+        self.x = self
+            .x
+            .clamp(frame.x + width / 2.0, frame.x + frame.w - width / 2.0);
+        self.y = self
+            .y
+            .clamp(frame.y + height / 2.0, frame.y + frame.h - height / 2.0);
+        Rect::new(self.x - width / 2.0, self.y - height / 2.0, width, height)
+    }
+}
+
+// Everything photo mode remembers while it's open
+#[derive(Clone, Copy, Default)]
+pub struct PhotoMode {
+    pub camera: PhotoCamera,  // Where the picture is taken from
+    pub filter: PhotoFilter,  // Color laid over the picture
+    pub hide_hud: bool,       // Leave the score and badges out of the picture
+    pub save_requested: bool, // Save a photo on the next frame drawn
+}
+
+impl PhotoMode {
+    // Start again looking at the whole court, with no filter
+    pub fn reset(&mut self) {
+        *self = PhotoMode::default();
+    }
+}
+
+// The name a photo of this moment of the match is saved under, e.g.
+// "rally-3F9C2A11-1234.png" for tick 1234 of the match with that seed
+pub fn photo_file_name(sim: &Simulation) -> String {
+    format!("rally-{:08X}-{}.png", sim.match_seed, sim.ticks)
+}
+
+// Lay a filter's color over everything drawn so far in `area`
+pub fn draw_filter(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    filter: PhotoFilter,
+    area: Rect,
+) -> ggez::GameResult {
+    if let Some(tint) = filter.tint() {
+        let cover = Mesh::new_rectangle(ctx, DrawMode::fill(), area, tint)?;
+        canvas.set_blend_mode(BlendMode::MULTIPLY);
+        canvas.draw(&cover, DrawParam::default());
+        canvas.set_blend_mode(BlendMode::ALPHA);
+    }
+    Ok(())
+}
+
+// Opening photo mode from the pause screen, and saving what it frames
+impl GameState {
+    // Frame the paused match for a photo, starting from the whole court
+    pub fn open_photo_mode(&mut self) {
+        if self.scene == Scene::Paused {
+            self.photo.reset();
+            self.status = None;
+            self.scene = Scene::Photo;
+        }
+    }
+
+    // Go back to the pause screen from photo mode
+    pub fn leave_photo_mode(&mut self) {
+        if self.scene == Scene::Photo {
+            self.status = None;
+            self.scene = Scene::Paused;
+        }
+    }
+
+    // Save a picture of the match as photo mode frames it, at
+    // PHOTO_EXPORT_SCALE times the play area's size. Photos go in ggez's user
+    // data folder, and where one went (or why it couldn't) is shown on screen
+    pub fn save_photo(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
+        let image = Image::new_canvas_image(
+            ctx,
+            ImageFormat::Rgba8UnormSrgb,
+            SCREEN_WIDTH as u32 * PHOTO_EXPORT_SCALE,
+            SCREEN_HEIGHT as u32 * PHOTO_EXPORT_SCALE,
+            1,
+        );
+        // A saved photo isn't shown on a TV, so its HUD goes right out to the edges
+        let mut canvas = Canvas::from_image(ctx, image.clone(), self.theme.background);
+        let whole = Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT);
+        self.draw_photo(&mut canvas, ctx, whole, whole)?;
+        canvas.finish(ctx)?;
+
+        let name = photo_file_name(&self.sim);
+        self.status = Some(
+            match image.encode(ctx, ImageEncodingFormat::Png, format!("/{}", name)) {
+                Ok(()) => format!(
+                    "Photo saved to {}",
+                    ctx.fs.user_data_dir().join(&name).display()
+                ),
+                Err(error) => format!("Could not save photo: {}", error),
+            },
+        );
+        Ok(())
+    }
+
+    // Draw the match as photo mode frames it: the court through the camera,
+    // then the filter and the HUD (if it's shown) at their usual size. `frame`
+    // is what the canvas shows when the camera isn't zoomed in, and `hud` the
+    // coordinates the HUD is drawn in (inside the safe area on screen)
+    pub fn draw_photo(
+        &mut self,
+        canvas: &mut Canvas,
+        ctx: &mut ggez::Context,
+        frame: Rect,
+        hud: Rect,
+    ) -> ggez::GameResult {
+        canvas.set_screen_coordinates(self.photo.camera.view(frame));
+        let mut renderer = GameRenderer::new(ctx, &self.meshes);
+        renderer.render_court(canvas, self)?;
+        canvas.set_screen_coordinates(frame);
+        draw_filter(canvas, ctx, self.photo.filter, frame)?;
+        if !self.photo.hide_hud {
+            canvas.set_screen_coordinates(hud);
+            GameRenderer::new(ctx, &self.meshes).render_hud(canvas, self)?;
+        }
+        Ok(())
+    }
+}
@@ -1,121 +1,734 @@
 // Bring in needed functions and types for drawing the game
 use crate::game::constants::*;
+use crate::game::instant_replay::Replay;
+use crate::game::scene::Scene;
+use crate::game::simulation::{GameMode, Simulation};
+use crate::game::spectator::SpectatorClient;
 use crate::game::state::GameState;
-use ggez::graphics::{Canvas, Color, DrawParam, Text, TextFragment};
-use std::time::Instant;
+use crate::graphics::broadcast::{draw_scoreboard, HudLayout};
+use crate::graphics::countdown::{countdown_frame, go_frame, CountdownFrame};
+use crate::graphics::glow::ball_glow;
+use crate::graphics::hud::{Anchor, HudLine};
+use crate::graphics::mesh_cache::MeshCache;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use std::f32::consts::{FRAC_PI_2, TAU};
 
 // This struct handles all the drawing in the game
 pub struct GameRenderer<'a> {
     ctx: &'a mut ggez::Context, // Stores drawing tools
+    meshes: &'a MeshCache,      // Paddle and ball shapes kept between frames
+    camera: [f32; 2],           // Shift for the court, paddles and ball (screen shake)
 }
 
 impl<'a> GameRenderer<'a> {
-    // Create new renderer with drawing tools
-    pub fn new(ctx: &'a mut ggez::Context) -> Self {
-        GameRenderer { ctx }
+    // Create new renderer with drawing tools and the shapes kept so far
+    pub fn new(ctx: &'a mut ggez::Context, meshes: &'a MeshCache) -> Self {
+        GameRenderer {
+            ctx,
+            meshes,
+            camera: [0.0, 0.0],
+        }
+    }
+
+    // Main function that draws everything in the game. The HUD is drawn in
+    // the `hud` screen coordinates, which keep it inside the TV safe area
+    pub fn render(
+        &mut self,
+        canvas: &mut Canvas,
+        state: &GameState,
+        hud: Rect,
+    ) -> ggez::GameResult {
+        self.render_court(canvas, state)?;
+        canvas.set_screen_coordinates(hud);
+        self.render_hud(canvas, state)
+    }
+
+    // Draw the court and everything on it: paddles, blocks and the ball
+    pub fn render_court(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let alpha = state.interpolation();
+        self.camera = state.juice.camera_offset();
+
+        // Draw the table markings under everything else
+        self.draw_court(canvas, &state.sim, &state.theme)?;
+
+        // A point being played back in slow motion is drawn from the replay
+        if let Some(replay) = &state.replay {
+            self.draw_obstacles(canvas, &state.sim, &state.theme)?;
+            return self.draw_replay(canvas, state, replay);
+        }
+
+        // First draw the paddles, and any blocks in obstacle mode
+        self.draw_paddles(canvas, &state.sim, &state.theme, alpha)?;
+        self.draw_obstacles(canvas, &state.sim, &state.theme)?;
+
+        // A watched match is drawn like one being played here
+        let live = state.in_match() || state.scene == Scene::Spectating;
+
+        // Draw ball except during countdown. Low-power mode skips the trail
+        // to save drawing work
+        if !live || state.sim.countdown.is_none() {
+            self.draw_ball(canvas, &state.sim, &state.theme, alpha, !state.low_power)?;
+        }
+        Ok(())
+    }
+
+    // Draw today's holiday snow or fireworks across the play area, if they're
+    // shown. The ball's circle is scaled down for each particle
+    pub fn render_particles(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let field = match state.shown_particles() {
+            Some(field) => field,
+            None => return Ok(()),
+        };
+        let dot = self.meshes.ball(self.ctx, &state.sim.ball)?;
+        for particle in &field.particles {
+            canvas.draw(
+                &dot,
+                DrawParam::default()
+                    .dest([particle.x, particle.y])
+                    .scale([particle.size, particle.size])
+                    .color(field.color_of(particle)),
+            );
+        }
+        Ok(())
     }
 
-    // Main function that draws everything in the game
-    pub fn render(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
-        // First draw the paddles
-        self.draw_paddles(canvas, state)?;
+    // Draw the score, countdown, badges and stats over the court
+    pub fn render_hud(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let live = state.in_match() || state.scene == Scene::Spectating;
+
+        // Draw the score at the top, as a line of text or the broadcast
+        // scoreboard (which also shows the games won and the rally)
+        let training = state.sim.mode == GameMode::Training;
+        let broadcast = state.hud_layout == HudLayout::Broadcast && !training;
+        if broadcast {
+            draw_scoreboard(canvas, self.ctx, &state.scoreboard(), &state.theme)?;
+        } else if !training {
+            state
+                .sim
+                .score
+                .draw(canvas, self.ctx, &state.theme, &state.hud)?;
+        }
 
-        // Draw ball except during countdown
-        if !state.game_running || state.countdown_start.is_none() {
-            self.draw_ball(canvas, state)?;
+        // Show remaining lives under the score in lives mode, the games won
+        // so far in a best-of match, or the returns in a row in training
+        if training {
+            state
+                .sim
+                .launcher
+                .draw_streak(canvas, self.ctx, &state.theme)?;
+        } else if state.sim.lives_mode {
+            state.sim.lives.draw(canvas, self.ctx, &state.theme)?;
+        } else if state.sim.rules.best_of > 1 && !broadcast {
+            self.draw_games(canvas, state)?;
         }
 
-        // Draw the score at the top
-        state.score.draw(canvas, self.ctx)?;
+        // Show who has been winning recent points under the scoreboard, and
+        // the hits in the rally under that
+        if state.in_match() && !training {
+            self.draw_momentum_bar(canvas, state)?;
+            if !broadcast {
+                self.draw_rally_counter(canvas, state)?;
+            }
+        }
 
-        // Show countdown if game is running
-        if state.game_running {
-            if let Some(countdown_start) = state.countdown_start {
-                self.draw_countdown(canvas, countdown_start)?;
+        // Show countdown if game is running (before a serve or after
+        // unpausing), then "GO!" for a moment as play starts
+        if live && state.replay.is_none() {
+            if let Some(elapsed) = state.sim.countdown.or(state.resume_countdown) {
+                self.draw_countdown(canvas, state, elapsed)?;
+            } else if let Some(frame) = state.sim.go_flash.and_then(go_frame) {
+                self.draw_countdown_text(canvas, frame, state)?;
             }
         }
 
+        // The coach's tip after a lost point, for a player who turned tips on
+        // (not while the computer has their paddle)
+        if state.coach_tips && state.in_match() && !state.sim.autopilot {
+            if let Some(tip) = state.sim.coach.tip {
+                self.draw_coach_tip(canvas, tip, &state.theme)?;
+            }
+        }
+
+        // Remind the player that low-power mode or the auto-pilot is on, or
+        // that the match is someone else's, one badge under another
+        let mut badges = Vec::new();
+        if state.low_power {
+            badges.push("LOW POWER".to_string());
+        }
+        if state.sim.autopilot && state.sim.mode != GameMode::Demo {
+            badges.push("AUTO-PILOT".to_string());
+        }
+        if state.scene == Scene::Spectating {
+            let replay = state
+                .watching
+                .as_ref()
+                .is_some_and(SpectatorClient::is_replay);
+            badges.push(if replay { "REPLAY" } else { "SPECTATING" }.to_string());
+        }
+        if state.replay.is_some() {
+            badges.push("INSTANT REPLAY".to_string());
+        }
+        if let Some(shots) = &state.sim.launcher.recording {
+            badges.push(format!("REC {}/{}", shots.len(), DRILL_MAX_SHOTS));
+        }
+        for (row, badge) in badges.iter().enumerate() {
+            self.draw_badge(canvas, badge, 8.0 + 16.0 * row as f32, &state.theme)?;
+        }
+
+        // Show match stats while Tab (or the controller stats button) is held,
+        // during the match or after it ends
+        if (state.in_match() || state.scene == Scene::GameOver) && state.input.stats_held() {
+            self.draw_stats_overlay(canvas, state)?;
+        }
+
         Ok(())
     }
 
+    // Draw the computer players' match behind the main menu, faded so the
+    // menu on top stays easy to read. A demo held still in low-power mode is
+    // drawn without the ball's trail, which would hang in the air
+    pub fn render_demo(
+        &mut self,
+        canvas: &mut Canvas,
+        sim: &Simulation,
+        theme: &Theme,
+        show_trail: bool,
+    ) -> ggez::GameResult {
+        self.draw_court(canvas, sim, theme)?;
+        self.draw_paddles(canvas, sim, theme, 1.0)?;
+        self.draw_obstacles(canvas, sim, theme)?;
+        if sim.countdown.is_none() {
+            self.draw_ball(canvas, sim, theme, 1.0, show_trail)?;
+        }
+
+        let veil = Mesh::new_rectangle(
+            self.ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT),
+            Color {
+                a: DEMO_VEIL_OPACITY,
+                ..theme.background
+            },
+        )?;
+        canvas.draw(&veil, DrawParam::default());
+        Ok(())
+    }
+
+    // Where the court's top-left corner is drawn, moved by any screen shake
+    fn court_origin(&self, sim: &Simulation) -> [f32; 2] {
+        let [x, y] = sim.court.offset();
+        [x + self.camera[0], y + self.camera[1]]
+    }
+
     // Draw both player paddles - green for left, blue for right
-    fn draw_paddles(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+    fn draw_paddles(
+        &mut self,
+        canvas: &mut Canvas,
+        sim: &Simulation,
+        theme: &Theme,
+        alpha: f32, // How far between the last two steps to draw (0 to 1)
+    ) -> ggez::GameResult {
+        let [x, y] = self.court_origin(sim);
+
         // Create and draw left paddle in green
-        let paddle1_mesh = state.player1.get_mesh(self.ctx)?;
+        let paddle1_mesh = self.meshes.paddle(self.ctx, &sim.player1)?;
+        let shift1 = blend_shift(sim.prev_paddle_y.0, sim.player1.y, alpha);
         canvas.draw(
             &paddle1_mesh,
-            DrawParam::default().color(Color::from_rgb(0, 255, 0)),
+            DrawParam::default()
+                .dest([x + sim.player1.x, y + sim.player1.y + shift1])
+                .color(theme.paddle_left),
         );
 
+        // In training the ball machine stands in for the right paddle
+        if sim.mode == GameMode::Training {
+            let machine = sim.launcher.get_mesh(self.ctx, &sim.court)?;
+            canvas.draw(
+                &machine,
+                DrawParam::default().dest([x, y]).color(theme.paddle_right),
+            );
+            return Ok(());
+        }
+
         // Create and draw right paddle in blue
-        let paddle2_mesh = state.player2.get_mesh(self.ctx)?;
+        let paddle2_mesh = self.meshes.paddle(self.ctx, &sim.player2)?;
+        let shift2 = blend_shift(sim.prev_paddle_y.1, sim.player2.y, alpha);
         canvas.draw(
             &paddle2_mesh,
-            DrawParam::default().color(Color::from_rgb(0, 0, 255)),
+            DrawParam::default()
+                .dest([x + sim.player2.x, y + sim.player2.y + shift2])
+                .color(theme.paddle_right),
         );
 
         Ok(())
     }
 
-    // Draw the ball in yellow
-    fn draw_ball(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
-        let ball_mesh = state.ball.get_mesh(self.ctx)?;
-        canvas.draw(&ball_mesh, DrawParam::default().color(Color::YELLOW));
+    // Draw the blocks in the middle of the court in obstacle mode, in the
+    // court line color so they read as part of the table
+    fn draw_obstacles(
+        &mut self,
+        canvas: &mut Canvas,
+        sim: &Simulation,
+        theme: &Theme,
+    ) -> ggez::GameResult {
+        let [x, y] = self.court_origin(sim);
+        for obstacle in &sim.obstacles {
+            let mesh = obstacle.get_mesh(self.ctx)?;
+            canvas.draw(
+                &mesh,
+                DrawParam::default().dest([x, y]).color(theme.court_lines),
+            );
+        }
         Ok(())
     }
 
-    // Draw countdown numbers in different colors
-    fn draw_countdown(
+    // Draw the ball in yellow, with a fading trail behind it and a glow
+    // around it once it's going fast
+    fn draw_ball(
         &mut self,
         canvas: &mut Canvas,
-        countdown_start: Instant,
+        sim: &Simulation,
+        theme: &Theme,
+        alpha: f32,    // How far between the last two steps to draw (0 to 1)
+        effects: bool, // Whether to draw the fading trail and the glow
     ) -> ggez::GameResult {
-        // Get time passed since countdown started
-        let elapsed = countdown_start.elapsed().as_secs_f32();
-
-        // Show different numbers based on time:
-        // 0-1 sec: show 3
-        // 1-2 sec: show 2
-        // 2-3 sec: show 1
-        let count = if elapsed < 1.0 {
-            3
-        } else if elapsed < 2.0 {
-            2
-        } else if elapsed < 3.0 {
-            1
-        } else {
-            0
+        let [x, y] = self.court_origin(sim);
+
+        // The ball warms from its normal color towards red as it speeds up
+        let ball_color = theme.ball_color(sim.ball.speed());
+
+        // The trail is empty when the ball is still
+        let ball_mesh = self.meshes.ball(self.ctx, &sim.ball)?;
+        if effects {
+            Self::draw_trail(
+                canvas,
+                &ball_mesh,
+                [x, y],
+                sim.ball.trail.iter().copied(),
+                ball_color,
+            );
+        }
+        let shift_x = blend_shift(sim.prev_ball.0, sim.ball.x, alpha);
+        let shift_y = blend_shift(sim.prev_ball.1, sim.ball.y, alpha);
+
+        let center = [x + sim.ball.x + shift_x, y + sim.ball.y + shift_y];
+
+        // A fast ball glows, in circles stacked from the outer edge inward so
+        // the halo is brightest near the ball (not once the match is over)
+        let time = sim.ticks as f32 * FIXED_TIMESTEP;
+        let glow = match effects && sim.winner.is_none() {
+            true => ball_glow(sim.ball.speed(), time),
+            false => None,
         };
+        if let Some(glow) = glow {
+            for layer in 0..BALL_GLOW_LAYERS {
+                let reach = 1.0 - layer as f32 / BALL_GLOW_LAYERS as f32; // 1 = outer edge
+                let radius = BALL_RADIUS + (glow.radius - BALL_RADIUS) * reach;
+                let mut color = ball_color;
+                color.a = glow.alpha / BALL_GLOW_LAYERS as f32;
+                canvas.draw(
+                    &ball_mesh,
+                    DrawParam::default()
+                        .dest(center)
+                        .scale([radius, radius])
+                        .color(color),
+                );
+            }
+        }
+
+        // Dim the ball once the match is over
+        let mut color = ball_color;
+        if sim.winner.is_some() {
+            color.a = 0.15;
+        }
 
-        // Only show numbers 3,2,1
-        if count > 0 {
-            // Pick color for current number:
-            // 3 = red, 2 = yellow, 1 = green
-            let color = match count {
-                3 => Color::RED,
-                2 => Color::YELLOW,
-                1 => Color::GREEN,
-                _ => Color::WHITE,
+        canvas.draw(
+            &ball_mesh,
+            DrawParam::default()
+                .dest(center)
+                .scale([BALL_RADIUS, BALL_RADIUS])
+                .color(color),
+        );
+        Ok(())
+    }
+
+    // Draw the ball's fading trail through `trail` (oldest first), with older
+    // spots fainter and smaller
+    fn draw_trail(
+        canvas: &mut Canvas,
+        ball_mesh: &Mesh,
+        origin: [f32; 2], // Where the court's top-left corner is drawn
+        trail: impl ExactSizeIterator<Item = (f32, f32)>,
+        ball_color: Color,
+    ) {
+        let length = trail.len();
+        for (index, (trail_x, trail_y)) in trail.enumerate() {
+            let age = (index + 1) as f32 / length as f32; // 1 = newest
+            let mut color = ball_color;
+            color.a = BALL_TRAIL_OPACITY * age;
+            canvas.draw(
+                ball_mesh,
+                DrawParam::default()
+                    .dest([origin[0] + trail_x, origin[1] + trail_y])
+                    .scale([BALL_RADIUS * age, BALL_RADIUS * age])
+                    .color(color),
+            );
+        }
+    }
+
+    // Draw the paddles and ball where they were at this moment of the replay,
+    // with the ball's trail from the steps just before (not in low-power mode)
+    fn draw_replay(
+        &mut self,
+        canvas: &mut Canvas,
+        state: &GameState,
+        replay: &Replay,
+    ) -> ggez::GameResult {
+        let (sim, theme) = (&state.sim, &state.theme);
+        let [x, y] = self.court_origin(sim);
+        let frame = replay.frame();
+        for (paddle, top, color) in [
+            (&sim.player1, frame.paddles[0], theme.paddle_left),
+            (&sim.player2, frame.paddles[1], theme.paddle_right),
+        ] {
+            let mesh = self.meshes.paddle(self.ctx, paddle)?;
+            canvas.draw(
+                &mesh,
+                DrawParam::default()
+                    .dest([x + paddle.x, y + top])
+                    .color(color),
+            );
+        }
+
+        let ball_color = theme.ball_color(frame.ball_speed);
+        let ball_mesh = self.meshes.ball(self.ctx, &sim.ball)?;
+        if !state.low_power {
+            Self::draw_trail(
+                canvas,
+                &ball_mesh,
+                [x, y],
+                replay.trail().into_iter(),
+                ball_color,
+            );
+        }
+        canvas.draw(
+            &ball_mesh,
+            DrawParam::default()
+                .dest([x + frame.ball[0], y + frame.ball[1]])
+                .scale([BALL_RADIUS, BALL_RADIUS])
+                .color(ball_color),
+        );
+        Ok(())
+    }
+
+    // Draw a small warning badge like "LOW POWER" at the right edge, `top` pixels down
+    fn draw_badge(
+        &mut self,
+        canvas: &mut Canvas,
+        label: &str,
+        top: f32,
+        theme: &Theme,
+    ) -> ggez::GameResult {
+        let badge = Text::new(TextFragment::new(label).scale(12.0).color(theme.warning));
+        let dims = badge.measure(self.ctx)?;
+        canvas.draw(
+            &badge,
+            DrawParam::default().dest([SCREEN_WIDTH - dims.x - 10.0, top]),
+        );
+        Ok(())
+    }
+
+    // Draw the match score (games won) under the points
+    fn draw_games(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let games_text = Text::new(
+            TextFragment::new(format!(
+                "Games: {} - {}  (best of {})",
+                state.sim.score.games_player1,
+                state.sim.score.games_player2,
+                state.sim.rules.best_of
+            ))
+            .scale(14.0)
+            .color(state.theme.text_dim),
+        );
+        let dims = games_text.measure(self.ctx)?;
+        canvas.draw(
+            &games_text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 45.0]),
+        );
+        Ok(())
+    }
+
+    // Draw a coach's tip across the court, under the countdown
+    fn draw_coach_tip(
+        &mut self,
+        canvas: &mut Canvas,
+        tip: &str,
+        theme: &Theme,
+    ) -> ggez::GameResult {
+        let tip_text = Text::new(TextFragment::new(tip).scale(16.0).color(theme.highlight));
+        let dims = tip_text.measure(self.ctx)?;
+        canvas.draw(
+            &tip_text,
+            DrawParam::default().dest([
+                SCREEN_WIDTH / 2.0 - dims.x / 2.0,
+                SCREEN_HEIGHT / 2.0 + COUNTDOWN_RING_RADIUS + 40.0,
+            ]),
+        );
+        Ok(())
+    }
+
+    // Draw how many paddle hits the current rally has had, small and dim so
+    // it doesn't pull the eye from the ball (nothing before the first hit)
+    fn draw_rally_counter(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let rally = state.sim.rally();
+        if rally == 0 {
+            return Ok(());
+        }
+        let rally_text = Text::new(
+            TextFragment::new(format!("Rally: {}", rally))
+                .scale(14.0)
+                .color(state.theme.text_dim),
+        );
+        let dims = rally_text.measure(self.ctx)?;
+        canvas.draw(
+            &rally_text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 84.0]),
+        );
+        Ok(())
+    }
+
+    // Draw a tug-of-war bar that leans toward whoever has won recent points
+    fn draw_momentum_bar(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let center_x = SCREEN_WIDTH / 2.0;
+        let y = 70.0;
+        let half_width = MOMENTUM_BAR_WIDTH / 2.0;
+
+        // Grey track for the whole bar
+        let track = Mesh::new_rectangle(
+            self.ctx,
+            DrawMode::fill(),
+            Rect::new(
+                center_x - half_width,
+                y,
+                MOMENTUM_BAR_WIDTH,
+                MOMENTUM_BAR_HEIGHT,
+            ),
+            state.theme.momentum_track,
+        )?;
+        canvas.draw(&track, DrawParam::default());
+
+        // Fill from the middle toward the leading side in that player's paddle color
+        let lean = state.momentum_shown * half_width;
+        if lean.abs() >= 1.0 {
+            let (x, color) = if lean < 0.0 {
+                (center_x + lean, state.theme.paddle_left)
+            } else {
+                (center_x, state.theme.paddle_right)
             };
+            let fill = Mesh::new_rectangle(
+                self.ctx,
+                DrawMode::fill(),
+                Rect::new(x, y, lean.abs(), MOMENTUM_BAR_HEIGHT),
+                color,
+            )?;
+            canvas.draw(&fill, DrawParam::default());
+        }
+
+        // Tick marking the middle
+        let tick = Mesh::new_rectangle(
+            self.ctx,
+            DrawMode::fill(),
+            Rect::new(center_x - 1.0, y - 2.0, 2.0, MOMENTUM_BAR_HEIGHT + 4.0),
+            state.theme.text,
+        )?;
+        canvas.draw(&tick, DrawParam::default());
+        Ok(())
+    }
 
-            // Create number text with chosen color
-            let fragment = TextFragment::new(count.to_string())
-                .scale(35.0)
-                .color(color);
+    // Draw the court like a real table: border, dashed center line and center circle
+    fn draw_court(
+        &mut self,
+        canvas: &mut Canvas,
+        sim: &Simulation,
+        theme: &Theme,
+    ) -> ggez::GameResult {
+        let court = &sim.court;
+        let [x, y] = self.court_origin(sim);
+
+        // An arena tints the floor and has court lines of its own color
+        let arena = sim.arena.rules();
+        if let Some([r, g, b, a]) = arena.floor {
+            let floor = Mesh::new_rectangle(
+                self.ctx,
+                DrawMode::fill(),
+                Rect::new(x, y, court.width, court.height),
+                Color::from_rgba(r, g, b, a),
+            )?;
+            canvas.draw(&floor, DrawParam::default());
+        }
+        let (court_lines, center_line) = match arena.lines {
+            Some([r, g, b]) => {
+                let lines = Color::from_rgb(r, g, b);
+                (lines, Color { a: 0.6, ..lines })
+            }
+            None => (theme.court_lines, theme.center_line),
+        };
 
-            // Set up text for drawing
-            let countdown_text = Text::new(fragment);
-            let dims = countdown_text.measure(self.ctx)?;
+        // Border lines. On a full-window court they are pulled just inside
+        // the edges so they don't get cut off
+        let half_line = COURT_LINE_WIDTH / 2.0;
+        let inset = if court.fills_window() { half_line } else { 0.0 };
+        let border = Mesh::new_rectangle(
+            self.ctx,
+            DrawMode::stroke(COURT_LINE_WIDTH),
+            Rect::new(
+                x + inset,
+                y + inset,
+                court.width - 2.0 * inset,
+                court.height - 2.0 * inset,
+            ),
+            court_lines,
+        )?;
+        canvas.draw(&border, DrawParam::default());
 
-            // Draw number in center of screen
+        // Dashed line down the middle
+        let dash = Mesh::new_rectangle(
+            self.ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, COURT_LINE_WIDTH, CENTER_LINE_DASH),
+            center_line,
+        )?;
+        let (center_x, center_y) = court.center();
+        let mut dash_y = 0.0;
+        while dash_y < court.height {
             canvas.draw(
-                &countdown_text,
-                DrawParam::default().dest([
-                    SCREEN_WIDTH / 2.0 - dims.x / 2.0,  // Center horizontally
-                    SCREEN_HEIGHT / 2.0 - dims.y / 2.0, // Center vertically
-                ]),
+                &dash,
+                DrawParam::default().dest([x + center_x - half_line, y + dash_y]),
             );
+            dash_y += CENTER_LINE_DASH + CENTER_LINE_GAP;
+        }
+
+        // Circle around the serve spot, if the theme wants one
+        if theme.center_circle {
+            let circle = Mesh::new_circle(
+                self.ctx,
+                DrawMode::stroke(COURT_LINE_WIDTH),
+                [x + center_x, y + center_y],
+                CENTER_CIRCLE_RADIUS,
+                0.5,
+                center_line,
+            )?;
+            canvas.draw(&circle, DrawParam::default());
         }
         Ok(())
     }
+
+    // Draw countdown numbers in different colors
+    fn draw_countdown(
+        &mut self,
+        canvas: &mut Canvas,
+        state: &GameState,
+        elapsed: f32, // Time passed since countdown started
+    ) -> ggez::GameResult {
+        // An arc around the serve spot that winds down as the countdown runs out
+        let remaining = 1.0 - (elapsed / COUNTDOWN_DURATION).clamp(0.0, 1.0);
+        if remaining > 0.01 {
+            let center = [SCREEN_WIDTH / 2.0, SCREEN_HEIGHT / 2.0];
+            let segments = (64.0 * remaining).ceil() as usize + 1;
+            let points: Vec<[f32; 2]> = (0..=segments)
+                .map(|i| {
+                    // Start at the top and go clockwise
+                    let angle = -FRAC_PI_2 + TAU * remaining * i as f32 / segments as f32;
+                    [
+                        center[0] + COUNTDOWN_RING_RADIUS * angle.cos(),
+                        center[1] + COUNTDOWN_RING_RADIUS * angle.sin(),
+                    ]
+                })
+                .collect();
+            let ring = Mesh::new_polyline(
+                self.ctx,
+                DrawMode::stroke(3.0),
+                &points,
+                state.theme.countdown_ring,
+            )?;
+            canvas.draw(&ring, DrawParam::default());
+        }
+
+        // The number for this second (3, 2, then 1), growing as it fades
+        if let Some(frame) = countdown_frame(elapsed) {
+            self.draw_countdown_text(canvas, frame, state)?;
+        }
+        Ok(())
+    }
+
+    // Draw a countdown number (or "GO!") in the middle of the screen, at its
+    // size and fade for the moment. Each number is built once at its biggest
+    // size and shrunk to fit, so it isn't built again as it grows
+    fn draw_countdown_text(
+        &mut self,
+        canvas: &mut Canvas,
+        frame: CountdownFrame,
+        state: &GameState,
+    ) -> ggez::GameResult {
+        // Each number has the theme's color for it; "GO!" uses the one for 1
+        let color = Color {
+            a: frame.alpha,
+            ..state.theme.countdown[frame.count.max(1) - 1]
+        };
+        let biggest = COUNTDOWN_TEXT_SIZE * COUNTDOWN_GROWTH.max(1.0);
+        let line = HudLine::new(frame.label(), biggest, color)
+            .at(Anchor::Center, 0.0)
+            .scaled(frame.size / biggest);
+        state.hud.draw(canvas, self.ctx, "countdown", &line)
+    }
+
+    // Draw a table of live match stats in the middle of the screen
+    fn draw_stats_overlay(&mut self, canvas: &mut Canvas, state: &GameState) -> ggez::GameResult {
+        let stats = &state.sim.stats;
+        let streak = match stats.streak_player {
+            Some(1) => format!("You, {} in a row", stats.streak),
+            Some(_) => format!("Player 2, {} in a row", stats.streak),
+            None => "None yet".to_string(),
+        };
+        let mut overlay = format!(
+            "Won on serve:   You {}  |  Player 2 {}\n\
+             Won on return:  You {}  |  Player 2 {}\n\
+             Average rally:  {:.1} hits\n\
+             Longest rally:  {} hits\n\
+             This rally:     {} hits\n\
+             Current streak: {}",
+            stats.player1.points_on_serve,
+            stats.player2.points_on_serve,
+            stats.player1.points_on_return,
+            stats.player2.points_on_return,
+            stats.average_rally_length(),
+            stats.longest_rally,
+            state.sim.rally(),
+            streak
+        );
+        if let Some(player) = stats.forfeited_by {
+            let who = if player == 1 { "You" } else { "Player 2" };
+            overlay.push_str(&format!("\nResigned:       {}", who));
+        }
+
+        let overlay_text = Text::new(TextFragment::new(overlay).scale(16.0).color(Color {
+            a: 0.86,
+            ..state.theme.text
+        }));
+        let dims = overlay_text.measure(self.ctx)?;
+
+        // Draw just below the score so the ball stays visible
+        canvas.draw(
+            &overlay_text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, 120.0]),
+        );
+        Ok(())
+    }
+}
+
+// How far to nudge a shape from its latest position so it is drawn partway
+// between its previous and latest simulation steps (alpha 0 = previous, 1 = latest)
+fn blend_shift(previous: f32, current: f32, alpha: f32) -> f32 {
+    (previous - current) * (1.0 - alpha)
 }
@@ -0,0 +1,303 @@
+// This file holds the holiday looks that switch on by themselves at certain
+// times of year: snow falling (and a snowball for a ball) through December,
+// and fireworks over New Year. Each event is a row of data giving the days it
+// runs, the theme colors it swaps in and the particles it shows, so another
+// one only needs another row. They can be turned off in the settings
+
+use crate::game::constants::*;
+use crate::graphics::theme::Theme;
+use ggez::graphics::Color;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A day of the year, without the year
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CalendarDay {
+    pub month: u32, // 1 (January) to 12 (December)
+    pub day: u32,   // Day of the month, from 1
+}
+
+impl CalendarDay {
+    // The day `days` days after 1 January 1970
+    pub fn from_days(days: i64) -> Self {
+        // Count in 400-year cycles starting from 1 March, so the leap day
+        // lands at the end of each year
+        let shifted = days + 719_468;
+        let day_of_cycle = shifted.rem_euclid(146_097);
+        let year_of_cycle = (day_of_cycle - day_of_cycle / 1460 + day_of_cycle / 36_524
+            - day_of_cycle / 146_096)
+            / 365;
+        let day_of_year =
+            day_of_cycle - (365 * year_of_cycle + year_of_cycle / 4 - year_of_cycle / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = match month_from_march < 10 {
+            true => month_from_march + 3,
+            false => month_from_march - 9,
+        };
+        CalendarDay {
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    // Today, going by the system clock (in UTC, which is close enough for
+    // events that last a day or more)
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        CalendarDay::from_days((seconds / 86_400) as i64)
+    }
+
+    // Month and day as one number that sorts through the year (1 March = 301)
+    fn key(self) -> u32 {
+        self.month * 100 + self.day
+    }
+}
+
+// How an event's particles move
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Motion {
+    Falling,  // Drift down the screen and start again at the top
+    Bursting, // Fly out from a point every so often, fall and fade
+}
+
+// What an event shows over the court
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ParticlePreset {
+    pub motion: Motion,           // How they move
+    pub count: usize,             // Particles on screen when falling, or sparks per burst
+    pub speed: [f32; 2],          // Slowest and fastest, in pixels per second
+    pub size: [f32; 2],           // Smallest and largest radius, in pixels
+    pub lifetime: f32,            // Seconds a spark lasts (falling ones last forever)
+    pub interval: f32,            // Seconds between bursts
+    pub colors: &'static [Color], // Picked at random for each particle or burst
+}
+
+// Theme colors an event swaps in (None keeps the theme's own)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ThemeTint {
+    pub ball: Option<Color>,
+    pub ball_hot: Option<[Color; 2]>,
+    pub highlight: Option<Color>,
+    pub menu_selected: Option<Color>,
+}
+
+// A holiday look and the days it's shown
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SeasonalEvent {
+    pub name: &'static str,
+    pub first: CalendarDay, // First day it runs
+    pub last: CalendarDay,  // Last day it runs (may be in the next year)
+    pub tint: ThemeTint,
+    pub particles: ParticlePreset,
+}
+
+// Colors shared by the rows below
+const SNOW_WHITE: Color = Color::new(1.0, 1.0, 1.0, 0.85);
+const ICE_BLUE: Color = Color::new(0.75, 0.88, 1.0, 0.85);
+const GOLD: Color = Color::new(1.0, 0.82, 0.2, 1.0);
+
+// Every event, checked in order, so a shorter one listed first wins on the
+// days they share
+pub const SEASONAL_EVENTS: [SeasonalEvent; 2] = [
+    SeasonalEvent {
+        name: "New Year",
+        first: CalendarDay { month: 12, day: 31 },
+        last: CalendarDay { month: 1, day: 1 },
+        tint: ThemeTint {
+            ball: Some(GOLD),
+            ball_hot: None,
+            highlight: Some(GOLD),
+            menu_selected: Some(GOLD),
+        },
+        particles: ParticlePreset {
+            motion: Motion::Bursting,
+            count: 40,
+            speed: [60.0, 180.0],
+            size: [1.5, 2.5],
+            lifetime: 1.4,
+            interval: 0.9,
+            colors: &[
+                GOLD,
+                Color::new(1.0, 0.3, 0.3, 1.0),
+                Color::new(0.3, 1.0, 0.5, 1.0),
+                Color::new(0.3, 0.8, 1.0, 1.0),
+                Color::new(1.0, 0.4, 1.0, 1.0),
+            ],
+        },
+    },
+    SeasonalEvent {
+        name: "Snow",
+        first: CalendarDay { month: 12, day: 1 },
+        last: CalendarDay { month: 12, day: 31 },
+        tint: ThemeTint {
+            ball: Some(Color::WHITE),
+            ball_hot: Some([ICE_BLUE, Color::new(0.45, 0.7, 1.0, 1.0)]),
+            highlight: None,
+            menu_selected: None,
+        },
+        particles: ParticlePreset {
+            motion: Motion::Falling,
+            count: 80,
+            speed: [30.0, 80.0],
+            size: [1.5, 3.5],
+            lifetime: 0.0,
+            interval: 0.0,
+            colors: &[SNOW_WHITE, ICE_BLUE],
+        },
+    },
+];
+
+impl SeasonalEvent {
+    // Whether the event is shown on this day
+    pub fn runs_on(&self, day: CalendarDay) -> bool {
+        let (first, last, day) = (self.first.key(), self.last.key(), day.key());
+        if first <= last {
+            (first..=last).contains(&day)
+        } else {
+            // Runs over the end of the year
+            day >= first || day <= last
+        }
+    }
+
+    // The theme with the event's colors swapped in
+    pub fn apply(&self, theme: Theme) -> Theme {
+        let tint = self.tint;
+        Theme {
+            ball: tint.ball.unwrap_or(theme.ball),
+            ball_hot: tint.ball_hot.unwrap_or(theme.ball_hot),
+            highlight: tint.highlight.unwrap_or(theme.highlight),
+            menu_selected: tint.menu_selected.unwrap_or(theme.menu_selected),
+            ..theme
+        }
+    }
+}
+
+// The event shown on this day, if any
+pub fn event_on(day: CalendarDay) -> Option<&'static SeasonalEvent> {
+    SEASONAL_EVENTS.iter().find(|event| event.runs_on(day))
+}
+
+// One snowflake or spark, in play-area pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    pub dx: f32,      // Sideways speed (pixels per second)
+    pub dy: f32,      // Downward speed (pixels per second)
+    pub size: f32,    // Radius in pixels
+    pub age: f32,     // Seconds since it appeared
+    pub color: Color, // Color at full strength
+}
+
+// The particles of the event showing, moved along each frame
+pub struct ParticleField {
+    pub preset: ParticlePreset,   // How they look and move
+    pub particles: Vec<Particle>, // Every particle on screen
+    pub until_burst: f32,         // Seconds until the next firework bursts
+    rng: StdRng,                  // Where and how fast new particles start
+}
+
+impl ParticleField {
+    // Start the particles for a preset: falling ones are spread over the
+    // whole screen straight away, and the first burst goes off at once
+    pub fn new(preset: ParticlePreset, seed: u64) -> Self {
+        let mut field = ParticleField {
+            preset,
+            particles: Vec::new(),
+            until_burst: 0.0,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        if preset.motion == Motion::Falling {
+            for _ in 0..preset.count {
+                let y = field.rng.gen_range(0.0..SCREEN_HEIGHT);
+                let flake = field.new_flake(y);
+                field.particles.push(flake);
+            }
+        }
+        field
+    }
+
+    // A snowflake at a random spot across `y`
+    fn new_flake(&mut self, y: f32) -> Particle {
+        let preset = self.preset;
+        Particle {
+            x: self.rng.gen_range(0.0..SCREEN_WIDTH),
+            y,
+            dx: self.rng.gen_range(-SNOW_SWAY..=SNOW_SWAY),
+            dy: self.rng.gen_range(preset.speed[0]..=preset.speed[1]),
+            size: self.rng.gen_range(preset.size[0]..=preset.size[1]),
+            age: 0.0,
+            color: preset.colors[self.rng.gen_range(0..preset.colors.len())],
+        }
+    }
+
+    // A firework going off somewhere in the top half of the screen, with
+    // sparks flying out evenly all around
+    fn burst(&mut self) {
+        let preset = self.preset;
+        let x = self.rng.gen_range(0.15..0.85) * SCREEN_WIDTH;
+        let y = self.rng.gen_range(0.15..0.5) * SCREEN_HEIGHT;
+        let color = preset.colors[self.rng.gen_range(0..preset.colors.len())];
+        for index in 0..preset.count {
+            let angle = index as f32 / preset.count as f32 * std::f32::consts::TAU;
+            let speed = self.rng.gen_range(preset.speed[0]..=preset.speed[1]);
+            self.particles.push(Particle {
+                x,
+                y,
+                dx: speed * angle.cos(),
+                dy: speed * angle.sin(),
+                size: self.rng.gen_range(preset.size[0]..=preset.size[1]),
+                age: 0.0,
+                color,
+            });
+        }
+    }
+
+    // Move every particle on by `delta` seconds
+    pub fn update(&mut self, delta: f32) {
+        for particle in &mut self.particles {
+            particle.x += particle.dx * delta;
+            particle.y += particle.dy * delta;
+            particle.age += delta;
+        }
+
+        match self.preset.motion {
+            Motion::Falling => {
+                // Flakes that fall off the bottom start again at the top
+                for index in 0..self.particles.len() {
+                    let particle = self.particles[index];
+                    if particle.y - particle.size > SCREEN_HEIGHT {
+                        self.particles[index] = self.new_flake(-particle.size);
+                    } else {
+                        self.particles[index].x = particle.x.rem_euclid(SCREEN_WIDTH);
+                    }
+                }
+            }
+            Motion::Bursting => {
+                for particle in &mut self.particles {
+                    particle.dy += FIREWORK_GRAVITY * delta;
+                }
+                let lifetime = self.preset.lifetime;
+                self.particles.retain(|particle| particle.age < lifetime);
+                self.until_burst -= delta;
+                if self.until_burst <= 0.0 {
+                    self.burst();
+                    self.until_burst += self.preset.interval;
+                }
+            }
+        }
+    }
+
+    // Color to draw a particle in: sparks fade out over their lifetime
+    pub fn color_of(&self, particle: &Particle) -> Color {
+        let mut color = particle.color;
+        if self.preset.motion == Motion::Bursting {
+            color.a *= (1.0 - particle.age / self.preset.lifetime).clamp(0.0, 1.0);
+        }
+        color
+    }
+}
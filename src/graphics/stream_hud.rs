@@ -0,0 +1,155 @@
+// This file draws the streaming HUD: a small borderless window showing only
+// the score, the games won and the match clock on flat green, so streaming
+// software (OBS and the like) can key the green out and lay the live match
+// data over its capture of the main window. ggez only opens one window per
+// program, so the HUD is a second copy of the game started with --hud-window,
+// and the game being played sends it the match when started with
+// --stream-hud (the same snapshots spectators get). Only built with the
+// stream-hud feature
+
+use crate::game::constants::*;
+use crate::game::spectator::{Snapshot, SpectatorClient};
+use ggez::conf::{WindowMode, WindowSetup};
+use ggez::event::{self, EventHandler};
+use ggez::graphics::{Canvas, Color, DrawParam, Rect, Text, TextFragment};
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::{ContextBuilder, GameResult};
+
+// The background the streaming software keys out
+pub const CHROMA_KEY: Color = Color::new(0.0, 1.0, 0.0, 1.0);
+
+// Minutes and seconds, e.g. "2:05"
+pub fn match_clock(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+// The HUD's two lines for a match: the score, then whatever else applies
+// (games, lives, pause or winner) with the match clock
+pub fn hud_lines(snapshot: &Snapshot) -> [String; 2] {
+    let score = format!("{}  -  {}", snapshot.score[0], snapshot.score[1]);
+    let mut parts = Vec::new();
+    if snapshot.best_of > 1 {
+        parts.push(format!(
+            "Games {} - {}",
+            snapshot.games[0], snapshot.games[1]
+        ));
+    }
+    if let Some([left, right]) = snapshot.lives {
+        parts.push(format!("Lives {} - {}", left, right));
+    }
+    match (snapshot.winner, snapshot.paused) {
+        (Some(winner), _) => parts.push(format!("Player {} wins", winner)),
+        (None, Some(_)) => parts.push("Paused".to_string()),
+        (None, None) => {}
+    }
+    parts.push(match_clock(snapshot.clock));
+    [score, parts.join("   |   ")]
+}
+
+// The HUD window, following the match from the game being played
+pub struct HudWindow {
+    address: String,               // The game's HUD feed
+    feed: Option<SpectatorClient>, // Connection to it, once it's reached
+    retry: f32,                    // Seconds until trying to reach it again
+}
+
+impl HudWindow {
+    // A HUD that will look for the game at `address`
+    pub fn new(address: &str) -> Self {
+        HudWindow {
+            address: address.to_string(),
+            feed: None,
+            retry: 0.0,
+        }
+    }
+
+    // Read the latest match, or keep trying to reach the game if it isn't
+    // running (yet, or any more)
+    pub fn follow(&mut self, delta: f32) {
+        match &mut self.feed {
+            Some(feed) => {
+                if feed.poll(delta).is_err() {
+                    self.feed = None;
+                    self.retry = STREAM_HUD_RETRY;
+                }
+            }
+            None => {
+                self.retry -= delta;
+                if self.retry <= 0.0 {
+                    self.feed = SpectatorClient::connect(&self.address).ok();
+                    self.retry = STREAM_HUD_RETRY;
+                }
+            }
+        }
+    }
+
+    // What to show right now
+    pub fn lines(&self) -> [String; 2] {
+        match self.feed.as_ref().and_then(|feed| feed.latest.as_ref()) {
+            Some(snapshot) => hud_lines(snapshot),
+            None => [
+                "Waiting for the match".to_string(),
+                format!("Start the game with --stream-hud ({})", self.address),
+            ],
+        }
+    }
+}
+
+impl EventHandler for HudWindow {
+    fn update(&mut self, ctx: &mut ggez::Context) -> GameResult {
+        self.follow(ctx.time.delta().as_secs_f32());
+        Ok(())
+    }
+
+    // Draw both lines centered on the green, with a dark shadow so the edges
+    // of the letters stay clear once the green is keyed out
+    fn draw(&mut self, ctx: &mut ggez::Context) -> GameResult {
+        let mut canvas = Canvas::from_frame(ctx, CHROMA_KEY);
+        canvas.set_screen_coordinates(Rect::new(0.0, 0.0, STREAM_HUD_WIDTH, STREAM_HUD_HEIGHT));
+        let mut top = 12.0;
+        for (line, size) in self.lines().iter().zip([48.0, 20.0]) {
+            let text = Text::new(TextFragment::new(line.as_str()).scale(size));
+            let dims = text.measure(ctx)?;
+            let x = (STREAM_HUD_WIDTH - dims.x) / 2.0;
+            canvas.draw(
+                &text,
+                DrawParam::default()
+                    .dest([x + 2.0, top + 2.0])
+                    .color(Color::BLACK),
+            );
+            canvas.draw(
+                &text,
+                DrawParam::default().dest([x, top]).color(Color::WHITE),
+            );
+            top += dims.y + 8.0;
+        }
+        canvas.finish(ctx)
+    }
+
+    // The window has no title bar, so Escape closes it
+    fn key_down_event(
+        &mut self,
+        ctx: &mut ggez::Context,
+        input: KeyInput,
+        _repeat: bool,
+    ) -> GameResult {
+        if input.keycode == Some(KeyCode::Escape) {
+            ctx.request_quit();
+        }
+        Ok(())
+    }
+}
+
+// Open the HUD window and run it until it's closed
+pub fn run_hud_window() -> GameResult {
+    let (ctx, event_loop) = ContextBuilder::new("Ping Pong HUD", "Prudhveraj Botta")
+        .window_setup(WindowSetup::default().title("Ping Pong HUD"))
+        .window_mode(
+            WindowMode::default()
+                .dimensions(STREAM_HUD_WIDTH, STREAM_HUD_HEIGHT)
+                .borderless(true),
+        )
+        .build()?;
+    event::run(ctx, event_loop, HudWindow::new(STREAM_HUD_ADDRESS))
+}
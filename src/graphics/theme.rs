@@ -0,0 +1,176 @@
+// This file holds every color used to draw the game, so they live in one place
+// and can be swapped for a different look while playing
+
+use crate::game::constants::*;
+use ggez::graphics::Color;
+use serde::{Deserialize, Serialize};
+
+// Colors (and small style choices) for drawing the game
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Theme {
+    pub background: Color,     // Color behind everything
+    pub court_lines: Color,    // Border lines around the court
+    pub center_line: Color,    // Dashed line down the middle
+    pub center_circle: bool,   // Draw a circle around the serve spot
+    pub countdown_ring: Color, // Arc that winds down around the serve spot before a serve
+    pub countdown: [Color; 3], // Colors of the "1", "2" and "3"
+    pub paddle_left: Color,    // Your (left) paddle
+    pub paddle_right: Color,   // AI or player 2 (right) paddle
+    pub ball: Color,           // The ball and its trail at serve speed
+    pub ball_hot: [Color; 2],  // Ball color at half and at full top speed
+    pub text: Color,           // Normal text (score, messages, menu entries)
+    pub text_dim: Color,       // Hints and less important text
+    pub highlight: Color,      // Score flash for whoever just scored
+    pub menu_selected: Color,  // Selected menu entry
+    pub life: Color,           // Lives icons
+    pub warning: Color,        // Low-power badge
+    pub momentum_track: Color, // Empty part of the momentum bar
+    pub overlay: Color,        // See-through layer dimming the court when paused
+}
+
+// Built-in looks to switch between with T
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ThemePreset {
+    Classic,  // Dark court, green and blue paddles, yellow ball
+    Neon,     // Bright pink and cyan on deep blue
+    Phosphor, // Old green-screen monitor
+}
+
+impl Theme {
+    // Dark court with soft grey lines, like the original game
+    pub fn classic() -> Self {
+        Theme {
+            background: Color::from_rgb(30, 30, 30),
+            court_lines: Color::from_rgb(90, 90, 90),
+            center_line: Color::from_rgb(70, 70, 70),
+            center_circle: true,
+            countdown_ring: Color::from_rgb(200, 200, 200),
+            countdown: [Color::GREEN, Color::YELLOW, Color::RED],
+            paddle_left: Color::from_rgb(0, 255, 0),
+            paddle_right: Color::from_rgb(0, 0, 255),
+            ball: Color::YELLOW,
+            ball_hot: [Color::from_rgb(255, 140, 0), Color::RED],
+            text: Color::WHITE,
+            text_dim: Color::from_rgb(180, 180, 180),
+            highlight: Color::GREEN,
+            menu_selected: Color::YELLOW,
+            life: Color::from_rgb(255, 80, 80),
+            warning: Color::from_rgb(255, 170, 0),
+            momentum_track: Color::from_rgb(70, 70, 70),
+            overlay: Color::from_rgba(0, 0, 0, 160),
+        }
+    }
+
+    // Glowing arcade colors on a deep blue court
+    pub fn neon() -> Self {
+        Theme {
+            background: Color::from_rgb(10, 5, 35),
+            court_lines: Color::from_rgb(120, 60, 200),
+            center_line: Color::from_rgb(80, 40, 150),
+            center_circle: true,
+            countdown_ring: Color::from_rgb(0, 255, 255),
+            countdown: [
+                Color::from_rgb(0, 255, 170),
+                Color::from_rgb(255, 230, 0),
+                Color::from_rgb(255, 40, 160),
+            ],
+            paddle_left: Color::from_rgb(255, 40, 160),
+            paddle_right: Color::from_rgb(0, 230, 255),
+            ball: Color::from_rgb(255, 255, 120),
+            ball_hot: [Color::from_rgb(255, 150, 40), Color::from_rgb(255, 40, 160)],
+            text: Color::from_rgb(240, 230, 255),
+            text_dim: Color::from_rgb(150, 130, 200),
+            highlight: Color::from_rgb(0, 255, 170),
+            menu_selected: Color::from_rgb(255, 230, 0),
+            life: Color::from_rgb(255, 40, 160),
+            warning: Color::from_rgb(255, 230, 0),
+            momentum_track: Color::from_rgb(50, 30, 90),
+            overlay: Color::from_rgba(10, 5, 35, 170),
+        }
+    }
+
+    // Everything in shades of green on black, like an old terminal
+    pub fn phosphor() -> Self {
+        let bright = Color::from_rgb(80, 255, 80);
+        let mid = Color::from_rgb(40, 170, 40);
+        let dark = Color::from_rgb(20, 80, 20);
+        Theme {
+            background: Color::from_rgb(0, 12, 0),
+            court_lines: dark,
+            center_line: dark,
+            center_circle: false,
+            countdown_ring: mid,
+            countdown: [bright, mid, mid],
+            paddle_left: bright,
+            paddle_right: bright,
+            ball: bright,
+            ball_hot: [Color::from_rgb(180, 255, 180), Color::WHITE],
+            text: bright,
+            text_dim: mid,
+            highlight: Color::from_rgb(180, 255, 180),
+            menu_selected: Color::from_rgb(180, 255, 180),
+            life: bright,
+            warning: Color::from_rgb(180, 255, 180),
+            momentum_track: dark,
+            overlay: Color::from_rgba(0, 12, 0, 170),
+        }
+    }
+
+    // Color of a ball moving at `speed`: the normal ball color at serve speed,
+    // heating up along `ball_hot` until it reaches the top speed
+    pub fn ball_color(&self, speed: f32) -> Color {
+        let heat = ball_heat(speed);
+        if heat < 0.5 {
+            mix(self.ball, self.ball_hot[0], heat * 2.0)
+        } else {
+            mix(self.ball_hot[0], self.ball_hot[1], heat * 2.0 - 1.0)
+        }
+    }
+}
+
+// How heated up a ball moving at `speed` is, from 0 (at or under
+// BALL_HEAT_START_SPEED) to 1 (at BALL_HEAT_FULL_SPEED and over)
+pub fn ball_heat(speed: f32) -> f32 {
+    ((speed - BALL_HEAT_START_SPEED) / (BALL_HEAT_FULL_SPEED - BALL_HEAT_START_SPEED))
+        .clamp(0.0, 1.0)
+}
+
+// Blend from color `from` (amount 0) to color `to` (amount 1)
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let blend = |a: f32, b: f32| a + (b - a) * amount;
+    Color::new(
+        blend(from.r, to.r),
+        blend(from.g, to.g),
+        blend(from.b, to.b),
+        blend(from.a, to.a),
+    )
+}
+
+impl ThemePreset {
+    // Build the colors for this preset
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemePreset::Classic => Theme::classic(),
+            ThemePreset::Neon => Theme::neon(),
+            ThemePreset::Phosphor => Theme::phosphor(),
+        }
+    }
+
+    // Move on to the next preset (wraps back to Classic)
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Classic => ThemePreset::Neon,
+            ThemePreset::Neon => ThemePreset::Phosphor,
+            ThemePreset::Phosphor => ThemePreset::Classic,
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Classic => "Classic",
+            ThemePreset::Neon => "Neon",
+            ThemePreset::Phosphor => "Green Phosphor",
+        }
+    }
+}
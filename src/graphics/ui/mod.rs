@@ -0,0 +1,2 @@
+// This file holds the building blocks the game's screens are drawn with
+pub mod option_list; // Lists of options changed in place with the arrow keys
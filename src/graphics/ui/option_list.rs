@@ -0,0 +1,85 @@
+// This file draws a list of options that are changed in place with the arrow
+// keys, like the settings screen. A list too long for the screen scrolls so
+// the highlighted option always stays in view
+
+use crate::game::constants::*;
+use crate::graphics::theme::Theme;
+use ggez::graphics::{Canvas, DrawParam, Text, TextFragment};
+use std::ops::Range;
+
+// Which of `len` options are on screen when `selected` is highlighted and at
+// most `rows` fit. The highlight stays in the middle until an end is reached
+pub fn visible_rows(len: usize, selected: usize, rows: usize) -> Range<usize> {
+    if len <= rows {
+        return 0..len;
+    }
+    let first = selected.saturating_sub(rows / 2).min(len - rows);
+    first..first + rows
+}
+
+// Draw a title with the options below it. The highlighted option gets arrows
+// either side, as Left and Right change it
+pub fn draw_option_list(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    title: &str,
+    options: &[String],
+    selected: usize,
+    theme: &Theme,
+) -> ggez::GameResult {
+    // Title across the top
+    let title_text = Text::new(TextFragment::new(title).scale(36.0).color(theme.text));
+    let title_dims = title_text.measure(ctx)?;
+    let mut y = 40.0;
+    canvas.draw(
+        &title_text,
+        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - title_dims.x / 2.0, y]),
+    );
+    y += title_dims.y + 20.0;
+
+    // The space for the top note is kept even when it isn't shown, so the
+    // options don't jump as the list scrolls
+    let rows = visible_rows(options.len(), selected, OPTION_LIST_ROWS);
+    if rows.start > 0 {
+        draw_note(canvas, ctx, "more above", y, theme)?;
+    }
+    y += 20.0;
+    let below = rows.end < options.len();
+
+    // One line per option on screen, the highlighted one in the theme's color
+    for index in rows {
+        let (label, color) = if index == selected {
+            (format!("<  {}  >", options[index]), theme.menu_selected)
+        } else {
+            (options[index].clone(), theme.text)
+        };
+        let option_text = Text::new(TextFragment::new(label).scale(18.0).color(color));
+        let dims = option_text.measure(ctx)?;
+        canvas.draw(
+            &option_text,
+            DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, y]),
+        );
+        y += dims.y + 6.0;
+    }
+    if below {
+        draw_note(canvas, ctx, "more below", y, theme)?;
+    }
+    Ok(())
+}
+
+// Draw a dim line of text across the middle, saying there are more options off the list
+fn draw_note(
+    canvas: &mut Canvas,
+    ctx: &mut ggez::Context,
+    text: &str,
+    y: f32,
+    theme: &Theme,
+) -> ggez::GameResult {
+    let note_text = Text::new(TextFragment::new(text).scale(14.0).color(theme.text_dim));
+    let dims = note_text.measure(ctx)?;
+    canvas.draw(
+        &note_text,
+        DrawParam::default().dest([SCREEN_WIDTH / 2.0 - dims.x / 2.0, y]),
+    );
+    Ok(())
+}
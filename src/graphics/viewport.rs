@@ -0,0 +1,125 @@
+// This file fits the game's fixed-size play area into a window of any size.
+// Everything is drawn as if the window were SCREEN_WIDTH x SCREEN_HEIGHT, and
+// ggez stretches that onto the real window. It also holds the window sizes
+// the settings screen offers, the size a window snaps to when its shape is
+// locked to the play area's, and the TV safe area the HUD is kept inside
+
+use crate::game::constants::*;
+use ggez::graphics::Rect;
+
+// Window sizes that can be picked on the settings screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WindowPreset {
+    Original, // The play area's own size
+    Double,   // Pixel-perfect, twice the play area
+    Triple,   // Pixel-perfect, three times the play area
+    Hd720,    // 1280 x 720, with bars on the sides
+    Hd1080,   // 1920 x 1080, with bars on the sides
+}
+
+impl WindowPreset {
+    // Every preset, in the order the settings screen slides through them
+    pub const ALL: [WindowPreset; 5] = [
+        WindowPreset::Original,
+        WindowPreset::Double,
+        WindowPreset::Triple,
+        WindowPreset::Hd720,
+        WindowPreset::Hd1080,
+    ];
+
+    // How big the window is with this preset (pixels)
+    pub fn size(self) -> (f32, f32) {
+        match self {
+            WindowPreset::Original => (SCREEN_WIDTH, SCREEN_HEIGHT),
+            WindowPreset::Double => (SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0),
+            WindowPreset::Triple => (SCREEN_WIDTH * 3.0, SCREEN_HEIGHT * 3.0),
+            WindowPreset::Hd720 => (1280.0, 720.0),
+            WindowPreset::Hd1080 => (1920.0, 1080.0),
+        }
+    }
+
+    // Name shown on screen
+    pub fn name(self) -> &'static str {
+        match self {
+            WindowPreset::Original => "1x",
+            WindowPreset::Double => "2x",
+            WindowPreset::Triple => "3x",
+            WindowPreset::Hd720 => "720p",
+            WindowPreset::Hd1080 => "1080p",
+        }
+    }
+
+    // The preset a window of this size matches, if any
+    pub fn matching(width: f32, height: f32) -> Option<Self> {
+        WindowPreset::ALL.into_iter().find(|preset| {
+            let (preset_width, preset_height) = preset.size();
+            (preset_width - width).abs() < 1.0 && (preset_height - height).abs() < 1.0
+        })
+    }
+}
+
+// The size a window resized to `width` x `height` snaps to when its shape is
+// locked to the play area's. Its scale is the average of how much wider and
+// how much taller than the play area it was dragged, so pulling either edge
+// (or a corner) still grows or shrinks it. It never gets shorter than
+// MIN_WINDOW_SIZE
+pub fn lock_aspect(width: f32, height: f32) -> (f32, f32) {
+    let scale = (width / SCREEN_WIDTH + height / SCREEN_HEIGHT) / 2.0;
+    let scale = scale.max(MIN_WINDOW_SIZE / SCREEN_HEIGHT);
+    (
+        (SCREEN_WIDTH * scale).round(),
+        (SCREEN_HEIGHT * scale).round(),
+    )
+}
+
+// The screen coordinates that show the whole play area as large as possible
+// without stretching it. If the window's shape doesn't match, the extra space
+// becomes bars on the sides (wide windows) or above and below (tall windows)
+pub fn letterbox(window_width: f32, window_height: f32) -> Rect {
+    let game_aspect = SCREEN_WIDTH / SCREEN_HEIGHT;
+    let window_aspect = window_width / window_height;
+
+    if window_aspect > game_aspect {
+        // Window is wider than the game: widen the view, keeping the game centered
+        let width = SCREEN_HEIGHT * window_aspect;
+        Rect::new(-(width - SCREEN_WIDTH) / 2.0, 0.0, width, SCREEN_HEIGHT)
+    } else {
+        // Window is taller than the game (or the same shape)
+        let height = SCREEN_WIDTH / window_aspect;
+        Rect::new(0.0, -(height - SCREEN_HEIGHT) / 2.0, SCREEN_WIDTH, height)
+    }
+}
+
+// How big the HUD is drawn with a TV safe-area margin of `margin` percent of
+// the window on each side (1 = full size, with no margin)
+pub fn safe_scale(margin: u32) -> f32 {
+    1.0 - 2.0 * margin as f32 / 100.0
+}
+
+// The screen coordinates for the score, menus and messages, kept inside a TV
+// safe area so overscan doesn't cut them off: `frame` zoomed out about its
+// middle, so everything drawn in the play area's coordinates shrinks toward
+// the middle of the window, leaving `margin` percent of it clear on each side
+pub fn safe_area(frame: Rect, margin: u32) -> Rect {
+    let width = frame.w / safe_scale(margin);
+    let height = frame.h / safe_scale(margin);
+    Rect::new(
+        frame.x - (width - frame.w) / 2.0,
+        frame.y - (height - frame.h) / 2.0,
+        width,
+        height,
+    )
+}
+
+// Where the whole court is in the coordinates of `safe_area`, so a dimmed
+// screen behind a menu still covers all of the court
+pub fn court_cover(margin: u32) -> Rect {
+    let width = SCREEN_WIDTH / safe_scale(margin);
+    let height = SCREEN_HEIGHT / safe_scale(margin);
+    Rect::new(
+        (SCREEN_WIDTH - width) / 2.0,
+        (SCREEN_HEIGHT - height) / 2.0,
+        width,
+        height,
+    )
+}
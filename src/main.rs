@@ -1,6 +1,24 @@
 // Import what we need to run the game
+use crate::game::bundle::bundle_file;
+#[cfg(feature = "stream-hud")]
+use crate::game::constants::STREAM_HUD_ADDRESS;
+use crate::game::cli::{usage, CliOptions};
+use crate::game::drills::drills_file;
+use crate::game::headless::run_headless;
+use crate::game::preferences::preferences_file;
+use crate::game::saved_match::saved_match_file;
+use crate::game::simulation::{GameMode, Simulation};
+#[cfg(feature = "stream-hud")]
+use crate::game::spectator::SpectatorServer;
+use crate::game::sounds::Sounds;
 use crate::game::state::GameState;
+use crate::game::stats::stats_file;
+use crate::graphics::seasonal::CalendarDay;
+#[cfg(feature = "stream-hud")]
+use crate::graphics::stream_hud::run_hud_window;
 use ggez::{event, ContextBuilder};
+use std::path::Path;
+use std::time::Instant;
 
 // Organize our code into folders
 mod components; // Game pieces (ball, paddles)
@@ -9,16 +27,136 @@ mod graphics; // Drawing code
 mod tests; // Testing code
 
 fn main() -> ggez::GameResult {
+    // Read the command line; bad options print the usage and stop
+    let options = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, usage());
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", usage());
+        return Ok(());
+    }
+
+    // Play without a window and report how it went
+    if let Some(ticks) = options.headless_ticks {
+        let mut sim = Simulation::new(GameMode::Demo);
+        options.apply(&mut sim);
+        let started = Instant::now();
+        let report = run_headless(&mut sim, ticks);
+        let seconds = started.elapsed().as_secs_f64();
+        println!("{}", report);
+        println!(
+            "Ran in {:.3} s ({:.0} ticks per second)",
+            seconds,
+            ticks as f64 / seconds.max(f64::EPSILON)
+        );
+        return Ok(());
+    }
+
+    // Open only the streaming HUD, fed by a game started with --stream-hud
+    #[cfg(feature = "stream-hud")]
+    if options.hud_window {
+        return run_hud_window();
+    }
+
     // Set up game window
     let (ctx, event_loop) = ContextBuilder::new("Ping Pong", "Prudhveraj Botta")
-        .window_setup(ggez::conf::WindowSetup::default().title("Ping Pong Game"))
-        .window_mode(ggez::conf::WindowMode::default().dimensions(
-            game::constants::SCREEN_WIDTH,
-            game::constants::SCREEN_HEIGHT,
-        ))
+        .window_setup(
+            ggez::conf::WindowSetup::default()
+                .title("Ping Pong Game")
+                .vsync(options.vsync),
+        )
+        .window_mode(
+            ggez::conf::WindowMode::default()
+                .dimensions(options.width, options.height)
+                .resizable(true), // The play area scales to fit any window size
+        )
         .build()?;
 
     // Create new game and start running it
-    let game = GameState::new();
+    let mut game = GameState::new(GameMode::VsAi);
+    game.set_window_size(options.width, options.height);
+    game.vsync = options.vsync;
+    game.set_calendar_day(CalendarDay::today()); // Snow in December, fireworks at New Year
+
+    // Send every match to the streaming HUD window
+    #[cfg(feature = "stream-hud")]
+    if options.stream_hud {
+        match SpectatorServer::bind(STREAM_HUD_ADDRESS) {
+            Ok(feed) => game.hud_feed = Some(feed),
+            Err(error) => eprintln!(
+                "Could not open the HUD feed on {}: {}",
+                STREAM_HUD_ADDRESS, error
+            ),
+        }
+    }
+
+    // Let other copies of the game watch every match, not only bot matches
+    if options.spectate {
+        game.open_spectator_port();
+    }
+
+    // Watch someone else's match instead of playing
+    if let Some(address) = &options.watch {
+        if let Err(error) = game.watch(address) {
+            eprintln!("Could not watch the match at {}: {}", address, error);
+            std::process::exit(1);
+        }
+    }
+
+    // Save the watched match to a file as it comes in
+    if let (Some(path), Some(watching)) = (&options.capture, &mut game.watching) {
+        if let Err(error) = watching.capture_to(Path::new(path)) {
+            eprintln!("Could not save the match to {}: {}", path, error);
+            std::process::exit(1);
+        }
+    }
+
+    // Or play back a match saved earlier
+    if let Some(path) = &options.replay_capture {
+        if let Err(error) = game.watch_capture(Path::new(path)) {
+            eprintln!("Could not play back {}: {}", path, error);
+            std::process::exit(1);
+        }
+    }
+
+    // Load the sound cues; play silently if there is no audio output
+    match Sounds::new(&ctx) {
+        Ok(sounds) => game.sounds = Some(sounds),
+        Err(error) => eprintln!("Sound is off: {}", error),
+    }
+
+    // Pick up lifetime stats from earlier runs (a benchmark's matches don't count)
+    if let (Some(path), None) = (stats_file(), options.bench_frames) {
+        game.use_stats_file(path);
+    }
+    game.bundle_path = bundle_file(); // Where Export/Import Setup reads and writes
+
+    // Pick up the practice drills recorded in earlier runs
+    if let Some(path) = drills_file() {
+        game.use_drills_file(path);
+    }
+
+    // Pick up a match left unfinished last time, and save one if the game closes mid-match
+    if let Some(path) = saved_match_file() {
+        game.use_saved_match_file(path);
+    }
+
+    // Pick up the settings from last time, and show What's New once after an update
+    if let Some(path) = preferences_file() {
+        game.use_preferences_file(path);
+    }
+
+    // Options given on the command line win over the saved settings
+    options.apply(&mut game.sim);
+
+    // Time a computer-against-computer match instead of playing
+    if let Some(frames) = options.bench_frames {
+        game.start_bench(frames);
+    }
+
     event::run(ctx, event_loop, game)
 }
@@ -2,7 +2,10 @@
 mod tests {
     use crate::{
         components::ball::Ball,
-        game::constants::{BALL_SPEED, SCREEN_HEIGHT, SCREEN_WIDTH},
+        game::constants::{
+            BALL_SPEED, BALL_SPIN_MIN_BOUNCE_ANGLE, BALL_SPIN_TRANSFER, BALL_TRAIL_LENGTH,
+            MAX_BALL_SPEED, MAX_DEFLECTION_ANGLE, SCREEN_HEIGHT, SCREEN_WIDTH,
+        },
     };
 
     use approx::assert_relative_eq;
@@ -29,4 +32,155 @@ mod tests {
         assert_relative_eq!(ball.x, initial_x + BALL_SPEED);
         assert_relative_eq!(ball.y, initial_y + BALL_SPEED);
     }
+
+    #[test]
+    fn test_ball_speed() {
+        let mut ball = Ball::new();
+        ball.dx = 300.0;
+        ball.dy = -400.0;
+        assert_relative_eq!(ball.speed(), 500.0);
+    }
+
+    #[test]
+    fn test_ball_launch_keeps_speed() {
+        let mut ball = Ball::new();
+        ball.launch(500.0, 0.5, -1.0);
+
+        assert!(ball.dx < 0.0);
+        assert!(ball.dy > 0.0);
+        assert_relative_eq!(ball.speed(), 500.0, epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_scale_speed_keeps_direction() {
+        let mut ball = Ball::new();
+        ball.dx = 300.0;
+        ball.dy = -400.0;
+        ball.scale_speed(1.1);
+
+        assert_relative_eq!(ball.speed(), 550.0, epsilon = 0.001);
+        assert_relative_eq!(ball.dx / ball.dy, 300.0 / -400.0, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_scale_speed_is_capped() {
+        let mut ball = Ball::new();
+        ball.launch(MAX_BALL_SPEED - 10.0, 0.3, 1.0);
+        ball.scale_speed(2.0);
+        assert_relative_eq!(ball.speed(), MAX_BALL_SPEED, epsilon = 0.001);
+
+        // Repeated speed-ups stay at the cap
+        for _ in 0..10 {
+            ball.scale_speed(1.5);
+        }
+        assert_relative_eq!(ball.speed(), MAX_BALL_SPEED, epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_scale_speed_on_still_ball() {
+        let mut ball = Ball::new();
+        ball.scale_speed(2.0);
+        assert_relative_eq!(ball.speed(), 0.0);
+    }
+
+    #[test]
+    fn test_ball_launch_flat() {
+        let mut ball = Ball::new();
+        ball.launch(BALL_SPEED, 0.0, 1.0);
+        assert_relative_eq!(ball.dx, BALL_SPEED);
+        assert_relative_eq!(ball.dy, 0.0);
+    }
+
+    #[test]
+    fn test_trail_keeps_recent_positions() {
+        let mut ball = Ball::new();
+        ball.dx = BALL_SPEED;
+        let start_x = ball.x;
+
+        for _ in 0..BALL_TRAIL_LENGTH + 5 {
+            ball.update(0.01);
+        }
+        assert_eq!(ball.trail.len(), BALL_TRAIL_LENGTH);
+        // The oldest spots have been dropped
+        assert!(ball.trail.front().unwrap().0 > start_x);
+    }
+
+    #[test]
+    fn test_no_trail_while_still() {
+        let mut ball = Ball::new();
+        ball.dx = BALL_SPEED;
+        ball.update(0.01);
+        assert_eq!(ball.trail.len(), 1);
+
+        ball.stop();
+        assert!(ball.trail.is_empty());
+        ball.update(0.01);
+        assert!(ball.trail.is_empty());
+        assert_eq!(ball.dx, 0.0);
+    }
+
+    #[test]
+    fn test_spin_curves_path_and_wears_off() {
+        let mut ball = Ball::new();
+        ball.dx = BALL_SPEED;
+        ball.spin = 2.0; // Clockwise: a ball moving right bends downward
+        ball.update(0.1);
+
+        assert!(ball.dy > 0.0);
+        assert_relative_eq!(ball.speed(), BALL_SPEED, epsilon = 1e-3);
+        assert!(ball.spin > 0.0 && ball.spin < 2.0);
+    }
+
+    #[test]
+    fn test_paddle_spins_ball_its_way() {
+        // Both paddles moving down make the ball swerve down, whichever way it goes
+        let mut ball = Ball::new();
+        ball.dx = BALL_SPEED;
+        ball.take_spin(100.0, false);
+        assert_relative_eq!(ball.spin, 100.0 * BALL_SPIN_TRANSFER);
+
+        ball.dx = -BALL_SPEED;
+        ball.take_spin(100.0, false);
+        assert_relative_eq!(ball.spin, -100.0 * BALL_SPIN_TRANSFER);
+        ball.update(0.1);
+        assert!(ball.dy > 0.0);
+
+        // A still paddle leaves no spin, and stopping the ball clears it
+        ball.take_spin(0.0, false);
+        assert_eq!(ball.spin, 0.0);
+        ball.spin = 1.0;
+        ball.stop();
+        assert_eq!(ball.spin, 0.0);
+    }
+
+    #[test]
+    fn test_spin_changes_wall_bounce() {
+        // Just bounced off the top wall, heading down and to the right
+        let mut ball = Ball::new();
+        ball.launch(BALL_SPEED, 0.5, 1.0);
+        let (plain_dx, plain_dy) = (ball.dx, ball.dy);
+
+        // Without spin the bounce is untouched
+        ball.spin_off_wall(true);
+        assert_eq!((ball.dx, ball.dy), (plain_dx, plain_dy));
+
+        // Clockwise spin slows it sideways off the top wall, keeping its speed
+        ball.spin = 1.0;
+        ball.spin_off_wall(true);
+        assert!(ball.dx < plain_dx);
+        assert!(ball.dy > 0.0);
+        assert_relative_eq!(ball.speed(), BALL_SPEED, epsilon = 1e-3);
+
+        // Huge spin can't send it steeper than a paddle-edge return or flatter than the limit
+        ball.launch(BALL_SPEED, 0.5, 1.0);
+        ball.spin = 1000.0;
+        ball.spin_off_wall(true);
+        assert_relative_eq!(ball.dy.atan2(ball.dx), MAX_DEFLECTION_ANGLE, epsilon = 1e-4);
+        ball.spin_off_wall(false);
+        assert_relative_eq!(
+            ball.dy.atan2(ball.dx),
+            BALL_SPIN_MIN_BOUNCE_ANGLE,
+            epsilon = 1e-4
+        );
+    }
 }
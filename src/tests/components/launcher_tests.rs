@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::components::ball::Ball;
+    use crate::components::launcher::BallLauncher;
+    use crate::game::constants::*;
+    use crate::game::court::CourtPreset;
+    use crate::game::drills::{Drill, Shot};
+    use crate::game::events::{EventListener, GameEvent};
+    use crate::game::recording::Contact;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // A return by `player`'s paddle
+    fn hit(player: u8) -> GameEvent {
+        GameEvent::PaddleHit {
+            contact: Contact {
+                player,
+                offset: 0.0,
+                angle_in: 0.0,
+                angle_out: 0.0,
+                speed: 300.0,
+            },
+            speed: 312.0,
+        }
+    }
+
+    #[test]
+    fn test_balls_come_at_the_player_from_the_machine() {
+        let court = CourtPreset::Narrow.court();
+        let mut launcher = BallLauncher::new();
+        let mut ball = Ball::new();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut heights = Vec::new();
+        for _ in 0..20 {
+            launcher.fire(&mut ball, &court, &mut rng);
+            assert!(ball.dx < 0.0);
+            assert!(ball.x > court.width - LAUNCHER_WIDTH - BALL_RADIUS * 2.0);
+            assert_eq!(ball.y, launcher.y);
+            assert!(ball.speed() >= LAUNCHER_MIN_SPEED - 0.01);
+            assert!(ball.speed() <= LAUNCHER_MAX_SPEED + 0.01);
+            assert!((ball.dy / ball.dx).atan().abs() <= LAUNCHER_MAX_ANGLE + 0.001);
+            heights.push(launcher.y);
+        }
+        // Not the same ball every time
+        assert!(heights.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_streak_counts_returns_until_a_miss() {
+        let mut launcher = BallLauncher::new();
+        for _ in 0..3 {
+            launcher.on_event(&hit(1));
+        }
+        launcher.on_event(&hit(2)); // Not the player's paddle
+        assert_eq!((launcher.streak, launcher.best_streak), (3, 3));
+
+        launcher.on_event(&GameEvent::PointScored { winner: 2 });
+        launcher.on_event(&hit(1));
+        assert_eq!((launcher.streak, launcher.best_streak), (1, 3));
+
+        launcher.reset();
+        assert_eq!((launcher.streak, launcher.best_streak), (0, 0));
+    }
+
+    #[test]
+    fn test_drill_shots_fire_in_order_on_loop() {
+        let court = CourtPreset::Narrow.court();
+        let mut launcher = BallLauncher::new();
+        let shot = |height: f32, speed: f32| Shot {
+            height,
+            speed,
+            angle: 0.2,
+        };
+        launcher.drill = Some(Drill {
+            name: "corners".to_string(),
+            shots: vec![shot(0.0, 300.0), shot(0.5, 420.0)],
+        });
+        let mut ball = Ball::new();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut fired = Vec::new();
+        for _ in 0..4 {
+            launcher.fire(&mut ball, &court, &mut rng);
+            fired.push((launcher.y, ball.speed().round()));
+        }
+        // The top of the court is as high as the machine's mouth can go
+        let top = LAUNCHER_HEIGHT / 2.0;
+        let middle = court.height / 2.0;
+        assert_eq!(
+            fired,
+            vec![(top, 300.0), (middle, 420.0), (top, 300.0), (middle, 420.0)]
+        );
+
+        // A new session starts the drill again, still playing it
+        launcher.reset();
+        assert_eq!(launcher.next_shot, 0);
+        assert!(launcher.drill.is_some());
+    }
+
+    #[test]
+    fn test_returns_are_recorded_only_while_recording() {
+        let court = CourtPreset::Narrow.court();
+        let mut launcher = BallLauncher::new();
+        let mut ball = Ball::new();
+        ball.launch(400.0, 0.3, 1.0);
+        launcher.record_return(&ball, &court);
+        assert!(launcher.recording.is_none());
+
+        launcher.recording = Some(Vec::new());
+        for _ in 0..DRILL_MAX_SHOTS + 5 {
+            launcher.record_return(&ball, &court);
+        }
+        let shots = launcher.recording.as_ref().unwrap();
+        assert_eq!(shots.len(), DRILL_MAX_SHOTS);
+        assert!((shots[0].speed - 400.0).abs() < 0.01);
+        assert!((shots[0].angle - 0.3).abs() < 0.001);
+    }
+}
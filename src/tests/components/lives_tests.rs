@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use crate::components::lives::Lives;
+
+    #[test]
+    fn test_new_lives() {
+        let lives = Lives::new(3);
+        assert_eq!(lives.player1, 3);
+        assert_eq!(lives.player2, 3);
+        assert!(!lives.is_out(1));
+        assert!(!lives.is_out(2));
+    }
+
+    #[test]
+    fn test_lose_life() {
+        let mut lives = Lives::new(2);
+        lives.lose_life(1);
+        assert_eq!(lives.player1, 1);
+        assert_eq!(lives.player2, 2);
+
+        lives.lose_life(1);
+        assert!(lives.is_out(1));
+        assert!(!lives.is_out(2));
+    }
+
+    #[test]
+    fn test_lives_never_negative() {
+        let mut lives = Lives::new(1);
+        lives.lose_life(2);
+        lives.lose_life(2);
+        assert_eq!(lives.player2, 0);
+    }
+
+    #[test]
+    fn test_lives_reset() {
+        let mut lives = Lives::new(3);
+        lives.lose_life(1);
+        lives.lose_life(2);
+        lives.reset(4);
+        assert_eq!(lives.player1, 4);
+        assert_eq!(lives.player2, 4);
+    }
+}
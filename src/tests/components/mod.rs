@@ -1,6 +1,12 @@
 #[cfg(test)]
 pub mod ball_tests;
 #[cfg(test)]
+pub mod launcher_tests;
+#[cfg(test)]
+pub mod lives_tests;
+#[cfg(test)]
+pub mod obstacle_tests;
+#[cfg(test)]
 pub mod paddle_tests;
 #[cfg(test)]
 pub mod score_tests;
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crate::components::ball::Ball;
+    use crate::components::obstacle::{self, Obstacle};
+    use crate::game::constants::*;
+    use crate::game::court::CourtPreset;
+    use ggez::graphics::Rect;
+
+    // A ball at (x, y) moving with speed (dx, dy)
+    fn ball_at(x: f32, y: f32, dx: f32, dy: f32) -> Ball {
+        let mut ball = Ball::new();
+        ball.x = x;
+        ball.y = y;
+        ball.dx = dx;
+        ball.dy = dy;
+        ball
+    }
+
+    #[test]
+    fn test_ball_bounces_back_off_the_side_it_hit() {
+        let block = Rect::new(100.0, 100.0, 20.0, 80.0);
+
+        // Coming in from the left
+        let mut ball = ball_at(100.0 - BALL_RADIUS + 2.0, 140.0, 300.0, 50.0);
+        assert!(obstacle::bounce_off_rect(&mut ball, block));
+        assert!(ball.dx < 0.0);
+        assert!(ball.dy > 0.0); // Only the side it hit turns around
+        assert_eq!(ball.x, 100.0 - BALL_RADIUS);
+
+        // Coming in from the right
+        let mut ball = ball_at(120.0 + BALL_RADIUS - 2.0, 140.0, -300.0, 0.0);
+        assert!(obstacle::bounce_off_rect(&mut ball, block));
+        assert!(ball.dx > 0.0);
+        assert_eq!(ball.x, 120.0 + BALL_RADIUS);
+    }
+
+    #[test]
+    fn test_ball_bounces_off_the_top_and_bottom() {
+        let block = Rect::new(100.0, 100.0, 20.0, 80.0);
+
+        let mut ball = ball_at(110.0, 100.0 - BALL_RADIUS + 2.0, 100.0, 200.0);
+        assert!(obstacle::bounce_off_rect(&mut ball, block));
+        assert!(ball.dy < 0.0);
+        assert!(ball.dx > 0.0);
+
+        let mut ball = ball_at(110.0, 180.0 + BALL_RADIUS - 2.0, 100.0, -200.0);
+        assert!(obstacle::bounce_off_rect(&mut ball, block));
+        assert!(ball.dy > 0.0);
+    }
+
+    #[test]
+    fn test_ball_clear_of_the_block_is_left_alone() {
+        let block = Rect::new(100.0, 100.0, 20.0, 80.0);
+
+        // Near a corner, but not touching it
+        let mut ball = ball_at(100.0 - BALL_RADIUS, 100.0 - BALL_RADIUS, 300.0, 300.0);
+        let before = (ball.x, ball.y, ball.dx, ball.dy);
+        assert!(!obstacle::bounce_off_rect(&mut ball, block));
+        assert_eq!((ball.x, ball.y, ball.dx, ball.dy), before);
+    }
+
+    #[test]
+    fn test_sliding_block_turns_around_at_the_ends() {
+        let mut block = Obstacle::sliding(0.0, 10.0, 100.0, 0.0, 50.0);
+        block.update(1.0);
+        assert_eq!(block.y, 50.0); // Stopped at the bottom of its track...
+        assert!(block.speed < 0.0); // ...and heading back up
+        block.update(1.0);
+        assert_eq!(block.y, 0.0);
+        assert!(block.speed > 0.0);
+
+        // Fixed blocks stay put
+        let mut fixed = Obstacle::fixed(5.0, 5.0, 10.0, 10.0);
+        fixed.update(1.0);
+        assert_eq!(fixed.y, 5.0);
+    }
+
+    #[test]
+    fn test_layout_leaves_the_serve_and_paddles_clear() {
+        for preset in CourtPreset::ALL {
+            let court = preset.court();
+            let (center_x, center_y) = court.center();
+            for block in obstacle::layout(&court) {
+                // The ball is served from the middle...
+                let mut ball = ball_at(center_x, center_y, 0.0, 0.0);
+                assert!(!obstacle::bounce_off_rect(&mut ball, block.rect()));
+
+                // ...and no block reaches the paddles, even while sliding
+                assert!(block.x > court.left_paddle_x() + PADDLE_WIDTH);
+                assert!(block.x + block.width < court.right_paddle_x());
+                assert!(block.min_y >= 0.0);
+                assert!(block.max_y + block.height <= court.height);
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use crate::{components::paddle::Paddle, game::constants::{PADDLE_HEIGHT, SCREEN_HEIGHT}};
+    use crate::{
+        components::paddle::Paddle,
+        game::constants::{
+            MAX_DEFLECTION_ANGLE, PADDLE_HEIGHT, PADDLE_HIT_COOLDOWN, PLAYER_PADDLE_SPEED,
+            SCREEN_HEIGHT,
+        },
+    };
 
     use approx::assert_relative_eq;
     use ggez::graphics::Color;
@@ -17,12 +23,12 @@ mod tests {
     fn test_paddle_move_within_bounds() {
         let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
         let initial_y = paddle.y;
-        
+
         // Move up
         paddle.move_by(-50.0);
         assert!(paddle.y < initial_y);
-        
-        // Move to initial position        
+
+        // Move to initial position
         paddle.move_by(50.0);
 
         //Move down
@@ -33,7 +39,7 @@ mod tests {
     #[test]
     fn test_paddle_upper_boundary() {
         let mut paddle = Paddle::new(0.0, 10.0);
-        
+
         // Try to move past top boundary
         paddle.move_by(-100.0);
         assert_relative_eq!(paddle.y, 0.0);
@@ -42,10 +48,119 @@ mod tests {
     #[test]
     fn test_paddle_lower_boundary() {
         let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT - PADDLE_HEIGHT - 10.0);
-        
+
         // Try to move past bottom boundary
         paddle.move_by(100.0);
         assert_relative_eq!(paddle.y, SCREEN_HEIGHT - PADDLE_HEIGHT);
     }
-    
-}
\ No newline at end of file
+
+    #[test]
+    fn test_paddle_keeps_its_own_size_and_speed() {
+        let mut paddle = Paddle::new(0.0, 0.0);
+        assert_relative_eq!(paddle.height, PADDLE_HEIGHT);
+        assert_relative_eq!(paddle.speed, PLAYER_PADDLE_SPEED);
+        assert_relative_eq!(paddle.speed_scale(), 1.0);
+
+        // A taller paddle stops sooner at the bottom of the court
+        paddle.height = 150.0;
+        paddle.move_by(SCREEN_HEIGHT);
+        assert_relative_eq!(paddle.y, SCREEN_HEIGHT - 150.0);
+
+        paddle.speed = PLAYER_PADDLE_SPEED * 1.5;
+        assert_relative_eq!(paddle.speed_scale(), 1.5);
+    }
+
+    #[test]
+    fn test_paddle_ramps_up_gradually() {
+        let mut paddle = Paddle::new(0.0, 0.0);
+
+        // Half the ramp time only gets us to half speed
+        paddle.accelerate_towards(500.0, 1000.0, 0.25);
+        assert_relative_eq!(paddle.velocity, 250.0);
+
+        // Never overshoots the target speed
+        paddle.accelerate_towards(500.0, 1000.0, 1.0);
+        assert_relative_eq!(paddle.velocity, 500.0);
+    }
+
+    #[test]
+    fn test_paddle_ramps_down_to_stop() {
+        let mut paddle = Paddle::new(0.0, 0.0);
+        paddle.velocity = -500.0;
+
+        paddle.accelerate_towards(0.0, 2000.0, 0.1);
+        assert_relative_eq!(paddle.velocity, -300.0);
+
+        paddle.accelerate_towards(0.0, 2000.0, 1.0);
+        assert_relative_eq!(paddle.velocity, 0.0);
+    }
+
+    #[test]
+    fn test_paddle_force_respects_mass() {
+        let mut light = Paddle::new(0.0, 0.0);
+        let mut heavy = Paddle::new(0.0, 0.0);
+        heavy.mass = light.mass * 2.0;
+
+        light.apply_force(1000.0, 0.01);
+        heavy.apply_force(1000.0, 0.01);
+
+        assert!(light.velocity > 0.0);
+        assert_relative_eq!(light.velocity, heavy.velocity * 2.0);
+    }
+
+    #[test]
+    fn test_paddle_friction_slows_sliding() {
+        let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
+        paddle.velocity = 300.0;
+
+        paddle.apply_force(0.0, 0.01);
+        assert!(paddle.velocity < 300.0);
+        assert!(paddle.acceleration < 0.0);
+    }
+
+    #[test]
+    fn test_deflection_angle_center_is_flat() {
+        let paddle = Paddle::new(0.0, 100.0);
+        assert_relative_eq!(paddle.deflection_angle(100.0 + PADDLE_HEIGHT / 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_deflection_angle_edges_are_steep() {
+        let paddle = Paddle::new(0.0, 100.0);
+        assert_relative_eq!(paddle.deflection_angle(100.0), -MAX_DEFLECTION_ANGLE);
+        assert_relative_eq!(
+            paddle.deflection_angle(100.0 + PADDLE_HEIGHT),
+            MAX_DEFLECTION_ANGLE
+        );
+
+        // Hits past the edge don't get any steeper
+        assert_relative_eq!(paddle.deflection_angle(0.0), -MAX_DEFLECTION_ANGLE);
+    }
+
+    #[test]
+    fn test_paddle_hit_cooldown() {
+        let mut paddle = Paddle::new(0.0, 0.0);
+        assert!(paddle.can_hit());
+
+        paddle.start_hit_cooldown();
+        assert!(!paddle.can_hit());
+
+        // Still cooling down halfway through
+        paddle.update_cooldown(PADDLE_HIT_COOLDOWN / 2.0);
+        assert!(!paddle.can_hit());
+
+        paddle.update_cooldown(PADDLE_HIT_COOLDOWN);
+        assert!(paddle.can_hit());
+        assert_relative_eq!(paddle.hit_cooldown, 0.0);
+    }
+
+    #[test]
+    fn test_paddle_stops_at_wall() {
+        let mut paddle = Paddle::new(0.0, 5.0);
+        paddle.velocity = -300.0;
+
+        paddle.move_by(-50.0);
+        assert_relative_eq!(paddle.y, 0.0);
+        assert_relative_eq!(paddle.velocity, 0.0);
+    }
+}
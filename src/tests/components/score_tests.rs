@@ -1,18 +1,35 @@
 #[cfg(test)]
 mod tests {
 
-    use crate::components::score::Score;
+    use crate::components::score::{Score, ScoreEvent, ScoreListener};
+    use crate::game::constants::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
-    use std::thread::sleep;
-    use std::time::Duration;
+    // Listener that remembers every event it was told about
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<ScoreEvent>,
+        resets: u32,
+    }
+
+    impl ScoreListener for Recorder {
+        fn on_score(&mut self, event: &ScoreEvent) {
+            self.events.push(*event);
+        }
+
+        fn on_reset(&mut self) {
+            self.resets += 1;
+        }
+    }
 
     #[test]
     fn test_new_score() {
         let score = Score::new();
         assert_eq!(score.player1, 0);
         assert_eq!(score.player2, 0);
-        assert!(score.flash_winner.is_none());
-        assert!(score.flash_start.is_none());
+        assert!(score.flash.borrow().winner.is_none());
+        assert!(score.flash.borrow().age.is_none());
     }
 
     #[test]
@@ -21,8 +38,8 @@ mod tests {
         score.increment_player1();
         assert_eq!(score.player1, 1);
         assert_eq!(score.player2, 0);
-        assert_eq!(score.flash_winner, Some(1));
-        assert!(score.flash_start.is_some());
+        assert_eq!(score.flash.borrow().winner, Some(1));
+        assert!(score.flash.borrow().age.is_some());
     }
 
     #[test]
@@ -31,8 +48,8 @@ mod tests {
         score.increment_player2();
         assert_eq!(score.player1, 0);
         assert_eq!(score.player2, 1);
-        assert_eq!(score.flash_winner, Some(2));
-        assert!(score.flash_start.is_some());
+        assert_eq!(score.flash.borrow().winner, Some(2));
+        assert!(score.flash.borrow().age.is_some());
     }
 
     #[test]
@@ -44,8 +61,8 @@ mod tests {
 
         assert_eq!(score.player1, 0);
         assert_eq!(score.player2, 0);
-        assert!(score.flash_winner.is_none());
-        assert!(score.flash_start.is_none());
+        assert!(score.flash.borrow().winner.is_none());
+        assert!(score.flash.borrow().age.is_none());
     }
 
     #[test]
@@ -53,7 +70,59 @@ mod tests {
         let mut score = Score::new();
         score.increment_player1();
 
-        assert!(score.flash_winner.is_some());
-        assert!(score.flash_start.is_some());
+        assert!(score.flash.borrow().winner.is_some());
+        assert!(score.flashing());
+
+        // Only game time passed in counts toward the highlight
+        score.update(SCORE_FLASH_DURATION - 0.5);
+        assert!(score.flashing());
+        score.update(0.5);
+        assert!(!score.flashing());
+        assert_eq!(score.flash.borrow().winner, Some(1));
+    }
+
+    #[test]
+    fn test_listeners_hear_score_changes() {
+        let mut score = Score::new();
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        score.add_listener(recorder.clone());
+
+        score.increment_player1();
+        score.increment_player2();
+        score.reset();
+
+        let recorder = recorder.borrow();
+        assert_eq!(
+            recorder.events,
+            vec![
+                ScoreEvent {
+                    scorer: 1,
+                    player1: 1,
+                    player2: 0
+                },
+                ScoreEvent {
+                    scorer: 2,
+                    player1: 1,
+                    player2: 1
+                },
+            ]
+        );
+        assert_eq!(recorder.resets, 1);
+    }
+
+    #[test]
+    fn test_win_game_starts_next_game() {
+        let mut score = Score::new();
+        score.increment_player2();
+        score.increment_player1();
+        score.win_game(1);
+
+        assert_eq!(score.games_player1, 1);
+        assert_eq!(score.games_player2, 0);
+        assert_eq!(score.player1, 0);
+        assert_eq!(score.player2, 0);
+
+        score.reset();
+        assert_eq!(score.games_player1, 0);
     }
 }
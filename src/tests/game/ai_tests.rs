@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{ball::Ball, paddle::Paddle},
+        game::{
+            ai::{
+                self, adaptive_push, AdaptiveAi, AdaptiveBounds, AiDifficulty, AiTuning,
+                PredictiveAi,
+            },
+            constants::{
+                ADAPTIVE_CLOSE_RALLY, ADAPTIVE_SCORE_SPAN, BALL_RADIUS, FIXED_TIMESTEP,
+                PADDLE_HEIGHT, PADDLE_WIDTH, SCREEN_HEIGHT, SCREEN_WIDTH,
+            },
+            controller::{GameView, PaddleController},
+        },
+    };
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_harder_levels_are_faster_and_more_accurate() {
+        let levels = [
+            AiDifficulty::Easy,
+            AiDifficulty::Medium,
+            AiDifficulty::Hard,
+            AiDifficulty::Impossible,
+        ];
+        for pair in levels.windows(2) {
+            let easier = pair[0].params();
+            let harder = pair[1].params();
+            assert!(harder.reaction_speed > easier.reaction_speed);
+            assert!(harder.error_margin <= easier.error_margin);
+            assert!(harder.hesitation_chance <= easier.hesitation_chance);
+        }
+    }
+
+    #[test]
+    fn test_sliders_fine_tune_a_difficulty() {
+        let hard = AiDifficulty::Hard.params();
+        let tuned = AiDifficulty::Hard.tuned_params(AiTuning {
+            speed: 2,
+            accuracy: -3,
+        });
+        assert!(tuned.reaction_speed > hard.reaction_speed);
+        assert!(tuned.error_margin > hard.error_margin);
+        assert!(tuned.hesitation_chance > hard.hesitation_chance);
+        assert_eq!(tuned.predictive, hard.predictive);
+        assert_eq!(AiDifficulty::Hard.tuned_params(AiTuning::default()), hard);
+
+        // Already perfect aim can't get any better
+        let sharp = AiTuning {
+            speed: 0,
+            accuracy: 5,
+        };
+        assert_relative_eq!(
+            AiDifficulty::Impossible.tuned_params(sharp).error_margin,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_slider_wraps_at_the_ends() {
+        assert_eq!(AiTuning::nudge(0, 1), 1);
+        assert_eq!(AiTuning::nudge(5, 1), -5);
+        assert_eq!(AiTuning::nudge(-5, -1), 5);
+    }
+
+    #[test]
+    fn test_difficulty_cycles() {
+        let mut difficulty = AiDifficulty::Easy;
+        for _ in 0..4 {
+            difficulty = difficulty.next();
+        }
+        assert_eq!(difficulty, AiDifficulty::Easy);
+    }
+
+    #[test]
+    fn test_predict_straight_path() {
+        let mut ball = Ball::new();
+        ball.dx = 300.0;
+        ball.dy = 0.0;
+        let paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        assert_relative_eq!(ai::predict_ball_y(&ball, &paddle), SCREEN_HEIGHT / 2.0);
+    }
+
+    #[test]
+    fn test_predict_bounces_off_wall() {
+        let mut ball = Ball::new();
+        let paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        let target_x = paddle.x - BALL_RADIUS;
+        ball.x = target_x - 100.0;
+        ball.y = BALL_RADIUS + 20.0;
+        ball.dx = 100.0;
+        ball.dy = -100.0;
+
+        // Travels 100px up, hits the top wall after 20px and comes back 80px
+        assert_relative_eq!(
+            ai::predict_ball_y(&ball, &paddle),
+            BALL_RADIUS + 80.0,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn test_impossible_ai_tracks_without_overshooting() {
+        let mut paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        let mut ball = Ball::new();
+        ball.dx = 300.0;
+        ball.y = PADDLE_HEIGHT / 2.0 + 5.0;
+
+        let params = AiDifficulty::Impossible.params();
+        let mut rng = rand::thread_rng(); // No hesitation or error at this level anyway
+        paddle.move_by(ai::chase_distance(&paddle, ball.y, &params, 1.0, &mut rng));
+        assert_relative_eq!(paddle.y + PADDLE_HEIGHT / 2.0, ball.y);
+    }
+
+    #[test]
+    fn test_ai_ignores_ball_moving_away() {
+        let paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        let mut ball = Ball::new();
+        ball.dx = -300.0;
+
+        let mut ai = PredictiveAi::new(AiDifficulty::Impossible, 0);
+        assert_relative_eq!(ai.desired_move(&view(&ball, &paddle, false), 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_ai_plays_the_left_paddle_too() {
+        let paddle = Paddle::new(0.0, 0.0);
+        let mut ball = Ball::new();
+        ball.dx = -300.0;
+        ball.dy = 0.0;
+
+        // Coming straight at the left paddle: it should head down to meet the ball
+        assert_relative_eq!(ai::predict_ball_y(&ball, &paddle), SCREEN_HEIGHT / 2.0);
+        let mut ai = PredictiveAi::new(AiDifficulty::Impossible, 0);
+        assert!(ai.desired_move(&view(&ball, &paddle, false), FIXED_TIMESTEP) > 0.0);
+
+        assert_relative_eq!(ai.target_y().unwrap(), SCREEN_HEIGHT / 2.0);
+
+        // Moving away from it, it stays put
+        ball.dx = 300.0;
+        assert_relative_eq!(ai.desired_move(&view(&ball, &paddle, false), 1.0), 0.0);
+        assert!(ai.target_y().is_none());
+    }
+
+    #[test]
+    fn test_ai_heads_to_the_middle_before_a_serve() {
+        let paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        let ball = Ball::new();
+        let mut ai = ai::controller_for(AiDifficulty::Easy, AiTuning::default(), 0);
+        assert!(ai.desired_move(&view(&ball, &paddle, true), FIXED_TIMESTEP) > 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_push_follows_the_score() {
+        // Level scores leave the AI as it is, however long the rallies
+        assert_eq!(adaptive_push(0, 0.0), 0.0);
+        assert_eq!(adaptive_push(0, 20.0), 0.0);
+
+        // Behind pushes harder and ahead eases off, a point at a time,
+        // up to the full amount ADAPTIVE_SCORE_SPAN points either way
+        let span = ADAPTIVE_SCORE_SPAN as i32;
+        let pushes: Vec<f32> = (-span..=span).map(|gap| adaptive_push(gap, 0.0)).collect();
+        assert!(pushes.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_relative_eq!(adaptive_push(-1, 0.0), 1.0 / ADAPTIVE_SCORE_SPAN);
+        assert_eq!(adaptive_push(-span, 0.0), 1.0);
+        assert_eq!(adaptive_push(-span - 4, 0.0), 1.0);
+        assert_eq!(adaptive_push(span + 4, 0.0), -1.0);
+    }
+
+    #[test]
+    fn test_adaptive_push_is_gentler_when_rallies_are_long() {
+        let short = adaptive_push(-3, 1.0);
+        let medium = adaptive_push(-3, ADAPTIVE_CLOSE_RALLY / 2.0);
+        let long = adaptive_push(-3, ADAPTIVE_CLOSE_RALLY);
+        assert!(short > medium && medium > long);
+        assert_relative_eq!(long, 0.5);
+        assert_eq!(adaptive_push(-3, ADAPTIVE_CLOSE_RALLY * 3.0), long);
+    }
+
+    #[test]
+    fn test_bounds_limit_how_far_the_ai_strays() {
+        let bounds = AdaptiveBounds {
+            min_speed: 200.0,
+            max_speed: 400.0,
+            min_error: 0.0,
+            max_error: 20.0,
+        };
+        let medium = AiDifficulty::Medium.params();
+
+        // No push keeps the difficulty's own numbers
+        let level = bounds.adjust(medium, 0.0);
+        assert_eq!(level, medium);
+
+        // All the way either way reaches the bounds, and no further
+        let hardest = bounds.adjust(medium, 1.0);
+        assert_eq!((hardest.reaction_speed, hardest.error_margin), (400.0, 0.0));
+        let easiest = bounds.adjust(medium, -5.0);
+        assert_eq!(
+            (easiest.reaction_speed, easiest.error_margin),
+            (200.0, 20.0)
+        );
+
+        // Halfway is halfway between the difficulty and the bound
+        let half = bounds.adjust(medium, 0.5);
+        assert_relative_eq!(half.reaction_speed, (medium.reaction_speed + 400.0) / 2.0);
+        assert_relative_eq!(half.error_margin, medium.error_margin / 2.0);
+
+        // A difficulty outside the bounds is brought inside them
+        let impossible = bounds.adjust(AiDifficulty::Impossible.params(), 0.0);
+        assert_eq!(impossible.reaction_speed, 400.0);
+        assert_eq!(impossible.hesitation_chance, 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_ai_moves_faster_when_behind() {
+        let paddle = Paddle::new(SCREEN_WIDTH - PADDLE_WIDTH, 0.0);
+        let mut ball = Ball::new();
+        ball.launch(300.0, 0.0, 1.0);
+        ball.y = SCREEN_HEIGHT - 50.0;
+        let step = |score_gap: i32| {
+            // Sharpest aim, so it never hesitates
+            let mut ai = AdaptiveAi::new(AiDifficulty::Medium, 0);
+            ai.tuning.accuracy = 5;
+            let view = GameView {
+                score_gap,
+                ..view(&ball, &paddle, false)
+            };
+            ai.desired_move(&view, FIXED_TIMESTEP)
+        };
+        assert!(step(-3) > step(0));
+        assert!(step(0) > step(3));
+    }
+
+    // What an AI sees, with nobody pressing anything and the score level
+    fn view<'a>(ball: &'a Ball, paddle: &'a Paddle, serving: bool) -> GameView<'a> {
+        GameView {
+            ball,
+            paddle,
+            input: 0.0,
+            serving,
+            momentum_physics: false,
+            score_gap: 0,
+            recent_rally: 0.0,
+        }
+    }
+}
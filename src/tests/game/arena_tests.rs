@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        arena::Arena,
+        constants::{BALL_RADIUS, FIXED_TIMESTEP, MAX_BALL_SPEED},
+        simulation::{GameMode, Simulation},
+    };
+
+    use approx::assert_relative_eq;
+
+    // A two-player match in `arena`, past the countdown with the ball still
+    fn match_in(arena: Arena) -> Simulation {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.arena = arena;
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+        sim
+    }
+
+    // Send the ball into the top wall and let it bounce
+    fn bounce_off_top(sim: &mut Simulation, dx: f32, dy: f32, spin: f32) {
+        sim.ball.x = sim.court.width / 2.0;
+        sim.ball.y = BALL_RADIUS + 2.0;
+        sim.ball.dx = dx;
+        sim.ball.dy = dy;
+        sim.ball.spin = spin;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(sim.ball.dy > 0.0);
+    }
+
+    #[test]
+    fn test_settings_cycle_through_every_arena() {
+        let mut arena = Arena::Standard;
+        for expected in Arena::ALL {
+            assert_eq!(arena, expected);
+            arena = arena.next();
+        }
+        assert_eq!(arena, Arena::Standard);
+    }
+
+    #[test]
+    fn test_rubber_walls_speed_the_ball_up() {
+        let mut sim = match_in(Arena::Rubber);
+        bounce_off_top(&mut sim, 300.0, -300.0, 0.0);
+        assert!(sim.ball.dy > 310.0);
+
+        // ...but never past the top speed
+        bounce_off_top(&mut sim, 0.0, -MAX_BALL_SPEED, 0.0);
+        assert!(sim.ball.speed() <= MAX_BALL_SPEED + 0.01);
+
+        // The standard court keeps the speed as it was
+        let mut sim = match_in(Arena::Standard);
+        bounce_off_top(&mut sim, 300.0, -300.0, 0.0);
+        assert_relative_eq!(sim.ball.dy, 300.0, epsilon = 0.01);
+    }
+
+    #[test]
+    fn test_spin_does_not_grip_the_walls_in_space() {
+        let mut space = match_in(Arena::Space);
+        bounce_off_top(&mut space, 300.0, -300.0, 2.0);
+        let mut standard = match_in(Arena::Standard);
+        bounce_off_top(&mut standard, 300.0, -300.0, 2.0);
+
+        // Spin still curves the path a little in flight, but only the
+        // standard court's wall changes the angle much
+        assert_relative_eq!(space.ball.dx, space.ball.dy, epsilon = 3.0);
+        assert!((standard.ball.dx - standard.ball.dy).abs() > 10.0);
+    }
+
+    #[test]
+    fn test_paddles_slide_on_ice() {
+        // Hold down for half a second, then let go for a fifth of a second
+        let speed_after_release = |arena: Arena| {
+            let mut sim = match_in(arena);
+            for _ in 0..60 {
+                sim.step(FIXED_TIMESTEP, [1.0, 0.0]);
+            }
+            for _ in 0..24 {
+                sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            }
+            sim.player1.velocity
+        };
+
+        // On ice the paddle is still going; normally it has long stopped
+        assert!(speed_after_release(Arena::Ice) > 50.0);
+        assert_eq!(speed_after_release(Arena::Standard), 0.0);
+    }
+}
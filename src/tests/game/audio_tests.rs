@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::audio::{AudioScheduler, Cue, MusicIntensity, SoundCategory, SoundMix};
+    use crate::game::constants::*;
+    use crate::game::events::{EventListener, GameEvent};
+
+    #[test]
+    fn test_cues_wait_for_their_time() {
+        let mut audio = AudioScheduler::new();
+        audio.schedule_in(1.0, Cue::CountdownBeep);
+        audio.schedule_in(0.5, Cue::Goal);
+
+        audio.advance(0.4);
+        assert!(audio.take_ready().is_empty());
+
+        // Both come due in one long step, soonest first
+        audio.advance(0.7);
+        assert_eq!(audio.take_ready(), vec![Cue::Goal, Cue::CountdownBeep]);
+        assert!(audio.take_ready().is_empty());
+    }
+
+    #[test]
+    fn test_zero_delay_plays_right_away() {
+        let mut audio = AudioScheduler::new();
+        audio.schedule_in(0.0, Cue::Goal);
+        assert_eq!(audio.take_ready(), vec![Cue::Goal]);
+    }
+
+    #[test]
+    fn test_many_small_steps_land_on_time() {
+        // 120 steps of 1/120 s add up to a little under or over 1.0
+        let mut audio = AudioScheduler::new();
+        audio.schedule_in(1.0, Cue::CountdownGo);
+        for _ in 0..119 {
+            audio.advance(1.0 / 120.0);
+        }
+        assert!(audio.take_ready().is_empty());
+        audio.advance(1.0 / 120.0);
+        assert_eq!(audio.take_ready(), vec![Cue::CountdownGo]);
+    }
+
+    #[test]
+    fn test_clear_drops_waiting_cues() {
+        let mut audio = AudioScheduler::new();
+        audio.schedule_in(0.0, Cue::Goal);
+        audio.schedule_in(1.0, Cue::CountdownBeep);
+        audio.clear();
+        audio.advance(2.0);
+        assert!(audio.take_ready().is_empty());
+    }
+
+    #[test]
+    fn test_bounces_queue_hit_sounds() {
+        let mut audio = AudioScheduler::new();
+        audio.on_event(&GameEvent::WallBounce);
+        audio.on_event(&GameEvent::ObstacleBounce);
+        assert_eq!(audio.take_ready(), vec![Cue::Bounce, Cue::Bounce]);
+        assert_eq!(Cue::Bounce.category(), SoundCategory::Hits);
+        assert_eq!(Cue::PaddleHit.category(), SoundCategory::Hits);
+        assert_eq!(Cue::Goal.category(), SoundCategory::Announcer);
+        assert_eq!(Cue::MenuClick.category(), SoundCategory::Ui);
+    }
+
+    #[test]
+    fn test_each_kind_of_sound_has_its_own_volume() {
+        let mut mix = SoundMix::default();
+        for category in SoundCategory::ALL {
+            assert_eq!(mix.level(category), 1.0);
+        }
+
+        // Turning the crowd down leaves everything else alone
+        mix.slide(SoundCategory::Crowd, -4);
+        assert_eq!(mix.volume(SoundCategory::Crowd), MAX_SOUND_VOLUME - 4);
+        assert_eq!(mix.volume(SoundCategory::Hits), MAX_SOUND_VOLUME);
+
+        // Past full wraps round to off
+        mix.slide(SoundCategory::Ui, 1);
+        assert_eq!(mix.level(SoundCategory::Ui), 0.0);
+    }
+
+    // Feed the music `seconds` of play at `speed`, a frame at a time
+    fn follow(music: &mut MusicIntensity, seconds: f32, speed: f32) {
+        for _ in 0..(seconds * 60.0) as usize {
+            music.update(1.0 / 60.0, speed);
+        }
+    }
+
+    #[test]
+    fn test_music_stays_calm_at_serve_speed() {
+        let mut music = MusicIntensity::new();
+        follow(&mut music, 10.0, BALL_SPEED);
+        assert_eq!(music.level(), 0.0);
+    }
+
+    #[test]
+    fn test_music_builds_up_gradually_with_a_fast_ball() {
+        let mut music = MusicIntensity::new();
+        let mut last = 0.0;
+        for _ in 0..60 {
+            follow(&mut music, 0.5, MAX_BALL_SPEED);
+            // Never more than the allowed change in half a second
+            assert!(music.level() - last <= MUSIC_INTENSITY_RATE * 0.5 + 0.001);
+            assert!(music.level() >= last);
+            last = music.level();
+        }
+        // ...and it stops at the cap however long the rally goes on
+        assert!((music.level() - MUSIC_MAX_INTENSITY).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_music_settles_after_a_fast_rally() {
+        let mut music = MusicIntensity::new();
+        follow(&mut music, 15.0, MAX_BALL_SPEED);
+        let peak = music.level();
+
+        // One slow second only takes it down a little
+        follow(&mut music, 1.0, 0.0);
+        assert!(music.level() >= peak - MUSIC_INTENSITY_RATE - 0.001);
+        follow(&mut music, 15.0, 0.0);
+        assert_eq!(music.level(), 0.0);
+
+        follow(&mut music, 15.0, MAX_BALL_SPEED);
+        music.reset();
+        assert_eq!(music.level(), 0.0);
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{bench::FrameTimes, scene::Scene, simulation::GameMode, state::GameState};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_report_gives_percentiles() {
+        let mut times = FrameTimes::new(100);
+        // 1 ms to 100 ms, out of order
+        for ms in (1..=100).rev() {
+            times.record(ms as f32 / 1000.0);
+        }
+        assert!(times.done());
+        let report = times.report();
+        assert_eq!(report.frames, 100);
+        assert_relative_eq!(report.mean, 0.0505, epsilon = 1e-5);
+        assert_relative_eq!(report.p50, 0.050);
+        assert_relative_eq!(report.p90, 0.090);
+        assert_relative_eq!(report.p99, 0.099);
+        assert_relative_eq!(report.worst, 0.100);
+        assert!(report
+            .to_string()
+            .starts_with("Rendered 100 frames, 50.50 ms on average"));
+    }
+
+    #[test]
+    fn test_frames_past_the_end_are_left_out() {
+        let mut times = FrameTimes::new(2);
+        assert_eq!(times.report().frames, 0);
+        for _ in 0..5 {
+            times.record(0.01);
+        }
+        assert_eq!(times.times.len(), 2);
+        assert_relative_eq!(times.report().p99, 0.01);
+    }
+
+    #[test]
+    fn test_bench_plays_on_until_every_frame_is_timed() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_bench(3);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.mode, GameMode::Demo);
+
+        // A finished match is followed by another one
+        state.sim.winner = Some(1);
+        state.step(0.0);
+        assert_eq!(state.scene, Scene::GameOver);
+        state.run_bench(0.016);
+        assert_eq!(state.scene, Scene::Playing);
+        assert!(!state.should_exit);
+
+        state.run_bench(0.016);
+        state.run_bench(0.016);
+        assert!(state.should_exit);
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        bot_server::BotServer,
+        constants::FIXED_TIMESTEP,
+        scene::Scene,
+        simulation::{GameMode, Simulation},
+        state::GameState,
+    };
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::Duration;
+
+    // A server on a free port with a bot connected to it
+    fn connected_pair() -> (BotServer, TcpStream) {
+        let mut server = BotServer::bind("127.0.0.1:0").unwrap();
+        let bot = TcpStream::connect(server.address().unwrap()).unwrap();
+        bot.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        for _ in 0..100 {
+            server.poll();
+            if server.connected() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(server.connected());
+        (server, bot)
+    }
+
+    // Keep polling until the server has read a command (or give up)
+    fn poll_until(server: &mut BotServer, direction: f32) {
+        for _ in 0..100 {
+            server.poll();
+            if server.direction == direction {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_bot_receives_state_and_sends_moves() {
+        let (mut server, mut bot) = connected_pair();
+        let mut sim = Simulation::new(GameMode::Bot);
+        sim.start_match(GameMode::Bot);
+
+        // One JSON line per step, describing the match from the bot's side
+        server.send(&sim);
+        let mut reader = BufReader::new(bot.try_clone().unwrap());
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let state: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(state["tick"], 0);
+        assert_eq!(state["serving"], true);
+        assert_eq!(state["you"]["x"], sim.player2.x as f64);
+        assert!(state["winner"].is_null());
+
+        // Commands are clamped to full speed
+        bot.write_all(b"{\"move\": 5}\n").unwrap();
+        poll_until(&mut server, 1.0);
+        assert_eq!(server.direction, 1.0);
+    }
+
+    #[test]
+    fn test_bad_commands_get_an_error() {
+        let (mut server, mut bot) = connected_pair();
+        bot.write_all(b"{\"jump\": true}\n{\"move\": -0.5}\n").unwrap();
+        poll_until(&mut server, -0.5);
+        assert_eq!(server.direction, -0.5); // The good line still counts
+
+        let mut reader = BufReader::new(bot);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let reply: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(reply["error"].is_string());
+    }
+
+    #[test]
+    fn test_disconnect_stops_the_paddle() {
+        let (mut server, mut bot) = connected_pair();
+        bot.write_all(b"{\"move\": 1}\n").unwrap();
+        poll_until(&mut server, 1.0);
+        drop(bot);
+
+        for _ in 0..100 {
+            server.poll();
+            if !server.connected() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!server.connected());
+        assert_eq!(server.direction, 0.0);
+    }
+
+    #[test]
+    fn test_bot_drives_right_paddle_in_a_match() {
+        let (server, mut bot) = connected_pair();
+        let mut state = GameState::new(GameMode::VsAi);
+        state.bot = Some(server);
+        state.start_match(GameMode::Bot);
+        assert_eq!(state.scene, Scene::Playing);
+
+        bot.write_all(b"{\"move\": 1}\n").unwrap();
+        let start_y = state.sim.player2.y;
+        for _ in 0..200 {
+            state.step(FIXED_TIMESTEP);
+            if state.sim.player2.y > start_y {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(state.sim.player2.y > start_y);
+    }
+
+    // Turn on lockstep and wait until the server has seen it
+    fn ask_for_lockstep(server: &mut BotServer, bot: &mut TcpStream) {
+        bot.write_all(b"{\"lockstep\": true}\n").unwrap();
+        for _ in 0..100 {
+            server.poll();
+            if server.lockstep {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("lockstep was never turned on");
+    }
+
+    #[test]
+    fn test_lockstep_waits_for_the_move() {
+        let (mut server, mut bot) = connected_pair();
+        ask_for_lockstep(&mut server, &mut bot);
+        server.send(&Simulation::new(GameMode::Bot));
+
+        // A slow bot answers the state after a while
+        let player = thread::spawn(move || {
+            let mut reader = BufReader::new(bot.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            bot.write_all(b"{\"move\": 1, \"tick\": 0}\n").unwrap();
+            bot
+        });
+        assert!(server.receive_move(Duration::from_secs(5)));
+        assert_eq!(server.direction, 1.0);
+        assert!(server.take_waited() > 0.0); // Left out of game time
+        assert_eq!(server.take_waited(), 0.0);
+        player.join().unwrap();
+    }
+
+    #[test]
+    fn test_lockstep_gives_up_and_ignores_late_moves() {
+        let (mut server, mut bot) = connected_pair();
+        ask_for_lockstep(&mut server, &mut bot);
+        let sim = Simulation::new(GameMode::Bot);
+        server.send(&sim);
+        assert!(!server.receive_move(Duration::from_millis(20)));
+
+        // The bot is told it missed the tick
+        let mut reader = BufReader::new(bot.try_clone().unwrap());
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap(); // The state
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.contains("no move for tick 0"));
+
+        // Its late answer to tick 0 doesn't count for tick 1
+        server.send(&sim);
+        bot.write_all(b"{\"move\": 1, \"tick\": 0}\n").unwrap();
+        assert!(!server.receive_move(Duration::from_millis(50)));
+        assert_eq!(server.direction, 0.0);
+    }
+}
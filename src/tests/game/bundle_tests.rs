@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::audio::{SoundCategory, SoundMix};
+    use crate::game::bundle::{Bundle, BundleError};
+    use crate::game::constants::{BUNDLE_VERSION, MAX_SAFE_MARGIN, MAX_SOUND_VOLUME};
+    use crate::game::scene::SettingsItem;
+    use crate::game::simulation::GameMode;
+    use crate::game::state::GameState;
+    use crate::graphics::theme::ThemePreset;
+    use crate::tests::support::temp_file;
+
+    #[test]
+    fn test_export_then_import_restores_setup() {
+        let path = temp_file("bundle_roundtrip", "setup.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.bundle_path = Some(path.clone());
+        state.change_setting(SettingsItem::PointsToWin);
+        state.change_setting(SettingsItem::LowPower);
+        state.next_theme();
+        state.career.lifetime.wins = 7;
+        state.change_setting(SettingsItem::ExportSetup);
+        assert!(state.status.as_ref().unwrap().starts_with("Setup saved"));
+
+        // A different machine with default settings picks it up
+        let mut other = GameState::new(GameMode::VsAi);
+        other.bundle_path = Some(path.clone());
+        other.change_setting(SettingsItem::ImportSetup);
+        assert!(other.status.as_ref().unwrap().starts_with("Setup loaded"));
+        assert_eq!(other.settings(), state.settings());
+        assert_eq!(other.theme_preset, ThemePreset::Neon);
+        assert_eq!(other.career.lifetime.wins, 7);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_newer_versions_are_refused() {
+        let state = GameState::new(GameMode::VsAi);
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        assert!(bundle.validate().is_ok());
+
+        bundle.version = BUNDLE_VERSION + 1;
+        assert!(matches!(bundle.validate(), Err(BundleError::Version(_))));
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_refused() {
+        let state = GameState::new(GameMode::VsAi);
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        bundle.settings.options.rules.points_to_win = 1000;
+        let error = bundle.validate().unwrap_err();
+        assert!(error.to_string().contains("points to win"));
+
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        bundle.settings.options.ai_tuning.speed = 9;
+        let error = bundle.validate().unwrap_err();
+        assert!(error.to_string().contains("AI speed"));
+
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        bundle.settings.sound_volume = MAX_SOUND_VOLUME + 1;
+        let error = bundle.validate().unwrap_err();
+        assert!(error.to_string().contains("sound volume"));
+
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        bundle.settings.sound_mix.crowd = MAX_SOUND_VOLUME + 1;
+        let error = bundle.validate().unwrap_err();
+        assert!(error.to_string().contains("crowd volume"));
+
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        bundle.settings.safe_margin = MAX_SAFE_MARGIN + 1;
+        let error = bundle.validate().unwrap_err();
+        assert!(error.to_string().contains("safe area"));
+    }
+
+    #[test]
+    fn test_bad_file_leaves_settings_alone() {
+        let path = temp_file("bundle_broken", "setup.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ \"version\": 1 }").unwrap();
+
+        let mut state = GameState::new(GameMode::VsAi);
+        state.bundle_path = Some(path.clone());
+        let before = state.settings();
+        state.import_setup();
+        assert!(state
+            .status
+            .as_ref()
+            .unwrap()
+            .starts_with("Could not import"));
+        assert_eq!(state.settings(), before);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_fields_are_reported() {
+        let state = GameState::new(GameMode::VsAi);
+        let bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        let text = serde_json::to_string_pretty(&bundle).unwrap();
+
+        // Both among the match options and the rest of the settings
+        for (name, typo) in [("adaptive_ai", "adaptive_al"), ("coach_tips", "coach_tipz")] {
+            let text = text.replace(&format!("\"{}\"", name), &format!("\"{}\"", typo));
+            let error = Bundle::parse(&text).unwrap_err();
+            assert!(matches!(error, BundleError::Format(_)));
+            assert!(error
+                .to_string()
+                .contains(&format!("unknown field `{}`", typo)));
+        }
+    }
+
+    #[test]
+    fn test_version_is_checked_before_fields() {
+        // A newer file with options we don't know yet says so plainly
+        let text = "{ \"version\": 99, \"new_option\": true }";
+        assert!(matches!(Bundle::parse(text), Err(BundleError::Version(99))));
+
+        let error = Bundle::parse("{ \"settings\": {} }").unwrap_err();
+        assert!(error.to_string().contains("version"));
+    }
+
+    #[test]
+    fn test_older_setups_keep_screen_effects_on() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.change_setting(SettingsItem::ScreenEffects);
+        let bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        let text = serde_json::to_string_pretty(&bundle)
+            .unwrap()
+            .replace("\"screen_effects\": false,", "");
+
+        let loaded = Bundle::parse(&text).unwrap();
+        assert!(loaded.settings.screen_effects);
+    }
+
+    #[test]
+    fn test_older_setups_keep_holiday_events_on() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.change_setting(SettingsItem::HolidayEvents);
+        let bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        let text = serde_json::to_string_pretty(&bundle)
+            .unwrap()
+            .replace(",\n    \"holiday_events\": false", "");
+        assert!(!text.contains("holiday_events"));
+
+        let loaded = Bundle::parse(&text).unwrap();
+        assert!(loaded.settings.holiday_events);
+    }
+
+    #[test]
+    fn test_older_setups_keep_point_replays_on() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.change_setting(SettingsItem::PointReplays);
+        let bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        let text = serde_json::to_string_pretty(&bundle)
+            .unwrap()
+            .replace(",\n    \"point_replays\": false", "");
+        assert!(!text.contains("point_replays"));
+
+        let loaded = Bundle::parse(&text).unwrap();
+        assert!(loaded.settings.point_replays);
+    }
+
+    #[test]
+    fn test_older_setups_play_every_sound_at_full() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.slide_setting(SettingsItem::SoundVolume(SoundCategory::Hits), -3);
+        let mut bundle = Bundle::new(state.settings(), state.career.lifetime.clone());
+        let mut json = serde_json::to_value(&bundle).unwrap();
+        json["settings"]
+            .as_object_mut()
+            .unwrap()
+            .remove("sound_mix");
+
+        let loaded = Bundle::parse(&json.to_string()).unwrap();
+        assert_eq!(loaded.settings.sound_mix, SoundMix::default());
+        bundle.settings.sound_mix = SoundMix::default();
+        assert_eq!(loaded, bundle);
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::changelog::{current_version, unseen_releases, CHANGELOG};
+
+    #[test]
+    fn test_newest_entry_matches_the_running_version() {
+        // Bumping the version in Cargo.toml needs a changelog entry too
+        assert_eq!(CHANGELOG[0].version, current_version());
+        assert!(CHANGELOG.iter().all(|release| !release.changes.is_empty()));
+    }
+
+    #[test]
+    fn test_unseen_releases() {
+        assert!(unseen_releases(current_version()).is_empty());
+
+        // First run, or a version we no longer list: just the newest release
+        let unseen = unseen_releases("");
+        assert_eq!(unseen.len(), 1);
+        assert_eq!(unseen[0].version, current_version());
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::checksum::fnv1a;
+
+    #[test]
+    fn test_fnv1a_matches_the_published_values() {
+        assert_eq!(fnv1a(b""), 0x811c_9dc5);
+        assert_eq!(fnv1a(b"a"), 0xe40c_292c);
+        assert_eq!(fnv1a(b"foobar"), 0xbf9c_f968);
+    }
+}
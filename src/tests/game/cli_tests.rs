@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        ai::AiDifficulty,
+        cli::{CliError, CliOptions},
+        constants::{SCREEN_HEIGHT, SCREEN_WIDTH},
+        court::CourtPreset,
+        simulation::{GameMode, Simulation},
+    };
+
+    // Parse a command line written as one string
+    fn parse(line: &str) -> Result<CliOptions, CliError> {
+        CliOptions::parse(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_no_options_keeps_the_defaults() {
+        let options = parse("").unwrap();
+        assert_eq!(options, CliOptions::default());
+        assert_eq!(
+            (options.width, options.height),
+            (SCREEN_WIDTH, SCREEN_HEIGHT)
+        );
+        assert!(options.vsync);
+    }
+
+    #[test]
+    fn test_reads_every_option() {
+        let options = parse(
+            "--width 1280 --height=720 --no-vsync --points 7 --difficulty HARD --court tall \
+             --seed 42 --headless-simulate 1000 --bench-render 600 --watch 127.0.0.1:7879",
+        )
+        .unwrap();
+        assert_eq!((options.width, options.height), (1280.0, 720.0));
+        assert!(!options.vsync);
+        assert_eq!(options.points_to_win, Some(7));
+        assert_eq!(options.difficulty, Some(AiDifficulty::Hard));
+        assert_eq!(options.court, Some(CourtPreset::Tall));
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.headless_ticks, Some(1000));
+        assert_eq!(options.bench_frames, Some(600));
+        assert_eq!(options.watch.as_deref(), Some("127.0.0.1:7879"));
+        assert!(parse("-h").unwrap().help);
+        assert!(parse("--spectate").unwrap().spectate);
+
+        let options = parse("--watch 127.0.0.1:7879 --capture match.jsonl").unwrap();
+        assert_eq!(options.capture.as_deref(), Some("match.jsonl"));
+        let options = parse("--replay-capture match.jsonl").unwrap();
+        assert_eq!(options.replay_capture.as_deref(), Some("match.jsonl"));
+    }
+
+    #[test]
+    fn test_bad_options_are_reported() {
+        assert_eq!(
+            parse("--speed 3"),
+            Err(CliError::Unknown("--speed".to_string()))
+        );
+        assert_eq!(
+            parse("--seed"),
+            Err(CliError::MissingValue("--seed".to_string()))
+        );
+        // Only the winning scores the settings screen offers
+        assert!(matches!(parse("--points 4"), Err(CliError::Invalid(..))));
+        assert!(matches!(
+            parse("--difficulty brutal"),
+            Err(CliError::Invalid(..))
+        ));
+        assert!(matches!(parse("--court round"), Err(CliError::Invalid(..))));
+        // There's nothing to save without a match to watch
+        assert!(matches!(
+            parse("--capture match.jsonl"),
+            Err(CliError::Invalid(..))
+        ));
+        assert!(matches!(parse("--width -5"), Err(CliError::Invalid(..))));
+        assert!(matches!(parse("--height 10"), Err(CliError::Invalid(..))));
+    }
+
+    #[test]
+    fn test_options_are_applied_to_the_match() {
+        let options = parse("--points 11 --difficulty easy --court wide").unwrap();
+        let mut sim = Simulation::new(GameMode::VsAi);
+        options.apply(&mut sim);
+        assert_eq!(sim.rules.points_to_win, 11);
+        assert_eq!(sim.ai_difficulty, AiDifficulty::Easy);
+        assert_eq!(sim.court, CourtPreset::Wide.court());
+        assert_eq!(sim.player1.court_height, sim.court.height);
+    }
+}
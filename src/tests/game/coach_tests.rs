@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::coach::{tip_for, Coach};
+    use crate::game::constants::*;
+    use crate::game::events::{EventListener, GameEvent};
+    use crate::game::recording::{Contact, Miss};
+
+    // A flat, easy ball that went by `offset` half paddle heights from the middle
+    fn miss(player: u8, offset: f32) -> Miss {
+        Miss {
+            player,
+            offset,
+            angle: 5.0,
+            speed: 300.0,
+        }
+    }
+
+    // Player 1's return, `offset` along the paddle
+    fn return_at(offset: f32) -> Contact {
+        Contact {
+            player: 1,
+            offset,
+            angle_in: 0.0,
+            angle_out: 0.0,
+            speed: 300.0,
+        }
+    }
+
+    #[test]
+    fn test_tip_says_which_way_the_paddle_was_off() {
+        assert!(tip_for(&miss(1, 2.0), None).contains("too high"));
+        assert!(tip_for(&miss(1, -2.0), None).contains("too low"));
+        assert!(tip_for(&miss(1, 0.9), None).contains("end of your paddle"));
+    }
+
+    #[test]
+    fn test_fast_steep_and_edge_balls_get_their_own_tips() {
+        let fast = Miss {
+            speed: COACH_FAST_BALL,
+            ..miss(1, COACH_FAR_MISS + 1.0)
+        };
+        assert!(tip_for(&fast, None).contains("fast"));
+
+        let steep = Miss {
+            angle: -COACH_STEEP_ANGLE - 5.0,
+            ..miss(1, 2.0)
+        };
+        assert!(tip_for(&steep, None).contains("steep"));
+
+        let edge = return_at(0.95);
+        assert!(tip_for(&miss(1, 2.0), Some(&edge)).contains("edge"));
+        let middle = return_at(0.0);
+        assert!(tip_for(&miss(1, 2.0), Some(&middle)).contains("too high"));
+    }
+
+    #[test]
+    fn test_coach_tips_player_one_after_a_lost_point_for_a_while() {
+        let mut coach = Coach::default();
+        coach.on_event(&GameEvent::BallMissed { miss: miss(2, 2.0) });
+        assert!(coach.tip.is_none()); // Only player 1 is coached
+
+        // The last return of the rally counts, not one from an earlier point
+        coach.on_event(&GameEvent::PaddleHit {
+            contact: return_at(1.0),
+            speed: 300.0,
+        });
+        coach.on_event(&GameEvent::PointScored { winner: 1 });
+        coach.on_event(&GameEvent::BallMissed {
+            miss: miss(1, -2.0),
+        });
+        assert!(coach.tip.unwrap().contains("too low"));
+
+        coach.update(COACH_TIP_DURATION - 0.1);
+        assert!(coach.tip.is_some());
+        coach.update(0.1);
+        assert!(coach.tip.is_none());
+    }
+}
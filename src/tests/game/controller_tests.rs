@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{ball::Ball, paddle::Paddle},
+        game::{
+            constants::{PLAYER_PADDLE_SPEED, SCREEN_HEIGHT},
+            controller::{GameView, HumanController, PaddleController},
+        },
+    };
+
+    use approx::assert_relative_eq;
+
+    // What a person's controller sees while holding `input`
+    fn view<'a>(ball: &'a Ball, paddle: &'a Paddle, input: f32, momentum: bool) -> GameView<'a> {
+        GameView {
+            ball,
+            paddle,
+            input,
+            serving: false,
+            momentum_physics: momentum,
+            score_gap: 0,
+            recent_rally: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_human_ramps_up_to_full_speed() {
+        let ball = Ball::new();
+        let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
+
+        // The first step is slower than full speed, but keeps getting faster
+        let first = HumanController.desired_move(&view(&ball, &paddle, 1.0, false), 0.01);
+        assert!(first > 0.0 && first < PLAYER_PADDLE_SPEED * 0.01);
+
+        paddle.velocity = PLAYER_PADDLE_SPEED;
+        let full = HumanController.desired_move(&view(&ball, &paddle, 1.0, false), 0.01);
+        assert_relative_eq!(full, PLAYER_PADDLE_SPEED * 0.01);
+    }
+
+    #[test]
+    fn test_human_steers_at_the_paddles_own_speed() {
+        let ball = Ball::new();
+        let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
+        paddle.speed = PLAYER_PADDLE_SPEED / 2.0;
+
+        // Once up to speed, a slower paddle stays at its own top speed
+        paddle.velocity = PLAYER_PADDLE_SPEED;
+        let slowed = HumanController.desired_move(&view(&ball, &paddle, 1.0, false), 0.01);
+        assert!(slowed < PLAYER_PADDLE_SPEED * 0.01);
+        paddle.velocity = paddle.speed;
+        let held = HumanController.desired_move(&view(&ball, &paddle, 1.0, false), 0.01);
+        assert_relative_eq!(held, paddle.speed * 0.01);
+
+        // With momentum, the push is balanced by friction at that speed too
+        let sliding = HumanController.desired_move(&view(&ball, &paddle, 1.0, true), 0.01);
+        assert_relative_eq!(sliding, paddle.speed * 0.01, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_human_does_not_move_the_paddle_itself() {
+        let ball = Ball::new();
+        let paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
+        HumanController.desired_move(&view(&ball, &paddle, -1.0, true), 0.1);
+        assert_relative_eq!(paddle.y, SCREEN_HEIGHT / 2.0);
+        assert_relative_eq!(paddle.velocity, 0.0);
+    }
+
+    #[test]
+    fn test_momentum_keeps_sliding_after_release() {
+        let ball = Ball::new();
+        let mut paddle = Paddle::new(0.0, SCREEN_HEIGHT / 2.0);
+        paddle.velocity = 300.0;
+        let distance = HumanController.desired_move(&view(&ball, &paddle, 0.0, true), 0.01);
+        assert!(distance > 0.0);
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        constants::{PADDLE_HEIGHT, PADDLE_WIDTH, SCREEN_HEIGHT, SCREEN_WIDTH},
+        court::{Court, CourtPreset},
+        simulation::GameMode,
+        state::GameState,
+    };
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_classic_court_fills_window() {
+        let court = CourtPreset::Classic.court();
+        assert!(court.fills_window());
+        assert_eq!(court.offset(), [0.0, 0.0]);
+        assert_relative_eq!(court.left_paddle_x(), 0.0);
+        assert_relative_eq!(court.right_paddle_x(), SCREEN_WIDTH - PADDLE_WIDTH);
+    }
+
+    #[test]
+    fn test_inset_paddles() {
+        let court = Court::new(800.0, 500.0, 40.0);
+        assert_relative_eq!(court.left_paddle_x(), 40.0);
+        assert_relative_eq!(court.right_paddle_x(), 800.0 - PADDLE_WIDTH - 40.0);
+        assert_relative_eq!(court.paddle_start_y(PADDLE_HEIGHT), (500.0 - PADDLE_HEIGHT) / 2.0);
+        assert_eq!(court.center(), (400.0, 250.0));
+    }
+
+    #[test]
+    fn test_small_court_is_centered_in_window() {
+        let court = Court::new(SCREEN_WIDTH - 100.0, SCREEN_HEIGHT - 50.0, 0.0);
+        assert!(!court.fills_window());
+        assert_eq!(court.offset(), [50.0, 25.0]);
+    }
+
+    #[test]
+    fn test_apply_court_moves_paddles_and_ball() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.sim.apply_court(CourtPreset::Narrow);
+
+        let court = CourtPreset::Narrow.court();
+        assert_eq!(state.sim.court_preset, CourtPreset::Narrow);
+        assert_relative_eq!(state.sim.player2.x, court.right_paddle_x());
+        assert_relative_eq!(state.sim.player2.court_height, court.height);
+        assert_relative_eq!(state.sim.ball.x, court.width / 2.0);
+    }
+
+    #[test]
+    fn test_wide_and_tall_courts_keep_their_shape_in_the_window() {
+        let wide = CourtPreset::Wide.court();
+        assert!(wide.width > wide.height * 1.9);
+        assert_eq!(wide.offset(), [0.0, (SCREEN_HEIGHT - wide.height) / 2.0]);
+        let tall = CourtPreset::Tall.court();
+        assert!(tall.height > tall.width);
+        assert_eq!(tall.offset(), [(SCREEN_WIDTH - tall.width) / 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_presets_are_found_by_name() {
+        assert_eq!(CourtPreset::from_name("WIDE"), Some(CourtPreset::Wide));
+        assert_eq!(
+            CourtPreset::from_name("pulled-in"),
+            Some(CourtPreset::PulledIn)
+        );
+        assert_eq!(CourtPreset::from_name("round"), None);
+    }
+
+    #[test]
+    fn test_presets_cycle() {
+        let mut preset = CourtPreset::Classic;
+        for _ in 0..CourtPreset::ALL.len() {
+            preset = preset.next();
+        }
+        assert_eq!(preset, CourtPreset::Classic);
+    }
+}
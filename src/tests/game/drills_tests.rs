@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::components::ball::Ball;
+    use crate::game::constants::*;
+    use crate::game::court::CourtPreset;
+    use crate::game::drills::{Drill, DrillBook, Shot};
+
+    // A drill with `count` flat shots from the middle
+    fn drill(name: &str, count: usize) -> Drill {
+        Drill {
+            name: name.to_string(),
+            shots: vec![
+                Shot {
+                    height: 0.5,
+                    speed: 300.0,
+                    angle: 0.0,
+                };
+                count
+            ],
+        }
+    }
+
+    #[test]
+    fn test_return_becomes_a_shot_the_other_way() {
+        let court = CourtPreset::Narrow.court();
+        let mut ball = Ball::new();
+        ball.y = court.height / 4.0;
+        ball.launch(450.0, 0.25, 1.0);
+        let shot = Shot::from_return(&ball, &court);
+        assert!((shot.height - 0.25).abs() < 0.001);
+        assert!((shot.speed - 450.0).abs() < 0.01);
+        assert!((shot.angle - 0.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_drills_that_cant_be_fired_are_caught() {
+        assert!(drill("flat", 3).validate().is_ok());
+        assert!(drill("", 3).validate().is_err());
+        assert!(drill("empty", 0).validate().is_err());
+        assert!(drill("long", DRILL_MAX_SHOTS + 1).validate().is_err());
+
+        let mut off_court = drill("off court", 1);
+        off_court.shots[0].height = 1.5;
+        assert!(off_court.validate().is_err());
+        let mut broken = drill("broken", 1);
+        broken.shots[0].speed = f32::NAN;
+        assert!(broken.validate().is_err());
+    }
+
+    #[test]
+    fn test_same_name_replaces_and_a_full_book_says_no() {
+        let mut book = DrillBook::default();
+        book.add(drill("serves", 2)).unwrap();
+        book.add(drill("serves", 5)).unwrap();
+        assert_eq!(book.drills.len(), 1);
+        assert_eq!(book.drills[0].shots.len(), 5);
+
+        for index in 1..MAX_DRILLS {
+            book.add(drill(&format!("drill {}", index), 1)).unwrap();
+        }
+        assert!(book.add(drill("one too many", 1)).is_err());
+        assert!(book.add(drill("serves", 1)).is_ok()); // Saving over one still works
+    }
+
+    #[test]
+    fn test_saved_drills_load_back_and_damage_is_reported() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_drill_file_{}", std::process::id()));
+        let path = dir.join(DRILLS_FILE_NAME);
+        assert_eq!(DrillBook::load(&path).unwrap(), DrillBook::default()); // No file yet
+
+        let mut book = DrillBook::default();
+        book.add(drill("top corner", 4)).unwrap();
+        book.save(&path).unwrap();
+        assert_eq!(DrillBook::load(&path).unwrap(), book);
+
+        std::fs::write(&path, r#"{ "drills": [{ "name": "x", "shots": [] }] }"#).unwrap();
+        let error = DrillBook::load(&path).unwrap_err();
+        assert!(error.contains(DRILLS_FILE_NAME) && error.contains("shots"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
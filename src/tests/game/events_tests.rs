@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        ai::AiDifficulty,
+        constants::FIXED_TIMESTEP,
+        events::GameEvent,
+        rules::MatchEnd,
+        simulation::{GameMode, Simulation},
+    };
+
+    // Play until the first point is over, keeping every event along the way
+    fn first_point(sim: &mut Simulation) -> Vec<GameEvent> {
+        let mut events = sim.events.clone(); // The first countdown number
+        while sim.stats.points_played == 0 {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            events.extend(sim.events.iter().copied());
+        }
+        events
+    }
+
+    #[test]
+    fn test_a_point_is_announced_in_order() {
+        let mut sim = Simulation::new(GameMode::Demo);
+        sim.ai_difficulty = AiDifficulty::Easy; // Misses now and then
+        sim.seed(7);
+        sim.start_match(GameMode::Demo);
+        let events = first_point(&mut sim);
+
+        let ticks = events
+            .iter()
+            .take_while(|event| **event == GameEvent::CountdownTick)
+            .count();
+        assert_eq!(ticks, 3); // "3", "2", "1"
+        assert!(matches!(events[3], GameEvent::Serve { .. }));
+
+        // The point, then straight away the countdown to the next serve
+        let end = &events[events.len() - 2..];
+        assert!(matches!(end[0], GameEvent::PointScored { .. }));
+        assert_eq!(end[1], GameEvent::CountdownTick);
+
+        // The stats and the recording were built from the same events
+        let hits = events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::PaddleHit { .. }))
+            .count();
+        assert_eq!(sim.recording.contacts.len(), hits);
+        assert_eq!(sim.stats.longest_rally as usize, hits);
+    }
+
+    #[test]
+    fn test_only_the_latest_step_is_kept() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        assert_eq!(sim.events, vec![GameEvent::CountdownTick]);
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(sim.events.is_empty());
+    }
+
+    #[test]
+    fn test_resigning_ends_the_match_with_an_event() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.resign(2);
+        assert_eq!(
+            sim.events.last(),
+            Some(&GameEvent::GameOver {
+                winner: 1,
+                reason: MatchEnd::Forfeit
+            })
+        );
+        assert_eq!(sim.stats.forfeited_by, Some(2));
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        headless::run_headless,
+        simulation::{GameMode, Simulation},
+    };
+
+    // A headless run from a fresh, seeded match
+    fn seeded_run(seed: u64, ticks: u64) -> String {
+        let mut sim = Simulation::new(GameMode::Demo);
+        sim.seed(seed);
+        run_headless(&mut sim, ticks).to_string()
+    }
+
+    #[test]
+    fn test_same_seed_plays_the_same_match() {
+        let first = seeded_run(7, 20_000);
+        assert_eq!(first, seeded_run(7, 20_000));
+        assert!(first.starts_with("Simulated 20000 ticks"));
+    }
+
+    #[test]
+    fn test_points_are_played_and_matches_finish() {
+        let mut sim = Simulation::new(GameMode::Demo);
+        sim.seed(1);
+        let report = run_headless(&mut sim, 120 * 600); // Ten minutes of play
+        assert_eq!(report.ticks, 120 * 600);
+        assert!(report.points_played > 0);
+        assert!(report.matches_won[0] + report.matches_won[1] > 0);
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::input::{apply_deadzone, mouse_direction, Action, InputState, PaddleInput};
+    use crate::game::profiles::{SteerKeys, StickCurve};
+    use ggez::input::keyboard::KeyCode;
+
+    #[test]
+    fn test_keys_map_to_actions() {
+        let mut input = InputState::new();
+        assert_eq!(input.key_down(KeyCode::Return), Some(Action::Confirm));
+        assert_eq!(input.key_down(KeyCode::Escape), Some(Action::Back));
+        assert_eq!(input.key_down(KeyCode::P), Some(Action::Pause));
+        assert_eq!(input.key_down(KeyCode::R), Some(Action::Restart));
+        assert_eq!(input.key_down(KeyCode::E), Some(Action::Exit));
+        assert_eq!(input.key_down(KeyCode::F), Some(Action::Resign(1)));
+        assert_eq!(input.key_down(KeyCode::Q), Some(Action::Resign(2)));
+        assert_eq!(input.key_down(KeyCode::T), Some(Action::NextTheme));
+        assert_eq!(input.key_down(KeyCode::F11), Some(Action::ToggleFullscreen));
+        assert_eq!(input.key_down(KeyCode::A), Some(Action::Analysis));
+        assert_eq!(input.key_down(KeyCode::F3), Some(Action::ToggleDebug));
+        assert_eq!(input.key_down(KeyCode::F9), Some(Action::LatencyTest));
+        assert_eq!(input.key_down(KeyCode::O), Some(Action::ToggleAutopilot));
+        assert_eq!(input.key_down(KeyCode::B), Some(Action::ToggleBroadcast));
+        assert_eq!(input.key_down(KeyCode::G), Some(Action::QuickRestart));
+        assert_eq!(input.key_down(KeyCode::C), Some(Action::PhotoMode));
+        assert_eq!(input.key_down(KeyCode::Minus), Some(Action::ZoomOut));
+        assert_eq!(input.key_down(KeyCode::W), None);
+    }
+
+    #[test]
+    fn test_arrow_keys_steer_player_one() {
+        let mut input = InputState::new();
+        input.key_down(KeyCode::Up);
+        assert_eq!(input.paddle_direction(1), -1.0);
+        assert_eq!(input.paddle_direction(2), 0.0);
+
+        // Holding both keys cancels out
+        input.key_down(KeyCode::Down);
+        assert_eq!(input.paddle_direction(1), 0.0);
+
+        input.key_up(KeyCode::Up);
+        assert_eq!(input.paddle_direction(1), 1.0);
+    }
+
+    #[test]
+    fn test_held_arrows_point_a_direction() {
+        let mut input = InputState::new();
+        assert_eq!(input.arrow_direction(), (0.0, 0.0));
+        input.key_down(KeyCode::Left);
+        input.key_down(KeyCode::Down);
+        assert_eq!(input.arrow_direction(), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_w_s_steer_player_two() {
+        let mut input = InputState::new();
+        input.key_down(KeyCode::S);
+        assert_eq!(input.paddle_direction(2), 1.0);
+        assert_eq!(input.paddle_direction(1), 0.0);
+    }
+
+    #[test]
+    fn test_control_schemes_pick_the_steering_keys() {
+        let mut input = InputState::new();
+        input.schemes[0].keys = SteerKeys::Wasd;
+        input.schemes[1].keys = SteerKeys::Arrows;
+        input.key_down(KeyCode::W);
+        input.key_down(KeyCode::Down);
+        assert_eq!(input.paddle_direction(1), -1.0);
+        assert_eq!(input.paddle_direction(2), 1.0);
+    }
+
+    #[test]
+    fn test_tab_holds_stats() {
+        let mut input = InputState::new();
+        assert!(!input.stats_held());
+        input.key_down(KeyCode::Tab);
+        assert!(input.stats_held());
+        input.key_up(KeyCode::Tab);
+        assert!(!input.stats_held());
+    }
+
+    #[test]
+    fn test_deadzone_ignores_small_movements() {
+        let linear = StickCurve::Linear;
+        assert_eq!(apply_deadzone(0.0, GAMEPAD_DEADZONE, linear), 0.0);
+        assert_eq!(
+            apply_deadzone(GAMEPAD_DEADZONE, GAMEPAD_DEADZONE, linear),
+            0.0
+        );
+        assert_eq!(
+            apply_deadzone(-GAMEPAD_DEADZONE / 2.0, GAMEPAD_DEADZONE, linear),
+            0.0
+        );
+
+        // A smaller dead zone lets the same push through
+        assert!(apply_deadzone(GAMEPAD_DEADZONE, 0.1, linear) > 0.0);
+    }
+
+    #[test]
+    fn test_deadzone_rescales_the_rest() {
+        let linear = StickCurve::Linear;
+        assert_eq!(apply_deadzone(1.0, GAMEPAD_DEADZONE, linear), 1.0);
+        assert_eq!(apply_deadzone(-1.0, GAMEPAD_DEADZONE, linear), -1.0);
+
+        // Halfway between the dead zone and full tilt gives half speed
+        let halfway = (GAMEPAD_DEADZONE + 1.0) / 2.0;
+        assert!((apply_deadzone(halfway, GAMEPAD_DEADZONE, linear) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_stick_curve_bends_the_push() {
+        // Halfway past the dead zone, a gentle stick is slower and a quick
+        // one faster, but full tilt is still full speed either way
+        let halfway = (GAMEPAD_DEADZONE + 1.0) / 2.0;
+        let gentle = apply_deadzone(halfway, GAMEPAD_DEADZONE, StickCurve::Gentle);
+        let quick = apply_deadzone(-halfway, GAMEPAD_DEADZONE, StickCurve::Quick);
+        assert!((gentle - 0.25).abs() < 1e-5);
+        assert!((quick + 0.5_f32.sqrt()).abs() < 1e-5);
+        for curve in [StickCurve::Gentle, StickCurve::Quick] {
+            assert_eq!(apply_deadzone(1.0, GAMEPAD_DEADZONE, curve), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_mouse_direction_is_capped_at_full_speed() {
+        // Far from the paddle, the paddle goes as fast as the keys move it
+        assert_eq!(mouse_direction(500.0, 100.0), 1.0);
+        assert_eq!(mouse_direction(0.0, 300.0), -1.0);
+
+        // Close by, it slows down so it doesn't overshoot the pointer
+        let halfway = mouse_direction(100.0 + MOUSE_FOLLOW_DISTANCE / 2.0, 100.0);
+        assert!((halfway - 0.5).abs() < 1e-5);
+        assert_eq!(mouse_direction(100.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn test_paddle_input_switches_back_and_forth() {
+        assert_eq!(PaddleInput::default(), PaddleInput::Keys);
+        assert_eq!(PaddleInput::Keys.next(), PaddleInput::Mouse);
+        assert_eq!(PaddleInput::Mouse.next(), PaddleInput::Keys);
+    }
+}
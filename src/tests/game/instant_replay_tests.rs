@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        constants::*,
+        instant_replay::ReplayBuffer,
+        scene::{Scene, SettingsItem},
+        simulation::{GameMode, Simulation},
+        state::GameState,
+    };
+    use approx::assert_relative_eq;
+
+    // A buffer holding `steps` steps of the ball moving right at 120 pixels
+    // a second from x = 0
+    fn moving_ball(steps: usize) -> ReplayBuffer {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        let mut buffer = ReplayBuffer::default();
+        for step in 0..steps {
+            sim.ball.x = step as f32 * 120.0 * FIXED_TIMESTEP;
+            buffer.record(&sim);
+        }
+        buffer
+    }
+
+    // Start a two-player match that's been going long enough to be replayed,
+    // then send the ball straight past player 1
+    fn lose_a_point(state: &mut GameState) {
+        state.start_match(GameMode::TwoPlayer);
+        state.sim.countdown = None;
+        state.sim.ball.dx = 0.0;
+        state.sim.ball.dy = 0.0;
+        for _ in 0..(1.0 / FIXED_TIMESTEP) as usize {
+            state.step(FIXED_TIMESTEP);
+        }
+        state.sim.ball.x = BALL_RADIUS + 1.0;
+        state.sim.ball.y = state.sim.player1.y + PADDLE_HEIGHT * 1.5;
+        state.sim.ball.dx = -300.0;
+        state.step(FIXED_TIMESTEP);
+        assert_eq!(state.sim.score.player2, 1);
+    }
+
+    #[test]
+    fn test_only_the_last_moments_are_kept() {
+        let replay = moving_ball((REPLAY_LENGTH * 3.0 / FIXED_TIMESTEP) as usize)
+            .take_replay()
+            .unwrap();
+        assert_eq!(
+            replay.frames.len(),
+            (REPLAY_LENGTH / FIXED_TIMESTEP) as usize
+        );
+        assert_relative_eq!(replay.length(), REPLAY_LENGTH, epsilon = 0.05);
+
+        // Too short a point isn't worth showing
+        assert!(moving_ball(3).take_replay().is_none());
+
+        // Taking a replay (or clearing) starts the next point afresh
+        let mut buffer = moving_ball(100);
+        assert!(buffer.take_replay().is_some());
+        assert!(buffer.take_replay().is_none());
+        let mut buffer = moving_ball(100);
+        buffer.clear();
+        assert!(buffer.take_replay().is_none());
+    }
+
+    #[test]
+    fn test_replay_plays_smoothly_in_slow_motion() {
+        let mut replay = moving_ball(100).take_replay().unwrap();
+        assert_eq!(replay.frame().ball[0], 0.0);
+        assert!(replay.trail().is_empty());
+
+        // Half a step in, the ball is drawn half way between two frames
+        assert!(replay.advance(FIXED_TIMESTEP * 0.5 / REPLAY_SPEED));
+        assert_relative_eq!(
+            replay.frame().ball[0],
+            60.0 * FIXED_TIMESTEP,
+            epsilon = 1e-3
+        );
+
+        // It takes longer than the point did, then stops on the last frame
+        let real_time = replay.length() / REPLAY_SPEED;
+        assert!(replay.advance(real_time * 0.9));
+        assert_eq!(replay.trail().len(), BALL_TRAIL_LENGTH);
+        assert!(!replay.advance(real_time));
+        assert!(replay.finished());
+        assert_eq!(replay.frame(), replay.frames[99]);
+    }
+
+    #[test]
+    fn test_a_lost_point_is_replayed_before_the_next_serve() {
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        lose_a_point(&mut state);
+        let replay = state.replay.as_ref().unwrap();
+        assert!(replay.frames.len() >= (REPLAY_MIN_LENGTH / FIXED_TIMESTEP) as usize);
+        let length = replay.length();
+
+        // The match holds still (countdown and all) until the replay is over
+        assert!(state.run_replay(length));
+        assert!(state.sim.countdown.is_some());
+        assert!(!state.run_replay(length / REPLAY_SPEED));
+        assert!(state.replay.is_none());
+        assert_eq!(state.scene, Scene::Playing);
+    }
+
+    #[test]
+    fn test_replays_can_be_turned_off() {
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        assert_eq!(
+            state.settings_label(SettingsItem::PointReplays),
+            "Slow-Motion Replays: On"
+        );
+        state.change_setting(SettingsItem::PointReplays);
+        assert!(!state.settings().point_replays);
+        lose_a_point(&mut state);
+        assert!(state.replay.is_none());
+        assert!(!state.run_replay(1.0));
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        constants::{HIT_STOP_DURATION, SCREEN_SHAKE_DURATION},
+        events::{EventListener, GameEvent},
+        juice::Juice,
+        recording::Contact,
+    };
+
+    // A paddle hit in the middle of the left paddle
+    fn hit() -> GameEvent {
+        GameEvent::PaddleHit {
+            contact: Contact {
+                player: 1,
+                offset: 0.0,
+                angle_in: 0.0,
+                angle_out: 0.0,
+                speed: 300.0,
+            },
+            speed: 312.0,
+        }
+    }
+
+    #[test]
+    fn test_paddle_hit_shakes_then_settles() {
+        let mut juice = Juice::new();
+        juice.on_event(&GameEvent::WallBounce);
+        assert_eq!(juice.camera_offset(), [0.0, 0.0]);
+
+        juice.on_event(&hit());
+        juice.update(0.01);
+        assert_ne!(juice.camera_offset(), [0.0, 0.0]);
+        assert!(!juice.update(0.0)); // A hit doesn't stop the match
+
+        juice.update(SCREEN_SHAKE_DURATION);
+        assert_eq!(juice.camera_offset(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_point_holds_the_match_still() {
+        let mut juice = Juice::new();
+        juice.on_event(&GameEvent::PointScored { winner: 1 });
+
+        assert!(juice.update(HIT_STOP_DURATION / 2.0));
+        assert!(juice.update(HIT_STOP_DURATION / 2.0));
+        assert!(!juice.update(0.01)); // Over
+    }
+
+    #[test]
+    fn test_switched_off_does_nothing() {
+        let mut juice = Juice::new();
+        juice.enabled = false;
+        juice.on_event(&hit());
+        juice.on_event(&GameEvent::PointScored { winner: 1 });
+        assert!(!juice.update(0.01));
+        assert_eq!(juice.camera_offset(), [0.0, 0.0]);
+    }
+}
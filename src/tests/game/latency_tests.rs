@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::latency::{latency_text, pipeline_delay, LatencyProbe};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_press_is_timed_until_its_frame_is_shown() {
+        let mut probe = LatencyProbe::default();
+        let start = Instant::now();
+        assert!(!probe.flashing(start));
+
+        // A second press before the flash shows up belongs to the same flash
+        probe.press(start);
+        probe.press(start + Duration::from_millis(5));
+        assert!(probe.flashing(start));
+
+        probe.presented(start + Duration::from_millis(20));
+        assert_eq!(probe.last(), Some(Duration::from_millis(20)));
+        assert_eq!(probe.samples.len(), 1);
+
+        // Frames without a press don't add anything
+        probe.presented(start + Duration::from_millis(36));
+        assert_eq!(probe.samples.len(), 1);
+    }
+
+    #[test]
+    fn test_flash_stays_up_for_a_moment_after_it_shows() {
+        let mut probe = LatencyProbe::default();
+        let start = Instant::now();
+        probe.press(start);
+        probe.presented(start);
+        let flash = Duration::from_secs_f32(LATENCY_FLASH_TIME);
+        assert!(probe.flashing(start + flash / 2));
+        assert!(!probe.flashing(start + flash * 2));
+    }
+
+    #[test]
+    fn test_summary_covers_the_latest_presses() {
+        let mut probe = LatencyProbe::default();
+        assert_eq!(probe.summary(), None);
+        let start = Instant::now();
+        for millis in 1..=LATENCY_SAMPLES as u64 + 2 {
+            probe.press(start);
+            probe.presented(start + Duration::from_millis(millis * 10));
+        }
+        // The two oldest presses (10 and 20 ms) have dropped out
+        assert_eq!(probe.samples.len(), LATENCY_SAMPLES);
+        let (average, best, worst) = probe.summary().unwrap();
+        let newest = (LATENCY_SAMPLES as u64 + 2) * 10;
+        assert_eq!(best, Duration::from_millis(30));
+        assert_eq!(worst, Duration::from_millis(newest));
+        assert_eq!(average, Duration::from_millis((30 + newest) / 2));
+    }
+
+    #[test]
+    fn test_pipeline_delay_grows_with_frame_time() {
+        let fast = pipeline_delay(Duration::from_millis(4));
+        let slow = pipeline_delay(Duration::from_millis(16));
+        assert!(fast < slow);
+        assert_eq!(slow - fast, Duration::from_millis(18)); // One and a half frames more
+        assert!(fast > Duration::from_secs_f32(FIXED_TIMESTEP / 2.0));
+    }
+
+    #[test]
+    fn test_results_text() {
+        let mut probe = LatencyProbe::default();
+        let frame = Duration::from_millis(16);
+        let text = latency_text(&probe, frame, 60.0, true);
+        assert!(text.contains("60 FPS (16.0 ms per frame), vsync on"));
+        assert!(text.contains("Press any key"));
+
+        let start = Instant::now();
+        probe.press(start);
+        probe.presented(start + Duration::from_micros(25_400));
+        let text = latency_text(&probe, frame, 60.0, false);
+        assert!(text.contains("vsync off"));
+        assert!(text.contains("Last press: 25.4 ms"));
+        assert!(text.contains("Presses timed: 1 (average 25.4 ms"));
+    }
+}
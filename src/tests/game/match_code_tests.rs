@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        ai::{AiDifficulty, AiTuning},
+        arena::Arena,
+        constants::{FIXED_TIMESTEP, SMALL_PADDLE_HEIGHT},
+        court::CourtPreset,
+        match_code::MatchCode,
+        match_feel::{BallPace, PaddleSize},
+        scene::Scene,
+        simulation::{GameMode, Simulation},
+        state::GameState,
+    };
+
+    // Play a few seconds and note where the ball went
+    fn ball_path(sim: &mut Simulation) -> Vec<(f32, f32)> {
+        (0..2000)
+            .map(|_| {
+                sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+                (sim.ball.x, sim.ball.y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_code_round_trips_every_setting() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.ai_difficulty = AiDifficulty::Impossible;
+        sim.apply_court(CourtPreset::Narrow);
+        sim.rules.next_points_to_win();
+        sim.rules.win_by_two = true;
+        sim.rules.next_best_of();
+        sim.rules.classic = true;
+        sim.momentum_physics = true;
+        sim.obstacle_mode = true;
+        sim.arena = Arena::Space;
+        sim.opening_server = Some(2);
+        sim.adaptive_ai = true;
+        sim.start_match(GameMode::TwoPlayer);
+
+        let code = MatchCode::for_match(&sim);
+        assert!(code.options.rules.classic);
+        assert!(code.options.obstacle_mode);
+        assert!(code.options.adaptive_ai);
+        let text = code.to_string();
+        assert_eq!(text.len(), 25); // e.g. 2T3E57-131155-50BFDD3E-02
+        assert_eq!(MatchCode::parse(&text), Ok(code));
+
+        // Case, spaces and dashes don't matter when typing it in
+        let typed = text.to_lowercase().replace('-', " ");
+        assert_eq!(MatchCode::parse(&typed), Ok(code));
+    }
+
+    #[test]
+    fn test_code_holds_the_ai_sliders() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.ai_tuning = AiTuning {
+            speed: 2,
+            accuracy: -5,
+        };
+        sim.start_match(GameMode::VsAi);
+
+        let code = MatchCode::for_match(&sim);
+        let text = code.to_string();
+        assert_eq!(text.get(11..13), Some("70")); // e.g. 2A0001-001170-1223B032-3C
+        assert_eq!(MatchCode::parse(&text), Ok(code));
+
+        let mut replay = Simulation::new(GameMode::VsAi);
+        code.apply(&mut replay);
+        assert_eq!(replay.ai_tuning, sim.ai_tuning);
+    }
+
+    #[test]
+    fn test_code_holds_the_ball_speed_and_paddle_size() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.ball_pace = BallPace::Fast;
+        sim.paddle_size = PaddleSize::Small;
+        sim.start_match(GameMode::VsAi);
+
+        let code = MatchCode::for_match(&sim);
+        let text = code.to_string();
+        assert_eq!(text.get(9..11), Some("20")); // e.g. 2A0001-002055-70D269B7-3D
+        assert_eq!(MatchCode::parse(&text), Ok(code));
+
+        let mut replay = Simulation::new(GameMode::VsAi);
+        code.apply(&mut replay);
+        assert_eq!(replay.ball_pace, BallPace::Fast);
+        assert_eq!(replay.player1.height, SMALL_PADDLE_HEIGHT);
+    }
+
+    #[test]
+    fn test_code_holds_every_court() {
+        // Each court has its own digit, and every code is the same length
+        let mut sim = Simulation::new(GameMode::VsAi);
+        for (index, preset) in CourtPreset::ALL.into_iter().enumerate() {
+            sim.apply_court(preset);
+            sim.start_match(GameMode::VsAi);
+            let code = MatchCode::for_match(&sim);
+            let text = code.to_string();
+            assert_eq!(text.len(), 25);
+            assert_eq!(text.get(7..8), Some(index.to_string().as_str()));
+            assert_eq!(MatchCode::parse(&text), Ok(code));
+
+            let mut replay = Simulation::new(GameMode::VsAi);
+            code.apply(&mut replay);
+            assert_eq!(replay.court_preset, preset);
+            assert_eq!(replay.court, preset.court());
+        }
+    }
+
+    #[test]
+    fn test_codes_from_another_layout_are_turned_away() {
+        // Codes from before the layout version was added start with the mode letter
+        let error = MatchCode::parse("A0A41-3F9C2A11-7E").unwrap_err();
+        assert!(error.contains("another version"));
+        let error = MatchCode::parse("9A0A41-001155-3F9C2A11-00").unwrap_err();
+        assert!(error.contains("another version"));
+
+        // A code of this layout with a group left out is too short
+        let error = MatchCode::parse("2A0001-1223B032-3C").unwrap_err();
+        assert!(error.contains("22 letters and digits"));
+    }
+
+    #[test]
+    fn test_typos_are_caught() {
+        let code = MatchCode::for_match(&Simulation::new(GameMode::VsAi)).to_string();
+        let last = code.chars().nth(7).unwrap();
+        let changed = if last == '0' { '1' } else { '0' };
+        let typo = format!("{}{}{}", &code[..7], changed, &code[8..]);
+        assert!(MatchCode::parse(&typo).is_err());
+        assert!(MatchCode::parse("A0A41").is_err());
+        assert!(MatchCode::parse("").is_err());
+    }
+
+    #[test]
+    fn test_code_replays_the_same_match() {
+        let mut first = Simulation::new(GameMode::Demo);
+        first.ai_difficulty = AiDifficulty::Easy;
+        first.start_match(GameMode::Demo);
+        let path = ball_path(&mut first);
+        let code = MatchCode::for_match(&first); // Taken once the first serve is known
+
+        // A different computer, with different settings picked, plays it again
+        let mut again = Simulation::new(GameMode::VsAi);
+        MatchCode::parse(&code.to_string()).unwrap().apply(&mut again);
+        again.start_match(GameMode::Demo);
+        assert_eq!(again.match_seed, first.match_seed);
+        assert_eq!(again.ai_difficulty, AiDifficulty::Easy);
+        assert_eq!(ball_path(&mut again), path);
+    }
+
+    #[test]
+    fn test_code_typed_on_the_menu_starts_the_match() {
+        let mut played = Simulation::new(GameMode::VsAi);
+        played.start_match(GameMode::VsAi);
+        let code = MatchCode::for_match(&played).to_string();
+
+        let mut state = GameState::new(GameMode::VsAi);
+        state.scene = Scene::EnterCode;
+        for character in "not a code!".chars() {
+            state.type_code_char(character);
+        }
+        state.replay_match_code();
+        assert_eq!(state.scene, Scene::EnterCode);
+        assert!(state.status.is_some()); // Says why it didn't work
+
+        state.code_entry.clear();
+        for character in code.chars() {
+            state.type_code_char(character);
+        }
+        state.replay_match_code();
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.match_seed, played.match_seed);
+    }
+}
@@ -1,2 +1,64 @@
 #[cfg(test)]
-pub mod state_tests;
\ No newline at end of file
+pub mod ai_tests;
+#[cfg(test)]
+pub mod arena_tests;
+#[cfg(test)]
+pub mod audio_tests;
+#[cfg(test)]
+pub mod bench_tests;
+#[cfg(test)]
+pub mod bot_server_tests;
+#[cfg(test)]
+pub mod bundle_tests;
+#[cfg(test)]
+pub mod changelog_tests;
+#[cfg(test)]
+pub mod checksum_tests;
+#[cfg(test)]
+pub mod cli_tests;
+#[cfg(test)]
+pub mod coach_tests;
+#[cfg(test)]
+pub mod controller_tests;
+#[cfg(test)]
+pub mod court_tests;
+#[cfg(test)]
+pub mod drills_tests;
+#[cfg(test)]
+pub mod events_tests;
+#[cfg(test)]
+pub mod headless_tests;
+#[cfg(test)]
+pub mod input_tests;
+#[cfg(test)]
+pub mod instant_replay_tests;
+#[cfg(test)]
+pub mod juice_tests;
+#[cfg(test)]
+pub mod latency_tests;
+#[cfg(test)]
+pub mod match_code_tests;
+#[cfg(test)]
+pub mod momentum_tests;
+#[cfg(test)]
+pub mod profiles_tests;
+#[cfg(test)]
+pub mod recording_tests;
+#[cfg(test)]
+pub mod rules_tests;
+#[cfg(test)]
+pub mod saved_match_tests;
+#[cfg(test)]
+pub mod scene_tests;
+#[cfg(test)]
+pub mod simulation_tests;
+#[cfg(test)]
+pub mod sounds_tests;
+#[cfg(test)]
+pub mod spectator_tests;
+#[cfg(test)]
+pub mod state_tests;
+#[cfg(test)]
+pub mod stats_tests;
+#[cfg(test)]
+pub mod tournament_tests;
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::events::{EventListener, GameEvent};
+    use crate::game::momentum::Momentum;
+    use crate::game::recording::Contact;
+    use approx::assert_relative_eq;
+
+    // A return by player 1's paddle
+    fn hit() -> GameEvent {
+        GameEvent::PaddleHit {
+            contact: Contact {
+                player: 1,
+                offset: 0.0,
+                angle_in: 0.0,
+                angle_out: 0.0,
+                speed: 300.0,
+            },
+            speed: 312.0,
+        }
+    }
+
+    #[test]
+    fn test_points_lean_toward_the_winner() {
+        let mut momentum = Momentum::new();
+        assert_relative_eq!(momentum.value, 0.0);
+
+        momentum.point_won(1, 0);
+        assert_relative_eq!(momentum.value, -MOMENTUM_POINT_PUSH);
+        momentum.point_won(2, 0);
+        assert_relative_eq!(momentum.value, 0.0);
+
+        // A long run can't go past the end of the bar
+        for _ in 0..20 {
+            momentum.point_won(2, MOMENTUM_LONG_RALLY);
+        }
+        assert_relative_eq!(momentum.value, 1.0);
+    }
+
+    #[test]
+    fn test_long_rallies_push_further() {
+        let mut quick = Momentum::new();
+        quick.point_won(2, 0);
+        let mut long = Momentum::new();
+        long.point_won(2, MOMENTUM_LONG_RALLY * 3);
+        assert_relative_eq!(long.value, quick.value * 2.0);
+    }
+
+    #[test]
+    fn test_lean_fades_over_play() {
+        let mut momentum = Momentum::new();
+        momentum.point_won(1, MOMENTUM_LONG_RALLY);
+        let start = momentum.value;
+        momentum.update(MOMENTUM_HALF_LIFE);
+        assert_relative_eq!(momentum.value, start / 2.0, epsilon = 0.0001);
+
+        // The same time in small steps fades it just as much
+        let mut stepped = Momentum::new();
+        stepped.point_won(1, MOMENTUM_LONG_RALLY);
+        let steps = (MOMENTUM_HALF_LIFE / FIXED_TIMESTEP).round() as u32;
+        for _ in 0..steps {
+            stepped.update(FIXED_TIMESTEP);
+        }
+        assert_relative_eq!(stepped.value, momentum.value, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_events_count_the_rally() {
+        let mut momentum = Momentum::new();
+        momentum.on_event(&GameEvent::Serve {
+            server: 1,
+            speed: 300.0,
+        });
+        for _ in 0..MOMENTUM_LONG_RALLY {
+            momentum.on_event(&hit());
+        }
+        momentum.on_event(&GameEvent::PointScored { winner: 1 });
+        assert_relative_eq!(momentum.value, -MOMENTUM_POINT_PUSH * 2.0);
+
+        // The next point starts counting from zero
+        momentum.on_event(&GameEvent::PointScored { winner: 2 });
+        assert_relative_eq!(momentum.value, -MOMENTUM_POINT_PUSH);
+
+        momentum.reset();
+        assert_eq!(momentum, Momentum::new());
+    }
+}
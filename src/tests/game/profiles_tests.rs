@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::profiles::{ControlScheme, ProfileBook, SteerKeys, StickCurve};
+
+    #[test]
+    fn test_players_start_as_guests_with_the_usual_controls() {
+        let book = ProfileBook::default();
+        assert_eq!(book.chosen_name(1), "Guest");
+        assert_eq!(book.controls(1).keys, SteerKeys::Arrows);
+        assert_eq!(book.controls(2).keys, SteerKeys::Wasd);
+        assert!(book.controls(2).gamepad);
+    }
+
+    #[test]
+    fn test_names_are_kept_apart_and_limited() {
+        let mut book = ProfileBook::default();
+        book.add("Sam").unwrap();
+        assert!(book.add("Sam").is_err());
+        for index in 1..MAX_PROFILES {
+            book.add(&format!("Player {}", index)).unwrap();
+        }
+        assert!(book.add("One Too Many").is_err());
+        assert_eq!(book.profiles.len(), MAX_PROFILES);
+    }
+
+    #[test]
+    fn test_each_player_uses_their_own_profile() {
+        let mut book = ProfileBook::default();
+        book.add("Sam").unwrap();
+        book.add("Alex").unwrap();
+
+        // Guest, then each profile in turn, then back round to Guest
+        book.slide_choice(1, 1);
+        assert_eq!(book.chosen_name(1), "Sam");
+        book.slide_choice(2, -1);
+        assert_eq!(book.chosen_name(2), "Alex");
+        book.slide_choice(1, 2);
+        assert_eq!(book.chosen_name(1), "Guest");
+        book.slide_choice(1, 1);
+
+        // Changing the controls changes the picked profile, not the guest ones
+        book.controls_mut(2).keys = SteerKeys::Arrows;
+        book.controls_mut(1).gamepad = false;
+        assert_eq!(book.profiles[1].controls.keys, SteerKeys::Arrows);
+        assert!(!book.profiles[0].controls.gamepad);
+        assert_eq!(book.guests[1].keys, SteerKeys::Wasd);
+        assert_eq!(book.controls(1), book.profiles[0].controls);
+
+        // A pick that is no longer saved falls back to guest controls
+        book.profiles.remove(0);
+        assert_eq!(book.chosen_name(1), "Guest");
+        assert_eq!(book.controls(1), book.guests[0]);
+    }
+
+    #[test]
+    fn test_two_players_never_steer_with_the_same_keys() {
+        let mut book = ProfileBook::default();
+        book.add("Sam").unwrap();
+        book.add("Alex").unwrap();
+
+        // New profiles steer with the arrows, like player 1's guest controls,
+        // so picking one for player 2 moves player 1 over to W/S
+        book.slide_choice(2, 1);
+        book.keep_keys_apart(2);
+        assert_eq!(book.controls(2).keys, SteerKeys::Arrows);
+        assert_eq!(book.controls(1).keys, SteerKeys::Wasd);
+
+        // Switching keys to the other player's pair swaps theirs
+        book.slide_choice(1, 2);
+        book.controls_mut(1).keys = SteerKeys::Arrows;
+        book.keep_keys_apart(1);
+        assert_eq!(book.profiles[0].controls.keys, SteerKeys::Wasd);
+        assert_eq!(book.profiles[1].controls.keys, SteerKeys::Arrows);
+
+        // Both on one profile (or a clash edited into the file): player 2
+        // steers with the other pair, and the profile keeps its own
+        book.chosen[1] = Some("Alex".to_string());
+        book.keep_keys_apart(1);
+        let steering = book.steering();
+        assert_eq!(steering[0].keys, SteerKeys::Arrows);
+        assert_eq!(steering[1].keys, SteerKeys::Wasd);
+        assert_eq!(book.profiles[1].controls.keys, SteerKeys::Arrows);
+    }
+
+    #[test]
+    fn test_stick_sensitivity_steps_and_wraps() {
+        let mut controls = ControlScheme::for_player(1);
+        controls.nudge_sensitivity(1);
+        assert_eq!(controls.sensitivity, 1.0 + STICK_SENSITIVITY_STEP);
+        controls.sensitivity = STICK_SENSITIVITY_MAX;
+        controls.nudge_sensitivity(1);
+        assert_eq!(controls.sensitivity, STICK_SENSITIVITY_MIN);
+        controls.nudge_sensitivity(-1);
+        assert_eq!(controls.sensitivity, STICK_SENSITIVITY_MAX);
+
+        // A hand-edited value is kept in range
+        controls.sensitivity = 40.0;
+        assert_eq!(controls.stick_scale(), STICK_SENSITIVITY_MAX);
+    }
+
+    #[test]
+    fn test_stick_deadzone_steps_and_wraps() {
+        let mut controls = ControlScheme::for_player(2);
+        assert_eq!(controls.deadzone, GAMEPAD_DEADZONE);
+        controls.nudge_deadzone(1);
+        assert!((controls.deadzone - (GAMEPAD_DEADZONE + GAMEPAD_DEADZONE_STEP)).abs() < 1e-5);
+        controls.deadzone = GAMEPAD_DEADZONE_MAX;
+        controls.nudge_deadzone(1);
+        assert_eq!(controls.deadzone, 0.0);
+        controls.nudge_deadzone(-1);
+        assert!((controls.deadzone - GAMEPAD_DEADZONE_MAX).abs() < 1e-5);
+
+        // A hand-edited value is kept in range
+        controls.deadzone = 3.0;
+        assert_eq!(controls.stick_deadzone(), GAMEPAD_DEADZONE_MAX);
+    }
+
+    #[test]
+    fn test_older_preferences_start_without_profiles() {
+        let book: ProfileBook = serde_json::from_str("{}").unwrap();
+        assert_eq!(book, ProfileBook::default());
+
+        let mut saved = ProfileBook::default();
+        saved.add("Sam").unwrap();
+        saved.slide_choice(2, 1);
+        let text = serde_json::to_string(&saved).unwrap();
+        assert_eq!(serde_json::from_str::<ProfileBook>(&text).unwrap(), saved);
+    }
+
+    #[test]
+    fn test_win_streaks_grow_and_break() {
+        let mut book = ProfileBook::default();
+        book.add("Sam").unwrap();
+        book.slide_choice(1, 1);
+
+        // Each win adds to the streak, and the milestones get a banner
+        for _ in 0..3 {
+            assert!(book.record_result(1, true));
+        }
+        assert_eq!(book.win_streak(1), 3);
+        assert_eq!(book.streak_milestone(1), Some(3));
+        assert_eq!(
+            book.streak_banner(&[1, 2]).as_deref(),
+            Some("Sam is on a 3-win streak!")
+        );
+        book.record_result(1, true);
+        assert_eq!(book.streak_banner(&[1]), None);
+
+        // A loss breaks it; guests don't keep one
+        assert!(book.record_result(1, false));
+        assert_eq!(book.win_streak(1), 0);
+        assert!(!book.record_result(1, false));
+        assert!(!book.record_result(2, true));
+        assert_eq!(book.win_streak(2), 0);
+    }
+
+    #[test]
+    fn test_profiles_saved_before_streaks_start_on_none() {
+        let text = r#"{"profiles": [{"name": "Sam", "controls":
+            {"keys": "Wasd", "gamepad": false, "sensitivity": 1.0}}]}"#;
+        let book: ProfileBook = serde_json::from_str(text).unwrap();
+        assert_eq!(book.profiles[0].win_streak, 0);
+
+        // Nor had they a dead zone or curve of their own
+        assert_eq!(book.profiles[0].controls.deadzone, GAMEPAD_DEADZONE);
+        assert_eq!(book.profiles[0].controls.curve, StickCurve::Linear);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::recording::{travel_angle, Contact, MatchRecording};
+
+    use approx::assert_relative_eq;
+
+    fn contact(player: u8, offset: f32, speed: f32) -> Contact {
+        Contact {
+            player,
+            offset,
+            angle_in: 0.0,
+            angle_out: 0.0,
+            speed,
+        }
+    }
+
+    #[test]
+    fn test_contacts_are_split_by_player() {
+        let mut recording = MatchRecording::new();
+        recording.record(contact(1, 0.0, 300.0));
+        recording.record(contact(2, 0.9, 400.0));
+        recording.record(contact(1, -0.8, 500.0));
+
+        assert_eq!(recording.for_player(1).count(), 2);
+        assert_eq!(recording.for_player(2).count(), 1);
+        assert_relative_eq!(recording.average_speed(1), 400.0);
+        assert_relative_eq!(recording.average_speed(2), 400.0);
+
+        recording.reset();
+        assert!(recording.contacts.is_empty());
+        assert_relative_eq!(recording.average_speed(1), 0.0);
+    }
+
+    #[test]
+    fn test_edge_hits_are_mishits() {
+        let mut recording = MatchRecording::new();
+        recording.record(contact(1, 0.1, 300.0));
+        recording.record(contact(1, -0.95, 300.0)); // Top edge
+        recording.record(contact(1, 0.75, 300.0)); // Near the bottom
+        assert_eq!(recording.mishits(1), 2);
+        assert_eq!(recording.mishits(2), 0);
+    }
+
+    #[test]
+    fn test_travel_angle_reads_the_same_both_ways() {
+        assert_relative_eq!(travel_angle(300.0, 0.0), 0.0);
+        assert_relative_eq!(travel_angle(100.0, 100.0), 45.0, epsilon = 1e-4);
+        assert_relative_eq!(travel_angle(-100.0, 100.0), 45.0, epsilon = 1e-4);
+        assert_relative_eq!(travel_angle(-100.0, -100.0), -45.0, epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        constants::{COUNTDOWN_DURATION, FIXED_TIMESTEP},
+        rules::{MatchEnd, MatchRules},
+        simulation::{GameMode, Simulation},
+        state::GameState,
+    };
+
+    #[test]
+    fn test_default_rules_are_first_to_three() {
+        let rules = MatchRules::default();
+        assert_eq!(rules.points_to_win, 3);
+        assert!(!rules.win_by_two);
+        assert_eq!(rules.best_of, 1);
+        assert!(rules.game_won(3, 2));
+        assert!(!rules.game_won(2, 0));
+        assert_eq!(rules.describe(), "First to score 3 wins");
+    }
+
+    #[test]
+    fn test_win_by_two_needs_a_lead() {
+        let rules = MatchRules {
+            points_to_win: 3,
+            win_by_two: true,
+            best_of: 1,
+            classic: false,
+        };
+        assert!(!rules.game_won(3, 2));
+        assert!(rules.game_won(4, 2));
+        assert!(rules.game_won(3, 1));
+    }
+
+    #[test]
+    fn test_games_to_win_is_a_majority() {
+        let mut rules = MatchRules::default();
+        assert_eq!(rules.games_to_win(), 1);
+        rules.next_best_of();
+        assert_eq!(rules.best_of, 3);
+        assert_eq!(rules.games_to_win(), 2);
+        rules.next_best_of();
+        assert_eq!(rules.games_to_win(), 3);
+        rules.next_best_of();
+        assert_eq!(rules.best_of, 1); // Wraps back to a single game
+    }
+
+    #[test]
+    fn test_points_to_win_cycles() {
+        let mut rules = MatchRules::default();
+        rules.next_points_to_win();
+        assert_eq!(rules.points_to_win, 5);
+        assert_eq!(rules.describe(), "First to score 5 wins");
+        rules.win_by_two = true;
+        rules.best_of = 3;
+        assert_eq!(
+            rules.describe(),
+            "First to score 5, win by two, best of 3 games"
+        );
+    }
+
+    #[test]
+    fn test_classic_rules_play_to_eleven_by_two() {
+        let mut rules = MatchRules {
+            classic: true,
+            ..MatchRules::default()
+        };
+        assert_eq!(rules.points_needed(), 11);
+        assert!(!rules.game_won(10, 5));
+        assert!(rules.game_won(11, 9));
+        assert!(!rules.game_won(11, 10)); // Deuce: play on
+        assert!(rules.game_won(13, 11));
+        assert_eq!(
+            rules.describe(),
+            "Classic: first to 11 by two, serve changes every 2 points"
+        );
+
+        // Changing the score leaves classic rules
+        rules.next_points_to_win();
+        assert!(!rules.classic);
+        assert_eq!(rules.points_needed(), 3);
+    }
+
+    #[test]
+    fn test_classic_serve_changes_every_two_points() {
+        let rules = MatchRules {
+            classic: true,
+            ..MatchRules::default()
+        };
+        let servers: Vec<u8> = [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (5, 3)]
+            .iter()
+            .map(|&points| rules.server(1, 0, points, Some(1)))
+            .collect();
+        assert_eq!(servers, [1, 1, 2, 2, 1, 1]);
+
+        // From 10-10 the serve changes every point
+        assert_eq!(rules.server(1, 0, (10, 10), None), 1);
+        assert_eq!(rules.server(1, 0, (11, 10), None), 2);
+        assert_eq!(rules.server(1, 0, (11, 11), None), 1);
+
+        // The other player serves first in the second game
+        assert_eq!(rules.server(1, 1, (0, 0), Some(1)), 2);
+    }
+
+    #[test]
+    fn test_normal_serve_goes_to_the_player_who_lost_the_point() {
+        let rules = MatchRules::default();
+        assert_eq!(rules.server(2, 0, (0, 0), None), 2);
+        assert_eq!(rules.server(2, 0, (1, 0), Some(1)), 2);
+        assert_eq!(rules.server(2, 0, (1, 1), Some(2)), 1);
+    }
+
+    #[test]
+    fn test_classic_match_serves_in_turns() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.rules.classic = true;
+        sim.opening_server = Some(1);
+        sim.start_match(GameMode::TwoPlayer);
+
+        // Player 1 wins every point, but still hands over the serve every two
+        let mut servers = Vec::new();
+        for _ in 0..4 {
+            sim.countdown = Some(COUNTDOWN_DURATION);
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            servers.push(if sim.ball.dx > 0.0 { 1 } else { 2 });
+            sim.ball.x = sim.court.width;
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert_eq!(sim.score.player1, 4);
+        assert_eq!(servers, [1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_best_of_three_match() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.sim.rules.best_of = 3;
+        state.start_match(GameMode::VsAi);
+
+        // Player 1 takes the first game, which starts the next one at 0-0
+        for _ in 0..3 {
+            state.sim.score.increment_player1();
+            state.sim.check_winner(1);
+        }
+        assert_eq!(state.sim.score.games_player1, 1);
+        assert_eq!(state.sim.score.player1, 0);
+        assert!(state.sim.winner.is_none());
+
+        // A second game wins the match
+        for _ in 0..3 {
+            state.sim.score.increment_player1();
+            state.sim.check_winner(1);
+        }
+        assert_eq!(state.sim.winner, Some(1));
+        assert_eq!(state.sim.end_reason, Some(MatchEnd::ScoreReached));
+
+        // Starting over clears how the last match ended
+        state.reset_match();
+        assert!(state.sim.end_reason.is_none());
+    }
+
+    #[test]
+    fn test_lives_mode_ends_on_lives_lost() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.sim.lives_mode = true;
+        state.start_match(GameMode::VsAi);
+
+        while !state.sim.lives.is_out(2) {
+            state.sim.lives.lose_life(2);
+        }
+        state.sim.check_winner(1);
+        assert_eq!(state.sim.winner, Some(1));
+        assert_eq!(state.sim.end_reason, Some(MatchEnd::LivesLost));
+        assert_eq!(state.result_text(), "You Won!\nOpponent ran out of lives");
+    }
+}
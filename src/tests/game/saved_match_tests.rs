@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::{FIXED_TIMESTEP, SAVED_MATCH_VERSION};
+    use crate::game::input::Action;
+    use crate::game::rules::MatchRules;
+    use crate::game::saved_match::SavedMatch;
+    use crate::game::scene::{MenuItem, Scene};
+    use crate::game::simulation::GameMode;
+    use crate::game::state::GameState;
+    use crate::tests::support::temp_file;
+
+    #[test]
+    fn test_missing_or_other_version_files() {
+        let path = temp_file("saved_match_versions", "saved_match.json");
+        assert_eq!(SavedMatch::load(&path), Ok(None));
+
+        let state = GameState::new(GameMode::VsAi);
+        let mut saved = SavedMatch::new(&state.sim);
+        saved.save(&path).unwrap();
+        assert_eq!(SavedMatch::load(&path), Ok(Some(saved.clone())));
+
+        saved.version = SAVED_MATCH_VERSION + 1;
+        saved.save(&path).unwrap();
+        let error = SavedMatch::load(&path).unwrap_err();
+        assert!(error.contains("another version"));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_closing_mid_match_lets_it_be_continued() {
+        let path = temp_file("saved_match_continue", "saved_match.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_saved_match_file(path.clone());
+        state.sim.rules = MatchRules {
+            points_to_win: 7,
+            ..MatchRules::default()
+        };
+        state.start_match(GameMode::TwoPlayer);
+        for _ in 0..300 {
+            state.step(FIXED_TIMESTEP);
+        }
+        state.sim.score.player1 = 4;
+        state.sim.score.player2 = 2;
+        state.save_unfinished_match().unwrap();
+        let ball = (state.sim.ball.x, state.sim.ball.y);
+
+        // Next run: Continue is at the top of the menu
+        let mut again = GameState::new(GameMode::VsAi);
+        again.use_saved_match_file(path.clone());
+        assert_eq!(again.menu_items()[0], MenuItem::Continue);
+        assert_eq!(again.menu_items().len(), MenuItem::ALL.len() + 1);
+        again.handle_action(Action::Confirm);
+
+        // The match is back where it was, paused, with its own rules
+        assert_eq!(again.scene, Scene::Paused);
+        assert_eq!(again.sim.mode, GameMode::TwoPlayer);
+        assert_eq!(again.sim.rules.points_to_win, 7);
+        assert_eq!((again.sim.score.player1, again.sim.score.player2), (4, 2));
+        assert_eq!((again.sim.ball.x, again.sim.ball.y), ball);
+        assert_eq!(again.sim.ticks, state.sim.ticks);
+
+        // It can only be carried on once
+        assert!(!path.exists());
+        assert!(!again.menu_items().contains(&MenuItem::Continue));
+
+        // Its rules are only for this match: the player's own settings are
+        // the ones kept, and they're back once it's over
+        assert_eq!(again.settings().options.rules.points_to_win, 3);
+        again.quit_to_menu();
+        assert_eq!(again.sim.rules.points_to_win, 3);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_continued_match_plays_out_the_same() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.sim.next_seed = Some(42);
+        state.start_match(GameMode::VsAi);
+        for _ in 0..600 {
+            state.sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        let saved = SavedMatch::new(&state.sim);
+
+        // Carried on from the save, the serves and AI mistakes are the same
+        // as if the match had never stopped
+        let mut again = GameState::new(GameMode::VsAi);
+        again.saved_match = Some(saved);
+        again.handle_action(Action::Confirm);
+        for _ in 0..1200 {
+            state.sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            again.sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert_eq!(again.sim.rng_positions(), state.sim.rng_positions());
+        assert_eq!(SavedMatch::new(&again.sim), SavedMatch::new(&state.sim));
+    }
+
+    #[test]
+    fn test_only_unfinished_ordinary_matches_are_saved() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(state.unfinished_match().is_none()); // On the menu
+
+        state.start_match(GameMode::VsAi);
+        assert!(state.unfinished_match().is_some());
+        state.sim.winner = Some(1);
+        assert!(state.unfinished_match().is_none());
+
+        state.start_match(GameMode::Demo);
+        assert!(state.unfinished_match().is_none());
+        state.start_match(GameMode::Training);
+        assert!(state.unfinished_match().is_none());
+
+        state.open_tournament();
+        state.start_match(GameMode::VsAi);
+        assert!(state.unfinished_match().is_none());
+    }
+
+    #[test]
+    fn test_damaged_file_is_reported_and_saved_over() {
+        let path = temp_file("saved_match_damaged", "saved_match.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_saved_match_file(path.clone());
+        assert_eq!(state.scene, Scene::StartupError);
+        assert!(state.saved_match.is_none());
+
+        state.start_match(GameMode::VsAi);
+        state.save_unfinished_match().unwrap();
+        assert!(SavedMatch::load(&path).unwrap().is_some());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
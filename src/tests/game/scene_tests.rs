@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::scene::{MenuCursor, MenuItem};
+
+    #[test]
+    fn test_cursor_moves_down_and_wraps() {
+        let mut cursor = MenuCursor::default();
+        let len = MenuItem::ALL.len();
+        cursor.down(len);
+        assert_eq!(cursor.selected, 1);
+
+        for _ in 1..len {
+            cursor.down(len);
+        }
+        assert_eq!(cursor.selected, 0);
+    }
+
+    #[test]
+    fn test_cursor_moves_up_and_wraps() {
+        let mut cursor = MenuCursor::default();
+        let len = MenuItem::ALL.len();
+        cursor.up(len);
+        assert_eq!(cursor.selected, len - 1);
+    }
+}
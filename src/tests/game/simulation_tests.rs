@@ -0,0 +1,531 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        ai::AiDifficulty,
+        arena::Arena,
+        audio::Cue,
+        constants::{
+            BALL_RADIUS, COUNTDOWN_DURATION, COUNTDOWN_GO_DURATION, FIXED_TIMESTEP, LAUNCHER_WIDTH,
+            MAX_BALL_SPEED, PADDLE_HEIGHT, PADDLE_RESTITUTION, PADDLE_WIDTH, PLAYER_PADDLE_SPEED,
+            RALLY_SPEED_UP, WALL_RESTITUTION,
+        },
+        events::GameEvent,
+        rules::MatchEnd,
+        simulation::{GameMode, Simulation},
+    };
+
+    use approx::assert_relative_eq;
+
+    // Play steps with both paddles standing still until the match ends or we give up
+    fn play(sim: &mut Simulation, max_steps: u32) -> u32 {
+        for step in 0..max_steps {
+            if sim.winner.is_some() {
+                return step;
+            }
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        max_steps
+    }
+
+    #[test]
+    fn test_countdown_serves_the_ball() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+
+        let countdown_steps = (COUNTDOWN_DURATION / FIXED_TIMESTEP).ceil() as u32;
+        for _ in 0..countdown_steps - 1 {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert!(sim.countdown.is_some());
+        assert_eq!(sim.ball.dx, 0.0);
+
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(sim.countdown.is_none());
+        assert!(sim.ball.dx != 0.0);
+    }
+
+    #[test]
+    fn test_go_flashes_once_the_ball_is_served() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        while sim.countdown.is_some() {
+            assert_eq!(sim.go_flash, None);
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert!(sim.go_flash.is_some());
+
+        // Goes away on its own after a moment
+        let flash_steps = (COUNTDOWN_GO_DURATION / FIXED_TIMESTEP).ceil() as u32;
+        for _ in 0..=flash_steps {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert_eq!(sim.go_flash, None);
+    }
+
+    #[test]
+    fn test_ai_wins_against_a_still_paddle() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.ai_difficulty = AiDifficulty::Impossible; // Never misses
+        sim.start_match(GameMode::VsAi);
+
+        // Thousands of steps with no window: the AI returns everything,
+        // so the player who never moves should lose
+        let steps = play(&mut sim, 100_000);
+        assert!(steps < 100_000, "match never finished");
+        assert_eq!(sim.winner, Some(2));
+        assert_eq!(sim.end_reason, Some(MatchEnd::ScoreReached));
+    }
+
+    // Drop a ball fast just in front of the right paddle, so the AI is still
+    // chasing it down when it arrives, and play on until it's returned
+    fn ai_return(momentum_physics: bool) -> Simulation {
+        // The easy AI follows the ball where it is, so it's still moving as it hits
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.ai_difficulty = AiDifficulty::Easy;
+        sim.momentum_physics = momentum_physics;
+        sim.next_seed = Some(1);
+        sim.start_match(GameMode::VsAi);
+        sim.countdown = None;
+        sim.ball.x = sim.player2.x - 40.0;
+        sim.ball.y = sim.player2.y + PADDLE_HEIGHT / 2.0;
+        sim.ball.dx = 300.0;
+        sim.ball.dy = 300.0;
+        for _ in 0..60 {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            if !sim.recording.contacts.is_empty() {
+                break;
+            }
+        }
+        sim
+    }
+
+    #[test]
+    fn test_moving_ai_paddle_pushes_the_ball() {
+        let sim = ai_return(true);
+
+        // The AI's paddle was moving when it hit, and the ball picked that up
+        assert_eq!(sim.recording.contacts[0].player, 2);
+        assert!(sim.player2.velocity > 0.0);
+        assert!(sim.ball.dx < 0.0);
+        assert!(sim.ball.spin != 0.0);
+
+        // With momentum physics the paddle also drags the ball along
+        assert!(sim.ball.dy > ai_return(false).ball.dy);
+    }
+
+    #[test]
+    fn test_nothing_moves_after_the_match() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.resign(1);
+        assert_eq!(sim.winner, Some(2));
+
+        let paddle_y = sim.player1.y;
+        sim.step(FIXED_TIMESTEP, [1.0, 1.0]);
+        assert_eq!(sim.player1.y, paddle_y);
+    }
+
+    #[test]
+    fn test_directions_steer_both_paddles() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        let start_y = sim.player1.y;
+
+        for _ in 0..30 {
+            sim.step(FIXED_TIMESTEP, [-1.0, 1.0]);
+        }
+        assert!(sim.player1.y < start_y);
+        assert!(sim.player2.y > start_y);
+    }
+
+    #[test]
+    fn test_wall_bounce_keeps_the_wall_share_of_the_speed() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // Heading up into the top wall, well away from both paddles
+        sim.ball.x = sim.court.width / 2.0;
+        sim.ball.y = BALL_RADIUS + 1.0;
+        sim.ball.dx = 200.0;
+        sim.ball.dy = -200.0;
+        let before = sim.ball.speed();
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        assert!(sim.ball.dy > 0.0);
+        assert_relative_eq!(sim.ball.speed(), before * WALL_RESTITUTION, epsilon = 0.01);
+    }
+
+    #[test]
+    fn test_paddle_return_scales_the_speed() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // Flat into the middle of the left paddle, which is standing still
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH + BALL_RADIUS;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT / 2.0;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        assert!(sim.ball.dx > 0.0);
+        let expected = 300.0 * PADDLE_RESTITUTION * RALLY_SPEED_UP;
+        assert_relative_eq!(sim.ball.speed(), expected, epsilon = 0.01);
+    }
+
+    #[test]
+    fn test_practice_balls_bounce_off_both_end_walls() {
+        for arena in [Arena::Standard, Arena::Rubber] {
+            let mut sim = Simulation::new(GameMode::TwoPlayer);
+            sim.end_wall_bounce = true;
+            sim.arena = arena;
+            sim.start_match(GameMode::TwoPlayer);
+            sim.countdown = None;
+            let restitution = arena.rules().wall_restitution;
+
+            // Past the left paddle, high above it, and into the end wall
+            sim.ball.x = BALL_RADIUS + 1.0;
+            sim.ball.y = sim.court.height * 0.15;
+            sim.ball.dx = -300.0;
+            sim.ball.dy = 0.0;
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            assert_relative_eq!(sim.ball.dx, 300.0 * restitution, epsilon = 0.01);
+
+            // ...and the same at the right end
+            sim.ball.x = sim.court.width - BALL_RADIUS - 1.0;
+            sim.ball.dx = 300.0;
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            assert_relative_eq!(sim.ball.dx, -300.0 * restitution, epsilon = 0.01);
+
+            // Nobody scores in practice
+            assert_eq!((sim.score.player1, sim.score.player2), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_restart_game_keeps_games_won() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.rules.best_of = 3;
+        sim.start_match(GameMode::TwoPlayer);
+        sim.score.win_game(1);
+        sim.score.increment_player2();
+        sim.countdown = None;
+        sim.ball.dx = 300.0;
+
+        sim.restart_game();
+        assert_eq!((sim.score.player1, sim.score.player2), (0, 0));
+        assert_eq!(sim.score.games_player1, 1);
+        assert_eq!(sim.ball.dx, 0.0);
+        assert!(sim.countdown.is_some());
+        assert!(sim.winner.is_none());
+    }
+
+    #[test]
+    fn test_ball_machine_keeps_firing_in_training() {
+        let mut sim = Simulation::new(GameMode::Training);
+        sim.start_match(GameMode::Training);
+
+        // After the countdown the first ball comes from the machine
+        let countdown_steps = (COUNTDOWN_DURATION / FIXED_TIMESTEP).ceil() as u32 + 1;
+        for _ in 0..countdown_steps {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert!(sim.ball.dx < 0.0);
+        assert!(sim.ball.x > sim.court.width / 2.0);
+
+        // A ball that gets back to the machine is taken in and another is fired
+        sim.ball.x = sim.court.width - LAUNCHER_WIDTH - BALL_RADIUS - 1.0;
+        sim.ball.dx = 300.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(sim.ball.dx < 0.0);
+        assert!(sim
+            .events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Serve { .. })));
+
+        // A miss ends the streak but not the session
+        sim.launcher.streak = 4;
+        sim.ball.x = BALL_RADIUS + 1.0;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT + 50.0;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert_eq!(sim.launcher.streak, 0);
+        assert_eq!(sim.launcher.best_streak, 0); // Set by hand, not by returns
+        assert!(sim.winner.is_none());
+        assert!(sim.ball.dx < 0.0 && sim.ball.x > sim.court.width / 2.0);
+    }
+
+    #[test]
+    fn test_ball_past_a_paddle_is_recorded_as_a_miss() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.start_match(GameMode::VsAi);
+        sim.countdown = None;
+
+        // Straight past, a whole paddle height below player 1's paddle
+        sim.ball.x = BALL_RADIUS + 1.0;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT * 1.5;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        let miss = sim.recording.misses[0];
+        assert_eq!(miss.player, 1);
+        assert_relative_eq!(miss.offset, 2.0, epsilon = 0.05);
+        assert_relative_eq!(miss.speed, 300.0, epsilon = 0.01);
+        // Announced just before the point (the next countdown follows)
+        assert_eq!(
+            sim.events[..2],
+            [
+                GameEvent::BallMissed { miss },
+                GameEvent::PointScored { winner: 2 }
+            ]
+        );
+        assert!(sim.coach.tip.is_some());
+    }
+
+    #[test]
+    fn test_ball_bounces_off_obstacles() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.obstacle_mode = true;
+        sim.start_match(GameMode::TwoPlayer);
+        assert!(!sim.obstacles.is_empty());
+
+        // Send the ball straight at the first block
+        let block = sim.obstacles[0];
+        sim.countdown = None;
+        sim.ball.x = block.x - BALL_RADIUS - 1.0;
+        sim.ball.y = block.y + block.height / 2.0;
+        sim.ball.dx = 300.0;
+        sim.ball.dy = 0.0;
+
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(sim.ball.dx < 0.0);
+        assert!(sim.ball.x < block.x);
+        assert!(sim.events.contains(&GameEvent::ObstacleBounce));
+
+        // Without obstacle mode the court is empty
+        sim.obstacle_mode = false;
+        sim.start_match(GameMode::TwoPlayer);
+        assert!(sim.obstacles.is_empty());
+    }
+
+    #[test]
+    fn test_autopilot_takes_over_player_one() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.start_match(GameMode::VsAi);
+        sim.set_autopilot(true);
+        assert!(sim.autopilot_used);
+
+        // Put the ball low on player 1's side; the held keys are ignored
+        sim.countdown = None;
+        sim.ball.x = sim.court.width / 4.0;
+        sim.ball.y = sim.court.height - BALL_RADIUS;
+        sim.ball.dx = -200.0;
+        sim.ball.dy = 0.0;
+        let start_y = sim.player1.y;
+        for _ in 0..30 {
+            sim.step(FIXED_TIMESTEP, [-1.0, 0.0]);
+        }
+        assert!(sim.player1.y > start_y);
+
+        // Handing the paddle back lets the keys steer it again
+        sim.set_autopilot(false);
+        let handed_back_y = sim.player1.y;
+        for _ in 0..30 {
+            sim.step(FIXED_TIMESTEP, [-1.0, 0.0]);
+        }
+        assert!(sim.player1.y < handed_back_y);
+
+        // The auto-pilot stays on for the next match, which counts as helped too
+        sim.set_autopilot(true);
+        sim.start_match(GameMode::VsAi);
+        assert!(sim.autopilot);
+        assert!(sim.autopilot_used);
+    }
+
+    #[test]
+    fn test_paddle_contacts_are_recorded() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // Send the ball flat into the lower half of the left paddle
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH + BALL_RADIUS;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT * 0.75;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        assert_eq!(sim.recording.contacts.len(), 1);
+        let contact = sim.recording.contacts[0];
+        assert_eq!(contact.player, 1);
+        assert_relative_eq!(contact.offset, 0.5, epsilon = 1e-3);
+        assert_relative_eq!(contact.angle_in, 0.0);
+        assert_relative_eq!(contact.speed, 300.0, epsilon = 1e-3);
+        assert!(sim.ball.dx > 0.0);
+
+        // A new match starts a fresh recording
+        sim.start_match(GameMode::TwoPlayer);
+        assert!(sim.recording.contacts.is_empty());
+    }
+
+    #[test]
+    fn test_ball_leaving_a_paddle_is_not_hit_again() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // Still overlapping the left paddle once its cooldown is over, but
+        // already on its way back out
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT / 2.0;
+        sim.ball.dx = 300.0;
+        sim.ball.dy = 0.0;
+        assert!(sim.player1.can_hit());
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        assert!(sim.recording.contacts.is_empty());
+        assert_relative_eq!(sim.ball.speed(), 300.0, epsilon = 0.01);
+    }
+
+    #[test]
+    fn test_contacts_are_measured_on_the_paddles_own_height() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // A paddle made taller mid-match (say by a power-up) is measured by
+        // its own height, not the paddle size setting
+        sim.player1.height = PADDLE_HEIGHT * 2.0;
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH + BALL_RADIUS;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT * 1.5;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert_relative_eq!(sim.recording.contacts[0].offset, 0.5, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_countdown_cues_follow_game_time() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        assert_eq!(sim.audio.take_ready(), vec![Cue::CountdownBeep]); // "3"
+
+        // Just short of one second: still waiting for the "2"
+        let second = (1.0 / FIXED_TIMESTEP).round() as u32;
+        for _ in 0..second - 1 {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert!(sim.audio.take_ready().is_empty());
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert_eq!(sim.audio.take_ready(), vec![Cue::CountdownBeep]);
+
+        // The serve beep comes on the step that launches the ball
+        for _ in 0..second * 2 {
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        }
+        assert_eq!(
+            sim.audio.take_ready(),
+            vec![Cue::CountdownBeep, Cue::CountdownGo]
+        );
+        assert!(sim.countdown.is_none());
+    }
+
+    #[test]
+    fn test_demo_plays_itself_to_the_end() {
+        let mut sim = Simulation::new(GameMode::Demo);
+        sim.start_match(GameMode::Demo);
+
+        // Nobody presses anything, yet both paddles move and someone wins
+        let start_y = (sim.player1.y, sim.player2.y);
+        let mut moved = (false, false);
+        for _ in 0..200_000 {
+            if sim.winner.is_some() {
+                break;
+            }
+            sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            moved.0 |= sim.player1.y != start_y.0;
+            moved.1 |= sim.player2.y != start_y.1;
+        }
+        assert!(sim.winner.is_some(), "demo match never finished");
+        assert_eq!(moved, (true, true));
+        assert!(!sim.recording.contacts.is_empty());
+    }
+
+    #[test]
+    fn test_nobody_resigns_a_demo() {
+        let mut sim = Simulation::new(GameMode::Demo);
+        sim.start_match(GameMode::Demo);
+        sim.resign(1);
+        sim.resign(2);
+        assert_eq!(sim.winner, None);
+    }
+
+    #[test]
+    fn test_moving_paddle_spins_the_ball() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // Player 1 is already moving down when the ball arrives
+        sim.player1.velocity = PLAYER_PADDLE_SPEED;
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH + BALL_RADIUS;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT / 2.0;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [1.0, 0.0]);
+
+        assert_eq!(sim.recording.contacts.len(), 1);
+        assert!(sim.ball.spin > 0.0);
+    }
+
+    #[test]
+    fn test_fast_paddle_cannot_push_the_ball_past_top_speed() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.momentum_physics = true;
+        sim.start_match(GameMode::TwoPlayer);
+        sim.countdown = None;
+
+        // A top-speed ball meets player 1's paddle sliding down hard
+        sim.player1.velocity = PLAYER_PADDLE_SPEED * 4.0;
+        sim.ball.x = sim.player1.x + PADDLE_WIDTH + BALL_RADIUS;
+        sim.ball.y = sim.player1.y + PADDLE_HEIGHT / 2.0;
+        sim.ball.dx = -MAX_BALL_SPEED;
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [1.0, 0.0]);
+
+        // Dragged down by the paddle, but no faster than the top speed
+        assert_eq!(sim.recording.contacts.len(), 1);
+        assert!(sim.ball.dy > 0.0);
+        assert!(sim.ball.speed() <= MAX_BALL_SPEED + 0.01);
+    }
+
+    #[test]
+    fn test_checksum_matches_between_identical_runs() {
+        let mut first = Simulation::new(GameMode::Demo);
+        let mut second = Simulation::new(GameMode::Demo);
+        first.next_seed = Some(99);
+        second.next_seed = Some(99);
+        first.start_match(GameMode::Demo);
+        second.start_match(GameMode::Demo);
+
+        for _ in 0..1000 {
+            first.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            second.step(FIXED_TIMESTEP, [0.0, 0.0]);
+            assert_eq!(first.checksum(), second.checksum());
+        }
+        assert_eq!(first.ticks, 1000);
+
+        // The smallest nudge to the ball shows up
+        let before = first.checksum();
+        first.ball.x += 0.001;
+        assert_ne!(first.checksum(), before);
+
+        // A new match starts counting again
+        first.start_match(GameMode::Demo);
+        assert_eq!(first.ticks, 0);
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::SOUND_SAMPLE_RATE;
+    use crate::game::sounds::{crowd_wav, tone_wav};
+
+    #[test]
+    fn test_tone_is_a_wav_file_of_the_right_length() {
+        let bytes = tone_wav(&[(440.0, 0.5), (880.0, 0.5)]);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+
+        // One second of 16-bit samples after the 44-byte header
+        assert_eq!(bytes.len(), 44 + SOUND_SAMPLE_RATE as usize * 2);
+    }
+
+    #[test]
+    fn test_crowd_is_the_same_every_time() {
+        let bytes = crowd_wav(2.0);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(bytes.len(), 44 + SOUND_SAMPLE_RATE as usize * 4);
+        assert_eq!(bytes, crowd_wav(2.0));
+
+        // Not silent
+        assert!(bytes[44..].iter().any(|&byte| byte != 0));
+    }
+}
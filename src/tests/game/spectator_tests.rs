@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        constants::FIXED_TIMESTEP,
+        court::CourtPreset,
+        scene::Scene,
+        simulation::{GameMode, Simulation},
+        spectator::{Snapshot, SpectatorClient, SpectatorServer},
+        state::GameState,
+    };
+    use crate::tests::support::eventually;
+
+    // A match somewhere in the middle, with most things away from their defaults
+    fn match_in_progress() -> Simulation {
+        let mut sim = Simulation::new(GameMode::Bot);
+        sim.apply_court(CourtPreset::Narrow);
+        sim.rules.best_of = 3;
+        sim.lives_mode = true;
+        sim.start_match(GameMode::Bot);
+        sim.countdown = None;
+        sim.ball.x = 123.0;
+        sim.ball.y = 45.0;
+        sim.ball.dx = -300.0;
+        sim.ball.dy = 200.0;
+        sim.player1.y = 10.0;
+        sim.player2.y = 250.0;
+        sim.score.player1 = 2;
+        sim.score.games_player2 = 1;
+        sim.lives.lose_life(1);
+        sim.ticks = 90;
+        sim
+    }
+
+    #[test]
+    fn test_snapshot_copies_the_match() {
+        let host = match_in_progress();
+        let line = serde_json::to_string(&Snapshot::new(&host, 7)).unwrap();
+        let snapshot: Snapshot = serde_json::from_str(&line).unwrap();
+        assert_eq!(snapshot.tick, 7);
+
+        let mut copy = Simulation::new(GameMode::VsAi);
+        snapshot.apply(&mut copy);
+        assert_eq!(copy.mode, GameMode::Bot);
+        assert_eq!(copy.court_preset, CourtPreset::Narrow);
+        assert_eq!(copy.player2.x, host.player2.x);
+        assert_eq!((copy.ball.x, copy.ball.y), (123.0, 45.0));
+        assert_eq!(copy.prev_ball, (123.0, 45.0)); // Drawn where it is, not blended
+        assert_eq!((copy.player1.y, copy.player2.y), (10.0, 250.0));
+        assert_eq!((copy.score.player1, copy.score.games_player2), (2, 1));
+        assert_eq!(copy.rules.best_of, 3);
+        assert!(copy.lives_mode);
+        assert_eq!(copy.lives.player1, host.lives.player1);
+        assert!(copy.countdown.is_none());
+        assert_eq!(copy.ticks, 90); // So the match clock carries over
+    }
+
+    #[test]
+    fn test_every_spectator_gets_the_match() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.address().unwrap().to_string();
+        let mut spectators = vec![
+            SpectatorClient::connect(&address).unwrap(),
+            SpectatorClient::connect(&address).unwrap(),
+        ];
+        assert!(eventually(|| {
+            server.poll();
+            server.count() == 2
+        }));
+
+        let sim = match_in_progress();
+        server.broadcast(&sim, None);
+        for spectator in &mut spectators {
+            assert!(eventually(|| {
+                spectator.poll(0.0).unwrap();
+                spectator.latest.is_some()
+            }));
+            assert_eq!(spectator.latest, Some(Snapshot::new(&sim, 0)));
+        }
+
+        // A spectator that leaves is dropped without bothering the others
+        spectators.remove(0);
+        assert!(eventually(|| {
+            server.broadcast(&sim, None);
+            server.count() == 1
+        }));
+        let remaining = &mut spectators[0];
+        assert!(eventually(|| {
+            remaining.poll(0.0).unwrap();
+            remaining
+                .latest
+                .as_ref()
+                .is_some_and(|latest| latest.tick > 0)
+        }));
+    }
+
+    #[test]
+    fn test_ordinary_matches_can_be_watched_too() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.spectators = Some(SpectatorServer::bind("127.0.0.1:0").unwrap());
+        let address = state.spectators.as_ref().unwrap().address().unwrap();
+        let mut spectator = SpectatorClient::connect(&address.to_string()).unwrap();
+        assert!(eventually(|| {
+            let server = state.spectators.as_mut().unwrap();
+            server.poll();
+            server.count() == 1
+        }));
+
+        // Each step of a match against the computer is sent out
+        state.start_match(GameMode::VsAi);
+        state.step(FIXED_TIMESTEP);
+        assert!(eventually(|| {
+            spectator.poll(0.0).unwrap();
+            spectator.latest.is_some()
+        }));
+        assert_eq!(spectator.latest, Some(Snapshot::new(&state.sim, 0)));
+
+        // Opening the port again keeps the spectators already watching
+        state.open_spectator_port();
+        assert_eq!(state.spectators.as_ref().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_watching_follows_the_host_until_it_leaves() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.address().unwrap().to_string();
+        let mut state = GameState::new(GameMode::VsAi);
+        state.watch(&address).unwrap();
+        assert_eq!(state.scene, Scene::Spectating);
+        assert!(eventually(|| {
+            server.poll();
+            server.count() == 1
+        }));
+
+        let sim = match_in_progress();
+        server.broadcast(&sim, None);
+        assert!(eventually(|| {
+            state.follow_watched_match(0.0);
+            state.sim.ball.x == 123.0
+        }));
+        assert_eq!(state.sim.player2.y, 250.0);
+
+        // The host closing its game sends the spectator back to the menu
+        drop(server);
+        assert!(eventually(|| {
+            state.follow_watched_match(0.0);
+            state.scene == Scene::Menu
+        }));
+        assert!(state.status.as_deref().unwrap().contains("stopped sending"));
+        assert!(state.watching.is_none());
+    }
+
+    #[test]
+    fn test_captured_match_plays_back_one_step_at_a_time() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_capture_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("match.jsonl");
+
+        // Watch three snapshots arrive, saving them as they come
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = server.address().unwrap().to_string();
+        let mut spectator = SpectatorClient::connect(&address).unwrap();
+        spectator.capture_to(&path).unwrap();
+        assert!(eventually(|| {
+            server.poll();
+            server.count() == 1
+        }));
+        let mut sim = match_in_progress();
+        for x in [100.0, 200.0, 300.0] {
+            sim.ball.x = x;
+            server.broadcast(&sim, None);
+        }
+        assert!(eventually(|| {
+            spectator.poll(0.0).unwrap();
+            spectator
+                .latest
+                .as_ref()
+                .is_some_and(|latest| latest.tick == 2)
+        }));
+        drop(spectator);
+
+        // The file plays back through the spectating screen, one snapshot per
+        // step, and holds the last one at the end
+        let mut state = GameState::new(GameMode::VsAi);
+        state.watch_capture(&path).unwrap();
+        assert_eq!(state.scene, Scene::Spectating);
+        assert!(state.watching.as_ref().unwrap().is_replay());
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            state.follow_watched_match(FIXED_TIMESTEP);
+            seen.push(state.sim.ball.x);
+        }
+        assert_eq!(seen, vec![200.0, 300.0, 300.0, 300.0, 300.0]);
+        assert!(state.watching.as_ref().unwrap().finished());
+        assert_eq!(state.scene, Scene::Spectating);
+
+        // A file with no match in it isn't played
+        std::fs::write(&path, "not a match\n").unwrap();
+        assert!(GameState::new(GameMode::VsAi).watch_capture(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
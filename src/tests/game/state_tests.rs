@@ -1,17 +1,1147 @@
 #[cfg(test)]
 mod tests {
-    use crate::game::{constants::{SCREEN_HEIGHT, SCREEN_WIDTH}, state::GameState};
+    use crate::components::ball::Ball;
+    use crate::game::{
+        ai::AiDifficulty,
+        audio::SoundCategory,
+        cli::CliOptions,
+        constants::{
+            AUTOPILOT_REMATCH_DELAY, COUNTDOWN_DURATION, FIXED_TIMESTEP, HIDDEN_FPS, LOW_POWER_FPS,
+            MATCH_PAUSE_LIMIT, MAX_FRAME_TIME, MAX_SAFE_MARGIN, PLAYER_PADDLE_SPEED,
+            QUICK_RESTART_CONFIRM_TIME, SCREEN_HEIGHT, SCREEN_WIDTH, SMALL_PADDLE_HEIGHT,
+            STREAK_BANNER_TIME,
+        },
+        input::{Action, PaddleInput},
+        match_feel::BallPace,
+        profiles::SteerKeys,
+        rules::MatchEnd,
+        scene::{MenuItem, Scene, SettingsItem},
+        simulation::GameMode,
+        spectator::SpectatorServer,
+        state::GameState,
+        tournament::BracketSize,
+    };
+    use crate::graphics::broadcast::HudLayout;
+    use crate::graphics::seasonal::CalendarDay;
+    use crate::graphics::theme::ThemePreset;
+    use ggez::graphics::Color;
+    use std::time::Instant;
+
+    use approx::assert_relative_eq;
 
     #[test]
     fn test_new_game_state() {
-        let state = GameState::new();
-        assert!(!state.game_running);
-        assert_eq!(state.ball.x, SCREEN_WIDTH / 2.0);
-        assert_eq!(state.ball.y, SCREEN_HEIGHT / 2.0);
-        assert_eq!(state.ball.dx, 0.0);
-        assert_eq!(state.ball.dy, 0.0);
-        assert_eq!(state.score.player1, 0);
-        assert_eq!(state.score.player2, 0);
+        let state = GameState::new(GameMode::VsAi);
+        assert_eq!(state.sim.mode, GameMode::VsAi);
+        assert_eq!(state.scene, Scene::Menu);
+        assert_eq!(state.sim.ball.x, SCREEN_WIDTH / 2.0);
+        assert_eq!(state.sim.ball.y, SCREEN_HEIGHT / 2.0);
+        assert_eq!(state.sim.ball.dx, 0.0);
+        assert_eq!(state.sim.ball.dy, 0.0);
+        assert_eq!(state.sim.score.player1, 0);
+        assert_eq!(state.sim.score.player2, 0);
+    }
+
+    #[test]
+    fn test_new_two_player_game_state() {
+        let state = GameState::new(GameMode::TwoPlayer);
+        assert_eq!(state.sim.mode, GameMode::TwoPlayer);
+        assert_eq!(state.scene, Scene::Menu);
+        assert_eq!(state.sim.player1.y, state.sim.player2.y);
+    }
+
+    #[test]
+    fn test_start_match_begins_countdown() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::TwoPlayer);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.mode, GameMode::TwoPlayer);
+        assert!(state.sim.countdown.is_some());
+        assert!(state.in_match());
+        assert_eq!(state.sim.rally(), 0);
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+
+        state.pause();
+        assert_eq!(state.scene, Scene::Paused);
+        assert!(state.in_match());
+
+        state.resume();
+        assert_eq!(state.scene, Scene::Playing);
+        // Pausing during the serve countdown just carries it on
+        assert!(state.resume_countdown.is_none());
+    }
+
+    #[test]
+    fn test_resume_mid_rally_counts_down() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.countdown = None; // Ball already in play
+
+        state.pause();
+        state.resume();
+        assert!(state.resume_countdown.is_some());
+
+        // The countdown runs on the frame time passed in, not the clock
+        assert!(state.run_resume_countdown(COUNTDOWN_DURATION - 1.0));
+        assert_eq!(state.resume_countdown, Some(COUNTDOWN_DURATION - 1.0));
+        assert!(!state.run_resume_countdown(1.0));
+        assert!(state.resume_countdown.is_none());
+    }
+
+    #[test]
+    fn test_restart_from_pause() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::TwoPlayer);
+        state.sim.score.increment_player1();
+        state.pause();
+
+        state.handle_action(Action::Restart);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.mode, GameMode::TwoPlayer);
+        assert_eq!(state.sim.score.player1, 0);
+        assert!(state.sim.countdown.is_some());
+    }
+
+    #[test]
+    fn test_quick_restart_asks_once_points_are_played() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.score.increment_player1();
+
+        // The first press only asks
+        state.handle_action(Action::QuickRestart);
+        assert!(state.restart_prompt.is_some());
+        assert_eq!(state.sim.score.player1, 1);
+
+        // Left unanswered, the question goes away
+        state.expire_restart_prompt(QUICK_RESTART_CONFIRM_TIME);
+        assert!(state.restart_prompt.is_none());
+
+        // Asked and answered, from the pause screen too
+        state.pause();
+        state.handle_action(Action::QuickRestart);
+        state.handle_action(Action::QuickRestart);
+        assert!(state.restart_prompt.is_none());
+        assert_eq!(state.sim.score.player1, 0);
+        assert_eq!(state.scene, Scene::Playing);
+    }
+
+    #[test]
+    fn test_quick_restart_before_any_points_is_instant() {
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        state.start_match(GameMode::TwoPlayer);
+        state.handle_action(Action::QuickRestart);
+        assert!(state.restart_prompt.is_none());
+        assert!(state.sim.countdown.is_some());
+    }
+
+    #[test]
+    fn test_match_pause_time_runs_out() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::Bot);
+        assert_eq!(state.pause_time_left(), Some(MATCH_PAUSE_LIMIT));
+
+        // The clock only runs while paused, and adds up over the match
+        state.pause();
+        state.run_pause_clock(MATCH_PAUSE_LIMIT - 10.0);
+        state.resume();
+        state.run_pause_clock(20.0);
+        assert_eq!(state.pause_time_left(), Some(10.0));
+
+        // Once it runs out the match carries on, and can't be paused again
+        state.pause();
+        state.run_pause_clock(10.0);
+        assert_eq!(state.scene, Scene::Playing);
+        state.pause();
+        assert_eq!(state.scene, Scene::Playing);
+
+        // A new match gets the full time back
+        state.restart();
+        state.pause();
+        assert_eq!(state.scene, Scene::Paused);
+
+        // A match played only on this computer has no limit, unless it can be
+        // watched over the network
+        state.start_match(GameMode::TwoPlayer);
+        assert_eq!(state.pause_time_left(), None);
+        state.spectators = Some(SpectatorServer::bind("127.0.0.1:0").unwrap());
+        assert_eq!(state.pause_time_left(), Some(MATCH_PAUSE_LIMIT));
+
+        // Hiding the window stops the clock, except in a bot match
+        state.set_hidden(true);
+        state.run_pause_clock(MATCH_PAUSE_LIMIT);
+        assert_eq!(state.scene, Scene::Paused);
+        state.set_hidden(false);
+        state.run_pause_clock(MATCH_PAUSE_LIMIT);
+        assert_eq!(state.scene, Scene::Playing);
+        state.start_match(GameMode::Bot);
+        state.set_hidden(true);
+        state.run_pause_clock(MATCH_PAUSE_LIMIT);
+        assert_eq!(state.scene, Scene::Playing);
+        state.set_hidden(false);
+
+        // Training nobody can watch has no limit either
+        state.spectators = None;
+        state.start_match(GameMode::Training);
+        assert_eq!(state.pause_time_left(), None);
+    }
+
+    #[test]
+    fn test_quit_to_menu_resets_match() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.score.increment_player1();
+
+        state.quit_to_menu();
+        assert_eq!(state.scene, Scene::Menu);
+        assert_eq!(state.sim.score.player1, 0);
+        assert!(state.sim.countdown.is_none());
+    }
+
+    #[test]
+    fn test_change_setting_toggles_value() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(!state.sim.lives_mode);
+        state.change_setting(SettingsItem::LivesMode);
+        assert!(state.sim.lives_mode);
+        assert_eq!(
+            state.settings_label(SettingsItem::LivesMode),
+            "Lives Mode: On"
+        );
+
+        state.scene = Scene::Settings;
+        state.change_setting(SettingsItem::Back);
+        assert_eq!(state.scene, Scene::Menu);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_ai_sliders_move_both_ways() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.scene = Scene::Settings;
+        state.handle_action(Action::Down); // AI Speed
+        state.handle_action(Action::Right);
+        state.handle_action(Action::Right);
+        state.handle_action(Action::Down); // AI Accuracy
+        state.handle_action(Action::Left);
+        assert_eq!(state.sim.ai_tuning.speed, 2);
+        assert_eq!(state.sim.ai_tuning.accuracy, -1);
+        assert_eq!(
+            state.settings_label(SettingsItem::AiSpeed),
+            "AI Speed: [-------|---] +2"
+        );
+        assert_eq!(
+            state.settings_label(SettingsItem::Difficulty),
+            "AI Difficulty: Medium (Tuned)"
+        );
+
+        // Picking another difficulty starts from how it plays on its own
+        state.change_setting(SettingsItem::Difficulty);
+        assert!(!state.sim.ai_tuning.is_custom());
+        assert_eq!(
+            state.settings_label(SettingsItem::AiAccuracy),
+            "AI Accuracy: [-----|-----] Normal"
+        );
+    }
+
+    #[test]
+    fn test_actions_drive_menu_and_pause() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.handle_action(Action::Down);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.mode, GameMode::TwoPlayer);
+
+        state.handle_action(Action::Pause);
+        assert_eq!(state.scene, Scene::Paused);
+        state.handle_action(Action::Pause);
+        assert_eq!(state.scene, Scene::Playing);
+
+        state.handle_action(Action::Back);
+        assert_eq!(state.scene, Scene::Menu);
+
+        state.handle_action(Action::Exit);
+        assert!(state.should_exit);
+    }
+
+    #[test]
+    fn test_theme_switches_on_any_screen() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.handle_action(Action::NextTheme);
+        assert_eq!(state.theme_preset, ThemePreset::Neon);
+        assert_eq!(state.theme, ThemePreset::Neon.theme());
+
+        // Switching mid-match leaves the match running
+        state.start_match(GameMode::VsAi);
+        state.handle_action(Action::NextTheme);
+        assert_eq!(state.theme_preset, ThemePreset::Phosphor);
+        assert_eq!(state.scene, Scene::Playing);
+    }
+
+    #[test]
+    fn test_holiday_look_follows_the_date_and_the_setting() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.set_calendar_day(CalendarDay { month: 6, day: 1 });
+        assert_eq!(state.shown_event(), None);
+        assert!(state.shown_particles().is_none());
+        assert_eq!(state.theme, ThemePreset::Classic.theme());
+
+        // December snows, and the ball becomes a snowball on every theme
+        state.set_calendar_day(CalendarDay { month: 12, day: 5 });
+        assert!(state.shown_particles().is_some());
+        assert_eq!(state.theme.ball, Color::WHITE);
+        state.next_theme();
+        assert_eq!(state.theme.ball, Color::WHITE);
+        assert_eq!(state.theme.background, ThemePreset::Neon.theme().background);
+        assert_eq!(
+            state.settings_label(SettingsItem::HolidayEvents),
+            "Holiday Events: On (Snow)"
+        );
+
+        // Low-power mode keeps the colors but skips the snow
+        state.low_power = true;
+        assert!(state.shown_particles().is_none());
+        state.low_power = false;
+
+        // Turning it off puts the theme back and is remembered
+        state.change_setting(SettingsItem::HolidayEvents);
+        assert_eq!(state.shown_event(), None);
+        assert!(state.shown_particles().is_none());
+        assert_eq!(state.theme, ThemePreset::Neon.theme());
+        assert!(!state.settings().holiday_events);
+        assert_eq!(
+            state.settings_label(SettingsItem::HolidayEvents),
+            "Holiday Events: Off"
+        );
+    }
+
+    #[test]
+    fn test_broadcast_key_switches_the_score_display() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.handle_action(Action::ToggleBroadcast);
+        assert_eq!(state.hud_layout, HudLayout::Broadcast);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.settings().hud_layout, HudLayout::Broadcast);
+        assert_eq!(
+            state.settings_label(SettingsItem::HudLayout),
+            "Score Display: Broadcast"
+        );
+
+        // The settings entry switches it back
+        state.change_setting(SettingsItem::HudLayout);
+        assert_eq!(state.hud_layout, HudLayout::Normal);
+    }
+
+    #[test]
+    fn test_scoreboard_names_each_side() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        assert_eq!(state.scoreboard().names, ["Player 1", "CPU (Medium)"]);
+
+        // A player's profile name, and a tournament opponent's name
+        state.preferences.profiles.add("Sam").unwrap();
+        state.preferences.profiles.slide_choice(1, 1);
+        state.open_tournament();
+        state.play_tournament_round();
+        assert_eq!(state.scoreboard().names, ["Sam", "Steady Sam"]);
+
+        state.start_match(GameMode::TwoPlayer);
+        assert_eq!(state.scoreboard().names, ["Sam", "Player 2"]);
+    }
+
+    #[test]
+    fn test_resign_from_pause() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+
+        // Resigning only works from the pause screen
+        state.handle_action(Action::Resign(1));
+        assert_eq!(state.scene, Scene::Playing);
+
+        state.pause();
+        state.handle_action(Action::Resign(2)); // The AI can't resign
+        assert_eq!(state.scene, Scene::Paused);
+
+        state.handle_action(Action::Resign(1));
+        assert_eq!(state.scene, Scene::GameOver);
+        assert_eq!(state.sim.winner, Some(2));
+        assert_eq!(state.sim.end_reason, Some(MatchEnd::Forfeit));
+        assert_eq!(state.sim.stats.forfeited_by, Some(1));
+    }
+
+    #[test]
+    fn test_resigning_is_described_from_the_players_side() {
+        // The player who resigned against the computer is told they did
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.pause();
+        state.handle_action(Action::Resign(1));
+        assert_eq!(state.result_text(), "You Lost!\nYou resigned");
+
+        // Two players are named by number
+        state.start_match(GameMode::TwoPlayer);
+        state.pause();
+        state.handle_action(Action::Resign(2));
+        assert_eq!(state.result_text(), "Player 1 Wins!\nPlayer 2 resigned");
+    }
+
+    #[test]
+    fn test_game_over_counts_toward_career() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.pause();
+        state.handle_action(Action::Resign(1));
+        assert_eq!(state.career.session.losses, 1);
+        assert_eq!(state.career.lifetime.losses, 1);
+
+        // Two-player matches don't count as wins or losses
+        state.start_match(GameMode::TwoPlayer);
+        state.pause();
+        state.handle_action(Action::Resign(2));
+        assert_eq!(state.career.session.wins, 0);
+        assert_eq!(state.career.session.losses, 1);
+        assert!(state.career_text().contains("0 won, 1 lost"));
+
+        // The analysis opens from the game over screen and goes back to it
+        state.handle_action(Action::Analysis);
+        assert_eq!(state.scene, Scene::Analysis);
+        state.handle_action(Action::Back);
+        assert_eq!(state.scene, Scene::GameOver);
+
+        // Without a stats file, saving does nothing
+        assert!(state.save_stats().is_ok());
+    }
+
+    // A match with the ball already moving right at 100 pixels per second
+    #[test]
+    fn test_mouse_steering_follows_the_pointer_at_paddle_speed() {
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        state.start_match(GameMode::TwoPlayer);
+        let start_y = state.sim.player1.y;
+
+        // The pointer does nothing while the keys steer
+        state.mouse_moved(SCREEN_HEIGHT * 0.75);
+        state.step(FIXED_TIMESTEP);
+        assert_eq!(state.sim.player1.y, start_y);
+
+        // With the mouse, the paddle heads for the pointer no faster than
+        // the keys move it, and settles there
+        state.paddle_input = PaddleInput::Mouse;
+        let mut last_y = start_y;
+        for _ in 0..(1.0 / FIXED_TIMESTEP) as usize {
+            state.step(FIXED_TIMESTEP);
+            let moved = state.sim.player1.y - last_y;
+            let step_limit = PLAYER_PADDLE_SPEED * FIXED_TIMESTEP + 1e-3;
+            assert!((0.0..=step_limit).contains(&moved));
+            last_y = state.sim.player1.y;
+        }
+        let middle = state.sim.player1.y + state.sim.player1.height / 2.0;
+        assert_relative_eq!(middle, SCREEN_HEIGHT * 0.75, epsilon = 1.0);
+    }
+
+    fn rally_in_progress() -> GameState {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.countdown = None;
+        state.sim.ball.dx = 100.0;
+        state
+    }
+
+    #[test]
+    fn test_advance_runs_fixed_steps() {
+        let mut state = rally_in_progress();
+        let start_x = state.sim.ball.x;
+
+        // One 60 fps frame is exactly two 120 Hz steps
+        state.advance(2.0 * FIXED_TIMESTEP);
+        assert_relative_eq!(
+            state.sim.ball.x,
+            start_x + 200.0 * FIXED_TIMESTEP,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            state.sim.prev_ball.0,
+            start_x + 100.0 * FIXED_TIMESTEP,
+            epsilon = 1e-3
+        );
+        assert!(state.interpolation() < 1e-3);
+    }
+
+    #[test]
+    fn test_advance_keeps_leftover_time() {
+        let mut state = rally_in_progress();
+        let start_x = state.sim.ball.x;
+
+        // Half a step isn't enough to move, but is remembered for drawing
+        state.advance(FIXED_TIMESTEP / 2.0);
+        assert_relative_eq!(state.sim.ball.x, start_x);
+        assert_relative_eq!(state.interpolation(), 0.5, epsilon = 1e-3);
+
+        state.advance(FIXED_TIMESTEP / 2.0);
+        assert_relative_eq!(
+            state.sim.ball.x,
+            start_x + 100.0 * FIXED_TIMESTEP,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_same_frames_give_same_result() {
+        // Different frame rates over the same total time land in the same place
+        let mut fast = rally_in_progress();
+        let mut slow = rally_in_progress();
+        for _ in 0..12 {
+            fast.advance(FIXED_TIMESTEP);
+        }
+        for _ in 0..3 {
+            slow.advance(4.0 * FIXED_TIMESTEP);
+        }
+        assert_relative_eq!(fast.sim.ball.x, slow.sim.ball.x, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_long_frames_are_capped() {
+        let mut state = rally_in_progress();
+        let start_x = state.sim.ball.x;
+        state.advance(10.0);
+        assert!(state.sim.ball.x - start_x <= 100.0 * MAX_FRAME_TIME + 1e-3);
+    }
+
+    // Let the serve countdown run out so the ball is launched
+    fn serve(state: &mut GameState) {
+        state.sim.countdown = Some(COUNTDOWN_DURATION);
+        state.step(FIXED_TIMESTEP);
+    }
+
+    #[test]
+    fn test_rematch_keeps_settings_and_swaps_serve() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.sim.rules.best_of = 3;
+        state.start_match(GameMode::TwoPlayer);
+        serve(&mut state);
+        let first_server = state.sim.opening_server.unwrap();
+
+        state.resign(1);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.mode, GameMode::TwoPlayer);
+        assert_eq!(state.sim.rules.best_of, 3);
+        assert!(state.sim.winner.is_none());
+
+        serve(&mut state);
+        assert_ne!(state.sim.opening_server, Some(first_server));
+        assert_eq!(state.sim.stats.server, state.sim.opening_server);
+    }
+
+    #[test]
+    fn test_menu_match_forgets_opening_server() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        serve(&mut state);
+        assert!(state.sim.opening_server.is_some());
+
+        state.quit_to_menu();
+        assert!(state.sim.opening_server.is_none());
+    }
+
+    #[test]
+    fn test_momentum_bar_slides_toward_leader() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.momentum.point_won(1, 0);
+
+        state.advance(FIXED_TIMESTEP);
+        let first = state.momentum_shown;
+        assert!(first < 0.0);
+        assert!(first > state.sim.momentum.value); // Still on its way
+
+        state.advance(FIXED_TIMESTEP);
+        assert!(state.momentum_shown < first);
+    }
+
+    #[test]
+    fn test_low_power_caps_frame_rate() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(state.frame_cap().is_none());
+
+        state.change_setting(SettingsItem::LowPower);
+        assert_eq!(
+            state.settings_label(SettingsItem::LowPower),
+            "Low Power Mode: On"
+        );
+
+        // Menus run slower than a match
+        let menu_cap = state.frame_cap().unwrap();
+        state.start_match(GameMode::VsAi);
+        let match_cap = state.frame_cap().unwrap();
+        assert_relative_eq!(match_cap.as_secs_f32(), 1.0 / LOW_POWER_FPS, epsilon = 1e-4);
+        assert!(menu_cap > match_cap);
+    }
+
+    #[test]
+    fn test_hiding_window_pauses_and_throttles() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+
+        state.set_hidden(true);
+        assert_eq!(state.scene, Scene::Paused);
+        let cap = state.frame_cap().unwrap();
+        assert_relative_eq!(cap.as_secs_f32(), 1.0 / HIDDEN_FPS, epsilon = 1e-4);
+
+        // Showing the window again leaves the match paused until the player resumes
+        state.set_hidden(false);
+        assert_eq!(state.scene, Scene::Paused);
+        assert!(state.frame_cap().is_none());
+    }
+
+    #[test]
+    fn test_fullscreen_toggle_and_resize() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(!state.fullscreen);
+        state.handle_action(Action::ToggleFullscreen);
+        assert!(state.fullscreen);
+        state.handle_action(Action::ToggleFullscreen);
+        assert!(!state.fullscreen);
+
+        state.set_window_size(1920.0, 1080.0);
+        assert_eq!(state.window_size, (1920.0, 1080.0));
+
+        // A minimized window reports no size; keep the last one
+        state.set_window_size(0.0, 0.0);
+        assert_eq!(state.window_size, (1920.0, 1080.0));
+    }
+
+    #[test]
+    fn test_damaged_stats_file_shows_startup_error() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_startup_{}", std::process::id()));
+        let path = dir.join("stats.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "{ \"wins\": -1 }").unwrap();
+
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_stats_file(path.clone());
+        assert_eq!(state.scene, Scene::StartupError);
+        assert!(state.startup_errors[0].contains("stats.json"));
+        assert!(state.stats_path.is_none()); // The damaged file is left alone
+
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Menu);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recorded_drill_is_saved_and_picked_for_training() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_drills_{}", std::process::id()));
+        let path = dir.join("drills.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_drills_file(path.clone());
+
+        // Training starts from the drill list; with no drills, only random balls
+        state.menu_cursor.selected = MenuItem::ALL
+            .iter()
+            .position(|&item| item == MenuItem::Training)
+            .unwrap();
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Drills);
+        assert_eq!(state.drill_choices(), vec!["Random Balls", "Back"]);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.sim.mode, GameMode::Training);
+
+        // Record two returns, then stop and name the drill
+        state.handle_action(Action::RecordDrill);
+        let mut ball = Ball::new();
+        ball.launch(500.0, -0.2, 1.0);
+        state.sim.launcher.record_return(&ball, &state.sim.court);
+        state.sim.launcher.record_return(&ball, &state.sim.court);
+        state.handle_action(Action::RecordDrill);
+        assert_eq!(state.scene, Scene::NameDrill);
+        for character in "fast top".chars() {
+            state.type_drill_name_char(character);
+        }
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Paused);
+        assert_eq!(state.status.as_deref(), Some("Saved drill \"fast top\""));
+
+        // Next run, the drill is on the list and the machine fires its shots
+        let mut next_run = GameState::new(GameMode::VsAi);
+        next_run.use_drills_file(path);
+        next_run.scene = Scene::Drills;
+        assert_eq!(next_run.drill_choices()[1], "fast top (2 shots)");
+        next_run.handle_action(Action::Down);
+        next_run.handle_action(Action::Confirm);
+        assert_eq!(next_run.scene, Scene::Playing);
+        assert_eq!(
+            next_run.sim.launcher.drill.as_ref().unwrap().name,
+            "fast top"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_whats_new_shows_once_per_version() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_prefs_{}", std::process::id()));
+        let path = dir.join("preferences.json");
+
+        // First start of this version
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_preferences_file(path.clone());
+        assert_eq!(state.scene, Scene::WhatsNew);
+        assert!(!state.whats_new.is_empty());
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Menu);
+
+        // Started again: straight to the menu
+        let mut again = GameState::new(GameMode::VsAi);
+        again.use_preferences_file(path.clone());
+        assert_eq!(again.scene, Scene::Menu);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_settings_change_in_place_and_are_saved() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_live_{}", std::process::id()));
+        let path = dir.join("preferences.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_preferences_file(path.clone());
+        state.scene = Scene::Settings;
+
+        // Move to an option and slide it either way with the arrow keys
+        let select = |state: &mut GameState, item: SettingsItem| {
+            let index = SettingsItem::ALL.iter().position(|&entry| entry == item);
+            state.settings_cursor.selected = index.unwrap();
+        };
+        select(&mut state, SettingsItem::BallSpeed);
+        state.handle_action(Action::Right);
+        assert_eq!(state.sim.ball_pace, BallPace::Fast);
+        select(&mut state, SettingsItem::PaddleSize);
+        state.handle_action(Action::Left);
+        assert_eq!(state.sim.player1.height, SMALL_PADDLE_HEIGHT);
+        select(&mut state, SettingsItem::Volume);
+        state.handle_action(Action::Left);
+        state.handle_action(Action::Left);
+        assert_eq!(
+            state.settings_label(SettingsItem::Volume),
+            "Volume: [########--] 8"
+        );
+        select(&mut state, SettingsItem::SoundVolume(SoundCategory::Crowd));
+        state.handle_action(Action::Left);
+        assert_eq!(
+            state.settings_label(SettingsItem::SoundVolume(SoundCategory::Crowd)),
+            "Crowd: [#########-] 9"
+        );
+        select(&mut state, SettingsItem::Theme);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.theme_preset, ThemePreset::Neon);
+
+        // The next run starts with them
+        let mut again = GameState::new(GameMode::VsAi);
+        again.use_preferences_file(path.clone());
+        assert_eq!(again.settings(), state.settings());
+        assert_eq!(again.sim.player2.height, SMALL_PADDLE_HEIGHT);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_profiles_carry_each_players_controls() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_profiles_{}", std::process::id()));
+        let path = dir.join("preferences.json");
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        state.use_preferences_file(path.clone());
+
+        // Name a new profile from the settings screen
+        state.scene = Scene::Settings;
+        state.change_setting(SettingsItem::NewProfile);
+        assert_eq!(state.scene, Scene::NameProfile);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::NameProfile); // Nothing typed yet
+        for character in "Sam\n".chars() {
+            state.type_profile_name_char(character);
+        }
+        state.save_profile();
+        assert_eq!(state.scene, Scene::Settings);
+
+        // Player 2 picks it, moves it to W/S and makes the stick gentler
+        state.slide_setting(SettingsItem::PlayerProfile(2), 1);
+        state.change_setting(SettingsItem::PlayerKeys(2));
+        state.slide_setting(SettingsItem::StickSensitivity(2), -1);
+        state.slide_setting(SettingsItem::StickDeadzone(2), 1);
+        state.change_setting(SettingsItem::StickCurve(2));
+        assert_eq!(
+            state.settings_label(SettingsItem::PlayerProfile(2)),
+            "Player 2 Profile: Sam"
+        );
+        assert_eq!(
+            state.settings_label(SettingsItem::StickSensitivity(2)),
+            "Player 2 Stick Sensitivity: 0.75x"
+        );
+        assert_eq!(
+            state.settings_label(SettingsItem::StickDeadzone(2)),
+            "Player 2 Stick Dead Zone: 25%"
+        );
+        assert_eq!(
+            state.settings_label(SettingsItem::StickCurve(2)),
+            "Player 2 Stick Curve: Gentle"
+        );
+        assert_eq!(state.input.schemes[1].keys, SteerKeys::Wasd);
+        state.remember_settings();
+
+        // The next run picks the profile for player 2 again
+        let mut again = GameState::new(GameMode::TwoPlayer);
+        again.use_preferences_file(path.clone());
+        assert_eq!(again.input.schemes[1], state.input.schemes[1]);
+        assert_eq!(again.input.schemes[0].keys, SteerKeys::Arrows);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_window_size_presets_and_shape_lock() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert_eq!(
+            state.settings_label(SettingsItem::WindowSize),
+            "Window Size: 1x (900 x 600)"
+        );
+
+        // Picking the next size leaves fullscreen and asks for the new size
+        state.fullscreen = true;
+        state.change_setting(SettingsItem::WindowSize);
+        assert!(!state.fullscreen);
+        assert_eq!(
+            state.resize_request.take(),
+            Some((SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0))
+        );
+
+        // A window dragged to some other size is custom, and moves to the
+        // last size going back
+        state.set_window_size(1000.0, 500.0);
+        assert_eq!(
+            state.settings_label(SettingsItem::WindowSize),
+            "Window Size: Custom (1000 x 500)"
+        );
+        state.slide_setting(SettingsItem::WindowSize, -1);
+        assert_eq!(state.resize_request.take(), Some((1920.0, 1080.0)));
+
+        // Locking the shape snaps the window to the play area's shape
+        state.change_setting(SettingsItem::AspectLock);
+        assert!(state.aspect_lock);
+        let (width, height) = state.resize_request.take().unwrap();
+        assert_relative_eq!(width / height, SCREEN_WIDTH / SCREEN_HEIGHT, epsilon = 1e-2);
+
+        // Once it has that shape, nothing more is asked for
+        state.set_window_size(width, height);
+        state.snap_window_shape();
+        assert_eq!(state.resize_request, None);
+    }
+
+    #[test]
+    fn test_safe_area_steps_through_its_margins() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert_eq!(
+            state.settings_label(SettingsItem::SafeArea),
+            "TV Safe Area: Off"
+        );
+        state.change_setting(SettingsItem::SafeArea);
+        assert_eq!(
+            state.settings_label(SettingsItem::SafeArea),
+            "TV Safe Area: 1%"
+        );
+
+        // It wraps round both ways, and is part of the saved settings
+        state.slide_setting(SettingsItem::SafeArea, -2);
+        assert_eq!(state.safe_margin, MAX_SAFE_MARGIN);
+        assert_eq!(state.settings().safe_margin, MAX_SAFE_MARGIN);
+        state.slide_setting(SettingsItem::SafeArea, 1);
+        assert_eq!(state.safe_margin, 0);
+    }
+
+    #[test]
+    fn test_settings_open_from_the_pause_screen() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.sim.score.player1 = 2;
+        state.pause();
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Settings);
+        assert_eq!(state.settings_items(), &SettingsItem::IN_MATCH);
+
+        // Only the options that leave the match alone are offered
+        assert!(!state.settings_items().contains(&SettingsItem::Court));
+        state.handle_action(Action::Right); // Theme
+        assert_eq!(state.theme_preset, ThemePreset::Neon);
+
+        // Going back returns to the paused match, still as it was
+        state.handle_action(Action::Back);
+        assert_eq!(state.scene, Scene::Paused);
+        assert_eq!(state.sim.score.player1, 2);
+        assert_eq!(state.settings_items(), &SettingsItem::ALL);
+    }
+
+    #[test]
+    fn test_photo_mode_keeps_the_match_frozen() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.pause();
+        let ball = (state.sim.ball.x, state.sim.ball.y);
+        state.handle_action(Action::PhotoMode);
+        assert_eq!(state.scene, Scene::Photo);
+        assert!(state.in_match());
+
+        state.handle_action(Action::ZoomIn);
+        state.handle_action(Action::ToggleHud);
+        assert!(state.photo.camera.zoom > 1.0);
+        assert!(state.photo.hide_hud);
+
+        // Back on the pause screen nothing has moved, and a fresh visit starts over
+        state.handle_action(Action::Back);
+        assert_eq!(state.scene, Scene::Paused);
+        assert_eq!((state.sim.ball.x, state.sim.ball.y), ball);
+        state.handle_action(Action::PhotoMode);
+        assert_eq!(state.photo.camera.zoom, 1.0);
+        assert!(!state.photo.hide_hud);
+    }
+
+    #[test]
+    fn test_demo_runs_behind_the_main_menu_only() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(state.shows_demo());
+        assert_eq!(state.demo.mode, GameMode::Demo);
+
+        // Half a second past the countdown the ball is in play, and nothing was heard
+        for _ in 0..(COUNTDOWN_DURATION * 10.0) as u32 + 5 {
+            state.advance_demo(0.1);
+        }
+        assert!(state.demo.countdown.is_none());
+        assert!(state.demo.audio.take_ready().is_empty());
+
+        // Held still in low-power mode, and hidden on other screens
+        state.low_power = true;
+        assert!(state.shows_demo());
+        assert!(!state.runs_demo());
+        state.low_power = false;
+        assert!(state.runs_demo());
+        state.start_match(GameMode::VsAi);
+        assert!(!state.shows_demo());
+        assert!(!state.runs_demo());
+    }
+
+    #[test]
+    fn test_demo_waits_for_a_whole_fixed_step() {
+        let mut state = GameState::new(GameMode::VsAi);
+
+        // Less than one step of time doesn't move the demo on yet
+        state.advance_demo(FIXED_TIMESTEP * 0.6);
+        assert_eq!(state.demo.ticks, 0);
+
+        // Once the leftover time adds up to a step, it takes exactly one
+        state.advance_demo(FIXED_TIMESTEP * 0.6);
+        assert_eq!(state.demo.ticks, 1);
+    }
+
+    #[test]
+    fn test_debug_overlay_toggles_on_any_screen() {
+        let mut state = GameState::new(GameMode::VsAi);
+        assert!(!state.debug_overlay);
+        state.handle_action(Action::ToggleDebug);
+        assert!(state.debug_overlay);
+
+        // Toggling mid-match doesn't pause or change the screen
+        state.start_match(GameMode::VsAi);
+        state.handle_action(Action::ToggleDebug);
+        assert!(!state.debug_overlay);
+        assert_eq!(state.scene, Scene::Playing);
+    }
+
+    #[test]
+    fn test_latency_test_opens_only_from_the_main_menu() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.handle_action(Action::LatencyTest);
+        assert_eq!(state.scene, Scene::Playing);
+
+        // Presses are only timed on the test screen
+        state.time_press(Instant::now());
+        assert!(!state.latency.flashing(Instant::now()));
+
+        state.quit_to_menu();
+        state.handle_action(Action::LatencyTest);
+        assert_eq!(state.scene, Scene::LatencyTest);
+        state.time_press(Instant::now());
+        assert!(state.latency.flashing(Instant::now()));
+
+        state.handle_action(Action::Back);
+        assert_eq!(state.scene, Scene::Menu);
+    }
+
+    // A single-player match started with `--seed <seed>`, played for a few seconds
+    // of frames with nobody touching the keys
+    fn seeded_match(seed: &str) -> GameState {
+        let options = CliOptions::parse(["--seed".to_string(), seed.to_string()]).unwrap();
+        let mut state = GameState::new(GameMode::VsAi);
+        options.apply(&mut state.sim);
+        state.start_match(GameMode::VsAi);
+        for _ in 0..600 {
+            state.advance(1.0 / 60.0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_seed_option_repeats_the_whole_match() {
+        let first = seeded_match("42");
+        let again = seeded_match("42");
+        assert_eq!(again.sim.match_seed, first.sim.match_seed);
+        assert_eq!(again.sim.ticks, first.sim.ticks);
+        assert_eq!(again.sim.checksum(), first.sim.checksum());
+
+        // Serves and AI mistakes come from the seed, so another one plays differently
+        assert_ne!(seeded_match("43").sim.checksum(), first.sim.checksum());
+    }
+
+    #[test]
+    fn test_autopilot_plays_on_after_the_match() {
+        let mut state = GameState::new(GameMode::VsAi);
+        state.start_match(GameMode::VsAi);
+        state.handle_action(Action::ToggleAutopilot);
+        assert!(state.sim.autopilot);
+        assert_eq!(state.scene, Scene::Playing);
+
+        // The game over screen stays up for a while, then the next match starts
+        state.resign(1);
+        assert_eq!(state.scene, Scene::GameOver);
+        state.wait_for_rematch(AUTOPILOT_REMATCH_DELAY / 2.0);
+        assert_eq!(state.scene, Scene::GameOver);
+        state.wait_for_rematch(AUTOPILOT_REMATCH_DELAY / 2.0);
+        assert_eq!(state.scene, Scene::Playing);
+        assert!(state.sim.autopilot);
+
+        // Without the auto-pilot the game over screen waits for the player
+        state.handle_action(Action::ToggleAutopilot);
+        state.resign(1);
+        state.wait_for_rematch(AUTOPILOT_REMATCH_DELAY * 2.0);
+        assert_eq!(state.scene, Scene::GameOver);
+    }
+
+    #[test]
+    fn test_win_streaks_are_kept_and_announced() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_streak_{}", std::process::id()));
+        let path = dir.join("preferences.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_preferences_file(path.clone());
+        state.preferences.profiles.add("Sam").unwrap();
+        state.preferences.profiles.slide_choice(1, 1);
+
+        // Three wins in a row are announced when the next match starts
+        for _ in 0..3 {
+            state.start_match(GameMode::VsAi);
+            assert!(state.streak_banner.is_none());
+            state.sim.winner = Some(1);
+            state.step(FIXED_TIMESTEP);
+        }
+        state.start_match(GameMode::VsAi);
+        let (banner, _) = state.streak_banner.clone().unwrap();
+        assert_eq!(banner, "Sam is on a 3-win streak!");
+
+        // The banner only stays up for a while
+        state.expire_streak_banner(STREAK_BANNER_TIME + 0.1);
+        assert!(state.streak_banner.is_none());
+
+        // The streak lasts into the next run, until a resignation breaks it
+        let mut again = GameState::new(GameMode::VsAi);
+        again.use_preferences_file(path.clone());
+        assert_eq!(again.preferences.profiles.win_streak(1), 3);
+        again.start_match(GameMode::VsAi);
+        again.pause();
+        again.handle_action(Action::Resign(1));
+        assert_eq!(again.preferences.profiles.win_streak(1), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_a_profile_playing_itself_keeps_its_streak() {
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        state.preferences.profiles.add("Sam").unwrap();
+        state.preferences.profiles.add("Alex").unwrap();
+        state.preferences.profiles.slide_choice(1, 1);
+        state.start_match(GameMode::VsAi);
+        state.sim.winner = Some(1);
+        state.step(FIXED_TIMESTEP);
+        assert_eq!(state.preferences.profiles.win_streak(1), 1);
+
+        // Both sides on Sam's profile: the match neither adds to the streak
+        // nor breaks it, whichever side wins
+        state.preferences.profiles.slide_choice(2, 1);
+        assert!(state.preferences.profiles.shared());
+        for winner in [1, 2] {
+            state.start_match(GameMode::TwoPlayer);
+            state.sim.winner = Some(winner);
+            state.step(FIXED_TIMESTEP);
+            assert_eq!(state.preferences.profiles.win_streak(1), 1);
+        }
+
+        // Against another profile it counts again
+        state.preferences.profiles.slide_choice(2, 1);
+        state.start_match(GameMode::TwoPlayer);
+        state.sim.winner = Some(2);
+        state.step(FIXED_TIMESTEP);
+        assert_eq!(state.preferences.profiles.win_streak(1), 0);
+        assert_eq!(state.preferences.profiles.win_streak(2), 1);
+    }
+
+    #[test]
+    fn test_tournament_runs_through_the_bracket_and_saves_the_best() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_bracket_{}", std::process::id()));
+        let path = dir.join("preferences.json");
+        let mut state = GameState::new(GameMode::VsAi);
+        state.use_preferences_file(path.clone());
+        state.scene = Scene::Menu; // Past the What's New screen
+        state.sim.ai_difficulty = AiDifficulty::Impossible;
+        state.menu_cursor.selected = MenuItem::ALL
+            .iter()
+            .position(|&item| item == MenuItem::Tournament)
+            .unwrap();
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Bracket);
+
+        // Pick a bracket of 8 and win the first match against the easiest opponent
+        state.handle_action(Action::Right);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Playing);
+        assert_eq!(state.sim.ai_difficulty, AiDifficulty::Easy);
+        // The difficulty picked in the settings is the one remembered
+        assert_eq!(state.settings().options.ai_difficulty, AiDifficulty::Impossible);
+        state.sim.winner = Some(1);
+        state.step(FIXED_TIMESTEP);
+        assert_eq!(state.scene, Scene::GameOver);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Bracket);
+
+        // The size can't change once it's started; lose the semifinal
+        state.handle_action(Action::Left);
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.sim.ai_difficulty, AiDifficulty::Medium);
+        state.resign(1);
+        state.handle_action(Action::Confirm);
+        let tournament = state.tournament.as_ref().unwrap();
+        assert_eq!(tournament.size, BracketSize::Eight);
+        assert_eq!(tournament.summary(), "Knocked out in the Semifinal");
+
+        // Leaving puts the difficulty back, and the next run remembers how far it got
+        state.handle_action(Action::Confirm);
+        assert_eq!(state.scene, Scene::Menu);
+        assert!(state.tournament.is_none());
+        assert_eq!(state.sim.ai_difficulty, AiDifficulty::Impossible);
+        let mut again = GameState::new(GameMode::VsAi);
+        again.use_preferences_file(path.clone());
+        assert_eq!(again.preferences.tournament.best(BracketSize::Eight), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
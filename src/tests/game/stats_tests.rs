@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::rules::MatchEnd;
+    use crate::game::stats::{CareerStats, MatchRecord, MatchStats, PaceSample, StatTotals};
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new_stats() {
+        let stats = MatchStats::new();
+        assert_eq!(stats.points_played, 0);
+        assert_eq!(stats.streak, 0);
+        assert!(stats.streak_player.is_none());
+        assert_relative_eq!(stats.average_rally_length(), 0.0);
+    }
+
+    #[test]
+    fn test_points_split_by_server() {
+        let mut stats = MatchStats::new();
+
+        stats.start_point(1);
+        stats.record_point(1);
+        stats.start_point(2);
+        stats.record_point(1);
+
+        assert_eq!(stats.player1.points_on_serve, 1);
+        assert_eq!(stats.player1.points_on_return, 1);
+        assert_eq!(stats.player2.points_on_serve, 0);
+        assert_eq!(stats.player2.points_on_return, 0);
+    }
+
+    #[test]
+    fn test_average_rally_length() {
+        let mut stats = MatchStats::new();
+
+        stats.start_point(1);
+        stats.record_hit();
+        stats.record_hit();
+        stats.record_hit();
+        stats.record_point(2);
+        stats.start_point(2);
+        stats.record_hit();
+        stats.record_point(1);
+
+        assert_relative_eq!(stats.average_rally_length(), 2.0);
+    }
+
+    #[test]
+    fn test_recent_rally_length_looks_at_the_last_points() {
+        let mut stats = MatchStats::new();
+        assert_eq!(stats.recent_rally_length(2), 0.0);
+
+        for hits in [10, 2, 4] {
+            stats.start_point(1);
+            for _ in 0..hits {
+                stats.record_hit();
+            }
+            stats.record_point(1);
+        }
+        assert_relative_eq!(stats.recent_rally_length(2), 3.0);
+        assert_relative_eq!(stats.recent_rally_length(10), 16.0 / 3.0);
+    }
+
+    #[test]
+    fn test_pace_sample_per_point() {
+        let mut stats = MatchStats::new();
+
+        stats.start_point(1);
+        stats.record_ball_speed(300.0);
+        stats.record_hit();
+        stats.record_ball_speed(320.0);
+        stats.record_point(2);
+        stats.start_point(2);
+        stats.record_ball_speed(300.0);
+        stats.record_point(1);
+
+        assert_eq!(
+            stats.pace,
+            vec![
+                PaceSample {
+                    rally: 1,
+                    top_speed: 320.0
+                },
+                PaceSample {
+                    rally: 0,
+                    top_speed: 300.0
+                },
+            ]
+        );
+        stats.reset();
+        assert!(stats.pace.is_empty());
+    }
+
+    #[test]
+    fn test_streak_resets_on_other_winner() {
+        let mut stats = MatchStats::new();
+
+        stats.record_point(2);
+        stats.record_point(2);
+        assert_eq!(stats.streak_player, Some(2));
+        assert_eq!(stats.streak, 2);
+
+        stats.record_point(1);
+        assert_eq!(stats.streak_player, Some(1));
+        assert_eq!(stats.streak, 1);
+    }
+
+    #[test]
+    fn test_forfeit_drops_point_in_progress() {
+        let mut stats = MatchStats::new();
+        stats.start_point(1);
+        stats.record_hit();
+        stats.record_forfeit(2);
+
+        assert_eq!(stats.forfeited_by, Some(2));
+        assert_eq!(stats.current_rally, 0);
+        assert_eq!(stats.points_played, 0);
+    }
+
+    #[test]
+    fn test_longest_rally() {
+        let mut stats = MatchStats::new();
+        stats.start_point(1);
+        for _ in 0..4 {
+            stats.record_hit();
+        }
+        stats.record_point(1);
+        stats.start_point(2);
+        stats.record_hit();
+        stats.record_point(2);
+        assert_eq!(stats.longest_rally, 4);
+    }
+
+    #[test]
+    fn test_totals_add_up_matches() {
+        let mut stats = MatchStats::new();
+        stats.start_point(1);
+        stats.record_hit();
+        stats.record_point(1);
+
+        let mut totals = StatTotals::default();
+        totals.add_match(&stats, Some(true), MatchEnd::ScoreReached);
+        totals.add_match(&stats, Some(false), MatchEnd::LivesLost);
+        totals.add_match(&stats, None, MatchEnd::ScoreReached); // Two-player match
+        assert_eq!(totals.wins, 1);
+        assert_eq!(totals.losses, 1);
+        assert_eq!(totals.total_points, 3);
+        assert_eq!(totals.longest_rally, 1);
+    }
+
+    #[test]
+    fn test_history_keeps_how_the_latest_matches_ended() {
+        let mut stats = MatchStats::new();
+        stats.start_point(1);
+        stats.record_point(1);
+
+        let mut totals = StatTotals::default();
+        totals.add_match(&stats, Some(false), MatchEnd::Forfeit);
+        assert_eq!(
+            totals.history,
+            vec![MatchRecord {
+                won: Some(false),
+                end: MatchEnd::Forfeit,
+                points_played: 1,
+                longest_rally: 0,
+            }]
+        );
+
+        // Only the latest matches are kept, oldest dropped first
+        for _ in 0..MATCH_HISTORY_LENGTH {
+            totals.add_match(&stats, Some(true), MatchEnd::ScoreReached);
+        }
+        assert_eq!(totals.history.len(), MATCH_HISTORY_LENGTH);
+        assert!(totals
+            .history
+            .iter()
+            .all(|record| record.end == MatchEnd::ScoreReached));
+    }
+
+    #[test]
+    fn test_files_saved_before_the_history_load_without_one() {
+        let text = r#"{"wins": 2, "losses": 1, "longest_rally": 9, "total_points": 30}"#;
+        let totals: StatTotals = serde_json::from_str(text).unwrap();
+        assert_eq!(totals.wins, 2);
+        assert!(totals.history.is_empty());
+    }
+
+    #[test]
+    fn test_career_saves_and_loads_lifetime_totals() {
+        let path = std::env::temp_dir()
+            .join(format!("ping_pong_stats_{}", std::process::id()))
+            .join("stats.json");
+
+        let mut career = CareerStats::default();
+        let mut stats = MatchStats::new();
+        stats.start_point(1);
+        stats.record_point(1);
+        career.record_match(&stats, Some(true), MatchEnd::LivesLost);
+        career.save(&path).unwrap();
+
+        // Only lifetime totals come back; the session starts fresh
+        let loaded = CareerStats::load(&path).unwrap();
+        assert_eq!(loaded.lifetime, career.lifetime);
+        assert_eq!(loaded.lifetime.history[0].end, MatchEnd::LivesLost);
+        assert_eq!(loaded.session, StatTotals::default());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_starts_from_zero() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_missing_{}", std::process::id()));
+        let loaded = CareerStats::load(&dir.join("stats.json")).unwrap();
+        assert_eq!(loaded.lifetime, StatTotals::default());
+    }
+
+    #[test]
+    fn test_broken_file_reports_where_it_went_wrong() {
+        let dir = std::env::temp_dir().join(format!("ping_pong_broken_{}", std::process::id()));
+        let path = dir.join("stats.json");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(&path, "not json").unwrap();
+        let error = CareerStats::load(&path).unwrap_err();
+        assert!(error.contains("line 1"), "{}", error);
+
+        // Misspelled fields aren't quietly ignored
+        std::fs::write(&path, "{\n  \"wins\": 3,\n  \"loses\": 1\n}").unwrap();
+        let error = CareerStats::load(&path).unwrap_err();
+        assert!(error.contains("unknown field `loses`"), "{}", error);
+        assert!(error.contains("line 3"), "{}", error);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::ai::AiDifficulty;
+    use crate::game::tournament::{BracketSize, Tournament, TournamentRecord};
+
+    #[test]
+    fn test_opponents_get_harder_each_round() {
+        for size in [BracketSize::Four, BracketSize::Eight] {
+            let tournament = Tournament::new(size, AiDifficulty::Easy);
+            let opponents = tournament.opponents();
+            assert_eq!(opponents.len(), size.rounds());
+            assert!(opponents
+                .windows(2)
+                .all(|pair| pair[0].difficulty.next() == pair[1].difficulty));
+            // The final is always against the hardest opponent
+            assert_eq!(opponents.last().unwrap().difficulty, AiDifficulty::Hard);
+        }
+    }
+
+    #[test]
+    fn test_winning_every_round_makes_a_champion() {
+        let mut tournament = Tournament::new(BracketSize::Eight, AiDifficulty::Medium);
+        assert_eq!(tournament.round_name(0), "Quarterfinal");
+        for _ in 0..3 {
+            assert!(tournament.next_opponent().is_some());
+            tournament.record(true);
+        }
+        assert!(tournament.champion());
+        assert_eq!(tournament.next_opponent(), None);
+        assert_eq!(tournament.reached(), 4);
+        assert_eq!(tournament.summary(), "Champion!");
+
+        // Nothing more is kept once it's over
+        tournament.record(false);
+        assert_eq!(tournament.results.len(), 3);
+    }
+
+    #[test]
+    fn test_a_loss_knocks_the_player_out() {
+        let mut tournament = Tournament::new(BracketSize::Four, AiDifficulty::Medium);
+        tournament.record(true);
+        tournament.record(false);
+        assert!(tournament.over());
+        assert!(!tournament.champion());
+        assert_eq!(tournament.reached(), 2);
+        assert_eq!(tournament.summary(), "Knocked out in the Final");
+        assert_eq!(
+            tournament.lines(),
+            vec![
+                "Semifinal: Steady Sam (Medium)  -  Won",
+                "Final: Ace Alex (Hard)  -  Lost",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_size_is_only_picked_before_the_first_match() {
+        let mut tournament = Tournament::new(BracketSize::Four, AiDifficulty::Medium);
+        tournament.switch_size();
+        assert_eq!(tournament.size, BracketSize::Eight);
+        assert!(tournament.lines()[0].ends_with("Next Match"));
+
+        tournament.record(true);
+        tournament.switch_size();
+        assert_eq!(tournament.size, BracketSize::Eight);
+    }
+
+    #[test]
+    fn test_record_keeps_the_furthest_round_for_each_size() {
+        let mut record = TournamentRecord::default();
+        assert_eq!(record.describe(BracketSize::Four), "Best: not played yet");
+
+        let mut tournament = Tournament::new(BracketSize::Four, AiDifficulty::Medium);
+        tournament.record(false);
+        assert!(record.note(&tournament));
+        assert_eq!(
+            record.describe(BracketSize::Four),
+            "Best: reached the Semifinal"
+        );
+
+        let mut tournament = Tournament::new(BracketSize::Four, AiDifficulty::Medium);
+        tournament.record(true);
+        tournament.record(true);
+        assert!(record.note(&tournament));
+        assert_eq!(record.describe(BracketSize::Four), "Best: Champion");
+
+        // A worse run doesn't replace it, and the other size is kept apart
+        let mut tournament = Tournament::new(BracketSize::Four, AiDifficulty::Medium);
+        tournament.record(false);
+        assert!(!record.note(&tournament));
+        assert_eq!(record.best(BracketSize::Four), 3);
+        assert_eq!(record.best(BracketSize::Eight), 0);
+    }
+}
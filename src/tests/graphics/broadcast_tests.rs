@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::simulation::{GameMode, Simulation};
+    use crate::graphics::broadcast::{HudLayout, Scoreboard};
+
+    // Names for the tests' scoreboards
+    fn names() -> [String; 2] {
+        ["Sam".to_string(), "CPU (Medium)".to_string()]
+    }
+
+    #[test]
+    fn test_layouts_switch_back_and_forth() {
+        assert_eq!(HudLayout::default(), HudLayout::Normal);
+        assert_eq!(HudLayout::Normal.next(), HudLayout::Broadcast);
+        assert_eq!(HudLayout::Broadcast.next(), HudLayout::Normal);
+        assert_eq!(HudLayout::Broadcast.name(), "Broadcast");
+    }
+
+    #[test]
+    fn test_scoreboard_reads_the_match() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.score.player1 = 4;
+        sim.score.player2 = 7;
+        let board = Scoreboard::new(&sim, names());
+        assert_eq!(board.scores, [4, 7]);
+        assert_eq!(board.names, names());
+        assert_eq!(board.serving, sim.serving_player());
+        // A single game has no games to count, and nothing's been hit yet
+        assert_eq!(board.games, None);
+        assert_eq!(board.tab_text(), None);
+    }
+
+    #[test]
+    fn test_tab_shows_games_and_rally() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.rules.best_of = 3;
+        sim.score.games_player2 = 1;
+        let mut board = Scoreboard::new(&sim, names());
+        assert_eq!(board.games, Some([0, 1]));
+        assert_eq!(board.tab_text().unwrap(), "GAMES 0 - 1");
+
+        board.rally = 6;
+        assert_eq!(board.tab_text().unwrap(), "GAMES 0 - 1   |   RALLY 6");
+        board.games = None;
+        assert_eq!(board.tab_text().unwrap(), "RALLY 6");
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::graphics::countdown::{countdown_frame, go_frame};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_numbers_count_down_each_second() {
+        let counts: Vec<usize> = [0.0, 0.99, 1.0, 2.5]
+            .iter()
+            .map(|&elapsed| countdown_frame(elapsed).unwrap().count)
+            .collect();
+        assert_eq!(counts, vec![3, 3, 2, 1]);
+        assert_eq!(countdown_frame(0.2).unwrap().label(), "3");
+        assert_eq!(countdown_frame(COUNTDOWN_DURATION), None);
+        assert_eq!(countdown_frame(-0.1), None);
+    }
+
+    #[test]
+    fn test_each_number_grows_and_fades() {
+        // A number appears solid at its normal size
+        let start = countdown_frame(1.0).unwrap();
+        assert_relative_eq!(start.size, COUNTDOWN_TEXT_SIZE);
+        assert_relative_eq!(start.alpha, 1.0);
+
+        // Then gets bigger and fainter through its second
+        let middle = countdown_frame(1.5).unwrap();
+        let end = countdown_frame(1.99).unwrap();
+        assert!(start.size < middle.size && middle.size < end.size);
+        assert!(start.alpha > middle.alpha && middle.alpha > end.alpha);
+        assert!(end.size <= COUNTDOWN_TEXT_SIZE * COUNTDOWN_GROWTH);
+        assert!(end.alpha < 0.05);
+    }
+
+    #[test]
+    fn test_go_flashes_briefly_after_the_countdown() {
+        let go = go_frame(0.0).unwrap();
+        assert_eq!(go.label(), "GO!");
+        assert_relative_eq!(go.size, COUNTDOWN_TEXT_SIZE);
+        assert!(go_frame(COUNTDOWN_GO_DURATION / 2.0).unwrap().alpha < 1.0);
+        assert_eq!(go_frame(COUNTDOWN_GO_DURATION), None);
+    }
+}
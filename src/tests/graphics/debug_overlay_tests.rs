@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::FIXED_TIMESTEP;
+    use crate::game::simulation::{GameMode, Simulation};
+    use crate::graphics::debug_overlay::debug_text;
+    use std::time::Duration;
+
+    #[test]
+    fn test_shows_timing_and_ball_numbers() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.ball.dx = 300.0;
+        sim.ball.dy = -400.0;
+        let text = debug_text(&sim, 59.6, Duration::from_micros(16_700));
+
+        assert!(text.contains("FPS: 60"));
+        assert!(text.contains("Frame time: 16.70 ms"));
+        assert!(text.contains("Ball velocity: (300, -400)  speed 500"));
+        assert!(text.contains("Rally: 0 hits"));
+        assert!(text.contains("AI target: none")); // Nobody is an AI
+        assert!(text.contains(&format!("Tick 0  state {:08X}", sim.checksum())));
+    }
+
+    #[test]
+    fn test_shows_where_the_ai_is_aiming() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.start_match(GameMode::VsAi);
+        sim.countdown = None;
+        sim.ball.dx = 300.0; // Heading for the AI
+        sim.ball.dy = 0.0;
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+
+        let text = debug_text(&sim, 60.0, Duration::ZERO);
+        assert!(text.contains(&format!("AI target: P2 {:.0}", sim.ball.y)));
+    }
+}
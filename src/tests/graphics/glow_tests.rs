@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::graphics::glow::ball_glow;
+    use crate::graphics::theme::{ball_heat, Theme};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_heat_runs_from_serve_speed_to_top_speed() {
+        assert_eq!(ball_heat(BALL_SPEED), 0.0);
+        assert_eq!(ball_heat(BALL_SPEED / 2.0), 0.0);
+        assert_relative_eq!(ball_heat((BALL_SPEED + MAX_BALL_SPEED) / 2.0), 0.5);
+        assert_eq!(ball_heat(MAX_BALL_SPEED), 1.0);
+        assert_eq!(ball_heat(MAX_BALL_SPEED * 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_ball_color_runs_from_yellow_to_red() {
+        let theme = Theme::classic();
+        assert_eq!(theme.ball_color(BALL_SPEED), theme.ball);
+        assert_eq!(theme.ball_color(MAX_BALL_SPEED), theme.ball_hot[1]);
+    }
+
+    #[test]
+    fn test_a_slow_ball_has_no_glow() {
+        assert_eq!(ball_glow(BALL_SPEED, 0.0), None);
+        assert_eq!(ball_glow(BALL_SPEED, 3.7), None);
+    }
+
+    #[test]
+    fn test_glow_grows_with_speed() {
+        let warm = ball_glow((BALL_SPEED + MAX_BALL_SPEED) / 2.0, 0.0).unwrap();
+        let hot = ball_glow(MAX_BALL_SPEED, 0.0).unwrap();
+        assert!(warm.radius > BALL_RADIUS);
+        assert!(hot.radius > warm.radius);
+        assert!(hot.alpha > warm.alpha);
+        assert!(hot.radius <= BALL_RADIUS * BALL_GLOW_SIZE);
+        assert!(hot.alpha <= BALL_GLOW_OPACITY);
+    }
+
+    #[test]
+    fn test_glow_pulses_over_time() {
+        let period = 1.0 / BALL_GLOW_PULSE_RATE;
+        let low = ball_glow(MAX_BALL_SPEED, period * 0.75).unwrap();
+        let high = ball_glow(MAX_BALL_SPEED, period * 0.25).unwrap();
+        assert!(high.alpha > low.alpha);
+        // It comes back around after one pulse
+        let again = ball_glow(MAX_BALL_SPEED, period * 1.25).unwrap();
+        assert_relative_eq!(again.alpha, high.alpha, epsilon = 1e-4);
+    }
+}
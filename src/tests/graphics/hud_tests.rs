@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+    use crate::graphics::hud::{Anchor, HudLine, SlotCache};
+    use ggez::graphics::Color;
+
+    #[test]
+    fn test_anchors_center_text_across_the_screen() {
+        let size = [200.0, 20.0];
+        let left = SCREEN_WIDTH / 2.0 - 100.0;
+        assert_eq!(Anchor::TopCenter.place(size, 20.0), [left, 20.0]);
+        assert_eq!(
+            Anchor::BottomCenter.place(size, 20.0),
+            [left, SCREEN_HEIGHT - 40.0]
+        );
+        assert_eq!(
+            Anchor::Center.place(size, 0.0),
+            [left, SCREEN_HEIGHT / 2.0 - 10.0]
+        );
+    }
+
+    #[test]
+    fn test_lines_default_to_the_top_at_their_own_size() {
+        let line = HudLine::new("Rally: 3", 14.0, Color::WHITE);
+        assert_eq!(
+            (line.anchor, line.gap, line.scale),
+            (Anchor::TopCenter, 0.0, 1.0)
+        );
+
+        let line = line.at(Anchor::BottomCenter, 20.0).scaled(0.5);
+        assert_eq!(
+            (line.anchor, line.gap, line.scale),
+            (Anchor::BottomCenter, 20.0, 0.5)
+        );
+        assert_eq!(line.text, "Rally: 3");
+    }
+
+    #[test]
+    fn test_slots_are_only_built_again_when_their_key_changes() {
+        let mut cache = SlotCache::default();
+        let mut builds = 0;
+        let mut get = |cache: &mut SlotCache<u32, u32>, slot, key| {
+            *cache
+                .get_or_build(slot, key, || {
+                    builds += 1;
+                    Ok(builds)
+                })
+                .unwrap()
+        };
+
+        // The same score frame after frame keeps its first build
+        assert_eq!(get(&mut cache, "score", 0), 1);
+        assert_eq!(get(&mut cache, "score", 0), 1);
+
+        // Another slot is kept apart, and a new score builds again
+        assert_eq!(get(&mut cache, "instructions", 0), 2);
+        assert_eq!(get(&mut cache, "score", 1), 3);
+        assert_eq!(get(&mut cache, "instructions", 0), 2);
+    }
+}
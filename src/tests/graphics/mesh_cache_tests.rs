@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use crate::graphics::mesh_cache::SizedCache;
+
+    #[test]
+    fn test_shapes_are_only_built_for_new_sizes() {
+        let mut cache = SizedCache::default();
+        let mut builds = 0;
+        let mut get = |cache: &mut SizedCache<u32>, size: [f32; 2]| {
+            cache
+                .get_or_build(size, || {
+                    builds += 1;
+                    Ok(builds)
+                })
+                .unwrap()
+        };
+
+        // Drawing the same size again reuses the first shape
+        assert_eq!(get(&mut cache, [15.0, 100.0]), 1);
+        assert_eq!(get(&mut cache, [15.0, 100.0]), 1);
+
+        // A taller paddle gets its own shape, and both are kept
+        assert_eq!(get(&mut cache, [15.0, 140.0]), 2);
+        assert_eq!(get(&mut cache, [15.0, 100.0]), 1);
+
+        // A third size pushes out the oldest one
+        assert_eq!(get(&mut cache, [15.0, 60.0]), 3);
+        assert_eq!(get(&mut cache, [15.0, 140.0]), 2);
+        assert_eq!(get(&mut cache, [15.0, 100.0]), 4);
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+pub mod broadcast_tests;
+#[cfg(test)]
+pub mod countdown_tests;
+#[cfg(test)]
+pub mod debug_overlay_tests;
+#[cfg(test)]
+pub mod glow_tests;
+#[cfg(test)]
+pub mod hud_tests;
+#[cfg(test)]
+pub mod mesh_cache_tests;
+#[cfg(test)]
+pub mod pace_tests;
+#[cfg(test)]
+pub mod pause_card_tests;
+#[cfg(test)]
+pub mod photo_tests;
+#[cfg(test)]
+pub mod seasonal_tests;
+#[cfg(all(test, feature = "stream-hud"))]
+pub mod stream_hud_tests;
+#[cfg(test)]
+pub mod theme_tests;
+#[cfg(test)]
+pub mod ui;
+#[cfg(test)]
+pub mod viewport_tests;
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::graphics::pace::graph_points;
+    use approx::assert_relative_eq;
+    use ggez::graphics::Rect;
+
+    #[test]
+    fn test_points_fill_the_area() {
+        let area = Rect::new(100.0, 50.0, 200.0, 80.0);
+        let points = graph_points(&[0.0, 5.0, 10.0], 10.0, area);
+
+        // Left to right across the width, zero at the bottom and the max at the top
+        assert_eq!(points.len(), 3);
+        assert_relative_eq!(points[0][0], 100.0);
+        assert_relative_eq!(points[0][1], 130.0);
+        assert_relative_eq!(points[1][0], 200.0);
+        assert_relative_eq!(points[1][1], 90.0);
+        assert_relative_eq!(points[2][0], 300.0);
+        assert_relative_eq!(points[2][1], 50.0);
+    }
+
+    #[test]
+    fn test_single_point_and_out_of_range_values() {
+        let area = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let points = graph_points(&[20.0], 10.0, area);
+        assert_relative_eq!(points[0][0], 50.0); // Centered
+        assert_relative_eq!(points[0][1], 0.0); // Kept inside the top edge
+
+        // Nothing to scale against: everything sits on the baseline
+        let flat = graph_points(&[3.0, 4.0], 0.0, area);
+        assert!(flat.iter().all(|point| point[1] == 100.0));
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::ai::AiDifficulty;
+    use crate::game::constants::{COUNTDOWN_DURATION, FIXED_TIMESTEP};
+    use crate::game::court::CourtPreset;
+    use crate::game::simulation::{GameMode, Simulation};
+    use crate::graphics::pause_card::{active_mutators, pause_card_text};
+
+    #[test]
+    fn test_card_sums_up_the_match() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.ai_difficulty = AiDifficulty::Hard;
+        sim.rules.best_of = 3;
+        sim.opening_server = Some(2);
+        sim.start_match(GameMode::VsAi);
+        sim.score.increment_player1();
+        sim.score.games_player2 = 1;
+        sim.ticks = 125 * 120; // Just over two minutes at 120 steps a second
+
+        let text = pause_card_text(&sim);
+        assert!(text.starts_with("Against the AI (Hard)"));
+        assert!(text.contains("Score:    You 1 - 0 AI  (games 0 - 1)"));
+        assert!(text.contains("Rally:    0 hits now, longest 0, average 0.0"));
+        assert!(text.contains("Time:     2:05"));
+        assert!(text.contains("Rules:    None"));
+    }
+
+    #[test]
+    fn test_card_shows_who_serves_next() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.start_match(GameMode::TwoPlayer);
+        assert!(pause_card_text(&sim).contains("Serve:    Picked at random"));
+
+        // Once served, the card names the server, and keeps the one the
+        // rules pick while the next countdown runs
+        sim.opening_server = Some(2);
+        sim.countdown = Some(COUNTDOWN_DURATION);
+        sim.step(FIXED_TIMESTEP, [0.0, 0.0]);
+        assert!(pause_card_text(&sim).contains("Serve:    Player 2"));
+        sim.countdown = Some(0.0);
+        assert!(pause_card_text(&sim).contains("Serve:    Player 2"));
+    }
+
+    #[test]
+    fn test_mutators_list_the_changed_rules() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        assert!(active_mutators(&sim).is_empty());
+
+        sim.apply_court(CourtPreset::Narrow);
+        sim.rules.classic = true;
+        sim.rules.win_by_two = true; // Already part of classic rules
+        sim.lives_mode = true;
+        sim.momentum_physics = true;
+        sim.autopilot = true;
+        assert_eq!(
+            active_mutators(&sim),
+            [
+                "Narrow court",
+                "Classic table tennis",
+                "Lives mode",
+                "Momentum physics",
+                "Auto-pilot"
+            ]
+        );
+        assert!(pause_card_text(&sim).contains("(lives 5 - 5)"));
+    }
+}
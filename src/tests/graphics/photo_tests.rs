@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::game::simulation::{GameMode, Simulation};
+    use crate::graphics::photo::{photo_file_name, PhotoCamera, PhotoFilter};
+    use ggez::graphics::Rect;
+
+    fn play_area() -> Rect {
+        Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT)
+    }
+
+    #[test]
+    fn test_unzoomed_camera_shows_the_whole_frame() {
+        let mut camera = PhotoCamera::default();
+        assert_eq!(camera.view(play_area()), play_area());
+
+        // There's nowhere to move to without zooming in
+        camera.pan(100.0, -50.0);
+        assert_eq!(camera.view(play_area()), play_area());
+    }
+
+    #[test]
+    fn test_zoom_stays_in_range() {
+        let mut camera = PhotoCamera::default();
+        camera.zoom_by(-3);
+        assert_eq!(camera.zoom, 1.0);
+        camera.zoom_by(100);
+        assert_eq!(camera.zoom, PHOTO_MAX_ZOOM);
+
+        let view = camera.view(play_area());
+        assert!((view.w - SCREEN_WIDTH / PHOTO_MAX_ZOOM).abs() < 0.01);
+        assert!((view.h - SCREEN_HEIGHT / PHOTO_MAX_ZOOM).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_zoomed_camera_pans_but_stays_inside_the_frame() {
+        let mut camera = PhotoCamera::default();
+        camera.zoom_by(1);
+        camera.zoom_by(1);
+
+        // Further in, the same pan covers less of the court
+        let start = camera.x;
+        camera.pan(50.0, 0.0);
+        assert!((camera.x - start - 50.0 / camera.zoom).abs() < 0.01);
+
+        // Pushed far past the corner, the picture stops at the edges
+        camera.pan(-10_000.0, -10_000.0);
+        let view = camera.view(play_area());
+        assert!(view.x.abs() < 0.01 && view.y.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_filters_cycle_back_to_none() {
+        let mut filter = PhotoFilter::None;
+        assert!(filter.tint().is_none());
+        for _ in 0..4 {
+            filter = filter.next();
+            assert_eq!(filter.tint().is_some(), filter != PhotoFilter::None);
+        }
+        assert_eq!(filter, PhotoFilter::None);
+    }
+
+    #[test]
+    fn test_photo_name_comes_from_the_seed_and_tick() {
+        let mut sim = Simulation::new(GameMode::VsAi);
+        sim.match_seed = 0x3F9C2A11;
+        sim.ticks = 1234;
+        assert_eq!(photo_file_name(&sim), "rally-3F9C2A11-1234.png");
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+    use crate::graphics::seasonal::{event_on, CalendarDay, Motion, ParticleField};
+    use crate::graphics::theme::ThemePreset;
+    use ggez::graphics::Color;
+
+    // A day of the year
+    fn day(month: u32, day: u32) -> CalendarDay {
+        CalendarDay { month, day }
+    }
+
+    #[test]
+    fn test_days_since_1970_give_the_right_date() {
+        assert_eq!(CalendarDay::from_days(0), day(1, 1));
+        assert_eq!(CalendarDay::from_days(59), day(3, 1)); // 1970 wasn't a leap year
+        assert_eq!(CalendarDay::from_days(11_016), day(2, 29)); // 29 February 2000
+        assert_eq!(CalendarDay::from_days(20_818), day(12, 31)); // 31 December 2026
+        assert_eq!(CalendarDay::from_days(-1), day(12, 31)); // 31 December 1969
+    }
+
+    #[test]
+    fn test_events_run_on_their_days() {
+        assert_eq!(event_on(day(11, 30)), None);
+        assert_eq!(event_on(day(12, 1)).unwrap().name, "Snow");
+        assert_eq!(event_on(day(12, 24)).unwrap().name, "Snow");
+
+        // New Year runs over the end of the year, and wins over the snow
+        assert_eq!(event_on(day(12, 31)).unwrap().name, "New Year");
+        assert_eq!(event_on(day(1, 1)).unwrap().name, "New Year");
+        assert_eq!(event_on(day(1, 2)), None);
+    }
+
+    #[test]
+    fn test_snow_makes_a_snowball_and_keeps_the_rest_of_the_theme() {
+        let theme = ThemePreset::Neon.theme();
+        let snowy = event_on(day(12, 10)).unwrap().apply(theme);
+        assert_eq!(snowy.ball, Color::WHITE);
+        assert_ne!(snowy.ball_hot, theme.ball_hot);
+        assert_eq!(snowy.background, theme.background);
+        assert_eq!(snowy.highlight, theme.highlight);
+    }
+
+    #[test]
+    fn test_snow_falls_and_starts_again_at_the_top() {
+        let event = event_on(day(12, 10)).unwrap();
+        let mut field = ParticleField::new(event.particles, 3);
+        assert_eq!(field.particles.len(), event.particles.count);
+
+        // Long enough for every flake to reach the bottom at least once
+        for _ in 0..600 {
+            field.update(1.0 / 60.0);
+            assert_eq!(field.particles.len(), event.particles.count);
+            assert!(field.particles.iter().all(|flake| {
+                (0.0..SCREEN_WIDTH).contains(&flake.x)
+                    && flake.y - flake.size <= SCREEN_HEIGHT
+                    && field.color_of(flake) == flake.color
+            }));
+        }
+    }
+
+    #[test]
+    fn test_fireworks_burst_and_fade_away() {
+        let event = event_on(day(1, 1)).unwrap();
+        let preset = event.particles;
+        assert_eq!(preset.motion, Motion::Bursting);
+        let mut field = ParticleField::new(preset, 3);
+        assert!(field.particles.is_empty());
+
+        // The first burst goes off straight away
+        field.update(0.01);
+        assert_eq!(field.particles.len(), preset.count);
+        let spark = field.particles[0];
+        assert_eq!(field.color_of(&spark).a, spark.color.a);
+
+        // Sparks fade as they age, then go
+        field.update(preset.lifetime / 2.0);
+        let spark = field.particles[0];
+        assert!(field.color_of(&spark).a < spark.color.a);
+        field.until_burst = preset.lifetime; // Hold off the next burst
+        field.update(preset.lifetime / 2.0);
+        assert!(field.particles.is_empty());
+    }
+}
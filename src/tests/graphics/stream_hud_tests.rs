@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::cli::{usage, CliOptions};
+    use crate::game::constants::FIXED_TIMESTEP;
+    use crate::game::scene::Scene;
+    use crate::game::simulation::{GameMode, Simulation};
+    use crate::game::spectator::{Snapshot, SpectatorServer};
+    use crate::game::state::GameState;
+    use crate::graphics::stream_hud::{hud_lines, match_clock, HudWindow};
+    use crate::tests::support::eventually;
+
+    #[test]
+    fn test_streaming_options_are_read() {
+        let args = ["--stream-hud", "--hud-window"].map(String::from);
+        let options = CliOptions::parse(args).unwrap();
+        assert!(options.stream_hud && options.hud_window);
+        assert!(!CliOptions::default().stream_hud);
+        assert!(usage().contains("--hud-window"));
+    }
+
+    #[test]
+    fn test_clock_shows_minutes_and_seconds() {
+        assert_eq!(match_clock(0.0), "0:00");
+        assert_eq!(match_clock(65.9), "1:05");
+        assert_eq!(match_clock(600.0), "10:00");
+    }
+
+    #[test]
+    fn test_lines_show_the_score_and_clock() {
+        let mut sim = Simulation::new(GameMode::TwoPlayer);
+        sim.score.player1 = 3;
+        sim.score.player2 = 1;
+        sim.ticks = (75.0 / FIXED_TIMESTEP) as u64;
+        let snapshot = Snapshot::new(&sim, 0);
+        assert_eq!(
+            hud_lines(&snapshot),
+            ["3  -  1".to_string(), "1:15".to_string()]
+        );
+
+        // Games and the winner are added when they apply
+        sim.rules.best_of = 3;
+        sim.score.games_player2 = 1;
+        sim.winner = Some(2);
+        let [_, details] = hud_lines(&Snapshot::new(&sim, 0));
+        assert_eq!(details, "Games 0 - 1   |   Player 2 wins   |   1:15");
+    }
+
+    #[test]
+    fn test_hud_window_follows_a_streamed_match() {
+        let feed = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let address = feed.address().unwrap().to_string();
+        let mut window = HudWindow::new(&address);
+        assert_eq!(window.lines()[0], "Waiting for the match");
+
+        let mut state = GameState::new(GameMode::TwoPlayer);
+        state.hud_feed = Some(feed);
+        state.start_match(GameMode::TwoPlayer);
+        state.scene = Scene::Playing;
+        state.sim.score.player1 = 4;
+        assert!(eventually(|| {
+            window.follow(0.0);
+            state.step(FIXED_TIMESTEP);
+            window.lines()[0] == "4  -  0"
+        }));
+    }
+}
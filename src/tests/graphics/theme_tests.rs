@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::{BALL_SPEED, MAX_BALL_SPEED};
+    use crate::graphics::theme::{Theme, ThemePreset};
+    use ggez::graphics::Color;
+
+    #[test]
+    fn test_presets_cycle_back_to_classic() {
+        let mut preset = ThemePreset::Classic;
+        preset = preset.next();
+        assert_eq!(preset, ThemePreset::Neon);
+        preset = preset.next();
+        assert_eq!(preset, ThemePreset::Phosphor);
+        preset = preset.next();
+        assert_eq!(preset, ThemePreset::Classic);
+    }
+
+    #[test]
+    fn test_classic_keeps_original_colors() {
+        let theme = ThemePreset::Classic.theme();
+        assert_eq!(theme.paddle_left, Color::from_rgb(0, 255, 0));
+        assert_eq!(theme.paddle_right, Color::from_rgb(0, 0, 255));
+        assert_eq!(theme.ball, Color::from_rgb(255, 255, 0));
+        assert_eq!(theme.countdown, [Color::GREEN, Color::YELLOW, Color::RED]);
+        assert_ne!(Theme::neon().background, theme.background);
+    }
+
+    #[test]
+    fn test_ball_heats_up_with_speed() {
+        let theme = Theme::classic();
+        assert_eq!(theme.ball_color(0.0), theme.ball); // Never cooler than a serve
+        assert_eq!(theme.ball_color(BALL_SPEED), theme.ball);
+        let halfway = (BALL_SPEED + MAX_BALL_SPEED) / 2.0;
+        assert_eq!(theme.ball_color(halfway), theme.ball_hot[0]);
+        assert_eq!(theme.ball_color(MAX_BALL_SPEED * 2.0), Color::RED);
+
+        // In between, the ball gets steadily redder (less green)
+        let warmer = theme.ball_color(BALL_SPEED + 50.0);
+        assert_eq!(warmer.r, 1.0);
+        assert!(warmer.g < theme.ball.g && warmer.g > theme.ball_hot[0].g);
+    }
+}
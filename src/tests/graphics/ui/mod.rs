@@ -0,0 +1,2 @@
+#[cfg(test)]
+pub mod option_list_tests;
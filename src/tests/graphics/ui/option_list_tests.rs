@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::graphics::ui::option_list::visible_rows;
+
+    #[test]
+    fn test_short_lists_show_every_option() {
+        assert_eq!(visible_rows(5, 0, 10), 0..5);
+        assert_eq!(visible_rows(5, 4, 10), 0..5);
+        assert_eq!(visible_rows(10, 9, 10), 0..10);
+    }
+
+    #[test]
+    fn test_long_lists_scroll_to_keep_the_highlight_in_view() {
+        // Near the top the list starts at the first option...
+        assert_eq!(visible_rows(20, 2, 10), 0..10);
+        // ...in the middle the highlight sits in the middle...
+        assert_eq!(visible_rows(20, 9, 10), 4..14);
+        // ...and near the bottom the list ends at the last option
+        assert_eq!(visible_rows(20, 19, 10), 10..20);
+        for selected in 0..20 {
+            assert!(visible_rows(20, selected, 10).contains(&selected));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::game::constants::*;
+    use crate::graphics::viewport::{
+        court_cover, letterbox, lock_aspect, safe_area, safe_scale, WindowPreset,
+    };
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_matching_window_shows_play_area_exactly() {
+        // Twice the size but the same shape
+        let view = letterbox(SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0);
+        assert_relative_eq!(view.x, 0.0);
+        assert_relative_eq!(view.y, 0.0);
+        assert_relative_eq!(view.w, SCREEN_WIDTH);
+        assert_relative_eq!(view.h, SCREEN_HEIGHT);
+    }
+
+    #[test]
+    fn test_wide_window_adds_side_bars() {
+        // 16:9 is wider than the 3:2 play area
+        let view = letterbox(1920.0, 1080.0);
+        assert_relative_eq!(view.h, SCREEN_HEIGHT);
+        assert!(view.w > SCREEN_WIDTH);
+        // The play area stays centered
+        assert_relative_eq!(view.x + view.w / 2.0, SCREEN_WIDTH / 2.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_tall_window_adds_top_and_bottom_bars() {
+        let view = letterbox(600.0, 800.0);
+        assert_relative_eq!(view.w, SCREEN_WIDTH);
+        assert!(view.h > SCREEN_HEIGHT);
+        assert_relative_eq!(view.y + view.h / 2.0, SCREEN_HEIGHT / 2.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_window_presets_are_found_by_size() {
+        assert_eq!(
+            WindowPreset::Double.size(),
+            (SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0)
+        );
+        assert_eq!(WindowPreset::Hd720.size(), (1280.0, 720.0));
+        for preset in WindowPreset::ALL {
+            let (width, height) = preset.size();
+            assert_eq!(WindowPreset::matching(width, height), Some(preset));
+        }
+        assert_eq!(WindowPreset::matching(1000.0, 700.0), None);
+    }
+
+    #[test]
+    fn test_locked_window_keeps_the_play_area_shape() {
+        // Already the right shape: nothing changes
+        assert_eq!(
+            lock_aspect(SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0),
+            (SCREEN_WIDTH * 2.0, SCREEN_HEIGHT * 2.0)
+        );
+
+        // Dragging only the bottom edge down still makes the window bigger
+        let (width, height) = lock_aspect(SCREEN_WIDTH, SCREEN_HEIGHT * 2.0);
+        assert_relative_eq!(width / height, SCREEN_WIDTH / SCREEN_HEIGHT, epsilon = 1e-2);
+        assert!(width > SCREEN_WIDTH && height < SCREEN_HEIGHT * 2.0);
+
+        // It can't be shrunk away to nothing
+        let (_, height) = lock_aspect(1.0, 1.0);
+        assert_relative_eq!(height, MIN_WINDOW_SIZE);
+    }
+
+    #[test]
+    fn test_safe_area_keeps_the_hud_off_the_edges() {
+        // No margin: the HUD is drawn just like the court
+        let frame = letterbox(1920.0, 1080.0);
+        assert_eq!(safe_area(frame, 0), frame);
+
+        // With a 5% margin the play area's corners land 5% of the window in
+        // from each edge
+        let hud = safe_area(frame, 5);
+        assert_relative_eq!(safe_scale(5), 0.9);
+        let window_x = |x: f32| (x - hud.x) / hud.w;
+        let window_y = |y: f32| (y - hud.y) / hud.h;
+        assert_relative_eq!(window_y(0.0), 0.05, epsilon = 1e-4);
+        assert_relative_eq!(window_y(SCREEN_HEIGHT), 0.95, epsilon = 1e-4);
+        assert!(window_x(0.0) > 0.05); // Wide window: the bars already leave room
+        assert_relative_eq!(window_x(0.0) + window_x(SCREEN_WIDTH), 1.0, epsilon = 1e-4);
+
+        // The court's cover in HUD coordinates is where the court really is
+        let cover = court_cover(5);
+        let frame_x = |x: f32| (x - frame.x) / frame.w;
+        assert_relative_eq!(window_x(cover.x), frame_x(0.0), epsilon = 1e-4);
+        assert_relative_eq!(window_y(cover.y + cover.h), 1.0, epsilon = 1e-4);
+    }
+}
@@ -2,3 +2,7 @@
 pub mod components;
 #[cfg(test)]
 pub mod game;
+#[cfg(test)]
+pub mod graphics;
+#[cfg(test)]
+pub mod support;
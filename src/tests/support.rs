@@ -0,0 +1,24 @@
+// This file holds helpers shared by tests in more than one file
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+// A file called `file` in a fresh temporary folder for one test, named after
+// the test so tests running at the same time don't share it
+pub fn temp_file(name: &str, file: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("ping_pong_{}_{}", name, std::process::id()))
+        .join(file)
+}
+
+// Keep checking `done` for a while, giving the network time to catch up
+pub fn eventually(mut done: impl FnMut() -> bool) -> bool {
+    for _ in 0..100 {
+        if done() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    false
+}